@@ -0,0 +1,187 @@
+//! Command-line parsing.
+
+use std::collections::BTreeMap;
+
+pub const USAGE: &str = "\
+usage: fluxora-cli [global options] <command> [options]
+
+global options:
+  --network NAME       network passed to the stellar CLI (e.g. testnet)
+  --source ACCOUNT     signing identity or secret key passed to the stellar CLI
+  --stellar PATH       stellar CLI binary (default: stellar)
+  --dry-run            print the stellar commands instead of running them
+
+commands:
+  deploy         --wasm PATH
+  init           --id CONTRACT --token ADDRESS --admin ADDRESS --guardian ADDRESS
+  create-stream  --id CONTRACT --sender ADDRESS --recipient ADDRESS
+                 --per-month AMOUNT --days N
+                 [--decimals N] [--start UNIX_TIME] [--cliff-days N]
+  withdraw       --id CONTRACT --stream ID
+  cancel         --id CONTRACT --stream ID
+  inspect        --id CONTRACT --stream ID [--at UNIX_TIME]
+  list           --id CONTRACT [--from ID] [--limit N]
+";
+
+/// Default number of ids `list` scans.
+pub const DEFAULT_LIST_LIMIT: u64 = 20;
+
+/// Default token decimals for `--per-month`, as for USDC.
+pub const DEFAULT_DECIMALS: u32 = 7;
+
+/// Options shared by every command, forwarded to the stellar CLI.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Globals {
+    pub network: Option<String>,
+    pub source: Option<String>,
+    pub stellar: Option<String>,
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    Help,
+    Deploy {
+        wasm: String,
+    },
+    Init {
+        id: String,
+        token: String,
+        admin: String,
+        guardian: String,
+    },
+    CreateStream {
+        id: String,
+        sender: String,
+        recipient: String,
+        /// Decimal amount per 30-day month, in whole tokens.
+        per_month: String,
+        days: u64,
+        decimals: u32,
+        /// Defaults to the current time.
+        start: Option<u64>,
+        cliff_days: u64,
+    },
+    Withdraw {
+        id: String,
+        stream: u64,
+    },
+    Cancel {
+        id: String,
+        stream: u64,
+    },
+    Inspect {
+        id: String,
+        stream: u64,
+        /// Defaults to the current time.
+        at: Option<u64>,
+    },
+    List {
+        id: String,
+        from: u64,
+        limit: u64,
+    },
+}
+
+/// Parse the arguments after the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<(Globals, Command), String> {
+    let mut globals = Globals::default();
+    let mut command = None;
+    let mut options = Options::default();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => return Ok((globals, Command::Help)),
+            "--dry-run" => globals.dry_run = true,
+            flag if flag.starts_with("--") => {
+                let value = args.next().ok_or_else(|| format!("{flag} needs a value"))?;
+                let name = &flag[2..];
+                if options.values.insert(name.to_string(), value).is_some() {
+                    return Err(format!("{flag} given more than once"));
+                }
+            }
+            _ if command.is_none() => command = Some(arg),
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+
+    globals.network = options.optional("network");
+    globals.source = options.optional("source");
+    globals.stellar = options.optional("stellar");
+
+    let command = match command.as_deref() {
+        None | Some("help") => Command::Help,
+        Some("deploy") => Command::Deploy {
+            wasm: options.required("wasm")?,
+        },
+        Some("init") => Command::Init {
+            id: options.required("id")?,
+            token: options.required("token")?,
+            admin: options.required("admin")?,
+            guardian: options.required("guardian")?,
+        },
+        Some("create-stream") => Command::CreateStream {
+            id: options.required("id")?,
+            sender: options.required("sender")?,
+            recipient: options.required("recipient")?,
+            per_month: options.required("per-month")?,
+            days: options.number("days")?.ok_or("missing --days")?,
+            decimals: options.number("decimals")?.unwrap_or(DEFAULT_DECIMALS),
+            start: options.number("start")?,
+            cliff_days: options.number("cliff-days")?.unwrap_or(0),
+        },
+        Some("withdraw") => Command::Withdraw {
+            id: options.required("id")?,
+            stream: options.number("stream")?.ok_or("missing --stream")?,
+        },
+        Some("cancel") => Command::Cancel {
+            id: options.required("id")?,
+            stream: options.number("stream")?.ok_or("missing --stream")?,
+        },
+        Some("inspect") => Command::Inspect {
+            id: options.required("id")?,
+            stream: options.number("stream")?.ok_or("missing --stream")?,
+            at: options.number("at")?,
+        },
+        Some("list") => Command::List {
+            id: options.required("id")?,
+            from: options.number("from")?.unwrap_or(0),
+            limit: options.number("limit")?.unwrap_or(DEFAULT_LIST_LIMIT),
+        },
+        Some(other) => return Err(format!("unknown command: {other}")),
+    };
+
+    if let Some(flag) = options.values.keys().next() {
+        return Err(format!("unknown option --{flag}"));
+    }
+    Ok((globals, command))
+}
+
+/// `--name value` pairs, consumed as each command reads them so leftovers
+/// can be reported as unknown.
+#[derive(Default)]
+struct Options {
+    values: BTreeMap<String, String>,
+}
+
+impl Options {
+    fn optional(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+
+    fn required(&mut self, name: &str) -> Result<String, String> {
+        self.optional(name)
+            .ok_or_else(|| format!("missing --{name}"))
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        self.optional(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("--{name} must be a non-negative integer"))
+            })
+            .transpose()
+    }
+}
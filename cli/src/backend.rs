@@ -0,0 +1,188 @@
+//! Where invocations go: the stellar CLI, which simulates, signs and submits
+//! them with the configured identity and network.
+
+use std::process;
+
+use fluxora_client::xdr::{InvokeContractArgs, ScError, ScVal};
+use fluxora_client::ClientError;
+use serde_json::Value;
+
+use crate::args::Globals;
+
+/// Deploys contracts and runs invocations, returning results as the stellar
+/// CLI prints them: JSON, or `Null` for calls that return nothing.
+pub trait Backend {
+    /// Upload and deploy `wasm`, returning the new contract's strkey.
+    fn deploy(&mut self, wasm: &str) -> Result<String, ClientError>;
+
+    /// Simulate `call` and, unless it is read-only, sign and submit it.
+    fn invoke(&mut self, call: &InvokeContractArgs) -> Result<Value, ClientError>;
+}
+
+/// Runs the `stellar` binary as a subprocess.
+pub struct StellarCli {
+    globals: Globals,
+}
+
+impl StellarCli {
+    pub fn new(globals: Globals) -> Self {
+        StellarCli { globals }
+    }
+
+    /// Arguments for `stellar contract deploy`.
+    pub fn deploy_args(&self, wasm: &str) -> Vec<String> {
+        let mut args = vec![
+            "contract".into(),
+            "deploy".into(),
+            "--wasm".into(),
+            wasm.into(),
+        ];
+        self.push_account(&mut args);
+        args
+    }
+
+    /// Arguments for `stellar contract invoke`, with the contract function's
+    /// parameters passed by name after `--`.
+    pub fn invoke_args(&self, call: &InvokeContractArgs) -> Result<Vec<String>, ClientError> {
+        let function = call.function_name.to_utf8_string_lossy();
+        let names = parameter_names(&function)?;
+        if names.len() != call.args.len() {
+            return Err(ClientError::Decode(
+                "argument count does not match the function",
+            ));
+        }
+
+        let mut args = vec![
+            "contract".into(),
+            "invoke".into(),
+            "--id".into(),
+            call.contract_address.to_string(),
+        ];
+        self.push_account(&mut args);
+        args.push("--".into());
+        args.push(function);
+        for (name, value) in names.iter().zip(call.args.iter()) {
+            args.push(format!("--{name}"));
+            args.push(render(value)?);
+        }
+        Ok(args)
+    }
+
+    fn push_account(&self, args: &mut Vec<String>) {
+        if let Some(source) = &self.globals.source {
+            args.extend(["--source".into(), source.clone()]);
+        }
+        if let Some(network) = &self.globals.network {
+            args.extend(["--network".into(), network.clone()]);
+        }
+    }
+
+    fn run(&self, args: Vec<String>) -> Result<String, ClientError> {
+        let program = self.globals.stellar.as_deref().unwrap_or("stellar");
+        if self.globals.dry_run {
+            println!("{program} {}", args.join(" "));
+            return Ok(String::new());
+        }
+
+        let output = process::Command::new(program)
+            .args(&args)
+            .output()
+            .map_err(|error| ClientError::Transport(format!("cannot run {program}: {error}")))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(parse_failure(&String::from_utf8_lossy(&output.stderr)))
+        }
+    }
+}
+
+impl Backend for StellarCli {
+    fn deploy(&mut self, wasm: &str) -> Result<String, ClientError> {
+        self.run(self.deploy_args(wasm))
+    }
+
+    fn invoke(&mut self, call: &InvokeContractArgs) -> Result<Value, ClientError> {
+        let stdout = self.run(self.invoke_args(call)?)?;
+        if stdout.is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&stdout).unwrap_or(Value::String(stdout)))
+    }
+}
+
+/// The stream contract's parameter names, which the stellar CLI needs to
+/// pass arguments.
+fn parameter_names(function: &str) -> Result<&'static [&'static str], ClientError> {
+    Ok(match function {
+        "init" => &["token", "admin", "guardian"],
+        "create_stream" => &[
+            "sender",
+            "recipient",
+            "deposit_amount",
+            "rate_per_second",
+            "start_time",
+            "cliff_time",
+            "end_time",
+        ],
+        "withdraw" | "pause_stream" | "resume_stream" | "cancel_stream" | "get_stream_state"
+        | "get_tombstone" => &["stream_id"],
+        "extend_ttl_for" => &["stream_ids"],
+        "interface_version" => &[],
+        _ => return Err(ClientError::Decode("function not supported by the CLI")),
+    })
+}
+
+/// An argument in the form the stellar CLI parses.
+pub fn render(value: &ScVal) -> Result<String, ClientError> {
+    Ok(match value {
+        ScVal::Bool(value) => value.to_string(),
+        ScVal::U32(value) => value.to_string(),
+        ScVal::U64(value) => value.to_string(),
+        ScVal::I128(parts) => (((parts.hi as i128) << 64) | parts.lo as i128).to_string(),
+        ScVal::Address(address) => address.to_string(),
+        ScVal::Vec(Some(items)) => {
+            let items = items.iter().map(render).collect::<Result<Vec<_>, _>>()?;
+            format!("[{}]", items.join(","))
+        }
+        _ => {
+            return Err(ClientError::Decode(
+                "argument type not supported by the CLI",
+            ))
+        }
+    })
+}
+
+/// Classify a failed stellar CLI run from its error output. Typed contract
+/// errors are decoded, network trouble is transient and everything else,
+/// including contract panics, is reported as is.
+pub fn parse_failure(stderr: &str) -> ClientError {
+    const TRANSIENT: [&str; 6] = [
+        "timed out",
+        "timeout",
+        "connection",
+        "TRY_AGAIN_LATER",
+        "503",
+        "429",
+    ];
+
+    if let Some(code) = contract_error_code(stderr) {
+        return ClientError::from_sc_error(&ScError::Contract(code));
+    }
+    let message = stderr.trim().to_string();
+    if TRANSIENT.iter().any(|marker| message.contains(marker)) {
+        ClientError::Transport(message)
+    } else {
+        ClientError::Rejected(message)
+    }
+}
+
+/// `N` from the first `Error(Contract, #N)` in `text`.
+fn contract_error_code(text: &str) -> Option<u32> {
+    const MARKER: &str = "Error(Contract, #";
+    let start = text.find(MARKER)? + MARKER.len();
+    let digits: String = text[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
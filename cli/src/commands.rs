@@ -0,0 +1,235 @@
+//! Running parsed commands against a backend and formatting the results.
+
+use fluxora_client::xdr::{Int128Parts, InvokeContractArgs, ScMap, ScMapEntry, ScSymbol, ScVal};
+use fluxora_client::{
+    decode_status, parse_address, parse_amount, ClientError, CreateParams, RetryPolicy,
+    StreamContract, StreamState, SECONDS_PER_DAY,
+};
+use serde_json::Value;
+
+use crate::args::{Command, USAGE};
+use crate::backend::Backend;
+
+/// Run `command`, retrying transient failures under `retry`, and return
+/// what to print. `now` is the default start time for new streams and
+/// the time `inspect` and `list` report balances at.
+pub fn run(
+    backend: &mut impl Backend,
+    retry: &RetryPolicy,
+    command: Command,
+    now: u64,
+) -> Result<String, ClientError> {
+    Ok(match command {
+        Command::Help => USAGE.to_string(),
+        Command::Deploy { wasm } => {
+            let id = retry.run(|_| backend.deploy(&wasm))?;
+            format!("deployed {id}")
+        }
+        Command::Init {
+            id,
+            token,
+            admin,
+            guardian,
+        } => {
+            invoke(
+                backend,
+                retry,
+                StreamContract::new(&id)?.init(&token, &admin, &guardian)?,
+            )?;
+            format!("initialised {id}")
+        }
+        Command::CreateStream {
+            id,
+            sender,
+            recipient,
+            per_month,
+            days,
+            decimals,
+            start,
+            cliff_days,
+        } => {
+            let amount = parse_amount(&per_month, decimals)?;
+            let start = start.unwrap_or(now);
+            let duration = days
+                .checked_mul(SECONDS_PER_DAY)
+                .ok_or(ClientError::InvalidParams("duration overflows u64"))?;
+            let mut params = CreateParams::per_month(&recipient, amount, start, duration)?;
+            if cliff_days > 0 {
+                let cliff = cliff_days
+                    .checked_mul(SECONDS_PER_DAY)
+                    .ok_or(ClientError::InvalidParams("cliff overflows u64"))?;
+                params = params.with_cliff(cliff)?;
+            }
+            let result = invoke(
+                backend,
+                retry,
+                StreamContract::new(&id)?.create_stream(&sender, &params)?,
+            )?;
+            if result.is_null() {
+                return Ok(String::new());
+            }
+            format!(
+                "created stream {}: deposit {} at {} per second from {} to {}",
+                to_u64(&result)?,
+                params.deposit_amount,
+                params.rate_per_second,
+                params.start_time,
+                params.end_time,
+            )
+        }
+        Command::Withdraw { id, stream } => {
+            let result = invoke(backend, retry, StreamContract::new(&id)?.withdraw(stream))?;
+            if result.is_null() {
+                return Ok(String::new());
+            }
+            format!("withdrew {} from stream {stream}", to_i128(&result)?)
+        }
+        Command::Cancel { id, stream } => {
+            invoke(
+                backend,
+                retry,
+                StreamContract::new(&id)?.cancel_stream(stream),
+            )?;
+            format!("cancelled stream {stream}")
+        }
+        Command::Inspect { id, stream, at } => {
+            let result = invoke(
+                backend,
+                retry,
+                StreamContract::new(&id)?.get_stream_state(stream),
+            )?;
+            if result.is_null() {
+                return Ok(String::new());
+            }
+            inspect(&decode_stream(&result)?, at.unwrap_or(now))
+        }
+        Command::List { id, from, limit } => {
+            let contract = StreamContract::new(&id)?;
+            let mut rows = Vec::new();
+            for stream in from..from.saturating_add(limit) {
+                match invoke(backend, retry, contract.get_stream_state(stream)) {
+                    Ok(Value::Null) => break,
+                    Ok(result) => rows.push(list_row(&decode_stream(&result)?, now)),
+                    Err(ClientError::Rejected(message)) if message.contains("stream not found") => {
+                        // Closed streams leave a tombstone; an id without
+                        // one was never created, so the scan is done.
+                        match invoke(backend, retry, contract.get_tombstone(stream))? {
+                            Value::Null => break,
+                            status => rows.push(format!(
+                                "{stream}\t{:?}\tclosed",
+                                decode_status(&ScVal::U32(to_u32(&status)?))?
+                            )),
+                        }
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+            rows.join("\n")
+        }
+    })
+}
+
+fn invoke(
+    backend: &mut impl Backend,
+    retry: &RetryPolicy,
+    call: InvokeContractArgs,
+) -> Result<Value, ClientError> {
+    retry.run(|_| backend.invoke(&call))
+}
+
+fn inspect(stream: &StreamState, now: u64) -> String {
+    [
+        format!("stream        {}", stream.stream_id),
+        format!("status        {:?}", stream.status),
+        format!("sender        {}", stream.sender),
+        format!("recipient     {}", stream.recipient),
+        format!("token         {}", stream.token),
+        format!("deposit       {}", stream.deposit_amount),
+        format!("rate          {} per second", stream.rate_per_second),
+        format!("start         {}", stream.start_time),
+        format!("cliff         {}", stream.cliff_time),
+        format!("end           {}", stream.end_time),
+        format!("withdrawn     {}", stream.withdrawn_amount),
+        format!("accrued       {} at {now}", stream.accrued_at(now)),
+        format!("withdrawable  {} at {now}", stream.withdrawable_at(now)),
+    ]
+    .join("\n")
+}
+
+fn list_row(stream: &StreamState, now: u64) -> String {
+    format!(
+        "{}\t{:?}\t{}\t{}/{}\twithdrawable {}",
+        stream.stream_id,
+        stream.status,
+        stream.recipient,
+        stream.withdrawn_amount,
+        stream.deposit_amount,
+        stream.withdrawable_at(now),
+    )
+}
+
+/// Decode a stream as the stellar CLI prints it: a JSON object with
+/// strkey addresses, and i128 amounts as strings.
+pub fn decode_stream(value: &Value) -> Result<StreamState, ClientError> {
+    let object = value
+        .as_object()
+        .ok_or(ClientError::Decode("expected a stream object"))?;
+    let mut entries = Vec::new();
+    for (key, value) in object {
+        let val = match key.as_str() {
+            "sender" | "recipient" | "token" => ScVal::Address(parse_address(
+                value
+                    .as_str()
+                    .ok_or(ClientError::Decode("expected an address"))?,
+            )?),
+            "deposit_amount" | "rate_per_second" | "withdrawn_amount" => {
+                let amount = to_i128(value)?;
+                ScVal::I128(Int128Parts {
+                    hi: (amount >> 64) as i64,
+                    lo: amount as u64,
+                })
+            }
+            "stream_id" | "start_time" | "cliff_time" | "end_time" => ScVal::U64(to_u64(value)?),
+            "status" => ScVal::U32(to_u32(value)?),
+            "admin_cancelable" | "credit_refunds" => ScVal::Bool(
+                value
+                    .as_bool()
+                    .ok_or(ClientError::Decode("expected a bool"))?,
+            ),
+            _ => continue,
+        };
+        entries.push(ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(
+                key.as_str()
+                    .try_into()
+                    .map_err(|_| ClientError::Decode("field name too long"))?,
+            )),
+            val,
+        });
+    }
+    let map = ScMap(
+        entries
+            .try_into()
+            .map_err(|_| ClientError::Decode("too many fields"))?,
+    );
+    StreamState::from_scval(&ScVal::Map(Some(map)))
+}
+
+fn to_i128(value: &Value) -> Result<i128, ClientError> {
+    match value {
+        Value::String(text) => text.parse().ok(),
+        Value::Number(number) => number.as_i64().map(i128::from),
+        _ => None,
+    }
+    .ok_or(ClientError::Decode("expected an i128"))
+}
+
+fn to_u64(value: &Value) -> Result<u64, ClientError> {
+    value.as_u64().ok_or(ClientError::Decode("expected a u64"))
+}
+
+fn to_u32(value: &Value) -> Result<u32, ClientError> {
+    to_u64(value)?
+        .try_into()
+        .map_err(|_| ClientError::Decode("expected a u32"))
+}
@@ -0,0 +1,47 @@
+//! `fluxora-cli`: deploy and operate Fluxora stream contracts from scripts
+//! and CI.
+//!
+//! Invocations are built with `fluxora_client` and run through the stellar
+//! CLI, which handles simulation, signing and submission with its configured
+//! identities and networks. Pass `--dry-run` to print the stellar commands
+//! instead of running them.
+
+mod args;
+mod backend;
+mod commands;
+#[cfg(test)]
+mod test;
+
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fluxora_client::RetryPolicy;
+
+use crate::backend::StellarCli;
+
+fn main() -> ExitCode {
+    let (globals, command) = match args::parse(std::env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{}", args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    let mut backend = StellarCli::new(globals);
+    match commands::run(&mut backend, &RetryPolicy::default(), command, now) {
+        Ok(output) => {
+            if !output.is_empty() {
+                println!("{output}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use fluxora_client::xdr::{
+    AccountId, Hash, InvokeContractArgs, PublicKey, ScAddress, ScVal, Uint256,
+};
+use fluxora_client::{ClientError, ContractError, RetryPolicy, StreamContract, StreamStatus};
+use serde_json::{json, Value};
+
+use crate::args::{self, Command, Globals, DEFAULT_DECIMALS, DEFAULT_LIST_LIMIT};
+use crate::backend::{parse_failure, Backend, StellarCli};
+use crate::commands::{self, decode_stream};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+fn contract_id() -> String {
+    ScAddress::Contract(Hash([1; 32])).to_string()
+}
+
+fn account(seed: u8) -> String {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        [seed; 32],
+    ))))
+    .to_string()
+}
+
+fn parse(line: &str) -> Result<(Globals, Command), String> {
+    args::parse(line.split_whitespace().map(String::from))
+}
+
+fn no_wait() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    }
+}
+
+/// A 30-day stream of 1 unit per second as the stellar CLI prints it.
+fn stream_json(stream_id: u64, withdrawn: i128) -> Value {
+    json!({
+        "admin_cancelable": false,
+        "cliff_time": 0,
+        "credit_refunds": false,
+        "deposit_amount": "2592000",
+        "end_time": 2_592_000,
+        "rate_per_second": "1",
+        "recipient": account(3),
+        "sender": account(2),
+        "start_time": 0,
+        "status": 0,
+        "stream_id": stream_id,
+        "token": contract_id(),
+        "withdrawn_amount": withdrawn.to_string(),
+    })
+}
+
+/// Answers invocations from a script and records them.
+#[derive(Default)]
+struct FakeBackend {
+    responses: VecDeque<Result<Value, ClientError>>,
+    calls: Vec<InvokeContractArgs>,
+}
+
+impl FakeBackend {
+    fn reply(mut self, response: Result<Value, ClientError>) -> Self {
+        self.responses.push_back(response);
+        self
+    }
+
+    fn functions(&self) -> Vec<String> {
+        self.calls
+            .iter()
+            .map(|call| call.function_name.to_utf8_string_lossy())
+            .collect()
+    }
+}
+
+impl Backend for FakeBackend {
+    fn deploy(&mut self, _wasm: &str) -> Result<String, ClientError> {
+        Ok(contract_id())
+    }
+
+    fn invoke(&mut self, call: &InvokeContractArgs) -> Result<Value, ClientError> {
+        self.calls.push(call.clone());
+        self.responses.pop_front().expect("unexpected invocation")
+    }
+}
+
+fn run(backend: &mut FakeBackend, line: &str, now: u64) -> Result<String, ClientError> {
+    let (_, command) = parse(line).unwrap();
+    commands::run(backend, &no_wait(), command, now)
+}
+
+// ---------------------------------------------------------------------------
+// Tests — argument parsing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_parse_reads_globals_and_applies_defaults() {
+    let line = format!(
+        "--network testnet create-stream --id {} --sender {} --recipient {} \
+         --per-month 1000 --days 30 --source ops --dry-run",
+        contract_id(),
+        account(2),
+        account(3),
+    );
+    let (globals, command) = parse(&line).unwrap();
+    assert_eq!(globals.network.as_deref(), Some("testnet"));
+    assert_eq!(globals.source.as_deref(), Some("ops"));
+    assert!(globals.dry_run);
+    assert_eq!(
+        command,
+        Command::CreateStream {
+            id: contract_id(),
+            sender: account(2),
+            recipient: account(3),
+            per_month: "1000".into(),
+            days: 30,
+            decimals: DEFAULT_DECIMALS,
+            start: None,
+            cliff_days: 0,
+        }
+    );
+
+    let (_, command) = parse("list --id C").unwrap();
+    assert_eq!(
+        command,
+        Command::List {
+            id: "C".into(),
+            from: 0,
+            limit: DEFAULT_LIST_LIMIT,
+        }
+    );
+    assert_eq!(parse("").unwrap().1, Command::Help);
+}
+
+#[test]
+fn test_parse_rejects_bad_input() {
+    assert_eq!(parse("withdraw --id C").unwrap_err(), "missing --stream");
+    assert_eq!(
+        parse("withdraw --id C --stream one").unwrap_err(),
+        "--stream must be a non-negative integer"
+    );
+    assert_eq!(
+        parse("withdraw --id C --stream 1 --amount 5").unwrap_err(),
+        "unknown option --amount"
+    );
+    assert_eq!(parse("inspect --id").unwrap_err(), "--id needs a value");
+    assert_eq!(parse("migrate").unwrap_err(), "unknown command: migrate");
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stellar CLI backend
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_invoke_args_pass_parameters_by_name() {
+    let cli = StellarCli::new(Globals {
+        network: Some("testnet".into()),
+        source: Some("ops".into()),
+        ..Globals::default()
+    });
+    let contract = StreamContract::new(&contract_id()).unwrap();
+
+    assert_eq!(
+        cli.invoke_args(&contract.withdraw(7)).unwrap(),
+        [
+            "contract",
+            "invoke",
+            "--id",
+            &contract_id(),
+            "--source",
+            "ops",
+            "--network",
+            "testnet",
+            "--",
+            "withdraw",
+            "--stream_id",
+            "7",
+        ]
+    );
+
+    let args = cli
+        .invoke_args(
+            &contract
+                .init(&contract_id(), &account(2), &account(3))
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        args[9..],
+        [
+            "init",
+            "--token",
+            &contract_id(),
+            "--admin",
+            &account(2),
+            "--guardian",
+            &account(3),
+        ]
+    );
+
+    let args = cli.invoke_args(&contract.extend_ttl_for(&[1, 2])).unwrap();
+    assert_eq!(args[10..], ["--stream_ids", "[1,2]"]);
+}
+
+#[test]
+fn test_parse_failure_classifies_stellar_cli_errors() {
+    assert_eq!(
+        parse_failure("error: HostError: Error(Contract, #5)\n"),
+        ClientError::Contract(ContractError::IncompatibleInterface)
+    );
+    assert!(parse_failure("error: request timed out").is_transient());
+    assert_eq!(
+        parse_failure("error: HostError: Error(WasmVm, InvalidAction) \"stream not found\"\n"),
+        ClientError::Rejected(
+            "error: HostError: Error(WasmVm, InvalidAction) \"stream not found\"".into()
+        )
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — commands
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_stream_converts_the_monthly_amount() {
+    let mut backend = FakeBackend::default().reply(Ok(json!(4)));
+    let line = format!(
+        "create-stream --id {} --sender {} --recipient {} --per-month 2.592 \
+         --decimals 6 --days 30 --cliff-days 7",
+        contract_id(),
+        account(2),
+        account(3),
+    );
+    let output = run(&mut backend, &line, 1_000).unwrap();
+    assert_eq!(
+        output,
+        "created stream 4: deposit 2592000 at 1 per second from 1000 to 2593000"
+    );
+
+    let call = &backend.calls[0];
+    assert_eq!(call.function_name.to_utf8_string_lossy(), "create_stream");
+    let args = call.args.to_vec();
+    assert_eq!(args[4], ScVal::U64(1_000));
+    assert_eq!(args[5], ScVal::U64(1_000 + 7 * 86_400));
+    assert_eq!(args[6], ScVal::U64(2_593_000));
+}
+
+#[test]
+fn test_inspect_reports_withdrawable_balance() {
+    let state = decode_stream(&stream_json(0, 100)).unwrap();
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.withdrawn_amount, 100);
+    assert_eq!(state.recipient.to_string(), account(3));
+
+    let mut backend = FakeBackend::default().reply(Ok(stream_json(0, 100)));
+    let line = format!("inspect --id {} --stream 0 --at 500", contract_id());
+    let output = run(&mut backend, &line, 0).unwrap();
+    assert!(output.contains("status        Active"));
+    assert!(output.contains("accrued       500 at 500"));
+    assert!(output.contains("withdrawable  400 at 500"));
+}
+
+#[test]
+fn test_list_shows_closed_streams_and_stops_at_unused_ids() {
+    let not_found = || Err(ClientError::Rejected("panicked: stream not found".into()));
+    let mut backend = FakeBackend::default()
+        .reply(Ok(stream_json(0, 0)))
+        .reply(not_found())
+        .reply(Ok(json!(3)))
+        .reply(not_found())
+        .reply(Ok(Value::Null));
+    let line = format!("list --id {}", contract_id());
+    let output = run(&mut backend, &line, 10).unwrap();
+
+    let rows: Vec<_> = output.lines().collect();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].starts_with("0\tActive\t"));
+    assert!(rows[0].ends_with("0/2592000\twithdrawable 10"));
+    assert_eq!(rows[1], "1\tCancelled\tclosed");
+    assert_eq!(
+        backend.functions(),
+        [
+            "get_stream_state",
+            "get_stream_state",
+            "get_tombstone",
+            "get_stream_state",
+            "get_tombstone",
+        ]
+    );
+}
+
+#[test]
+fn test_commands_retry_transient_failures_only() {
+    let mut backend = FakeBackend::default()
+        .reply(Err(ClientError::Transport("timed out".into())))
+        .reply(Ok(json!("250")));
+    let line = format!("withdraw --id {} --stream 2", contract_id());
+    assert_eq!(
+        run(&mut backend, &line, 0).unwrap(),
+        "withdrew 250 from stream 2"
+    );
+    assert_eq!(backend.calls.len(), 2);
+
+    let mut backend =
+        FakeBackend::default().reply(Err(ClientError::Rejected("stream is paused".into())));
+    assert_eq!(
+        run(&mut backend, &line, 0),
+        Err(ClientError::Rejected("stream is paused".into()))
+    );
+    assert_eq!(backend.calls.len(), 1);
+}
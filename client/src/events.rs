@@ -0,0 +1,107 @@
+//! The stream contract's lifecycle events, decoded from XDR.
+
+use stellar_xdr::curr::{ContractEvent, ContractEventBody, ScAddress, ScVal};
+
+use crate::{scval, state, ClientError, StreamStatus};
+
+/// A lifecycle event published by the stream contract. Events without a
+/// variant here decode as `Other`, so new ones do not break indexers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamEvent {
+    Created {
+        stream_id: u64,
+        deposit_amount: i128,
+    },
+    Withdrew {
+        stream_id: u64,
+        amount: i128,
+    },
+    Paused {
+        stream_id: u64,
+    },
+    Resumed {
+        stream_id: u64,
+    },
+    /// `refunded` went back to the sender (or into their credit).
+    Cancelled {
+        stream_id: u64,
+        refunded: i128,
+    },
+    RecipientTransferred {
+        stream_id: u64,
+        from: ScAddress,
+        to: ScAddress,
+    },
+    Restored {
+        stream_id: u64,
+    },
+    Closed {
+        stream_id: u64,
+        status: StreamStatus,
+    },
+    Other {
+        /// The first topic, e.g. `"split_set"`.
+        name: String,
+    },
+}
+
+impl StreamEvent {
+    /// Decode an event from its topics and data.
+    pub fn decode(topics: &[ScVal], data: &ScVal) -> Result<Self, ClientError> {
+        let name = scval::to_symbol(
+            topics
+                .first()
+                .ok_or(ClientError::Decode("event has no topics"))?,
+        )?;
+        let stream_id = || {
+            scval::to_u64(
+                topics
+                    .get(1)
+                    .ok_or(ClientError::Decode("event has no stream id"))?,
+            )
+        };
+        Ok(match name.as_str() {
+            "created" => StreamEvent::Created {
+                stream_id: stream_id()?,
+                deposit_amount: scval::to_i128(data)?,
+            },
+            "withdrew" => StreamEvent::Withdrew {
+                stream_id: stream_id()?,
+                amount: scval::to_i128(data)?,
+            },
+            "paused" => StreamEvent::Paused {
+                stream_id: stream_id()?,
+            },
+            "resumed" => StreamEvent::Resumed {
+                stream_id: stream_id()?,
+            },
+            "cancelled" => StreamEvent::Cancelled {
+                stream_id: stream_id()?,
+                refunded: scval::to_i128(data)?,
+            },
+            "rcpt_xfer" => match scval::to_vec(data)? {
+                [from, to] => StreamEvent::RecipientTransferred {
+                    stream_id: stream_id()?,
+                    from: scval::to_address(from)?,
+                    to: scval::to_address(to)?,
+                },
+                _ => return Err(ClientError::Decode("expected [from, to]")),
+            },
+            "restored" => StreamEvent::Restored {
+                stream_id: stream_id()?,
+            },
+            "closed" => StreamEvent::Closed {
+                stream_id: stream_id()?,
+                status: state::decode_status(data)?,
+            },
+            _ => StreamEvent::Other { name },
+        })
+    }
+
+    /// Decode a contract event as delivered by RPC `getEvents` or a
+    /// transaction's result meta.
+    pub fn from_contract_event(event: &ContractEvent) -> Result<Self, ClientError> {
+        let ContractEventBody::V0(body) = &event.body;
+        StreamEvent::decode(&body.topics, &body.data)
+    }
+}
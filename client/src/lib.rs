@@ -0,0 +1,223 @@
+//! Off-chain Rust client for the Fluxora stream contract.
+//!
+//! Backend integrators use it in place of hand-written XDR:
+//!
+//! - [`StreamContract`] builds the `InvokeContractArgs` of each entrypoint
+//!   from strkey addresses and plain numbers;
+//! - [`CreateParams`] turns human inputs (an amount per month, a duration in
+//!   days, a decimal amount string) into validated creation terms;
+//! - [`StreamState`] decodes `get_stream_state` results and computes accrued
+//!   and withdrawable amounts locally;
+//! - [`StreamEvent`] decodes the contract's events;
+//! - [`RetryPolicy`] retries transient failures with exponential backoff.
+//!
+//! The crate does not talk to the network. Simulate, sign and submit the
+//! invocations with whichever RPC stack the backend already uses.
+
+mod events;
+mod params;
+mod retry;
+mod scval;
+mod state;
+
+use std::fmt;
+
+use stellar_xdr::curr::{InvokeContractArgs, ScAddress, ScError, ScVal};
+
+pub use events::StreamEvent;
+pub use fluxora_types::{Error as ContractError, StreamStatus, INTERFACE_VERSION};
+pub use params::{parse_amount, CreateParams, SECONDS_PER_DAY, SECONDS_PER_MONTH};
+pub use retry::RetryPolicy;
+pub use state::{decode_status, StreamState};
+pub use stellar_xdr::curr as xdr;
+
+/// Failures reported by the client.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClientError {
+    /// A string was not a valid account or contract strkey.
+    InvalidAddress(String),
+    /// Creation inputs the contract would reject, with the reason.
+    InvalidParams(&'static str),
+    /// A value returned by the contract did not have the expected shape.
+    Decode(&'static str),
+    /// The contract rejected the call with one of its typed errors.
+    Contract(ContractError),
+    /// The call failed with a message rather than a typed error, such as a
+    /// contract panic reported by a simulation.
+    Rejected(String),
+    /// The RPC layer failed; worth retrying.
+    Transport(String),
+}
+
+impl ClientError {
+    /// Map a host error from a simulation or transaction result. Contract
+    /// errors the client knows become `Contract`; anything else is reported
+    /// as a decode failure.
+    pub fn from_sc_error(error: &ScError) -> Self {
+        match error {
+            ScError::Contract(code) => match contract_error(*code) {
+                Some(error) => ClientError::Contract(error),
+                None => ClientError::Decode("unknown contract error code"),
+            },
+            _ => ClientError::Decode("host error"),
+        }
+    }
+
+    /// Whether retrying the same request could succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ClientError::Transport(_))
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::InvalidAddress(address) => write!(f, "invalid address: {address}"),
+            ClientError::InvalidParams(reason) => write!(f, "invalid stream parameters: {reason}"),
+            ClientError::Decode(reason) => write!(f, "unexpected contract value: {reason}"),
+            ClientError::Contract(error) => write!(f, "contract error: {error:?}"),
+            ClientError::Rejected(message) => write!(f, "call rejected: {message}"),
+            ClientError::Transport(message) => write!(f, "transport error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+fn contract_error(code: u32) -> Option<ContractError> {
+    Some(match code {
+        1 => ContractError::Overflow,
+        2 => ContractError::DepositTooLarge,
+        3 => ContractError::RateTooLarge,
+        4 => ContractError::HorizonTooFar,
+        5 => ContractError::IncompatibleInterface,
+        6 => ContractError::NonConformingToken,
+        _ => return None,
+    })
+}
+
+/// Parse an account (`G...`) or contract (`C...`) strkey.
+pub fn parse_address(strkey: &str) -> Result<ScAddress, ClientError> {
+    strkey
+        .parse()
+        .map_err(|_| ClientError::InvalidAddress(strkey.into()))
+}
+
+/// A deployed stream contract, for building invocations against it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamContract {
+    pub address: ScAddress,
+}
+
+impl StreamContract {
+    /// Target the contract with strkey `contract_id` (`C...`).
+    pub fn new(contract_id: &str) -> Result<Self, ClientError> {
+        Ok(StreamContract {
+            address: parse_address(contract_id)?,
+        })
+    }
+
+    fn invoke(&self, function: &str, args: Vec<ScVal>) -> InvokeContractArgs {
+        InvokeContractArgs {
+            contract_address: self.address.clone(),
+            function_name: scval::symbol(function),
+            args: args
+                .try_into()
+                .expect("argument count fits in an XDR vector"),
+        }
+    }
+
+    /// `init(token, admin, guardian)`, once after deployment.
+    pub fn init(
+        &self,
+        token: &str,
+        admin: &str,
+        guardian: &str,
+    ) -> Result<InvokeContractArgs, ClientError> {
+        Ok(self.invoke(
+            "init",
+            vec![
+                ScVal::Address(parse_address(token)?),
+                ScVal::Address(parse_address(admin)?),
+                ScVal::Address(parse_address(guardian)?),
+            ],
+        ))
+    }
+
+    /// `create_stream(sender, recipient, deposit, rate, start, cliff, end)`.
+    /// The sender must authorise it.
+    pub fn create_stream(
+        &self,
+        sender: &str,
+        params: &CreateParams,
+    ) -> Result<InvokeContractArgs, ClientError> {
+        Ok(self.invoke(
+            "create_stream",
+            vec![
+                ScVal::Address(parse_address(sender)?),
+                ScVal::Address(parse_address(&params.recipient)?),
+                scval::i128(params.deposit_amount),
+                scval::i128(params.rate_per_second),
+                ScVal::U64(params.start_time),
+                ScVal::U64(params.cliff_time),
+                ScVal::U64(params.end_time),
+            ],
+        ))
+    }
+
+    /// `withdraw(stream_id)`, authorised by the recipient.
+    pub fn withdraw(&self, stream_id: u64) -> InvokeContractArgs {
+        self.invoke("withdraw", vec![ScVal::U64(stream_id)])
+    }
+
+    /// `pause_stream(stream_id)`, authorised by the sender.
+    pub fn pause_stream(&self, stream_id: u64) -> InvokeContractArgs {
+        self.invoke("pause_stream", vec![ScVal::U64(stream_id)])
+    }
+
+    /// `resume_stream(stream_id)`, authorised by the sender.
+    pub fn resume_stream(&self, stream_id: u64) -> InvokeContractArgs {
+        self.invoke("resume_stream", vec![ScVal::U64(stream_id)])
+    }
+
+    /// `cancel_stream(stream_id)`, authorised by the sender.
+    pub fn cancel_stream(&self, stream_id: u64) -> InvokeContractArgs {
+        self.invoke("cancel_stream", vec![ScVal::U64(stream_id)])
+    }
+
+    /// `get_stream_state(stream_id)`; decode the result with
+    /// [`StreamState::from_scval`].
+    pub fn get_stream_state(&self, stream_id: u64) -> InvokeContractArgs {
+        self.invoke("get_stream_state", vec![ScVal::U64(stream_id)])
+    }
+
+    /// `get_tombstone(stream_id)`: the final status of a closed stream.
+    pub fn get_tombstone(&self, stream_id: u64) -> InvokeContractArgs {
+        self.invoke("get_tombstone", vec![ScVal::U64(stream_id)])
+    }
+
+    /// `extend_ttl_for(stream_ids)`, callable by anyone.
+    pub fn extend_ttl_for(&self, stream_ids: &[u64]) -> InvokeContractArgs {
+        let ids: Vec<ScVal> = stream_ids.iter().copied().map(ScVal::U64).collect();
+        self.invoke("extend_ttl_for", vec![scval::vec(ids)])
+    }
+
+    /// `interface_version()`; compare the result with
+    /// [`check_interface_version`].
+    pub fn interface_version(&self) -> InvokeContractArgs {
+        self.invoke("interface_version", vec![])
+    }
+}
+
+/// Check the result of `interface_version` against the revision this
+/// client was built for.
+pub fn check_interface_version(result: &ScVal) -> Result<(), ClientError> {
+    match result {
+        ScVal::U32(INTERFACE_VERSION) => Ok(()),
+        ScVal::U32(_) => Err(ClientError::Contract(ContractError::IncompatibleInterface)),
+        _ => Err(ClientError::Decode("interface version is not a u32")),
+    }
+}
+
+#[cfg(test)]
+mod test;
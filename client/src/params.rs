@@ -0,0 +1,110 @@
+//! Creation terms built from human inputs.
+
+use fluxora_core::Amount;
+
+use crate::ClientError;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+/// A 30-day month, the unit of [`CreateParams::per_month`].
+pub const SECONDS_PER_MONTH: u64 = 30 * SECONDS_PER_DAY;
+
+/// Terms of a `create_stream` call, in base units and ledger seconds.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreateParams {
+    /// Recipient strkey.
+    pub recipient: String,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+impl CreateParams {
+    /// Stream `amount_per_month` base units per 30-day month to `recipient`
+    /// for `duration` seconds from `start_time`, with no cliff.
+    ///
+    /// The contract pays whole base units per second, so the rate is rounded
+    /// down and the deposit is exactly what that rate streams; read the rate
+    /// back from `rate_per_second` if the result must be shown to users.
+    pub fn per_month(
+        recipient: &str,
+        amount_per_month: i128,
+        start_time: u64,
+        duration: u64,
+    ) -> Result<Self, ClientError> {
+        if amount_per_month <= 0 {
+            return Err(ClientError::InvalidParams("amount must be positive"));
+        }
+        if duration == 0 {
+            return Err(ClientError::InvalidParams("duration must be positive"));
+        }
+        let rate_per_second = amount_per_month / SECONDS_PER_MONTH as i128;
+        if rate_per_second == 0 {
+            return Err(ClientError::InvalidParams(
+                "amount per month is below one base unit per second",
+            ));
+        }
+        let deposit_amount = rate_per_second
+            .checked_mul(duration as i128)
+            .ok_or(ClientError::InvalidParams("deposit overflows i128"))?;
+        let end_time = start_time
+            .checked_add(duration)
+            .ok_or(ClientError::InvalidParams("end time overflows u64"))?;
+        Ok(CreateParams {
+            recipient: recipient.into(),
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time: start_time,
+            end_time,
+        })
+    }
+
+    /// Hold everything back until `cliff` seconds after the start.
+    pub fn with_cliff(mut self, cliff: u64) -> Result<Self, ClientError> {
+        let cliff_time = self.start_time.saturating_add(cliff);
+        if cliff_time > self.end_time {
+            return Err(ClientError::InvalidParams("cliff is after the end"));
+        }
+        self.cliff_time = cliff_time;
+        Ok(self)
+    }
+
+    /// What the rounded rate actually streams per 30-day month.
+    pub fn monthly_amount(&self) -> i128 {
+        self.rate_per_second * SECONDS_PER_MONTH as i128
+    }
+}
+
+/// Parse a decimal string such as `"1250.5"` into base units of a token
+/// with `decimals` decimal places.
+pub fn parse_amount(amount: &str, decimals: u32) -> Result<i128, ClientError> {
+    const INVALID: ClientError = ClientError::InvalidParams("amount is not a decimal number");
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(INVALID);
+    }
+    if fraction.len() > decimals as usize {
+        return Err(ClientError::InvalidParams(
+            "amount has more decimal places than the token",
+        ));
+    }
+    let digits = |part: &str| -> Result<i128, ClientError> {
+        if part.is_empty() {
+            return Ok(0);
+        }
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(INVALID);
+        }
+        part.parse().map_err(|_| INVALID)
+    };
+    let fraction = 10_i128
+        .checked_pow(decimals - fraction.len() as u32)
+        .and_then(|scale| digits(fraction).ok()?.checked_mul(scale))
+        .ok_or(ClientError::InvalidParams("amount overflows i128"))?;
+    Amount::from_whole(digits(whole)?, decimals)
+        .and_then(|whole| whole.checked_add(Amount(fraction)))
+        .map(|amount| amount.0)
+        .ok_or(ClientError::InvalidParams("amount overflows i128"))
+}
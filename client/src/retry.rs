@@ -0,0 +1,58 @@
+//! Retrying transient RPC failures.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::ClientError;
+
+/// How often, and how patiently, to retry an operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubled for each further one.
+    pub initial_backoff: Duration,
+    /// Ceiling on any single wait.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (1 for the first retry).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1_u32
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Run `op` until it succeeds, fails with an error that is not
+    /// transient, or runs out of attempts. `op` receives the attempt number,
+    /// starting at 1. Returns the last result.
+    pub fn run<T>(
+        &self,
+        mut op: impl FnMut(u32) -> Result<T, ClientError>,
+    ) -> Result<T, ClientError> {
+        let mut attempt = 1;
+        loop {
+            match op(attempt) {
+                Err(error) if error.is_transient() && attempt < self.max_attempts => {
+                    thread::sleep(self.backoff(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
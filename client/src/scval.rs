@@ -0,0 +1,86 @@
+//! Conversions between plain Rust values and `ScVal`.
+
+use stellar_xdr::curr::{Int128Parts, ScAddress, ScMap, ScSymbol, ScVal, ScVec};
+
+use crate::ClientError;
+
+pub(crate) fn symbol(name: &str) -> ScSymbol {
+    ScSymbol(name.try_into().expect("symbol fits in 32 bytes"))
+}
+
+pub(crate) fn i128(value: i128) -> ScVal {
+    ScVal::I128(Int128Parts {
+        hi: (value >> 64) as i64,
+        lo: value as u64,
+    })
+}
+
+pub(crate) fn vec(items: Vec<ScVal>) -> ScVal {
+    ScVal::Vec(Some(ScVec(
+        items.try_into().expect("item count fits in an XDR vector"),
+    )))
+}
+
+pub(crate) fn to_i128(value: &ScVal) -> Result<i128, ClientError> {
+    match value {
+        ScVal::I128(parts) => Ok(((parts.hi as i128) << 64) | parts.lo as i128),
+        _ => Err(ClientError::Decode("expected an i128")),
+    }
+}
+
+pub(crate) fn to_u64(value: &ScVal) -> Result<u64, ClientError> {
+    match value {
+        ScVal::U64(value) => Ok(*value),
+        _ => Err(ClientError::Decode("expected a u64")),
+    }
+}
+
+pub(crate) fn to_u32(value: &ScVal) -> Result<u32, ClientError> {
+    match value {
+        ScVal::U32(value) => Ok(*value),
+        _ => Err(ClientError::Decode("expected a u32")),
+    }
+}
+
+pub(crate) fn to_bool(value: &ScVal) -> Result<bool, ClientError> {
+    match value {
+        ScVal::Bool(value) => Ok(*value),
+        _ => Err(ClientError::Decode("expected a bool")),
+    }
+}
+
+pub(crate) fn to_address(value: &ScVal) -> Result<ScAddress, ClientError> {
+    match value {
+        ScVal::Address(address) => Ok(address.clone()),
+        _ => Err(ClientError::Decode("expected an address")),
+    }
+}
+
+pub(crate) fn to_symbol(value: &ScVal) -> Result<String, ClientError> {
+    match value {
+        ScVal::Symbol(symbol) => Ok(symbol.to_utf8_string_lossy()),
+        _ => Err(ClientError::Decode("expected a symbol")),
+    }
+}
+
+pub(crate) fn to_vec(value: &ScVal) -> Result<&[ScVal], ClientError> {
+    match value {
+        ScVal::Vec(Some(items)) => Ok(items.as_slice()),
+        _ => Err(ClientError::Decode("expected a vector")),
+    }
+}
+
+pub(crate) fn to_map(value: &ScVal) -> Result<&ScMap, ClientError> {
+    match value {
+        ScVal::Map(Some(map)) => Ok(map),
+        _ => Err(ClientError::Decode("expected a map")),
+    }
+}
+
+/// The value stored under symbol `key` in a contract struct's map.
+pub(crate) fn field<'a>(map: &'a ScMap, key: &'static str) -> Result<&'a ScVal, ClientError> {
+    map.iter()
+        .find(|entry| matches!(&entry.key, ScVal::Symbol(symbol) if symbol.as_slice() == key.as_bytes()))
+        .map(|entry| &entry.val)
+        .ok_or(ClientError::Decode(key))
+}
@@ -0,0 +1,108 @@
+//! Stream state decoded from `get_stream_state`, with local accrual.
+
+use fluxora_math::{FlowRate, Schedule};
+use stellar_xdr::curr::{ScAddress, ScVal};
+
+use crate::{scval, ClientError, StreamStatus};
+
+/// A stream as returned by `get_stream_state`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamState {
+    pub stream_id: u64,
+    pub sender: ScAddress,
+    pub recipient: ScAddress,
+    pub token: ScAddress,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+    pub withdrawn_amount: i128,
+    pub status: StreamStatus,
+    pub admin_cancelable: bool,
+    pub credit_refunds: bool,
+    /// Full-precision rate from `get_scaled_rate`, for streams created with
+    /// `create_stream_scaled`. Set it with [`StreamState::with_scaled_rate`].
+    pub scaled_rate: Option<u128>,
+}
+
+impl StreamState {
+    /// Decode the return value of `get_stream_state`.
+    pub fn from_scval(value: &ScVal) -> Result<Self, ClientError> {
+        let map = scval::to_map(value)?;
+        let field = |key| scval::field(map, key);
+        Ok(StreamState {
+            stream_id: scval::to_u64(field("stream_id")?)?,
+            sender: scval::to_address(field("sender")?)?,
+            recipient: scval::to_address(field("recipient")?)?,
+            token: scval::to_address(field("token")?)?,
+            deposit_amount: scval::to_i128(field("deposit_amount")?)?,
+            rate_per_second: scval::to_i128(field("rate_per_second")?)?,
+            start_time: scval::to_u64(field("start_time")?)?,
+            cliff_time: scval::to_u64(field("cliff_time")?)?,
+            end_time: scval::to_u64(field("end_time")?)?,
+            withdrawn_amount: scval::to_i128(field("withdrawn_amount")?)?,
+            status: status(scval::to_u32(field("status")?)?)?,
+            admin_cancelable: scval::to_bool(field("admin_cancelable")?)?,
+            credit_refunds: scval::to_bool(field("credit_refunds")?)?,
+            scaled_rate: None,
+        })
+    }
+
+    /// Accrue at the full-precision rate `get_scaled_rate` returned, instead
+    /// of the rounded `rate_per_second`.
+    pub fn with_scaled_rate(mut self, scaled_rate: Option<u128>) -> Self {
+        self.scaled_rate = scaled_rate;
+        self
+    }
+
+    /// Total accrued to the recipient at `now`, as `calculate_accrued`
+    /// would report it. `now` is on the stream's clock.
+    pub fn accrued_at(&self, now: u64) -> i128 {
+        let schedule = Schedule {
+            start: self.start_time,
+            cliff: self.cliff_time,
+            end: self.end_time,
+        };
+        let rate = match self.scaled_rate {
+            Some(scaled_rate) => FlowRate::Scaled(scaled_rate),
+            None => FlowRate::Whole(self.rate_per_second),
+        };
+        // Only a streamed amount far beyond any deposit overflows, and
+        // accrual is capped at the deposit anyway.
+        fluxora_math::accrued(&schedule, rate, self.deposit_amount, now)
+            .unwrap_or(self.deposit_amount)
+    }
+
+    /// What a `withdraw` at `now` would pay out. Zero while paused, once
+    /// completed or clawed back, and for cancelled streams that settle
+    /// through credits.
+    pub fn withdrawable_at(&self, now: u64) -> i128 {
+        let open = match self.status {
+            StreamStatus::Active => true,
+            StreamStatus::Cancelled => !self.credit_refunds,
+            _ => false,
+        };
+        if !open {
+            return 0;
+        }
+        (self.accrued_at(now) - self.withdrawn_amount).max(0)
+    }
+}
+
+fn status(code: u32) -> Result<StreamStatus, ClientError> {
+    Ok(match code {
+        0 => StreamStatus::Active,
+        1 => StreamStatus::Paused,
+        2 => StreamStatus::Completed,
+        3 => StreamStatus::Cancelled,
+        4 => StreamStatus::ClawedBack,
+        5 => StreamStatus::Distressed,
+        _ => return Err(ClientError::Decode("unknown stream status")),
+    })
+}
+
+/// Decode a `StreamStatus` returned on its own, as by `get_tombstone`.
+pub fn decode_status(value: &ScVal) -> Result<StreamStatus, ClientError> {
+    status(scval::to_u32(value)?)
+}
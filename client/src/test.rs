@@ -0,0 +1,273 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, Symbol, TryFromVal, Val, Vec,
+};
+use stellar_xdr::curr::{InvokeContractArgs, ScAddress, ScError, ScVal};
+
+use crate::{
+    check_interface_version, parse_amount, ClientError, ContractError, CreateParams, RetryPolicy,
+    StreamContract, StreamEvent, StreamStatus, SECONDS_PER_DAY, SECONDS_PER_MONTH,
+};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+fn strkey(address: &Address) -> String {
+    ScAddress::from(address).to_string()
+}
+
+fn to_scval(env: &Env, val: &Val) -> ScVal {
+    ScVal::try_from_val(env, val).unwrap()
+}
+
+/// Submit a built invocation to the in-memory ledger, as an RPC stack
+/// would on the network.
+fn submit(env: &Env, call: &InvokeContractArgs) -> ScVal {
+    let contract =
+        Address::try_from_val(env, &ScVal::Address(call.contract_address.clone())).unwrap();
+    let function = Symbol::new(env, &call.function_name.to_utf8_string_lossy());
+    let mut args = Vec::new(env);
+    for arg in call.args.iter() {
+        args.push_back(Val::try_from_val(env, arg).unwrap());
+    }
+    let result: Val = env.invoke_contract(&contract, &function, args);
+    to_scval(env, &result)
+}
+
+struct TestContext {
+    env: Env,
+    contract: StreamContract,
+    contract_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+        let token = register_token(&env);
+        let contract_id = register_stream(&env, &token);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token, &sender, 100_000_000);
+        TestContext {
+            contract: StreamContract::new(&strkey(&contract_id)).unwrap(),
+            env,
+            contract_id,
+            sender,
+            recipient,
+        }
+    }
+
+    /// Open a 30-day stream of 2_592_000 units a month (1 per second).
+    fn create(&self) -> u64 {
+        let params =
+            CreateParams::per_month(&strkey(&self.recipient), 2_592_000, 0, SECONDS_PER_MONTH)
+                .unwrap();
+        let call = self
+            .contract
+            .create_stream(&strkey(&self.sender), &params)
+            .unwrap();
+        match submit(&self.env, &call) {
+            ScVal::U64(stream_id) => stream_id,
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — invocations and state
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_created_stream_decodes_and_accrues_locally() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create();
+
+    let state = submit(&ctx.env, &ctx.contract.get_stream_state(stream_id));
+    let state = crate::StreamState::from_scval(&state).unwrap();
+    assert_eq!(state.stream_id, stream_id);
+    assert_eq!(state.recipient, ScAddress::from(&ctx.recipient));
+    assert_eq!(state.rate_per_second, 1);
+    assert_eq!(state.deposit_amount, SECONDS_PER_MONTH as i128);
+    assert_eq!(state.status, StreamStatus::Active);
+
+    let now = 10 * SECONDS_PER_DAY;
+    set_time(&ctx.env, now);
+    let client = FluxoraStreamClient::new(&ctx.env, &ctx.contract_id);
+    assert_eq!(state.accrued_at(now), client.calculate_accrued(&stream_id));
+    let withdrawn = submit(&ctx.env, &ctx.contract.withdraw(stream_id));
+    assert_eq!(
+        crate::scval::to_i128(&withdrawn),
+        Ok(state.withdrawable_at(now))
+    );
+}
+
+#[test]
+fn test_withdrawable_is_zero_while_paused() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create();
+    set_time(&ctx.env, 100);
+    submit(&ctx.env, &ctx.contract.pause_stream(stream_id));
+
+    let state = submit(&ctx.env, &ctx.contract.get_stream_state(stream_id));
+    let state = crate::StreamState::from_scval(&state).unwrap();
+    assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.accrued_at(100), 100);
+    assert_eq!(state.withdrawable_at(100), 0);
+}
+
+#[test]
+fn test_interface_version_matches_deployment() {
+    let ctx = TestContext::setup();
+    let version = submit(&ctx.env, &ctx.contract.interface_version());
+    assert_eq!(check_interface_version(&version), Ok(()));
+    assert_eq!(
+        check_interface_version(&ScVal::U32(0)),
+        Err(ClientError::Contract(ContractError::IncompatibleInterface))
+    );
+}
+
+#[test]
+fn test_invalid_strkeys_are_rejected() {
+    assert_eq!(
+        StreamContract::new("not-a-contract"),
+        Err(ClientError::InvalidAddress("not-a-contract".into()))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — events
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_lifecycle_events_decode() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create();
+    set_time(&ctx.env, 100);
+    submit(&ctx.env, &ctx.contract.withdraw(stream_id));
+    submit(&ctx.env, &ctx.contract.cancel_stream(stream_id));
+
+    let decoded: std::vec::Vec<StreamEvent> = ctx
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| *contract == ctx.contract_id)
+        .map(|(_, topics, data)| {
+            let topics: std::vec::Vec<ScVal> = topics
+                .iter()
+                .map(|topic| to_scval(&ctx.env, &topic))
+                .collect();
+            StreamEvent::decode(&topics, &to_scval(&ctx.env, &data)).unwrap()
+        })
+        .collect();
+    assert!(decoded.contains(&StreamEvent::Created {
+        stream_id,
+        deposit_amount: SECONDS_PER_MONTH as i128,
+    }));
+    assert!(decoded.contains(&StreamEvent::Withdrew {
+        stream_id,
+        amount: 100,
+    }));
+    assert!(decoded.contains(&StreamEvent::Cancelled {
+        stream_id,
+        refunded: SECONDS_PER_MONTH as i128 - 100,
+    }));
+}
+
+#[test]
+fn test_unknown_event_decodes_as_other() {
+    let topics = [
+        ScVal::Symbol("split_set".try_into().unwrap()),
+        ScVal::U64(0),
+    ];
+    assert_eq!(
+        StreamEvent::decode(&topics, &ScVal::Void),
+        Ok(StreamEvent::Other {
+            name: "split_set".into()
+        })
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — creation parameters
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_per_month_rounds_rate_down() {
+    let params = CreateParams::per_month("G", 5_000_000, 1_000, 90 * SECONDS_PER_DAY).unwrap();
+    assert_eq!(params.rate_per_second, 1);
+    assert_eq!(params.deposit_amount, 90 * SECONDS_PER_DAY as i128);
+    assert_eq!(params.end_time, 1_000 + 90 * SECONDS_PER_DAY);
+    assert_eq!(params.monthly_amount(), SECONDS_PER_MONTH as i128);
+
+    assert!(CreateParams::per_month("G", 1_000, 0, SECONDS_PER_MONTH).is_err());
+    assert!(params.with_cliff(91 * SECONDS_PER_DAY).is_err());
+}
+
+#[test]
+fn test_parse_amount() {
+    assert_eq!(parse_amount("1250.5", 7), Ok(12_505_000_000));
+    assert_eq!(parse_amount("3", 2), Ok(300));
+    assert_eq!(parse_amount(".25", 2), Ok(25));
+    assert!(parse_amount("1.234", 2).is_err());
+    assert!(parse_amount("-1", 2).is_err());
+    assert!(parse_amount("", 2).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — errors and retries
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_contract_error_codes_map_to_typed_errors() {
+    assert_eq!(
+        ClientError::from_sc_error(&ScError::Contract(5)),
+        ClientError::Contract(ContractError::IncompatibleInterface)
+    );
+    assert!(matches!(
+        ClientError::from_sc_error(&ScError::Contract(99)),
+        ClientError::Decode(_)
+    ));
+}
+
+#[test]
+fn test_retry_policy_retries_only_transient_errors() {
+    let policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    let calls = Cell::new(0);
+    let result = policy.run(|attempt| {
+        calls.set(attempt);
+        if attempt < 3 {
+            Err(ClientError::Transport("timeout".into()))
+        } else {
+            Ok(attempt)
+        }
+    });
+    assert_eq!(result, Ok(3));
+
+    let result: Result<(), _> = policy.run(|attempt| {
+        calls.set(attempt);
+        Err(ClientError::Contract(ContractError::Overflow))
+    });
+    assert!(result.is_err());
+    assert_eq!(calls.get(), 1);
+
+    let default = RetryPolicy::default();
+    assert_eq!(default.backoff(1), Duration::from_millis(500));
+    assert_eq!(default.backoff(3), Duration::from_secs(2));
+    assert_eq!(default.backoff(40), default.max_backoff);
+}
@@ -0,0 +1,192 @@
+#![no_std]
+
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype,
+    crypto::Hash,
+    symbol_short, Address, BytesN, Env, Symbol, TryFromVal, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Failures reported by `__check_auth`. The host requires a contract error
+/// here, unlike the string panics used elsewhere.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccountError {
+    /// The signing key is neither the owner nor a registered session key.
+    UnknownSigner = 1,
+    /// The session key's expiry has passed.
+    SessionExpired = 2,
+    /// The session key's policy does not cover one of the calls.
+    NotPermitted = 3,
+}
+
+/// An ed25519 signature over the authorisation payload.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AccountSignature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+/// What a session key may authorise: `withdraw` on the listed streams of one
+/// FluxoraStream contract, until `expiry`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionPolicy {
+    pub stream_contract: Address,
+    pub stream_ids: Vec<u64>,
+    /// Ledger timestamp after which the key is rejected.
+    pub expiry: u64,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Owner,               // Instance storage: ed25519 public key with full control.
+    Session(BytesN<32>), // Persistent storage: policy per session public key.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_owner(env: &Env) -> BytesN<32> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Owner)
+        .expect("contract not initialised: missing owner")
+}
+
+fn get_session(env: &Env, public_key: &BytesN<32>) -> Option<SessionPolicy> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Session(public_key.clone()))
+}
+
+/// Whether `context` is a `withdraw` on a stream the policy covers.
+fn is_permitted(env: &Env, policy: &SessionPolicy, context: &Context) -> bool {
+    let Context::Contract(call) = context else {
+        return false;
+    };
+    call.contract == policy.stream_contract
+        && call.fn_name == Symbol::new(env, "withdraw")
+        && call
+            .args
+            .get(0)
+            .and_then(|arg| u64::try_from_val(env, &arg).ok())
+            .is_some_and(|stream_id| policy.stream_ids.contains(stream_id))
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraAccount;
+
+#[contractimpl]
+impl FluxoraAccount {
+    /// Initialise the account with the owner's ed25519 public key. Can only
+    /// be called once.
+    pub fn init(env: Env, owner: BytesN<32>) {
+        if env.storage().instance().has(&DataKey::Owner) {
+            panic!("already initialised");
+        }
+        env.storage().instance().set(&DataKey::Owner, &owner);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Register (or replace) a session key limited by `policy`, e.g. a key
+    /// held by a mobile wallet that may only claim from the user's streams.
+    /// Requires the owner's signature.
+    pub fn add_session_key(env: Env, public_key: BytesN<32>, policy: SessionPolicy) {
+        env.current_contract_address().require_auth();
+
+        let key = DataKey::Session(public_key.clone());
+        env.storage().persistent().set(&key, &policy);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+
+        env.events()
+            .publish((symbol_short!("session"), public_key), policy.expiry);
+    }
+
+    /// Revoke a session key. Requires the owner's signature.
+    pub fn revoke_session_key(env: Env, public_key: BytesN<32>) {
+        env.current_contract_address().require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Session(public_key.clone()));
+
+        env.events()
+            .publish((symbol_short!("revoked"), public_key), ());
+    }
+
+    /// Replace the owner key. Requires the current owner's signature.
+    pub fn set_owner(env: Env, owner: BytesN<32>) {
+        env.current_contract_address().require_auth();
+        env.storage().instance().set(&DataKey::Owner, &owner);
+
+        env.events().publish((symbol_short!("owner"),), owner);
+    }
+
+    /// Return the policy of a session key, if registered.
+    pub fn get_session_key(env: Env, public_key: BytesN<32>) -> Option<SessionPolicy> {
+        get_session(&env, &public_key)
+    }
+
+    /// Return the owner's public key.
+    pub fn get_owner(env: Env) -> BytesN<32> {
+        get_owner(&env)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for FluxoraAccount {
+    type Signature = AccountSignature;
+    type Error = AccountError;
+
+    /// Accept the owner's signature for anything, and a session key's
+    /// signature only for calls its policy covers.
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: AccountSignature,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), AccountError> {
+        let owner = get_owner(&env);
+        let policy = if signature.public_key == owner {
+            None
+        } else {
+            Some(get_session(&env, &signature.public_key).ok_or(AccountError::UnknownSigner)?)
+        };
+
+        // Panics (failing authorisation) if the signature does not verify.
+        env.crypto().ed25519_verify(
+            &signature.public_key,
+            &signature_payload.to_bytes().into(),
+            &signature.signature,
+        );
+
+        if let Some(policy) = policy {
+            if env.ledger().timestamp() > policy.expiry {
+                return Err(AccountError::SessionExpired);
+            }
+            for context in auth_contexts.iter() {
+                if !is_permitted(&env, &policy, &context) {
+                    return Err(AccountError::NotPermitted);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,168 @@
+#[cfg(test)]
+extern crate std;
+
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    testutils::{Address as _, BytesN as _},
+    vec, Address, BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
+
+use fluxora_testutils::set_time;
+
+use crate::{AccountError, AccountSignature, FluxoraAccount, FluxoraAccountClient, SessionPolicy};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    account_id: Address,
+    stream_contract: Address,
+    owner: SigningKey,
+    session: SigningKey,
+}
+
+impl TestContext {
+    /// An account owned by one key, with a session key that may withdraw
+    /// from streams 1 and 2 until t=1000.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let owner = SigningKey::from_bytes(&[1; 32]);
+        let session = SigningKey::from_bytes(&[2; 32]);
+        let stream_contract = Address::generate(&env);
+
+        let account_id = env.register_contract(None, FluxoraAccount);
+        let account = FluxoraAccountClient::new(&env, &account_id);
+        account.init(&public_key(&env, &owner));
+        account.add_session_key(
+            &public_key(&env, &session),
+            &SessionPolicy {
+                stream_contract: stream_contract.clone(),
+                stream_ids: vec![&env, 1, 2],
+                expiry: 1_000,
+            },
+        );
+
+        TestContext {
+            env,
+            account_id,
+            stream_contract,
+            owner,
+            session,
+        }
+    }
+
+    fn call(&self, contract: &Address, function: &str, stream_id: u64) -> Context {
+        Context::Contract(ContractContext {
+            contract: contract.clone(),
+            fn_name: Symbol::new(&self.env, function),
+            args: vec![&self.env, stream_id.into_val(&self.env)],
+        })
+    }
+
+    fn withdraw(&self, stream_id: u64) -> Context {
+        self.call(&self.stream_contract, "withdraw", stream_id)
+    }
+
+    /// Run `__check_auth` with `key` signing a random payload for `contexts`.
+    fn check(
+        &self,
+        key: &SigningKey,
+        contexts: Vec<Context>,
+    ) -> Result<(), Result<AccountError, soroban_sdk::InvokeError>> {
+        let payload = BytesN::<32>::random(&self.env);
+        let signature = AccountSignature {
+            public_key: public_key(&self.env, key),
+            signature: BytesN::from_array(&self.env, &key.sign(&payload.to_array()).to_bytes()),
+        };
+        let signature: Val = signature.into_val(&self.env);
+        self.env.try_invoke_contract_check_auth::<AccountError>(
+            &self.account_id,
+            &payload,
+            signature,
+            &contexts,
+        )
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+// ---------------------------------------------------------------------------
+// Tests — __check_auth
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_owner_may_authorise_anything() {
+    let ctx = TestContext::setup();
+    let other = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.check(&ctx.owner, vec![&ctx.env, ctx.call(&other, "transfer", 9)]),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_session_key_limited_to_withdraw_on_listed_streams() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.check(
+            &ctx.session,
+            vec![&ctx.env, ctx.withdraw(1), ctx.withdraw(2)]
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        ctx.check(&ctx.session, vec![&ctx.env, ctx.withdraw(3)]),
+        Err(Ok(AccountError::NotPermitted))
+    );
+    assert_eq!(
+        ctx.check(
+            &ctx.session,
+            vec![&ctx.env, ctx.call(&ctx.stream_contract, "withdraw_to", 1)]
+        ),
+        Err(Ok(AccountError::NotPermitted))
+    );
+}
+
+#[test]
+fn test_expired_and_revoked_session_keys_rejected() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 1_001);
+    assert_eq!(
+        ctx.check(&ctx.session, vec![&ctx.env, ctx.withdraw(1)]),
+        Err(Ok(AccountError::SessionExpired))
+    );
+
+    FluxoraAccountClient::new(&ctx.env, &ctx.account_id)
+        .revoke_session_key(&public_key(&ctx.env, &ctx.session));
+    assert_eq!(
+        ctx.check(&ctx.session, vec![&ctx.env, ctx.withdraw(1)]),
+        Err(Ok(AccountError::UnknownSigner))
+    );
+}
+
+#[test]
+fn test_bad_signature_rejected() {
+    let ctx = TestContext::setup();
+    let payload = BytesN::<32>::random(&ctx.env);
+    let forged = AccountSignature {
+        public_key: public_key(&ctx.env, &ctx.owner),
+        signature: BytesN::from_array(&ctx.env, &ctx.session.sign(&payload.to_array()).to_bytes()),
+    };
+    assert!(ctx
+        .env
+        .try_invoke_contract_check_auth::<AccountError>(
+            &ctx.account_id,
+            &payload,
+            forged.into_val(&ctx.env),
+            &vec![&ctx.env, ctx.withdraw(1)],
+        )
+        .is_err());
+}
@@ -0,0 +1,15 @@
+//! Interfaces of external contracts the advance contract calls into.
+
+pub use fluxora_types::{OperatorAllowance, Stream, StreamStatus};
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used to secure advances.
+#[contractclient(name = "StreamClient")]
+pub trait StreamContract {
+    fn get_stream_state(env: Env, stream_id: u64) -> Stream;
+    fn calculate_accrued(env: Env, stream_id: u64) -> i128;
+    fn withdraw(env: Env, stream_id: u64) -> i128;
+    fn approve_operator(env: Env, stream_id: u64, operator: Address, amount: i128);
+    fn release_operator(env: Env, stream_id: u64);
+    fn get_operator(env: Env, stream_id: u64) -> Option<OperatorAllowance>;
+}
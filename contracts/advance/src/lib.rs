@@ -0,0 +1,291 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{OperatorAllowance, Stream, StreamClient, StreamContract, StreamStatus};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for an advance pool.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// FluxoraStream deployment whose streams secure advances.
+    pub stream_contract: Address,
+    /// Lending token; the pool is this contract's balance of it.
+    pub token: Address,
+    /// Maximum advance as a share of the stream's unvested balance.
+    pub max_ltv_bps: u32,
+    /// Flat fee added to every advance, in basis points of the principal.
+    pub fee_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Advance {
+    pub stream_id: u64,
+    pub borrower: Address,
+    pub principal: i128,
+    /// Principal plus fee, pledged out of the stream's future withdrawals.
+    pub owed: i128,
+    pub opened_at: u64,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,       // Instance storage for the pool terms and stream contract.
+    Advance(u64), // Persistent storage: latest advance per stream.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn set_config(env: &Env, config: &Config) {
+    assert!(
+        config.max_ltv_bps <= fluxora_math::BPS_DENOMINATOR,
+        "max_ltv_bps cannot exceed 10000"
+    );
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+fn load_advance(env: &Env, stream_id: u64) -> Advance {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Advance(stream_id))
+        .expect("advance not found")
+}
+
+/// Amount still to be repaid to this pool out of the stream's withdrawals.
+fn outstanding(env: &Env, stream: &StreamClient, stream_id: u64) -> i128 {
+    match stream.get_operator(&stream_id) {
+        Some(allowance) if allowance.operator == env.current_contract_address() => {
+            allowance.remaining
+        }
+        _ => 0,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraAdvance;
+
+#[contractimpl]
+impl FluxoraAdvance {
+    /// Initialise an advance pool managed by `admin`, lending `token` against
+    /// streams on `stream_contract`. Can only be called once.
+    ///
+    /// # Panics
+    /// - If `max_ltv_bps` exceeds 10000.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        stream_contract: Address,
+        token: Address,
+        max_ltv_bps: u32,
+        fee_bps: u32,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        set_config(
+            &env,
+            &Config {
+                stream_contract,
+                token,
+                max_ltv_bps,
+                fee_bps,
+            },
+        );
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Move `amount` of the lending token from `from` into the pool.
+    pub fn fund(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        token::Client::new(&env, &get_config(&env).token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("funded"), from), amount);
+    }
+
+    /// Withdraw idle liquidity from the pool to `to`. Only the admin may
+    /// call this.
+    pub fn withdraw_liquidity(env: Env, to: Address, amount: i128) {
+        fluxora_access::require_admin(&env);
+        let config = get_config(&env);
+        assert!(amount > 0, "amount must be positive");
+
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("defunded"), to), amount);
+    }
+
+    /// Advance `amount` to the recipient of `stream_id`, secured by the
+    /// stream's unvested balance. The principal plus fee is pledged to this
+    /// pool as an operator allowance, so it is repaid first out of every
+    /// future withdrawal. Only the stream's recipient may call this.
+    ///
+    /// The sender can still cancel the stream, which forfeits the unvested
+    /// collateral; `max_ltv_bps` should be set conservatively with that in
+    /// mind.
+    ///
+    /// # Panics
+    /// - If the stream is not active or pays in a different token.
+    /// - If the stream already carries an operator allowance.
+    /// - If `amount` exceeds `max_ltv_bps` of the unvested balance.
+    pub fn borrow(env: Env, stream_id: u64, amount: i128) -> Advance {
+        let config = get_config(&env);
+        let stream = StreamClient::new(&env, &config.stream_contract);
+        let state = stream.get_stream_state(&stream_id);
+        // Rooting the borrower's auth here also covers `approve_operator`.
+        state.recipient.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        assert!(state.status == StreamStatus::Active, "stream is not active");
+        assert!(state.token == config.token, "stream token not supported");
+        assert!(
+            stream.get_operator(&stream_id).is_none(),
+            "stream already has an operator"
+        );
+
+        let unvested = state.deposit_amount - stream.calculate_accrued(&stream_id);
+        assert!(
+            amount
+                <= fluxora_math::bps(unvested, config.max_ltv_bps)
+                    .expect("overflow calculating LTV limit"),
+            "advance exceeds LTV limit"
+        );
+
+        let owed = fluxora_math::with_fee(amount, config.fee_bps)
+            .expect("overflow calculating advance fee");
+        let pool = env.current_contract_address();
+        stream.approve_operator(&stream_id, &pool, &owed);
+        token::Client::new(&env, &config.token).transfer(&pool, &state.recipient, &amount);
+
+        let advance = Advance {
+            stream_id,
+            borrower: state.recipient.clone(),
+            principal: amount,
+            owed,
+            opened_at: env.ledger().timestamp(),
+        };
+        let key = DataKey::Advance(stream_id);
+        env.storage().persistent().set(&key, &advance);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+
+        env.events().publish(
+            (symbol_short!("advanced"), stream_id),
+            (state.recipient, amount, owed),
+        );
+        advance
+    }
+
+    /// Withdraw from the stream, repaying this pool first and sending the
+    /// rest to the recipient. Callable by anyone. Returns the amount repaid.
+    ///
+    /// # Panics
+    /// - If nothing is outstanding on the stream.
+    pub fn collect(env: Env, stream_id: u64) -> i128 {
+        let stream = StreamClient::new(&env, &get_config(&env).stream_contract);
+        let before = outstanding(&env, &stream, stream_id);
+        assert!(before > 0, "nothing outstanding");
+
+        stream.withdraw(&stream_id);
+        let repaid = before - outstanding(&env, &stream, stream_id);
+
+        env.events()
+            .publish((symbol_short!("repaid"), stream_id), repaid);
+        repaid
+    }
+
+    /// Repay the outstanding balance directly and release the stream's
+    /// allowance. Only the borrower may call this. Returns the amount paid.
+    ///
+    /// # Panics
+    /// - If nothing is outstanding on the stream.
+    pub fn repay(env: Env, stream_id: u64) -> i128 {
+        let config = get_config(&env);
+        let advance = load_advance(&env, stream_id);
+        advance.borrower.require_auth();
+
+        let stream = StreamClient::new(&env, &config.stream_contract);
+        let amount = outstanding(&env, &stream, stream_id);
+        assert!(amount > 0, "nothing outstanding");
+
+        token::Client::new(&env, &config.token).transfer(
+            &advance.borrower,
+            &env.current_contract_address(),
+            &amount,
+        );
+        stream.release_operator(&stream_id);
+
+        env.events()
+            .publish((symbol_short!("repaid"), stream_id), amount);
+        amount
+    }
+
+    /// Return the amount still owed to the pool on `stream_id`.
+    pub fn get_outstanding(env: Env, stream_id: u64) -> i128 {
+        let stream = StreamClient::new(&env, &get_config(&env).stream_contract);
+        outstanding(&env, &stream, stream_id)
+    }
+
+    /// Return the latest advance taken against `stream_id`.
+    pub fn get_advance(env: Env, stream_id: u64) -> Advance {
+        load_advance(&env, stream_id)
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the pool configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
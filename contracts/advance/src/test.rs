@@ -0,0 +1,131 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, Env};
+
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraAdvance, FluxoraAdvanceClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    advance_id: Address,
+    token_id: Address,
+    recipient: Address,
+    stream: u64,
+}
+
+impl TestContext {
+    /// A 5000-unit pool lending at 50% LTV and a 1% fee against a
+    /// 1000-unit, 1000-second salary stream.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+        let stream_client = FluxoraStreamClient::new(&env, &stream_id);
+
+        let lender = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &lender, 5_000);
+        mint(&env, &token_id, &sender, 1_000);
+
+        let advance_id = env.register_contract(None, FluxoraAdvance);
+        let advance = FluxoraAdvanceClient::new(&env, &advance_id);
+        advance.init(&lender, &stream_id, &token_id, &5_000, &100);
+        advance.fund(&lender, &5_000);
+
+        let stream = stream_client.create_stream(&sender, &recipient, &1_000, &1, &0, &0, &1_000);
+
+        TestContext {
+            env,
+            advance_id,
+            token_id,
+            recipient,
+            stream,
+        }
+    }
+
+    fn advance(&self) -> FluxoraAdvanceClient<'_> {
+        FluxoraAdvanceClient::new(&self.env, &self.advance_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — advances and repayment
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_advance_repaid_from_withdrawals() {
+    let ctx = TestContext::setup();
+    let advance = ctx.advance().borrow(&ctx.stream, &400);
+    assert_eq!(advance.owed, 404);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+
+    set_time(&ctx.env, 300);
+    assert_eq!(ctx.advance().collect(&ctx.stream), 300);
+    assert_eq!(ctx.advance().get_outstanding(&ctx.stream), 104);
+
+    set_time(&ctx.env, 500);
+    assert_eq!(ctx.advance().collect(&ctx.stream), 104);
+    assert_eq!(ctx.advance().get_outstanding(&ctx.stream), 0);
+    assert_eq!(ctx.token().balance(&ctx.advance_id), 5_004);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 496);
+}
+
+#[test]
+#[should_panic(expected = "advance exceeds LTV limit")]
+fn test_advance_above_ltv_panics() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 200);
+    // 800 unvested at 50% LTV.
+    ctx.advance().borrow(&ctx.stream, &401);
+}
+
+#[test]
+fn test_early_repayment_releases_stream() {
+    let ctx = TestContext::setup();
+    ctx.advance().borrow(&ctx.stream, &400);
+    mint(&ctx.env, &ctx.token_id, &ctx.recipient, 4);
+    assert_eq!(ctx.advance().repay(&ctx.stream), 404);
+    assert_eq!(ctx.advance().get_outstanding(&ctx.stream), 0);
+    assert!(ctx.advance().try_collect(&ctx.stream).is_err());
+}
+
+#[test]
+#[should_panic(expected = "stream already has an operator")]
+fn test_second_advance_on_stream_panics() {
+    let ctx = TestContext::setup();
+    ctx.advance().borrow(&ctx.stream, &100);
+    ctx.advance().borrow(&ctx.stream, &100);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.advance().propose_admin(&new_admin);
+    assert_ne!(ctx.advance().get_admin(), new_admin);
+
+    ctx.advance().accept_admin();
+    assert_eq!(ctx.advance().get_admin(), new_admin);
+    ctx.advance().withdraw_liquidity(&new_admin, &100);
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.token().balance(&new_admin), 100);
+}
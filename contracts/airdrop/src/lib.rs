@@ -0,0 +1,220 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// A funded one-off distribution committing to its recipients through
+/// `merkle_root`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Distribution {
+    pub distribution_id: u64,
+    pub creator: Address,
+    pub token: Address,
+    /// Root of the tree whose leaves are `sha256(AirdropLeaf XDR)`.
+    pub merkle_root: BytesN<32>,
+    /// Funds not yet claimed.
+    pub remaining: i128,
+    /// Claims are accepted strictly before this timestamp; afterwards the
+    /// creator may reclaim what is left.
+    pub expiration: u64,
+}
+
+/// One recipient's allocation, as committed in the distribution's tree.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AirdropLeaf {
+    /// Position of the leaf; each index can be claimed once.
+    pub index: u32,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    NextDistributionId, // Instance storage for the auto-incrementing distribution ID.
+    Distribution(u64),  // Persistent storage for individual distributions.
+    Claimed(u64, u32),  // Persistent storage: claimed leaves per (distribution, index).
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn load_distribution(env: &Env, distribution_id: u64) -> Distribution {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Distribution(distribution_id))
+        .expect("distribution not found")
+}
+
+fn save_distribution(env: &Env, distribution: &Distribution) {
+    let key = DataKey::Distribution(distribution.distribution_id);
+    env.storage().persistent().set(&key, distribution);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn is_claimed(env: &Env, distribution_id: u64, index: u32) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Claimed(distribution_id, index))
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraAirdrop;
+
+#[contractimpl]
+impl FluxoraAirdrop {
+    /// Fund a distribution with `amount` of `token` and commit to its
+    /// recipients via `merkle_root`. Returns the new distribution id.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive or `expiration` is not in the future.
+    pub fn create_distribution(
+        env: Env,
+        creator: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        amount: i128,
+        expiration: u64,
+    ) -> u64 {
+        creator.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        assert!(
+            expiration > env.ledger().timestamp(),
+            "expiration must be in the future"
+        );
+
+        token::Client::new(&env, &token).transfer(
+            &creator,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let distribution_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextDistributionId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextDistributionId, &(distribution_id + 1));
+
+        let distribution = Distribution {
+            distribution_id,
+            creator: creator.clone(),
+            token,
+            merkle_root,
+            remaining: amount,
+            expiration,
+        };
+        save_distribution(&env, &distribution);
+
+        env.events().publish(
+            (symbol_short!("created"), distribution_id),
+            (creator, amount),
+        );
+        distribution_id
+    }
+
+    /// Claim the allocation described by `leaf`, paying it to the recipient
+    /// immediately. Callable by anyone, since funds always go to
+    /// `leaf.recipient`.
+    ///
+    /// # Panics
+    /// - If the distribution has expired or the leaf was already claimed.
+    /// - If `proof` does not link `leaf` to the distribution's root.
+    pub fn claim(env: Env, distribution_id: u64, leaf: AirdropLeaf, proof: Vec<BytesN<32>>) {
+        let mut distribution = load_distribution(&env, distribution_id);
+        assert!(
+            env.ledger().timestamp() < distribution.expiration,
+            "distribution expired"
+        );
+        assert!(
+            !is_claimed(&env, distribution_id, leaf.index),
+            "already claimed"
+        );
+        assert!(
+            fluxora_merkle::verify(
+                &env,
+                &proof,
+                &distribution.merkle_root,
+                fluxora_merkle::leaf_hash(&env, leaf.clone())
+            ),
+            "invalid proof"
+        );
+        assert!(
+            leaf.amount <= distribution.remaining,
+            "distribution underfunded"
+        );
+
+        let key = DataKey::Claimed(distribution_id, leaf.index);
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+        distribution.remaining -= leaf.amount;
+        save_distribution(&env, &distribution);
+
+        token::Client::new(&env, &distribution.token).transfer(
+            &env.current_contract_address(),
+            &leaf.recipient,
+            &leaf.amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("claimed"), distribution_id, leaf.index),
+            (leaf.recipient, leaf.amount),
+        );
+    }
+
+    /// Return unclaimed funds to the creator once the distribution has
+    /// expired. Only the creator may call this. Returns the amount reclaimed.
+    ///
+    /// # Panics
+    /// - If the distribution has not expired yet.
+    pub fn reclaim(env: Env, distribution_id: u64) -> i128 {
+        let mut distribution = load_distribution(&env, distribution_id);
+        distribution.creator.require_auth();
+        assert!(
+            env.ledger().timestamp() >= distribution.expiration,
+            "distribution not expired"
+        );
+
+        let amount = distribution.remaining;
+        if amount > 0 {
+            token::Client::new(&env, &distribution.token).transfer(
+                &env.current_contract_address(),
+                &distribution.creator,
+                &amount,
+            );
+        }
+        distribution.remaining = 0;
+        save_distribution(&env, &distribution);
+
+        env.events()
+            .publish((symbol_short!("reclaimed"), distribution_id), amount);
+        amount
+    }
+
+    /// Return whether leaf `index` of the distribution has been claimed.
+    pub fn is_claimed(env: Env, distribution_id: u64, index: u32) -> bool {
+        is_claimed(&env, distribution_id, index)
+    }
+
+    /// Return the distribution identified by `distribution_id`.
+    pub fn get_distribution(env: Env, distribution_id: u64) -> Distribution {
+        load_distribution(&env, distribution_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,140 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::Address as _, token::Client as TokenClient, vec, Address, BytesN, Env,
+};
+
+use fluxora_merkle::{hash_pair, leaf_hash};
+
+use fluxora_testutils::{mint, register_token, set_time};
+
+use crate::{AirdropLeaf, FluxoraAirdrop, FluxoraAirdropClient};
+
+const EXPIRATION: u64 = 10_000;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    airdrop_id: Address,
+    token_id: Address,
+    creator: Address,
+    leaves: [AirdropLeaf; 2],
+    distribution_id: u64,
+}
+
+impl TestContext {
+    /// Deploy an airdrop of 100 and 250 units to two recipients, funded with 500.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+
+        let leaves = [
+            AirdropLeaf {
+                index: 0,
+                recipient: Address::generate(&env),
+                amount: 100,
+            },
+            AirdropLeaf {
+                index: 1,
+                recipient: Address::generate(&env),
+                amount: 250,
+            },
+        ];
+        let root = hash_pair(
+            &env,
+            &leaf_hash(&env, leaves[0].clone()),
+            &leaf_hash(&env, leaves[1].clone()),
+        );
+
+        let creator = Address::generate(&env);
+        mint(&env, &token_id, &creator, 500);
+        let airdrop_id = env.register_contract(None, FluxoraAirdrop);
+        let distribution_id = FluxoraAirdropClient::new(&env, &airdrop_id).create_distribution(
+            &creator,
+            &token_id,
+            &root,
+            &500,
+            &EXPIRATION,
+        );
+
+        TestContext {
+            env,
+            airdrop_id,
+            token_id,
+            creator,
+            leaves,
+            distribution_id,
+        }
+    }
+
+    fn airdrop(&self) -> FluxoraAirdropClient<'_> {
+        FluxoraAirdropClient::new(&self.env, &self.airdrop_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// Proof for leaf `i`: its sibling.
+    fn proof(&self, i: usize) -> soroban_sdk::Vec<BytesN<32>> {
+        vec![&self.env, leaf_hash(&self.env, self.leaves[1 - i].clone())]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — claiming
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_claim_pays_recipient_instantly() {
+    let ctx = TestContext::setup();
+    ctx.airdrop()
+        .claim(&ctx.distribution_id, &ctx.leaves[1], &ctx.proof(1));
+
+    assert_eq!(ctx.token().balance(&ctx.leaves[1].recipient), 250);
+    assert!(ctx.airdrop().is_claimed(&ctx.distribution_id, &1));
+    assert_eq!(
+        ctx.airdrop()
+            .get_distribution(&ctx.distribution_id)
+            .remaining,
+        250
+    );
+}
+
+#[test]
+#[should_panic(expected = "already claimed")]
+fn test_double_claim_panics() {
+    let ctx = TestContext::setup();
+    ctx.airdrop()
+        .claim(&ctx.distribution_id, &ctx.leaves[0], &ctx.proof(0));
+    ctx.airdrop()
+        .claim(&ctx.distribution_id, &ctx.leaves[0], &ctx.proof(0));
+}
+
+#[test]
+#[should_panic(expected = "invalid proof")]
+fn test_inflated_amount_rejected() {
+    let ctx = TestContext::setup();
+    let mut leaf = ctx.leaves[0].clone();
+    leaf.amount = 400;
+    ctx.airdrop()
+        .claim(&ctx.distribution_id, &leaf, &ctx.proof(0));
+}
+
+#[test]
+fn test_creator_reclaims_after_expiry() {
+    let ctx = TestContext::setup();
+    ctx.airdrop()
+        .claim(&ctx.distribution_id, &ctx.leaves[0], &ctx.proof(0));
+
+    set_time(&ctx.env, EXPIRATION);
+    assert_eq!(ctx.airdrop().reclaim(&ctx.distribution_id), 400);
+    assert_eq!(ctx.token().balance(&ctx.creator), 400);
+}
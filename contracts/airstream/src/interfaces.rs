@@ -0,0 +1,22 @@
+//! Interfaces of external contracts the airstream campaign contract calls into.
+
+pub use fluxora_types::FeeParams;
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used to open claimed streams.
+#[contractclient(name = "StreamClient")]
+pub trait Stream {
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream_with_token(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64;
+    fn get_fee_params(env: Env) -> FeeParams;
+}
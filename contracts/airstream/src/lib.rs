@@ -0,0 +1,273 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{FeeParams, Stream, StreamClient};
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, BytesN, Env, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// A funded campaign committing to its recipients through `merkle_root`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Campaign {
+    pub campaign_id: u64,
+    pub creator: Address,
+    /// FluxoraStream deployment that claimed streams are opened on.
+    pub stream_contract: Address,
+    pub token: Address,
+    /// Root of the tree whose leaves are `sha256(ClaimTerms XDR)`.
+    pub merkle_root: BytesN<32>,
+    /// Funds not yet committed to claimed streams.
+    pub remaining: i128,
+    /// Claims are accepted strictly before this timestamp; afterwards the
+    /// creator may reclaim what is left.
+    pub expiration: u64,
+}
+
+/// One recipient's vesting terms, as committed in the campaign's tree.
+/// The stream deposit is `rate_per_second × (end_time − start_time)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimTerms {
+    /// Position of the leaf; each index can be claimed once.
+    pub index: u32,
+    pub recipient: Address,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    NextCampaignId,    // Instance storage for the auto-incrementing campaign ID.
+    Campaign(u64),     // Persistent storage for individual campaigns.
+    Claimed(u64, u32), // Persistent storage: claimed leaves per (campaign, index).
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn load_campaign(env: &Env, campaign_id: u64) -> Campaign {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Campaign(campaign_id))
+        .expect("campaign not found")
+}
+
+fn save_campaign(env: &Env, campaign: &Campaign) {
+    let key = DataKey::Campaign(campaign.campaign_id);
+    env.storage().persistent().set(&key, campaign);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn is_claimed(env: &Env, campaign_id: u64, index: u32) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Claimed(campaign_id, index))
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraAirstream;
+
+#[contractimpl]
+impl FluxoraAirstream {
+    /// Fund a campaign with `amount` of `token` and commit to its recipients
+    /// via `merkle_root`. Returns the new campaign id.
+    ///
+    /// The pool must also cover the stream contract's protocol fee on every
+    /// claimed deposit, if one is configured.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive or `expiration` is not in the future.
+    /// - With `IncompatibleInterface` if `stream_contract` is not a
+    ///   compatible FluxoraStream deployment.
+    pub fn create_campaign(
+        env: Env,
+        creator: Address,
+        stream_contract: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        amount: i128,
+        expiration: u64,
+    ) -> u64 {
+        creator.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        assert!(
+            expiration > env.ledger().timestamp(),
+            "expiration must be in the future"
+        );
+        fluxora_types::require_compatible(&env, &stream_contract);
+
+        token::Client::new(&env, &token).transfer(
+            &creator,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let campaign_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextCampaignId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextCampaignId, &(campaign_id + 1));
+
+        let campaign = Campaign {
+            campaign_id,
+            creator: creator.clone(),
+            stream_contract,
+            token,
+            merkle_root,
+            remaining: amount,
+            expiration,
+        };
+        save_campaign(&env, &campaign);
+
+        env.events()
+            .publish((symbol_short!("campaign"), campaign_id), (creator, amount));
+        campaign_id
+    }
+
+    /// Claim the leaf described by `terms`, opening its stream on
+    /// FluxoraStream with this contract as sender. Callable by anyone, since
+    /// the stream always pays `terms.recipient`. Returns the new stream id.
+    ///
+    /// # Panics
+    /// - If the campaign has expired or the leaf was already claimed.
+    /// - If `proof` does not link `terms` to the campaign's root.
+    /// - If the remaining pool cannot cover the deposit and protocol fee.
+    /// - Any of the stream contract's creation checks.
+    pub fn claim(env: Env, campaign_id: u64, terms: ClaimTerms, proof: Vec<BytesN<32>>) -> u64 {
+        let mut campaign = load_campaign(&env, campaign_id);
+        assert!(
+            env.ledger().timestamp() < campaign.expiration,
+            "campaign expired"
+        );
+        assert!(
+            !is_claimed(&env, campaign_id, terms.index),
+            "already claimed"
+        );
+        assert!(
+            fluxora_merkle::verify(
+                &env,
+                &proof,
+                &campaign.merkle_root,
+                fluxora_merkle::leaf_hash(&env, terms.clone())
+            ),
+            "invalid proof"
+        );
+
+        let duration = terms.end_time.saturating_sub(terms.start_time) as i128;
+        let deposit = terms
+            .rate_per_second
+            .checked_mul(duration)
+            .expect("overflow calculating deposit");
+        let stream = StreamClient::new(&env, &campaign.stream_contract);
+        let fee_params = stream.get_fee_params();
+        let fee = fluxora_math::bps(deposit, fee_params.protocol_fee_bps)
+            .expect("overflow calculating protocol fee");
+        assert!(deposit + fee <= campaign.remaining, "campaign underfunded");
+
+        // The stream contract pulls the deposit (and any protocol fee) from
+        // this contract, one call below our direct invocation.
+        let this = env.current_contract_address();
+        let mut auths = vec![
+            &env,
+            fluxora_access::transfer_auth(
+                &env,
+                &campaign.token,
+                &this,
+                &campaign.stream_contract,
+                deposit,
+            ),
+        ];
+        if fee > 0 {
+            auths.push_back(fluxora_access::transfer_auth(
+                &env,
+                &campaign.token,
+                &this,
+                &fee_params.fee_collector,
+                fee,
+            ));
+        }
+        env.authorize_as_current_contract(auths);
+
+        let stream_id = stream.create_stream_with_token(
+            &this,
+            &terms.recipient,
+            &campaign.token,
+            &deposit,
+            &terms.rate_per_second,
+            &terms.start_time,
+            &terms.cliff_time,
+            &terms.end_time,
+        );
+
+        let key = DataKey::Claimed(campaign_id, terms.index);
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+        campaign.remaining -= deposit + fee;
+        save_campaign(&env, &campaign);
+
+        env.events().publish(
+            (symbol_short!("claimed"), campaign_id, terms.index),
+            (terms.recipient, stream_id),
+        );
+        stream_id
+    }
+
+    /// Return unclaimed funds to the creator once the campaign has expired.
+    /// Only the creator may call this. Returns the amount reclaimed.
+    ///
+    /// # Panics
+    /// - If the campaign has not expired yet.
+    pub fn reclaim(env: Env, campaign_id: u64) -> i128 {
+        let mut campaign = load_campaign(&env, campaign_id);
+        campaign.creator.require_auth();
+        assert!(
+            env.ledger().timestamp() >= campaign.expiration,
+            "campaign not expired"
+        );
+
+        let amount = campaign.remaining;
+        if amount > 0 {
+            token::Client::new(&env, &campaign.token).transfer(
+                &env.current_contract_address(),
+                &campaign.creator,
+                &amount,
+            );
+        }
+        campaign.remaining = 0;
+        save_campaign(&env, &campaign);
+
+        env.events()
+            .publish((symbol_short!("reclaimed"), campaign_id), amount);
+        amount
+    }
+
+    /// Return whether leaf `index` of the campaign has been claimed.
+    pub fn is_claimed(env: Env, campaign_id: u64, index: u32) -> bool {
+        is_claimed(&env, campaign_id, index)
+    }
+
+    /// Return the campaign identified by `campaign_id`.
+    pub fn get_campaign(env: Env, campaign_id: u64) -> Campaign {
+        load_campaign(&env, campaign_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,192 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::Address as _, token::Client as TokenClient, vec, Address, BytesN, Env, Vec,
+};
+
+use fluxora_merkle::{hash_pair, leaf_hash};
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{
+    mint, register_outdated_stream, register_stream, register_token, set_time,
+};
+
+use crate::{ClaimTerms, FluxoraAirstream, FluxoraAirstreamClient};
+
+const EXPIRATION: u64 = 10_000;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    airstream_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    creator: Address,
+    /// Four leaves; each recipient vests `rate × 1000` over `[0, 1000]`.
+    terms: [ClaimTerms; 4],
+    root: BytesN<32>,
+    campaign_id: u64,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream and a campaign of 4 recipients funded with
+    /// 10_000 units (the leaves need 1000 + 2000 + 3000 + 4000).
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+
+        let terms: [ClaimTerms; 4] = core::array::from_fn(|i| ClaimTerms {
+            index: i as u32,
+            recipient: Address::generate(&env),
+            rate_per_second: i as i128 + 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        });
+        let leaves: [BytesN<32>; 4] = core::array::from_fn(|i| leaf_hash(&env, terms[i].clone()));
+        let root = hash_pair(
+            &env,
+            &hash_pair(&env, &leaves[0], &leaves[1]),
+            &hash_pair(&env, &leaves[2], &leaves[3]),
+        );
+
+        let creator = Address::generate(&env);
+        mint(&env, &token_id, &creator, 10_000);
+        let airstream_id = env.register_contract(None, FluxoraAirstream);
+        let campaign_id = FluxoraAirstreamClient::new(&env, &airstream_id).create_campaign(
+            &creator,
+            &stream_id,
+            &token_id,
+            &root,
+            &10_000,
+            &EXPIRATION,
+        );
+
+        TestContext {
+            env,
+            airstream_id,
+            stream_id,
+            token_id,
+            creator,
+            terms,
+            root,
+            campaign_id,
+        }
+    }
+
+    fn airstream(&self) -> FluxoraAirstreamClient<'_> {
+        FluxoraAirstreamClient::new(&self.env, &self.airstream_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// Proof for leaf `i` of the 4-leaf tree.
+    fn proof(&self, i: usize) -> Vec<BytesN<32>> {
+        let leaves: [BytesN<32>; 4] =
+            core::array::from_fn(|j| leaf_hash(&self.env, self.terms[j].clone()));
+        let sibling = leaves[i ^ 1].clone();
+        let other = if i < 2 {
+            hash_pair(&self.env, &leaves[2], &leaves[3])
+        } else {
+            hash_pair(&self.env, &leaves[0], &leaves[1])
+        };
+        vec![&self.env, sibling, other]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — claiming
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_claim_opens_stream_for_recipient() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.airstream().get_campaign(&ctx.campaign_id).merkle_root,
+        ctx.root
+    );
+
+    // Anyone can submit the claim; the campaign contract authorises the deposit.
+    ctx.env.set_auths(&[]);
+    let stream_id = ctx
+        .airstream()
+        .claim(&ctx.campaign_id, &ctx.terms[2], &ctx.proof(2));
+
+    let stream = FluxoraStreamClient::new(&ctx.env, &ctx.stream_id).get_stream_state(&stream_id);
+    assert_eq!(stream.recipient, ctx.terms[2].recipient);
+    assert_eq!(stream.sender, ctx.airstream_id);
+    assert_eq!(stream.deposit_amount, 3000);
+    assert!(ctx.airstream().is_claimed(&ctx.campaign_id, &2));
+    assert_eq!(
+        ctx.airstream().get_campaign(&ctx.campaign_id).remaining,
+        7000
+    );
+}
+
+#[test]
+#[should_panic(expected = "already claimed")]
+fn test_double_claim_panics() {
+    let ctx = TestContext::setup();
+    ctx.airstream()
+        .claim(&ctx.campaign_id, &ctx.terms[0], &ctx.proof(0));
+    ctx.airstream()
+        .claim(&ctx.campaign_id, &ctx.terms[0], &ctx.proof(0));
+}
+
+#[test]
+#[should_panic(expected = "invalid proof")]
+fn test_tampered_terms_rejected() {
+    let ctx = TestContext::setup();
+    let mut terms = ctx.terms[0].clone();
+    terms.rate_per_second = 5;
+    ctx.airstream()
+        .claim(&ctx.campaign_id, &terms, &ctx.proof(0));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — expiry
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_creator_reclaims_after_expiry() {
+    let ctx = TestContext::setup();
+    ctx.airstream()
+        .claim(&ctx.campaign_id, &ctx.terms[3], &ctx.proof(3));
+    assert!(ctx.airstream().try_reclaim(&ctx.campaign_id).is_err());
+
+    set_time(&ctx.env, EXPIRATION);
+    assert!(ctx
+        .airstream()
+        .try_claim(&ctx.campaign_id, &ctx.terms[0], &ctx.proof(0))
+        .is_err());
+    assert_eq!(ctx.airstream().reclaim(&ctx.campaign_id), 6000);
+    assert_eq!(ctx.token().balance(&ctx.creator), 6000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream compatibility
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_campaign_on_outdated_stream_contract_panics() {
+    let ctx = TestContext::setup();
+    mint(&ctx.env, &ctx.token_id, &ctx.creator, 1_000);
+    ctx.airstream().create_campaign(
+        &ctx.creator,
+        &register_outdated_stream(&ctx.env),
+        &ctx.token_id,
+        &ctx.root,
+        &1_000,
+        &EXPIRATION,
+    );
+}
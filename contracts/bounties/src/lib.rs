@@ -0,0 +1,284 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{FeeParams, Stream, StreamClient};
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, BytesN, Env,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// How an awarded bounty was paid out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Award {
+    None,
+    /// Paid to the hunter in full on approval.
+    Instant(Address),
+    /// Streamed to the hunter through stream `u64` on the stream contract.
+    Streamed(Address, u64),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BountyStatus {
+    Open = 0,
+    Awarded = 1,
+    Cancelled = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bounty {
+    pub bounty_id: u64,
+    pub poster: Address,
+    pub token: Address,
+    /// Escrowed reward.
+    pub reward: i128,
+    /// Seconds over which the reward is streamed; zero pays instantly.
+    pub retention_period: u64,
+    pub status: BountyStatus,
+    pub award: Award,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    StreamContract, // Instance storage: FluxoraStream used for retention payouts.
+    NextBountyId,   // Instance storage for the auto-incrementing bounty ID.
+    Bounty(u64),    // Persistent storage for individual bounties.
+    Submission(u64, Address), // Persistent storage: submission hash per (bounty, hunter).
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_stream_contract(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::StreamContract)
+        .expect("contract not initialised: missing stream contract")
+}
+
+fn load_bounty(env: &Env, bounty_id: u64) -> Bounty {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Bounty(bounty_id))
+        .expect("bounty not found")
+}
+
+fn save_bounty(env: &Env, bounty: &Bounty) {
+    let key = DataKey::Bounty(bounty.bounty_id);
+    env.storage().persistent().set(&key, bounty);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn assert_open(bounty: &Bounty) {
+    assert!(bounty.status == BountyStatus::Open, "bounty is not open");
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraBounties;
+
+#[contractimpl]
+impl FluxoraBounties {
+    /// Initialise with the FluxoraStream deployment that carries retention
+    /// payouts. Can only be called once.
+    pub fn init(env: Env, stream_contract: Address) {
+        if env.storage().instance().has(&DataKey::StreamContract) {
+            panic!("already initialised");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamContract, &stream_contract);
+        env.storage().instance().set(&DataKey::NextBountyId, &0u64);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Post a bounty, escrowing `reward` of `token` from `poster`. The
+    /// winner is paid instantly if `retention_period` is zero, otherwise the
+    /// reward streams to them over that many seconds. Returns the bounty id.
+    ///
+    /// # Panics
+    /// - If `reward` is not positive.
+    /// - If `retention_period` does not divide the reward evenly.
+    pub fn post(
+        env: Env,
+        poster: Address,
+        token: Address,
+        reward: i128,
+        retention_period: u64,
+    ) -> u64 {
+        poster.require_auth();
+        assert!(reward > 0, "reward must be positive");
+        assert!(
+            retention_period == 0 || reward % retention_period as i128 == 0,
+            "retention_period must divide the reward evenly"
+        );
+
+        token::Client::new(&env, &token).transfer(
+            &poster,
+            &env.current_contract_address(),
+            &reward,
+        );
+
+        let bounty_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextBountyId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextBountyId, &(bounty_id + 1));
+
+        save_bounty(
+            &env,
+            &Bounty {
+                bounty_id,
+                poster: poster.clone(),
+                token,
+                reward,
+                retention_period,
+                status: BountyStatus::Open,
+                award: Award::None,
+            },
+        );
+
+        env.events()
+            .publish((symbol_short!("posted"), bounty_id), (poster, reward));
+        bounty_id
+    }
+
+    /// Submit work for an open bounty, identified by the hash of its
+    /// off-chain content. Resubmitting replaces the earlier hash.
+    pub fn submit(env: Env, bounty_id: u64, hunter: Address, submission: BytesN<32>) {
+        hunter.require_auth();
+        assert_open(&load_bounty(&env, bounty_id));
+
+        let key = DataKey::Submission(bounty_id, hunter.clone());
+        env.storage().persistent().set(&key, &submission);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+
+        env.events().publish(
+            (symbol_short!("submitted"), bounty_id),
+            (hunter, submission),
+        );
+    }
+
+    /// Approve `hunter`'s submission and pay the reward, instantly or as a
+    /// stream over the retention period. Any protocol fee on the stream is
+    /// paid by the poster on top of the escrow. Only the poster may call this.
+    ///
+    /// # Panics
+    /// - If the bounty is not open.
+    /// - If `hunter` has not submitted.
+    pub fn approve(env: Env, bounty_id: u64, hunter: Address) {
+        let mut bounty = load_bounty(&env, bounty_id);
+        bounty.poster.require_auth();
+        assert_open(&bounty);
+        assert!(
+            env.storage()
+                .persistent()
+                .has(&DataKey::Submission(bounty_id, hunter.clone())),
+            "no submission from hunter"
+        );
+
+        let escrow = env.current_contract_address();
+        let token_client = token::Client::new(&env, &bounty.token);
+        if bounty.retention_period == 0 {
+            token_client.transfer(&escrow, &hunter, &bounty.reward);
+            bounty.award = Award::Instant(hunter.clone());
+        } else {
+            let stream_contract = get_stream_contract(&env);
+            let stream = StreamClient::new(&env, &stream_contract);
+            let fee_params = stream.get_fee_params();
+
+            // The stream contract pulls the reward (and any protocol fee)
+            // from the escrow, one call below our direct invocation.
+            let mut auths = vec![
+                &env,
+                fluxora_access::transfer_auth(
+                    &env,
+                    &bounty.token,
+                    &escrow,
+                    &stream_contract,
+                    bounty.reward,
+                ),
+            ];
+            let fee = fluxora_math::bps(bounty.reward, fee_params.protocol_fee_bps)
+                .expect("overflow calculating protocol fee");
+            if fee > 0 {
+                token_client.transfer(&bounty.poster, &escrow, &fee);
+                auths.push_back(fluxora_access::transfer_auth(
+                    &env,
+                    &bounty.token,
+                    &escrow,
+                    &fee_params.fee_collector,
+                    fee,
+                ));
+            }
+            env.authorize_as_current_contract(auths);
+
+            let now = env.ledger().timestamp();
+            let stream_id = stream.create_stream_with_token(
+                &escrow,
+                &hunter,
+                &bounty.token,
+                &bounty.reward,
+                &(bounty.reward / bounty.retention_period as i128),
+                &now,
+                &now,
+                &(now + bounty.retention_period),
+            );
+            bounty.award = Award::Streamed(hunter.clone(), stream_id);
+        }
+        bounty.status = BountyStatus::Awarded;
+        save_bounty(&env, &bounty);
+
+        env.events()
+            .publish((symbol_short!("awarded"), bounty_id), hunter);
+    }
+
+    /// Cancel an open bounty and refund the escrow to the poster. Only the
+    /// poster may call this.
+    pub fn cancel(env: Env, bounty_id: u64) {
+        let mut bounty = load_bounty(&env, bounty_id);
+        bounty.poster.require_auth();
+        assert_open(&bounty);
+
+        token::Client::new(&env, &bounty.token).transfer(
+            &env.current_contract_address(),
+            &bounty.poster,
+            &bounty.reward,
+        );
+        bounty.status = BountyStatus::Cancelled;
+        save_bounty(&env, &bounty);
+
+        env.events()
+            .publish((symbol_short!("cancelled"), bounty_id), bounty.reward);
+    }
+
+    /// Return `hunter`'s submission hash for `bounty_id`, if any.
+    pub fn get_submission(env: Env, bounty_id: u64, hunter: Address) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Submission(bounty_id, hunter))
+    }
+
+    /// Return the bounty identified by `bounty_id`.
+    pub fn get_bounty(env: Env, bounty_id: u64) -> Bounty {
+        load_bounty(&env, bounty_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
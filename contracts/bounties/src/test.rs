@@ -0,0 +1,121 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, BytesN, Env};
+
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{Award, BountyStatus, FluxoraBounties, FluxoraBountiesClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    bounties_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    poster: Address,
+    hunter: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+
+        let bounties_id = env.register_contract(None, FluxoraBounties);
+        FluxoraBountiesClient::new(&env, &bounties_id).init(&stream_id);
+
+        let poster = Address::generate(&env);
+        let hunter = Address::generate(&env);
+        mint(&env, &token_id, &poster, 5_000);
+
+        TestContext {
+            env,
+            bounties_id,
+            stream_id,
+            token_id,
+            poster,
+            hunter,
+        }
+    }
+
+    fn bounties(&self) -> FluxoraBountiesClient<'_> {
+        FluxoraBountiesClient::new(&self.env, &self.bounties_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// Post a 1000-unit bounty and submit work for it.
+    fn post_and_submit(&self, retention_period: u64) -> u64 {
+        let id = self
+            .bounties()
+            .post(&self.poster, &self.token_id, &1_000, &retention_period);
+        self.bounties()
+            .submit(&id, &self.hunter, &BytesN::from_array(&self.env, &[1; 32]));
+        id
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — payouts
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_instant_payout() {
+    let ctx = TestContext::setup();
+    let id = ctx.post_and_submit(0);
+    assert_eq!(ctx.token().balance(&ctx.bounties_id), 1_000);
+
+    ctx.bounties().approve(&id, &ctx.hunter);
+    assert_eq!(ctx.token().balance(&ctx.hunter), 1_000);
+    assert_eq!(
+        ctx.bounties().get_bounty(&id).award,
+        Award::Instant(ctx.hunter.clone())
+    );
+}
+
+#[test]
+fn test_streamed_payout_over_retention_period() {
+    let ctx = TestContext::setup();
+    let id = ctx.post_and_submit(500);
+    ctx.bounties().approve(&id, &ctx.hunter);
+
+    let Award::Streamed(_, stream_id) = ctx.bounties().get_bounty(&id).award else {
+        panic!("expected a streamed award");
+    };
+    set_time(&ctx.env, 250);
+    FluxoraStreamClient::new(&ctx.env, &ctx.stream_id).withdraw(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.hunter), 500);
+    assert_eq!(ctx.token().balance(&ctx.bounties_id), 0);
+}
+
+#[test]
+fn test_cancel_refunds_poster() {
+    let ctx = TestContext::setup();
+    let id = ctx.post_and_submit(0);
+    ctx.bounties().cancel(&id);
+    assert_eq!(ctx.token().balance(&ctx.poster), 5_000);
+    assert_eq!(
+        ctx.bounties().get_bounty(&id).status,
+        BountyStatus::Cancelled
+    );
+    assert!(ctx.bounties().try_approve(&id, &ctx.hunter).is_err());
+}
+
+#[test]
+#[should_panic(expected = "no submission from hunter")]
+fn test_approve_without_submission_panics() {
+    let ctx = TestContext::setup();
+    let id = ctx.bounties().post(&ctx.poster, &ctx.token_id, &1_000, &0);
+    ctx.bounties().approve(&id, &ctx.hunter);
+}
@@ -0,0 +1,253 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Metadata describing one official Fluxora contract instance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deployment {
+    pub instance: Address,
+    /// Monotonic contract version deployed at `instance`.
+    pub version: u32,
+    /// Tokens the instance is expected to stream.
+    pub tokens: Vec<Address>,
+    /// Organization that operates the instance.
+    pub org: Address,
+    pub registered_at: u64,
+    /// Deprecated instances stay listed so history is discoverable, but
+    /// clients should stop routing new streams to them.
+    pub deprecated: bool,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    InstanceCount,             // Persistent storage: number of registered instances.
+    Instance(u32),             // Persistent storage: registered instance by position.
+    Deployment(Address),       // Persistent storage: metadata per instance.
+    OrgInstanceCount(Address), // Persistent storage: number of instances per organization.
+    OrgInstance(Address, u32), // Persistent storage: an organization's instance by position.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+/// Lists are kept one entry per position plus a count, so appending never
+/// rewrites, and no entry outgrows, the ledger entry size limit.
+fn list_len(env: &Env, count_key: &DataKey) -> u32 {
+    env.storage().persistent().get(count_key).unwrap_or(0)
+}
+
+fn push_to_list(
+    env: &Env,
+    count_key: &DataKey,
+    entry_key: impl Fn(u32) -> DataKey,
+    instance: &Address,
+) {
+    let len = list_len(env, count_key);
+    let key = entry_key(len);
+    env.storage().persistent().set(&key, instance);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+    env.storage()
+        .persistent()
+        .set(count_key, &len.checked_add(1).expect("overflow"));
+    env.storage()
+        .persistent()
+        .extend_ttl(count_key, 17280, 120960);
+}
+
+fn list_page(
+    env: &Env,
+    count_key: &DataKey,
+    entry_key: impl Fn(u32) -> DataKey,
+    start: u32,
+    limit: u32,
+) -> Vec<Address> {
+    let end = start.saturating_add(limit).min(list_len(env, count_key));
+    let mut page = Vec::new(env);
+    for i in start..end {
+        page.push_back(
+            env.storage()
+                .persistent()
+                .get(&entry_key(i))
+                .expect("instance not found"),
+        );
+    }
+    page
+}
+
+fn load_deployment(env: &Env, instance: &Address) -> Deployment {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Deployment(instance.clone()))
+        .expect("deployment not registered")
+}
+
+fn save_deployment(env: &Env, deployment: &Deployment) {
+    let key = DataKey::Deployment(deployment.instance.clone());
+    env.storage().persistent().set(&key, deployment);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraDeployments;
+
+#[contractimpl]
+impl FluxoraDeployments {
+    /// Initialise the directory with its admin. Can only be called once.
+    pub fn init(env: Env, admin: Address) {
+        fluxora_access::init_admin(&env, &admin);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// List `instance` as an official deployment. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If `instance` is already registered.
+    pub fn register(env: Env, instance: Address, version: u32, tokens: Vec<Address>, org: Address) {
+        fluxora_access::require_admin(&env);
+        assert!(
+            !env.storage()
+                .persistent()
+                .has(&DataKey::Deployment(instance.clone())),
+            "deployment already registered"
+        );
+
+        save_deployment(
+            &env,
+            &Deployment {
+                instance: instance.clone(),
+                version,
+                tokens,
+                org: org.clone(),
+                registered_at: env.ledger().timestamp(),
+                deprecated: false,
+            },
+        );
+        push_to_list(&env, &DataKey::InstanceCount, DataKey::Instance, &instance);
+        push_to_list(
+            &env,
+            &DataKey::OrgInstanceCount(org.clone()),
+            |i| DataKey::OrgInstance(org.clone(), i),
+            &instance,
+        );
+
+        env.events()
+            .publish((symbol_short!("register"), org), (instance, version));
+    }
+
+    /// Record an upgrade of `instance` to `version` and its current token set.
+    /// Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If `version` is lower than the registered version.
+    pub fn update(env: Env, instance: Address, version: u32, tokens: Vec<Address>) {
+        fluxora_access::require_admin(&env);
+        let mut deployment = load_deployment(&env, &instance);
+        assert!(
+            version >= deployment.version,
+            "version cannot be downgraded"
+        );
+
+        deployment.version = version;
+        deployment.tokens = tokens;
+        save_deployment(&env, &deployment);
+
+        env.events()
+            .publish((symbol_short!("update"), instance), version);
+    }
+
+    /// Mark `instance` as deprecated. Only the admin may call this.
+    pub fn deprecate(env: Env, instance: Address) {
+        fluxora_access::require_admin(&env);
+        let mut deployment = load_deployment(&env, &instance);
+        deployment.deprecated = true;
+        save_deployment(&env, &deployment);
+
+        env.events()
+            .publish((symbol_short!("deprecate"), instance), ());
+    }
+
+    /// Hand the directory to a new admin. Only the current admin may call this.
+    pub fn set_admin(env: Env, new_admin: Address) {
+        fluxora_access::set_admin(&env, &new_admin);
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the metadata registered for `instance`, if any.
+    pub fn get_deployment(env: Env, instance: Address) -> Option<Deployment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Deployment(instance))
+    }
+
+    /// Return up to `limit` registered deployments starting at position
+    /// `start`, in registration order.
+    pub fn get_deployments(env: Env, start: u32, limit: u32) -> Vec<Deployment> {
+        let instances = list_page(
+            &env,
+            &DataKey::InstanceCount,
+            DataKey::Instance,
+            start,
+            limit,
+        );
+        let mut page = Vec::new(&env);
+        for instance in instances.iter() {
+            page.push_back(load_deployment(&env, &instance));
+        }
+        page
+    }
+
+    /// Return the number of registered deployments.
+    pub fn count(env: Env) -> u32 {
+        list_len(&env, &DataKey::InstanceCount)
+    }
+
+    /// Return up to `limit` of the instances operated by `org` starting at
+    /// position `start`, in registration order.
+    pub fn get_org_instances(env: Env, org: Address, start: u32, limit: u32) -> Vec<Address> {
+        list_page(
+            &env,
+            &DataKey::OrgInstanceCount(org.clone()),
+            |i| DataKey::OrgInstance(org.clone(), i),
+            start,
+            limit,
+        )
+    }
+
+    /// Return the number of instances operated by `org`.
+    pub fn org_instance_count(env: Env, org: Address) -> u32 {
+        list_len(&env, &DataKey::OrgInstanceCount(org))
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
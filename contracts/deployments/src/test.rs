@@ -0,0 +1,130 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    vec, Address, Env, IntoVal, Vec,
+};
+
+use crate::{FluxoraDeployments, FluxoraDeploymentsClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    directory_id: Address,
+    org: Address,
+    token: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let directory_id = env.register_contract(None, FluxoraDeployments);
+        FluxoraDeploymentsClient::new(&env, &directory_id).init(&admin);
+
+        TestContext {
+            org: Address::generate(&env),
+            token: Address::generate(&env),
+            env,
+            directory_id,
+        }
+    }
+
+    fn directory(&self) -> FluxoraDeploymentsClient<'_> {
+        FluxoraDeploymentsClient::new(&self.env, &self.directory_id)
+    }
+
+    fn register(&self, org: &Address) -> Address {
+        let instance = Address::generate(&self.env);
+        self.directory()
+            .register(&instance, &1, &vec![&self.env, self.token.clone()], org);
+        instance
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — registration and enumeration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_register_and_enumerate() {
+    let ctx = TestContext::setup();
+    let other_org = Address::generate(&ctx.env);
+    let first = ctx.register(&ctx.org);
+    let second = ctx.register(&other_org);
+    let third = ctx.register(&ctx.org);
+
+    assert_eq!(ctx.directory().count(), 3);
+    let page = ctx.directory().get_deployments(&1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).instance, second);
+    assert_eq!(page.get_unchecked(1).org, ctx.org);
+    assert_eq!(ctx.directory().org_instance_count(&ctx.org), 2);
+    assert_eq!(
+        ctx.directory().get_org_instances(&ctx.org, &0, &10),
+        vec![&ctx.env, first, third.clone()]
+    );
+    assert_eq!(
+        ctx.directory().get_org_instances(&ctx.org, &1, &10),
+        vec![&ctx.env, third]
+    );
+}
+
+#[test]
+fn test_update_and_deprecate() {
+    let ctx = TestContext::setup();
+    let instance = ctx.register(&ctx.org);
+    let tokens: Vec<Address> = vec![&ctx.env, ctx.token.clone(), Address::generate(&ctx.env)];
+    ctx.directory().update(&instance, &2, &tokens);
+    ctx.directory().deprecate(&instance);
+
+    let deployment = ctx.directory().get_deployment(&instance).unwrap();
+    assert_eq!(deployment.version, 2);
+    assert_eq!(deployment.tokens, tokens);
+    assert!(deployment.deprecated);
+}
+
+#[test]
+#[should_panic(expected = "version cannot be downgraded")]
+fn test_version_downgrade_panics() {
+    let ctx = TestContext::setup();
+    let instance = ctx.register(&ctx.org);
+    ctx.directory()
+        .update(&instance, &0, &vec![&ctx.env, ctx.token.clone()]);
+}
+
+#[test]
+#[should_panic(expected = "deployment already registered")]
+fn test_duplicate_registration_panics() {
+    let ctx = TestContext::setup();
+    let instance = ctx.register(&ctx.org);
+    ctx.directory()
+        .register(&instance, &1, &vec![&ctx.env, ctx.token.clone()], &ctx.org);
+}
+
+#[test]
+fn test_non_admin_cannot_register() {
+    let ctx = TestContext::setup();
+    let outsider = Address::generate(&ctx.env);
+    let instance = Address::generate(&ctx.env);
+    let tokens: Vec<Address> = vec![&ctx.env, ctx.token.clone()];
+    ctx.env.mock_auths(&[MockAuth {
+        address: &outsider,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.directory_id,
+            fn_name: "register",
+            args: (instance.clone(), 1u32, tokens.clone(), ctx.org.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(ctx
+        .directory()
+        .try_register(&instance, &1, &tokens, &ctx.org)
+        .is_err());
+}
@@ -0,0 +1,338 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Length of the month used for run-rate views (30 days).
+pub const MONTH_SECS: u64 = 2_592_000;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// A supporter's ongoing donation stream to a creator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pledge {
+    pub stream_id: u64,
+    pub rate_per_second: i128,
+    pub end_time: u64,
+}
+
+/// A creator's public fundraising campaign.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Goal {
+    pub target: i128,
+    pub deadline: u64,
+}
+
+/// Progress of a creator's campaign.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalProgress {
+    pub target: i128,
+    pub deadline: u64,
+    /// Donated to the creator so far, across live and closed pledges.
+    pub raised: i128,
+    pub reached: bool,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    StreamContract,           // Instance storage: FluxoraStream carrying the donations.
+    Supporters(Address),      // Persistent storage: supporters with a pledge, per creator.
+    Pledge(Address, Address), // Persistent storage: pledge per (creator, supporter).
+    ClosedTotal(Address),     // Persistent storage: donated through closed pledges, per creator.
+    Goal(Address),            // Persistent storage: campaign goal per creator.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn stream_client(env: &Env) -> StreamClient<'_> {
+    let stream_contract: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::StreamContract)
+        .expect("contract not initialised: missing stream contract");
+    StreamClient::new(env, &stream_contract)
+}
+
+fn get_supporters(env: &Env, creator: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Supporters(creator.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_supporters(env: &Env, creator: &Address, supporters: &Vec<Address>) {
+    let key = DataKey::Supporters(creator.clone());
+    env.storage().persistent().set(&key, supporters);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn get_pledge(env: &Env, creator: &Address, supporter: &Address) -> Option<Pledge> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Pledge(creator.clone(), supporter.clone()))
+}
+
+fn get_closed_total(env: &Env, creator: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ClosedTotal(creator.clone()))
+        .unwrap_or(0)
+}
+
+/// Cancel a pledge's stream if it is still running, refunding the supporter,
+/// and fold what it donated into the creator's closed total.
+fn close_pledge(env: &Env, creator: &Address, supporter: &Address, pledge: &Pledge) {
+    let stream = stream_client(env);
+    let donated = fluxora_types::accrued(env, &stream.address, pledge.stream_id);
+    if env.ledger().timestamp() < pledge.end_time {
+        stream.cancel_stream(&pledge.stream_id);
+    }
+
+    let key = DataKey::ClosedTotal(creator.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &(get_closed_total(env, creator) + donated));
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Pledge(creator.clone(), supporter.clone()));
+}
+
+/// Open a stream from `supporter` to `creator` and record it as their pledge.
+fn open_pledge(
+    env: &Env,
+    creator: &Address,
+    supporter: &Address,
+    rate_per_second: i128,
+    duration: u64,
+) -> u64 {
+    assert!(rate_per_second > 0, "rate_per_second must be positive");
+    let start = env.ledger().timestamp();
+    let end_time = start
+        .checked_add(duration)
+        .expect("overflow calculating end time");
+    let deposit = rate_per_second
+        .checked_mul(duration as i128)
+        .expect("overflow calculating deposit");
+    let stream_id = stream_client(env).create_stream(
+        supporter,
+        creator,
+        &deposit,
+        &rate_per_second,
+        &start,
+        &start,
+        &end_time,
+    );
+
+    let key = DataKey::Pledge(creator.clone(), supporter.clone());
+    env.storage().persistent().set(
+        &key,
+        &Pledge {
+            stream_id,
+            rate_per_second,
+            end_time,
+        },
+    );
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+    stream_id
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraDonations;
+
+#[contractimpl]
+impl FluxoraDonations {
+    /// Initialise with the FluxoraStream deployment that carries donations.
+    /// Can only be called once.
+    pub fn init(env: Env, stream_contract: Address) {
+        if env.storage().instance().has(&DataKey::StreamContract) {
+            panic!("already initialised");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamContract, &stream_contract);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Start supporting `creator` at `rate_per_second` for `duration`
+    /// seconds, prepaid by `supporter`. Returns the stream id.
+    ///
+    /// # Panics
+    /// - If `supporter` already supports `creator` (use `adjust`).
+    pub fn support(
+        env: Env,
+        supporter: Address,
+        creator: Address,
+        rate_per_second: i128,
+        duration: u64,
+    ) -> u64 {
+        supporter.require_auth();
+        assert!(
+            get_pledge(&env, &creator, &supporter).is_none(),
+            "already supporting"
+        );
+
+        let stream_id = open_pledge(&env, &creator, &supporter, rate_per_second, duration);
+        let mut supporters = get_supporters(&env, &creator);
+        supporters.push_back(supporter.clone());
+        set_supporters(&env, &creator, &supporters);
+
+        env.events().publish(
+            (symbol_short!("support"), creator),
+            (supporter, rate_per_second),
+        );
+        stream_id
+    }
+
+    /// Replace `supporter`'s pledge to `creator` with a new rate and
+    /// duration from now. The unstreamed rest of the old pledge is refunded.
+    /// Returns the new stream id.
+    ///
+    /// # Panics
+    /// - If `supporter` does not support `creator`.
+    pub fn adjust(
+        env: Env,
+        supporter: Address,
+        creator: Address,
+        rate_per_second: i128,
+        duration: u64,
+    ) -> u64 {
+        supporter.require_auth();
+        let pledge = get_pledge(&env, &creator, &supporter).expect("not supporting");
+
+        close_pledge(&env, &creator, &supporter, &pledge);
+        let stream_id = open_pledge(&env, &creator, &supporter, rate_per_second, duration);
+
+        env.events().publish(
+            (symbol_short!("adjusted"), creator),
+            (supporter, rate_per_second),
+        );
+        stream_id
+    }
+
+    /// Stop supporting `creator`. The unstreamed rest of the pledge is
+    /// refunded; the creator keeps what already streamed.
+    ///
+    /// # Panics
+    /// - If `supporter` does not support `creator`.
+    pub fn stop(env: Env, supporter: Address, creator: Address) {
+        supporter.require_auth();
+        let pledge = get_pledge(&env, &creator, &supporter).expect("not supporting");
+
+        close_pledge(&env, &creator, &supporter, &pledge);
+        let mut supporters = get_supporters(&env, &creator);
+        if let Some(index) = supporters.first_index_of(&supporter) {
+            supporters.remove(index);
+        }
+        set_supporters(&env, &creator, &supporters);
+
+        env.events()
+            .publish((symbol_short!("stopped"), creator), supporter);
+    }
+
+    /// Set (or replace) `creator`'s public campaign goal.
+    ///
+    /// # Panics
+    /// - If `target` is not positive.
+    pub fn set_goal(env: Env, creator: Address, target: i128, deadline: u64) {
+        creator.require_auth();
+        assert!(target > 0, "target must be positive");
+
+        let key = DataKey::Goal(creator.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &Goal { target, deadline });
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+
+        env.events()
+            .publish((symbol_short!("goal"), creator), (target, deadline));
+    }
+
+    /// Return supporters whose pledge to `creator` is still streaming.
+    pub fn active_supporters(env: Env, creator: Address) -> Vec<Address> {
+        let now = env.ledger().timestamp();
+        let mut active = Vec::new(&env);
+        for supporter in get_supporters(&env, &creator).iter() {
+            if let Some(pledge) = get_pledge(&env, &creator, &supporter) {
+                if now < pledge.end_time {
+                    active.push_back(supporter);
+                }
+            }
+        }
+        active
+    }
+
+    /// Return `creator`'s combined donation rate per month across pledges
+    /// that are still streaming.
+    pub fn monthly_run_rate(env: Env, creator: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        get_supporters(&env, &creator)
+            .iter()
+            .filter_map(|supporter| get_pledge(&env, &creator, &supporter))
+            .filter(|pledge| now < pledge.end_time)
+            .map(|pledge| {
+                pledge
+                    .rate_per_second
+                    .checked_mul(MONTH_SECS as i128)
+                    .expect("overflow calculating run rate")
+            })
+            .sum()
+    }
+
+    /// Return the total donated to `creator` through this contract so far.
+    pub fn total_raised(env: Env, creator: Address) -> i128 {
+        let stream = stream_client(&env);
+        let live: i128 = get_supporters(&env, &creator)
+            .iter()
+            .filter_map(|supporter| get_pledge(&env, &creator, &supporter))
+            .map(|pledge| fluxora_types::accrued(&env, &stream.address, pledge.stream_id))
+            .sum();
+        get_closed_total(&env, &creator) + live
+    }
+
+    /// Return progress towards `creator`'s campaign goal.
+    ///
+    /// # Panics
+    /// - If `creator` has no goal.
+    pub fn goal_progress(env: Env, creator: Address) -> GoalProgress {
+        let goal: Goal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Goal(creator.clone()))
+            .expect("goal not found");
+        let raised = Self::total_raised(env, creator);
+        GoalProgress {
+            target: goal.target,
+            deadline: goal.deadline,
+            raised,
+            reached: raised >= goal.target,
+        }
+    }
+
+    /// Return `supporter`'s current pledge to `creator`, if any.
+    pub fn get_pledge(env: Env, creator: Address, supporter: Address) -> Option<Pledge> {
+        get_pledge(&env, &creator, &supporter)
+    }
+}
+
+#[cfg(test)]
+mod test;
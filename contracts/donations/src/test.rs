@@ -0,0 +1,147 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, vec, Address, Env};
+
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraDonations, FluxoraDonationsClient, MONTH_SECS};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    donations_id: Address,
+    token_id: Address,
+    creator: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+
+        let donations_id = env.register_contract(None, FluxoraDonations);
+        FluxoraDonationsClient::new(&env, &donations_id).init(&stream_id);
+
+        let creator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        mint(&env, &token_id, &alice, 10_000);
+        mint(&env, &token_id, &bob, 10_000);
+
+        TestContext {
+            env,
+            donations_id,
+            token_id,
+            creator,
+            alice,
+            bob,
+        }
+    }
+
+    fn donations(&self) -> FluxoraDonationsClient<'_> {
+        FluxoraDonationsClient::new(&self.env, &self.donations_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — pledges and views
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_supporters_and_run_rate() {
+    let ctx = TestContext::setup();
+    ctx.donations()
+        .support(&ctx.alice, &ctx.creator, &1, &1_000);
+    ctx.donations().support(&ctx.bob, &ctx.creator, &2, &2_000);
+
+    assert_eq!(
+        ctx.donations().active_supporters(&ctx.creator),
+        vec![&ctx.env, ctx.alice.clone(), ctx.bob.clone()]
+    );
+    assert_eq!(
+        ctx.donations().monthly_run_rate(&ctx.creator),
+        3 * MONTH_SECS as i128
+    );
+
+    // Alice's pledge runs out; Bob's keeps streaming.
+    set_time(&ctx.env, 1_500);
+    assert_eq!(
+        ctx.donations().active_supporters(&ctx.creator),
+        vec![&ctx.env, ctx.bob.clone()]
+    );
+    assert_eq!(
+        ctx.donations().monthly_run_rate(&ctx.creator),
+        2 * MONTH_SECS as i128
+    );
+}
+
+#[test]
+#[should_panic(expected = "overflow calculating deposit")]
+fn test_pledge_deposit_overflow_panics() {
+    let ctx = TestContext::setup();
+    ctx.donations()
+        .support(&ctx.alice, &ctx.creator, &i128::MAX, &2);
+}
+
+#[test]
+fn test_adjust_refunds_and_restarts_pledge() {
+    let ctx = TestContext::setup();
+    ctx.donations()
+        .support(&ctx.alice, &ctx.creator, &2, &1_000);
+    set_time(&ctx.env, 100);
+    ctx.donations().adjust(&ctx.alice, &ctx.creator, &1, &500);
+
+    // 200 streamed, 1800 refunded, then 500 prepaid for the new pledge.
+    assert_eq!(ctx.token().balance(&ctx.alice), 10_000 - 200 - 500);
+    let pledge = ctx
+        .donations()
+        .get_pledge(&ctx.creator, &ctx.alice)
+        .unwrap();
+    assert_eq!(pledge.rate_per_second, 1);
+    assert_eq!(pledge.end_time, 600);
+}
+
+#[test]
+fn test_stop_removes_supporter_and_keeps_raised() {
+    let ctx = TestContext::setup();
+    ctx.donations()
+        .support(&ctx.alice, &ctx.creator, &1, &1_000);
+    set_time(&ctx.env, 300);
+    ctx.donations().stop(&ctx.alice, &ctx.creator);
+
+    assert!(ctx.donations().active_supporters(&ctx.creator).is_empty());
+    assert_eq!(ctx.token().balance(&ctx.alice), 9_700);
+    set_time(&ctx.env, 900);
+    assert_eq!(ctx.donations().total_raised(&ctx.creator), 300);
+}
+
+#[test]
+fn test_goal_progress() {
+    let ctx = TestContext::setup();
+    ctx.donations().set_goal(&ctx.creator, &1_000, &5_000);
+    ctx.donations()
+        .support(&ctx.alice, &ctx.creator, &1, &2_000);
+    ctx.donations().support(&ctx.bob, &ctx.creator, &1, &2_000);
+
+    set_time(&ctx.env, 400);
+    let progress = ctx.donations().goal_progress(&ctx.creator);
+    assert_eq!(progress.raised, 800);
+    assert!(!progress.reached);
+
+    set_time(&ctx.env, 500);
+    assert!(ctx.donations().goal_progress(&ctx.creator).reached);
+}
@@ -0,0 +1,294 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Destination weights are expressed out of this total.
+pub const BPS_DENOMINATOR: u32 = fluxora_math::BPS_DENOMINATOR;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// How emitted tokens come into existence.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// Minted on release; this contract must be the token's admin.
+    Mint,
+    /// Paid out of an allocation transferred to this contract up front.
+    Release,
+}
+
+/// One leg of the schedule: a constant emission rate until `end_time`.
+/// Each phase starts where the previous one ends.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Phase {
+    pub end_time: u64,
+    pub rate_per_second: i128,
+}
+
+/// A receiver of emissions and its share of each release.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Destination {
+    pub account: Address,
+    /// Share of each release, in bps; all weights sum to `BPS_DENOMINATOR`.
+    pub weight_bps: u32,
+}
+
+/// Global configuration for the emissions controller.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Protocol token being emitted.
+    pub token: Address,
+    pub source: Source,
+    /// Start of the first phase.
+    pub start_time: u64,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,       // Instance storage for the token and schedule start.
+    Phases,       // Instance storage: the fixed emission schedule.
+    Destinations, // Instance storage: weighted receivers of each release.
+    Released,     // Instance storage: total emitted so far.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn get_phases(env: &Env) -> Vec<Phase> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Phases)
+        .expect("contract not initialised: missing phases")
+}
+
+fn get_destinations(env: &Env) -> Vec<Destination> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Destinations)
+        .expect("contract not initialised: missing destinations")
+}
+
+fn get_released(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Released)
+        .unwrap_or(0)
+}
+
+fn set_destinations(env: &Env, destinations: &Vec<Destination>) {
+    assert!(!destinations.is_empty(), "no destinations");
+    let mut total: u32 = 0;
+    for (i, destination) in destinations.iter().enumerate() {
+        assert!(destination.weight_bps > 0, "weight must be positive");
+        assert!(
+            destinations
+                .iter()
+                .position(|other| other.account == destination.account)
+                == Some(i),
+            "duplicate destination"
+        );
+        total = total.saturating_add(destination.weight_bps);
+    }
+    assert!(total == BPS_DENOMINATOR, "weights must sum to 10000 bps");
+    env.storage()
+        .instance()
+        .set(&DataKey::Destinations, destinations);
+}
+
+/// Total scheduled to be emitted by `timestamp`: elapsed time in each phase
+/// times its rate.
+fn emitted_at(env: &Env, timestamp: u64) -> i128 {
+    let phases = get_phases(env);
+    fluxora_math::piecewise(
+        get_config(env).start_time,
+        phases
+            .iter()
+            .map(|phase| (phase.end_time, phase.rate_per_second)),
+        timestamp,
+    )
+    .expect("overflow calculating emissions")
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraEmissions;
+
+#[contractimpl]
+impl FluxoraEmissions {
+    /// Initialise a schedule emitting `token` from `start_time` through
+    /// `phases`, split across `destinations` and re-weighted by `admin`.
+    /// With `Source::Release`, fund the contract with the whole allocation
+    /// before the first release. Can only be called once.
+    ///
+    /// # Panics
+    /// - If `phases` is empty, a phase ends before the previous one, or a
+    ///   rate is negative.
+    /// - If `destinations` is empty, lists an account twice, or the weights
+    ///   do not sum to `BPS_DENOMINATOR`.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        token: Address,
+        source: Source,
+        start_time: u64,
+        phases: Vec<Phase>,
+        destinations: Vec<Destination>,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        assert!(!phases.is_empty(), "no phases");
+        let mut cursor = start_time;
+        for phase in phases.iter() {
+            assert!(phase.end_time > cursor, "phases must be in order");
+            assert!(phase.rate_per_second >= 0, "rate must not be negative");
+            cursor = phase.end_time;
+        }
+
+        fluxora_access::init_admin(&env, &admin);
+        env.storage().instance().set(
+            &DataKey::Config,
+            &Config {
+                token,
+                source,
+                start_time,
+            },
+        );
+        env.storage().instance().set(&DataKey::Phases, &phases);
+        set_destinations(&env, &destinations);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Replace the destinations of future releases. Only the admin may call
+    /// this; release first to settle what is already due under the old split.
+    ///
+    /// # Panics
+    /// - If `destinations` is empty, lists an account twice, or the weights
+    ///   do not sum to `BPS_DENOMINATOR`.
+    pub fn set_destinations(env: Env, destinations: Vec<Destination>) {
+        fluxora_access::require_admin(&env);
+        set_destinations(&env, &destinations);
+
+        env.events()
+            .publish((symbol_short!("dests"),), destinations.len());
+    }
+
+    /// Emit everything scheduled since the last release to the destinations
+    /// by weight, with rounding dust going to the last one. Callable by
+    /// anyone. Returns the amount emitted.
+    ///
+    /// # Panics
+    /// - If nothing is due.
+    pub fn release(env: Env) -> i128 {
+        let config = get_config(&env);
+        let released = get_released(&env);
+        let amount = emitted_at(&env, env.ledger().timestamp()) - released;
+        assert!(amount > 0, "nothing to release");
+        env.storage()
+            .instance()
+            .set(&DataKey::Released, &(released + amount));
+
+        let destinations = get_destinations(&env);
+        let last = destinations.len() - 1;
+        let mut remaining = amount;
+        for (i, destination) in destinations.iter().enumerate() {
+            let share = if i as u32 == last {
+                remaining
+            } else {
+                fluxora_math::bps(amount, destination.weight_bps)
+                    .expect("overflow calculating emissions")
+            };
+            remaining -= share;
+            if share == 0 {
+                continue;
+            }
+            match config.source {
+                Source::Mint => token::StellarAssetClient::new(&env, &config.token)
+                    .mint(&destination.account, &share),
+                Source::Release => token::Client::new(&env, &config.token).transfer(
+                    &env.current_contract_address(),
+                    &destination.account,
+                    &share,
+                ),
+            }
+        }
+
+        env.events()
+            .publish((symbol_short!("emitted"),), (amount, released + amount));
+        amount
+    }
+
+    /// Return the amount a `release` would emit right now.
+    pub fn releasable(env: Env) -> i128 {
+        emitted_at(&env, env.ledger().timestamp()) - get_released(&env)
+    }
+
+    /// Return the total scheduled to be emitted by `timestamp`.
+    pub fn emitted_at(env: Env, timestamp: u64) -> i128 {
+        emitted_at(&env, timestamp)
+    }
+
+    /// Return the total emitted so far.
+    pub fn total_released(env: Env) -> i128 {
+        get_released(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the emissions configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the emission schedule.
+    pub fn get_phases(env: Env) -> Vec<Phase> {
+        get_phases(&env)
+    }
+
+    /// Return the current destinations.
+    pub fn get_destinations(env: Env) -> Vec<Destination> {
+        get_destinations(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
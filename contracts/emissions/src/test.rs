@@ -0,0 +1,173 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env,
+};
+
+use fluxora_testutils::{mint, register_token, set_time};
+
+use crate::{Destination, FluxoraEmissions, FluxoraEmissionsClient, Phase, Source};
+
+const YEAR: u64 = 31_536_000;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    emissions_id: Address,
+    token_id: Address,
+    staking: Address,
+    treasury: Address,
+}
+
+impl TestContext {
+    /// A two-year halving schedule from t=1000 (4/s then 2/s), split 75%
+    /// to staking and 25% to the treasury.
+    fn setup(source: Source) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let staking = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        let emissions_id = env.register_contract(None, FluxoraEmissions);
+        FluxoraEmissionsClient::new(&env, &emissions_id).init(
+            &Address::generate(&env),
+            &token_id,
+            &source,
+            &1_000,
+            &vec![
+                &env,
+                Phase {
+                    end_time: 1_000 + YEAR,
+                    rate_per_second: 4,
+                },
+                Phase {
+                    end_time: 1_000 + 2 * YEAR,
+                    rate_per_second: 2,
+                },
+            ],
+            &vec![
+                &env,
+                Destination {
+                    account: staking.clone(),
+                    weight_bps: 7_500,
+                },
+                Destination {
+                    account: treasury.clone(),
+                    weight_bps: 2_500,
+                },
+            ],
+        );
+
+        match source {
+            Source::Mint => StellarAssetClient::new(&env, &token_id).set_admin(&emissions_id),
+            Source::Release => mint(&env, &token_id, &emissions_id, 6 * YEAR as i128),
+        }
+
+        TestContext {
+            env,
+            emissions_id,
+            token_id,
+            staking,
+            treasury,
+        }
+    }
+
+    fn emissions(&self) -> FluxoraEmissionsClient<'_> {
+        FluxoraEmissionsClient::new(&self.env, &self.emissions_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — schedule and releases
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_release_splits_by_weight() {
+    let ctx = TestContext::setup(Source::Release);
+    set_time(&ctx.env, 1_101);
+    assert_eq!(ctx.emissions().release(), 404);
+    assert_eq!(ctx.token().balance(&ctx.staking), 303);
+    assert_eq!(ctx.token().balance(&ctx.treasury), 101);
+    assert_eq!(ctx.emissions().total_released(), 404);
+}
+
+#[test]
+fn test_schedule_steps_down_across_phases_and_ends() {
+    let ctx = TestContext::setup(Source::Release);
+    assert_eq!(
+        ctx.emissions().emitted_at(&(1_000 + YEAR + 10)),
+        4 * YEAR as i128 + 20
+    );
+
+    set_time(&ctx.env, 1_000 + 5 * YEAR);
+    assert_eq!(ctx.emissions().release(), 6 * YEAR as i128);
+    assert_eq!(ctx.emissions().releasable(), 0);
+    assert_eq!(ctx.token().balance(&ctx.emissions_id), 0);
+}
+
+#[test]
+fn test_mint_source_creates_supply() {
+    let ctx = TestContext::setup(Source::Mint);
+    set_time(&ctx.env, 1_010);
+    ctx.emissions().release();
+    assert_eq!(ctx.token().balance(&ctx.staking), 30);
+    assert_eq!(ctx.token().balance(&ctx.treasury), 10);
+}
+
+#[test]
+#[should_panic(expected = "nothing to release")]
+fn test_release_before_start_panics() {
+    let ctx = TestContext::setup(Source::Release);
+    set_time(&ctx.env, 999);
+    ctx.emissions().release();
+}
+
+#[test]
+#[should_panic(expected = "duplicate destination")]
+fn test_duplicate_destination_panics() {
+    let ctx = TestContext::setup(Source::Release);
+    let split = |weight_bps| Destination {
+        account: ctx.treasury.clone(),
+        weight_bps,
+    };
+    ctx.emissions()
+        .set_destinations(&vec![&ctx.env, split(5_000), split(5_000)]);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup(Source::Release);
+    let new_admin = Address::generate(&ctx.env);
+    ctx.emissions().propose_admin(&new_admin);
+    assert_ne!(ctx.emissions().get_admin(), new_admin);
+
+    ctx.emissions().accept_admin();
+    assert_eq!(ctx.emissions().get_admin(), new_admin);
+    let destinations = vec![
+        &ctx.env,
+        Destination {
+            account: ctx.treasury.clone(),
+            weight_bps: 10_000,
+        },
+    ];
+    ctx.emissions().set_destinations(&destinations);
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.emissions().get_destinations(), destinations);
+}
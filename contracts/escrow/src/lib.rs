@@ -0,0 +1,323 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, Env, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    /// Funded by the buyer, awaiting delivery.
+    Funded = 0,
+    /// A party raised a dispute; only the arbiter can settle it.
+    Disputed = 1,
+    /// Fully paid to the seller, at once or milestone by milestone.
+    Released = 2,
+    /// Returned to the buyer by the seller.
+    Refunded = 3,
+    /// Split by the arbiter's ruling.
+    Resolved = 4,
+}
+
+/// A deliverable-sized tranche of an escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    pub released: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Escrow {
+    pub escrow_id: u64,
+    pub buyer: Address,
+    pub seller: Address,
+    /// Pre-agreed third party who rules on disputes.
+    pub arbiter: Address,
+    pub token: Address,
+    /// Total funded; the sum of all milestone amounts.
+    pub amount: i128,
+    /// Tranches unlocked by approval. A plain escrow has a single milestone.
+    pub milestones: Vec<Milestone>,
+    /// Amount already paid to the seller through milestone approvals.
+    pub released: i128,
+    pub status: EscrowStatus,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    NextEscrowId, // Instance storage for the auto-incrementing escrow ID.
+    Escrow(u64),  // Persistent storage for individual escrows.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn load_escrow(env: &Env, escrow_id: u64) -> Escrow {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Escrow(escrow_id))
+        .expect("escrow not found")
+}
+
+fn save_escrow(env: &Env, escrow: &Escrow) {
+    let key = DataKey::Escrow(escrow.escrow_id);
+    env.storage().persistent().set(&key, escrow);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+/// Mark milestone `index` released and pay its tranche to the seller.
+/// Completes the escrow once every milestone is released.
+fn release_milestone(env: &Env, escrow: &mut Escrow, index: u32) {
+    let mut milestone = escrow.milestones.get(index).expect("milestone not found");
+    assert!(!milestone.released, "milestone already released");
+
+    milestone.released = true;
+    escrow.milestones.set(index, milestone.clone());
+    escrow.released += milestone.amount;
+    pay(env, escrow, &escrow.seller, milestone.amount);
+    if escrow.released == escrow.amount {
+        escrow.status = EscrowStatus::Released;
+    }
+
+    env.events().publish(
+        (symbol_short!("milestone"), escrow.escrow_id, index),
+        milestone.amount,
+    );
+}
+
+fn pay(env: &Env, escrow: &Escrow, to: &Address, amount: i128) {
+    if amount > 0 {
+        token::Client::new(env, &escrow.token).transfer(
+            &env.current_contract_address(),
+            to,
+            &amount,
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraEscrow;
+
+#[contractimpl]
+impl FluxoraEscrow {
+    /// Fund a new escrow of `amount` of `token` from `buyer` for `seller`,
+    /// with `arbiter` agreed to settle disputes. Returns the escrow id.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive.
+    /// - If the arbiter is the buyer or the seller.
+    pub fn create(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+    ) -> u64 {
+        let amounts = vec![&env, amount];
+        Self::create_with_milestones(env, buyer, seller, arbiter, token, amounts)
+    }
+
+    /// Fund a new escrow whose amount unlocks per approved milestone: each
+    /// entry of `amounts` is one deliverable's tranche. Returns the escrow id.
+    ///
+    /// # Panics
+    /// - If `amounts` is empty or any tranche is not positive.
+    /// - If the arbiter is the buyer or the seller.
+    pub fn create_with_milestones(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        arbiter: Address,
+        token: Address,
+        amounts: Vec<i128>,
+    ) -> u64 {
+        buyer.require_auth();
+        assert!(!amounts.is_empty(), "no milestones");
+        let mut amount: i128 = 0;
+        let mut milestones = Vec::new(&env);
+        for tranche in amounts.iter() {
+            assert!(tranche > 0, "amount must be positive");
+            amount = amount
+                .checked_add(tranche)
+                .expect("overflow summing milestones");
+            milestones.push_back(Milestone {
+                amount: tranche,
+                released: false,
+            });
+        }
+        assert!(buyer != seller, "buyer and seller must be different");
+        assert!(
+            arbiter != buyer && arbiter != seller,
+            "arbiter must be a third party"
+        );
+
+        token::Client::new(&env, &token).transfer(&buyer, &env.current_contract_address(), &amount);
+
+        let escrow_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextEscrowId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextEscrowId, &(escrow_id + 1));
+
+        let escrow = Escrow {
+            escrow_id,
+            buyer,
+            seller,
+            arbiter,
+            token,
+            amount,
+            milestones,
+            released: 0,
+            status: EscrowStatus::Funded,
+        };
+        save_escrow(&env, &escrow);
+
+        env.events()
+            .publish((symbol_short!("created"), escrow_id), amount);
+        escrow_id
+    }
+
+    /// Pay everything still held to the seller after delivery, releasing
+    /// every outstanding milestone. Only the buyer may call this.
+    ///
+    /// # Panics
+    /// - If the escrow is not `Funded`.
+    pub fn release(env: Env, escrow_id: u64) {
+        let mut escrow = load_escrow(&env, escrow_id);
+        escrow.buyer.require_auth();
+        assert!(
+            escrow.status == EscrowStatus::Funded,
+            "escrow is not funded"
+        );
+
+        let outstanding = escrow.amount - escrow.released;
+        for index in 0..escrow.milestones.len() {
+            if !escrow.milestones.get(index).unwrap().released {
+                release_milestone(&env, &mut escrow, index);
+            }
+        }
+        save_escrow(&env, &escrow);
+
+        env.events()
+            .publish((symbol_short!("released"), escrow_id), outstanding);
+    }
+
+    /// Approve milestone `index`, releasing its tranche to the seller.
+    /// Only the buyer or the arbiter may call this.
+    ///
+    /// # Panics
+    /// - If `approver` is neither the buyer nor the arbiter.
+    /// - If the escrow is not `Funded`, or the milestone was already released.
+    pub fn approve_milestone(env: Env, escrow_id: u64, index: u32, approver: Address) {
+        approver.require_auth();
+        let mut escrow = load_escrow(&env, escrow_id);
+        assert!(
+            approver == escrow.buyer || approver == escrow.arbiter,
+            "only the buyer or arbiter can approve"
+        );
+        assert!(
+            escrow.status == EscrowStatus::Funded,
+            "escrow is not funded"
+        );
+
+        release_milestone(&env, &mut escrow, index);
+        save_escrow(&env, &escrow);
+    }
+
+    /// Return everything still held to the buyer. Only the seller may call this.
+    ///
+    /// # Panics
+    /// - If the escrow is not `Funded`.
+    pub fn refund(env: Env, escrow_id: u64) {
+        let mut escrow = load_escrow(&env, escrow_id);
+        escrow.seller.require_auth();
+        assert!(
+            escrow.status == EscrowStatus::Funded,
+            "escrow is not funded"
+        );
+
+        let remaining = escrow.amount - escrow.released;
+        pay(&env, &escrow, &escrow.buyer, remaining);
+        escrow.status = EscrowStatus::Refunded;
+        save_escrow(&env, &escrow);
+
+        env.events()
+            .publish((symbol_short!("refunded"), escrow_id), remaining);
+    }
+
+    /// Raise a dispute, freezing the escrow until the arbiter rules.
+    /// Only the buyer or the seller may call this.
+    ///
+    /// # Panics
+    /// - If `caller` is neither party, or the escrow is not `Funded`.
+    pub fn raise_dispute(env: Env, escrow_id: u64, caller: Address) {
+        caller.require_auth();
+        let mut escrow = load_escrow(&env, escrow_id);
+        assert!(
+            caller == escrow.buyer || caller == escrow.seller,
+            "only a party can dispute"
+        );
+        assert!(
+            escrow.status == EscrowStatus::Funded,
+            "escrow is not funded"
+        );
+
+        escrow.status = EscrowStatus::Disputed;
+        save_escrow(&env, &escrow);
+
+        env.events()
+            .publish((symbol_short!("disputed"), escrow_id), caller);
+    }
+
+    /// Settle a dispute over the funds still held: `seller_amount` goes to the
+    /// seller and the rest back to the buyer. Only the arbiter may call this.
+    ///
+    /// # Panics
+    /// - If the escrow is not `Disputed`.
+    /// - If `seller_amount` is outside `[0, remaining]`.
+    pub fn resolve(env: Env, escrow_id: u64, seller_amount: i128) {
+        let mut escrow = load_escrow(&env, escrow_id);
+        escrow.arbiter.require_auth();
+        assert!(
+            escrow.status == EscrowStatus::Disputed,
+            "escrow is not disputed"
+        );
+        let remaining = escrow.amount - escrow.released;
+        assert!(
+            seller_amount >= 0 && seller_amount <= remaining,
+            "seller_amount must be within [0, remaining]"
+        );
+
+        pay(&env, &escrow, &escrow.seller, seller_amount);
+        pay(&env, &escrow, &escrow.buyer, remaining - seller_amount);
+        escrow.status = EscrowStatus::Resolved;
+        save_escrow(&env, &escrow);
+
+        env.events()
+            .publish((symbol_short!("resolved"), escrow_id), seller_amount);
+    }
+
+    /// Return the escrow identified by `escrow_id`.
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Escrow {
+        load_escrow(&env, escrow_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,216 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    token::Client as TokenClient,
+    vec, Address, Env, IntoVal,
+};
+
+use fluxora_testutils::{mint, register_token};
+
+use crate::{EscrowStatus, FluxoraEscrow, FluxoraEscrowClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    escrow_id: Address,
+    token_id: Address,
+    buyer: Address,
+    seller: Address,
+    arbiter: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let token_id = register_token(&env);
+        let escrow_id = env.register_contract(None, FluxoraEscrow);
+
+        let buyer = Address::generate(&env);
+        let seller = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+        mint(&env, &token_id, &buyer, 10_000);
+
+        TestContext {
+            env,
+            escrow_id,
+            token_id,
+            buyer,
+            seller,
+            arbiter,
+        }
+    }
+
+    fn escrow(&self) -> FluxoraEscrowClient<'_> {
+        FluxoraEscrowClient::new(&self.env, &self.escrow_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// Fund a 1000-unit escrow.
+    fn create_default_escrow(&self) -> u64 {
+        self.escrow().create(
+            &self.buyer,
+            &self.seller,
+            &self.arbiter,
+            &self.token_id,
+            &1000,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — happy paths
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_buyer_release_pays_seller() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    assert_eq!(ctx.token().balance(&ctx.escrow_id), 1000);
+
+    ctx.escrow().release(&id);
+    assert_eq!(ctx.token().balance(&ctx.seller), 1000);
+    assert_eq!(ctx.escrow().get_escrow(&id).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_seller_refund_returns_to_buyer() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    ctx.escrow().refund(&id);
+    assert_eq!(ctx.token().balance(&ctx.buyer), 10_000);
+    assert_eq!(ctx.escrow().get_escrow(&id).status, EscrowStatus::Refunded);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — disputes
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_arbiter_splits_disputed_escrow() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    ctx.escrow().raise_dispute(&id, &ctx.seller);
+
+    // A dispute freezes the buyer's release.
+    assert!(ctx.escrow().try_release(&id).is_err());
+
+    ctx.escrow().resolve(&id, &600);
+    assert_eq!(ctx.token().balance(&ctx.seller), 600);
+    assert_eq!(ctx.token().balance(&ctx.buyer), 9_400);
+    assert_eq!(ctx.escrow().get_escrow(&id).status, EscrowStatus::Resolved);
+}
+
+#[test]
+fn test_only_arbiter_can_resolve() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    ctx.escrow().raise_dispute(&id, &ctx.buyer);
+
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.buyer,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.escrow_id,
+            fn_name: "resolve",
+            args: (id, 0_i128).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(ctx.escrow().try_resolve(&id, &0).is_err());
+}
+
+#[test]
+#[should_panic(expected = "only a party can dispute")]
+fn test_outsider_cannot_dispute() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    ctx.escrow().raise_dispute(&id, &ctx.arbiter);
+}
+
+#[test]
+#[should_panic(expected = "seller_amount must be within [0, remaining]")]
+fn test_resolve_above_amount_panics() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    ctx.escrow().raise_dispute(&id, &ctx.buyer);
+    ctx.escrow().resolve(&id, &1001);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — milestone releases
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_milestones_release_per_approval() {
+    let ctx = TestContext::setup();
+    let id = ctx.escrow().create_with_milestones(
+        &ctx.buyer,
+        &ctx.seller,
+        &ctx.arbiter,
+        &ctx.token_id,
+        &vec![&ctx.env, 200_i128, 300_i128, 500_i128],
+    );
+    assert_eq!(ctx.escrow().get_escrow(&id).amount, 1000);
+
+    ctx.escrow().approve_milestone(&id, &1, &ctx.buyer);
+    assert_eq!(ctx.token().balance(&ctx.seller), 300);
+    ctx.escrow().approve_milestone(&id, &0, &ctx.arbiter);
+    assert_eq!(ctx.token().balance(&ctx.seller), 500);
+    assert_eq!(ctx.escrow().get_escrow(&id).status, EscrowStatus::Funded);
+
+    ctx.escrow().approve_milestone(&id, &2, &ctx.buyer);
+    let escrow = ctx.escrow().get_escrow(&id);
+    assert_eq!(escrow.released, 1000);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_dispute_after_milestone_splits_only_remainder() {
+    let ctx = TestContext::setup();
+    let id = ctx.escrow().create_with_milestones(
+        &ctx.buyer,
+        &ctx.seller,
+        &ctx.arbiter,
+        &ctx.token_id,
+        &vec![&ctx.env, 400_i128, 600_i128],
+    );
+    ctx.escrow().approve_milestone(&id, &0, &ctx.buyer);
+    ctx.escrow().raise_dispute(&id, &ctx.buyer);
+    assert!(ctx.escrow().try_resolve(&id, &601).is_err());
+
+    ctx.escrow().resolve(&id, &100);
+    assert_eq!(ctx.token().balance(&ctx.seller), 500);
+    assert_eq!(ctx.token().balance(&ctx.buyer), 10_000 - 1000 + 500);
+}
+
+#[test]
+#[should_panic(expected = "milestone already released")]
+fn test_milestone_cannot_release_twice() {
+    let ctx = TestContext::setup();
+    let id = ctx.escrow().create_with_milestones(
+        &ctx.buyer,
+        &ctx.seller,
+        &ctx.arbiter,
+        &ctx.token_id,
+        &vec![&ctx.env, 400_i128, 600_i128],
+    );
+    ctx.escrow().approve_milestone(&id, &0, &ctx.buyer);
+    ctx.escrow().approve_milestone(&id, &0, &ctx.arbiter);
+}
+
+#[test]
+#[should_panic(expected = "only the buyer or arbiter can approve")]
+fn test_seller_cannot_approve_milestone() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_escrow();
+    ctx.escrow().approve_milestone(&id, &0, &ctx.seller);
+}
@@ -0,0 +1,9 @@
+//! Interfaces of external contracts the factory calls into.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used by the factory.
+#[contractclient(name = "StreamClient")]
+pub trait Stream {
+    fn init(env: Env, token: Address, admin: Address, guardian: Address);
+}
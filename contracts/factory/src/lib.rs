@@ -0,0 +1,290 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient};
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for the factory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Installed FluxoraStream wasm deployed for each tenant.
+    pub wasm_hash: BytesN<32>,
+    /// Token used when a tenant does not choose one.
+    pub default_token: Address,
+    /// Guardian installed on every new deployment.
+    pub default_guardian: Address,
+}
+
+/// A FluxoraStream instance deployed by this factory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deployment {
+    pub instance: Address,
+    /// Organization that requested the deployment and administers it.
+    pub org: Address,
+    pub token: Address,
+    pub wasm_hash: BytesN<32>,
+    pub deployed_at: u64,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,                    // Instance storage for the wasm hash and defaults.
+    InstanceCount,             // Persistent storage: number of deployed instances.
+    Instance(u32),             // Persistent storage: deployed instance by position.
+    Deployment(Address),       // Persistent storage: deployment record per instance.
+    OrgInstanceCount(Address), // Persistent storage: number of instances per organization.
+    OrgInstance(Address, u32), // Persistent storage: an organization's instance by position.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn set_config(env: &Env, config: &Config) {
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+/// Lists are kept one entry per position plus a count, so appending never
+/// rewrites, and no entry outgrows, the ledger entry size limit.
+fn list_len(env: &Env, count_key: &DataKey) -> u32 {
+    env.storage().persistent().get(count_key).unwrap_or(0)
+}
+
+fn push_to_list(
+    env: &Env,
+    count_key: &DataKey,
+    entry_key: impl Fn(u32) -> DataKey,
+    instance: &Address,
+) {
+    let len = list_len(env, count_key);
+    let key = entry_key(len);
+    env.storage().persistent().set(&key, instance);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+    env.storage()
+        .persistent()
+        .set(count_key, &len.checked_add(1).expect("overflow"));
+    env.storage()
+        .persistent()
+        .extend_ttl(count_key, 17280, 120960);
+}
+
+fn list_page(
+    env: &Env,
+    count_key: &DataKey,
+    entry_key: impl Fn(u32) -> DataKey,
+    start: u32,
+    limit: u32,
+) -> Vec<Address> {
+    let end = start.saturating_add(limit).min(list_len(env, count_key));
+    let mut page = Vec::new(env);
+    for i in start..end {
+        page.push_back(
+            env.storage()
+                .persistent()
+                .get(&entry_key(i))
+                .expect("instance not found"),
+        );
+    }
+    page
+}
+
+/// Salts are namespaced per organization, so one organization cannot take
+/// the instance address another would derive from the same salt.
+fn org_salt(env: &Env, org: &Address, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = org.clone().to_xdr(env);
+    preimage.append(&Bytes::from(salt.clone()));
+    env.crypto().sha256(&preimage).into()
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraFactory;
+
+#[contractimpl]
+impl FluxoraFactory {
+    /// Initialise the factory with its admin, the FluxoraStream wasm hash and
+    /// the defaults applied to new deployments. Can only be called once.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        wasm_hash: BytesN<32>,
+        default_token: Address,
+        default_guardian: Address,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        set_config(
+            &env,
+            &Config {
+                wasm_hash,
+                default_token,
+                default_guardian,
+            },
+        );
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Deploy and initialise an isolated FluxoraStream instance administered
+    /// by `org`. `token` overrides the default token when set; `salt`, hashed
+    /// together with `org`, makes the instance address deterministic. Returns
+    /// the new instance address.
+    ///
+    /// # Panics
+    /// - If `org` already deployed an instance with the same `salt`.
+    pub fn deploy(env: Env, org: Address, salt: BytesN<32>, token: Option<Address>) -> Address {
+        org.require_auth();
+        let config = get_config(&env);
+        let token = token.unwrap_or(config.default_token);
+
+        let instance = env
+            .deployer()
+            .with_current_contract(org_salt(&env, &org, &salt))
+            .deploy(config.wasm_hash.clone());
+        StreamClient::new(&env, &instance).init(&token, &org, &config.default_guardian);
+
+        let deployment = Deployment {
+            instance: instance.clone(),
+            org: org.clone(),
+            token,
+            wasm_hash: config.wasm_hash,
+            deployed_at: env.ledger().timestamp(),
+        };
+        let key = DataKey::Deployment(instance.clone());
+        env.storage().persistent().set(&key, &deployment);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+        push_to_list(&env, &DataKey::InstanceCount, DataKey::Instance, &instance);
+        push_to_list(
+            &env,
+            &DataKey::OrgInstanceCount(org.clone()),
+            |i| DataKey::OrgInstance(org.clone(), i),
+            &instance,
+        );
+
+        env.events()
+            .publish((symbol_short!("deployed"), org), instance.clone());
+        instance
+    }
+
+    /// Point future deployments at a new FluxoraStream wasm. Existing
+    /// instances are unaffected. Only the admin may call this.
+    pub fn set_wasm_hash(env: Env, wasm_hash: BytesN<32>) {
+        fluxora_access::require_admin(&env);
+        let mut config = get_config(&env);
+        config.wasm_hash = wasm_hash.clone();
+        set_config(&env, &config);
+
+        env.events().publish((symbol_short!("wasm"),), wasm_hash);
+    }
+
+    /// Change the default token and guardian applied to future deployments.
+    /// Only the admin may call this.
+    pub fn set_defaults(env: Env, default_token: Address, default_guardian: Address) {
+        fluxora_access::require_admin(&env);
+        let mut config = get_config(&env);
+        config.default_token = default_token.clone();
+        config.default_guardian = default_guardian.clone();
+        set_config(&env, &config);
+
+        env.events().publish(
+            (symbol_short!("defaults"),),
+            (default_token, default_guardian),
+        );
+    }
+
+    /// Hand the factory to a new admin. Only the current admin may call this.
+    pub fn set_admin(env: Env, new_admin: Address) {
+        fluxora_access::set_admin(&env, &new_admin);
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the factory configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the deployment record for `instance`, if this factory deployed it.
+    pub fn get_deployment(env: Env, instance: Address) -> Option<Deployment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Deployment(instance))
+    }
+
+    /// Return up to `limit` deployed instances starting at position `start`,
+    /// oldest first.
+    pub fn get_instances(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        list_page(
+            &env,
+            &DataKey::InstanceCount,
+            DataKey::Instance,
+            start,
+            limit,
+        )
+    }
+
+    /// Return the number of instances deployed by this factory.
+    pub fn instance_count(env: Env) -> u32 {
+        list_len(&env, &DataKey::InstanceCount)
+    }
+
+    /// Return up to `limit` of `org`'s instances starting at position
+    /// `start`, oldest first.
+    pub fn get_org_instances(env: Env, org: Address, start: u32, limit: u32) -> Vec<Address> {
+        list_page(
+            &env,
+            &DataKey::OrgInstanceCount(org.clone()),
+            |i| DataKey::OrgInstance(org.clone(), i),
+            start,
+            limit,
+        )
+    }
+
+    /// Return the number of instances deployed for `org`.
+    pub fn org_instance_count(env: Env, org: Address) -> u32 {
+        list_len(&env, &DataKey::OrgInstanceCount(org))
+    }
+}
+
+#[cfg(test)]
+mod test;
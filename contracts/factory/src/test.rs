@@ -0,0 +1,174 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env};
+
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{mint, register_token, set_time};
+
+use crate::{FluxoraFactory, FluxoraFactoryClient};
+
+/// Release build of `contracts/stream`. Regenerate after changing the stream
+/// contract's `init` with:
+/// `cargo build -p fluxora_stream --target wasm32v1-none --release`.
+const STREAM_WASM: &[u8] = include_bytes!("../testdata/fluxora_stream.wasm");
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    factory_id: Address,
+    admin: Address,
+    token_id: Address,
+    guardian: Address,
+    org: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+        // Instantiating real wasm is metered; tests deploy several instances.
+        env.budget().reset_unlimited();
+
+        let token_id = register_token(&env);
+        let wasm_hash = env.deployer().upload_contract_wasm(STREAM_WASM);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        let org = Address::generate(&env);
+        let factory_id = env.register_contract(None, FluxoraFactory);
+        FluxoraFactoryClient::new(&env, &factory_id).init(&admin, &wasm_hash, &token_id, &guardian);
+
+        TestContext {
+            env,
+            factory_id,
+            admin,
+            token_id,
+            guardian,
+            org,
+        }
+    }
+
+    fn factory(&self) -> FluxoraFactoryClient<'_> {
+        FluxoraFactoryClient::new(&self.env, &self.factory_id)
+    }
+
+    fn salt(&self, byte: u8) -> BytesN<32> {
+        BytesN::from_array(&self.env, &[byte; 32])
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — deployment
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_deploy_initialises_instance_for_org() {
+    let ctx = TestContext::setup();
+    let instance = ctx.factory().deploy(&ctx.org, &ctx.salt(1), &None);
+
+    let config = FluxoraStreamClient::new(&ctx.env, &instance).get_config();
+    assert_eq!(config.admin, ctx.org);
+    assert_eq!(config.token, ctx.token_id);
+    assert_eq!(config.guardian, ctx.guardian);
+
+    let deployment = ctx.factory().get_deployment(&instance).unwrap();
+    assert_eq!(deployment.org, ctx.org);
+    assert_eq!(ctx.factory().instance_count(), 1);
+    assert_eq!(
+        ctx.factory().get_instances(&0, &10),
+        vec![&ctx.env, instance.clone()]
+    );
+    assert_eq!(ctx.factory().org_instance_count(&ctx.org), 1);
+}
+
+#[test]
+fn test_deployed_instance_streams_tokens() {
+    let ctx = TestContext::setup();
+    let instance = ctx.factory().deploy(&ctx.org, &ctx.salt(1), &None);
+
+    let sender = Address::generate(&ctx.env);
+    let recipient = Address::generate(&ctx.env);
+    mint(&ctx.env, &ctx.token_id, &sender, 1_000);
+    let stream = FluxoraStreamClient::new(&ctx.env, &instance);
+    let id = stream.create_stream(&sender, &recipient, &1000, &1, &0, &0, &1000);
+
+    set_time(&ctx.env, 400);
+    assert_eq!(stream.withdraw(&id), 400);
+}
+
+#[test]
+fn test_token_override_and_defaults() {
+    let ctx = TestContext::setup();
+    let other_token = register_token(&ctx.env);
+    let instance = ctx
+        .factory()
+        .deploy(&ctx.org, &ctx.salt(1), &Some(other_token.clone()));
+    assert_eq!(
+        FluxoraStreamClient::new(&ctx.env, &instance)
+            .get_config()
+            .token,
+        other_token
+    );
+
+    let new_guardian = Address::generate(&ctx.env);
+    ctx.factory().set_defaults(&other_token, &new_guardian);
+    let second = ctx.factory().deploy(&ctx.org, &ctx.salt(2), &None);
+    let config = FluxoraStreamClient::new(&ctx.env, &second).get_config();
+    assert_eq!(config.token, other_token);
+    assert_eq!(config.guardian, new_guardian);
+    assert_eq!(
+        ctx.factory().get_org_instances(&ctx.org, &1, &10),
+        vec![&ctx.env, second]
+    );
+}
+
+#[test]
+fn test_reused_salt_rejected() {
+    let ctx = TestContext::setup();
+    ctx.factory().deploy(&ctx.org, &ctx.salt(1), &None);
+    assert!(ctx
+        .factory()
+        .try_deploy(&ctx.org, &ctx.salt(1), &None)
+        .is_err());
+}
+
+#[test]
+fn test_salt_is_namespaced_per_org() {
+    let ctx = TestContext::setup();
+    let other_org = Address::generate(&ctx.env);
+    let first = ctx.factory().deploy(&ctx.org, &ctx.salt(1), &None);
+    let second = ctx.factory().deploy(&other_org, &ctx.salt(1), &None);
+
+    assert_ne!(first, second);
+    assert_eq!(
+        ctx.factory().get_instances(&0, &10),
+        vec![&ctx.env, first.clone(), second.clone()]
+    );
+    assert_eq!(
+        ctx.factory().get_org_instances(&other_org, &0, &10),
+        vec![&ctx.env, second]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.factory().get_admin(), ctx.admin);
+    let new_admin = Address::generate(&ctx.env);
+    ctx.factory().propose_admin(&new_admin);
+    ctx.factory().accept_admin();
+    assert_eq!(ctx.factory().get_admin(), new_admin);
+
+    let new_guardian = Address::generate(&ctx.env);
+    ctx.factory().set_defaults(&ctx.token_id, &new_guardian);
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+}
@@ -0,0 +1,249 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for the fee manager.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Minimum seconds between two distributions of the same token.
+    pub epoch_length: u64,
+}
+
+/// A revenue destination (treasury, stakers, insurance fund, ...) and its
+/// cut of every distribution, in basis points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Destination {
+    pub account: Address,
+    pub weight_bps: u32,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,                   // Instance storage for the distribution schedule.
+    Destinations,             // Instance storage: destinations whose weights sum to 10_000.
+    LastDistributed(Address), // Persistent storage: timestamp of the last distribution, per token.
+    TotalOwed(Address),       // Persistent storage: credited but unclaimed, per token.
+    Owed(Address, Address),   // Persistent storage: unclaimed, per (token, destination).
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn get_destinations(env: &Env) -> Vec<Destination> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Destinations)
+        .expect("contract not initialised: missing destinations")
+}
+
+fn set_destinations(env: &Env, destinations: &Vec<Destination>) {
+    let mut total: u32 = 0;
+    for (i, d) in destinations.iter().enumerate() {
+        assert!(d.weight_bps > 0, "weight must be positive");
+        assert!(
+            destinations
+                .iter()
+                .position(|other| other.account == d.account)
+                == Some(i),
+            "duplicate destination"
+        );
+        total = total.saturating_add(d.weight_bps);
+    }
+    assert!(
+        total == fluxora_math::BPS_DENOMINATOR,
+        "weights must sum to 10000 bps"
+    );
+    env.storage()
+        .instance()
+        .set(&DataKey::Destinations, destinations);
+}
+
+fn get_amount(env: &Env, key: &DataKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn set_amount(env: &Env, key: &DataKey, amount: i128) {
+    env.storage().persistent().set(key, &amount);
+    env.storage().persistent().extend_ttl(key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraFees;
+
+#[contractimpl]
+impl FluxoraFees {
+    /// Initialise with the admin, the destination set and the distribution
+    /// schedule. Point the stream contract's `fee_collector` at this contract
+    /// to route protocol fees here. Can only be called once.
+    ///
+    /// # Panics
+    /// - If weights are zero, duplicated, or do not sum to 10_000 bps.
+    pub fn init(env: Env, admin: Address, destinations: Vec<Destination>, epoch_length: u64) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        set_destinations(&env, &destinations);
+        env.storage()
+            .instance()
+            .set(&DataKey::Config, &Config { epoch_length });
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Credit every destination its share of the `token` fees received since
+    /// the last distribution. Permissionless; at most once per epoch per
+    /// token. Rounding dust stays in the pool for the next epoch. Returns the
+    /// amount credited.
+    ///
+    /// # Panics
+    /// - If the epoch since the last distribution has not elapsed.
+    /// - If no new fees were received.
+    pub fn distribute(env: Env, token: Address) -> i128 {
+        let config = get_config(&env);
+        let now = env.ledger().timestamp();
+        let last_key = DataKey::LastDistributed(token.clone());
+        if let Some(last) = env.storage().persistent().get::<_, u64>(&last_key) {
+            assert!(
+                now >= last.saturating_add(config.epoch_length),
+                "distribution not due"
+            );
+        }
+
+        let total_key = DataKey::TotalOwed(token.clone());
+        let total_owed = get_amount(&env, &total_key);
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let pool = balance - total_owed;
+        assert!(pool > 0, "nothing to distribute");
+
+        let mut credited: i128 = 0;
+        for d in get_destinations(&env).iter() {
+            let share = fluxora_math::bps(pool, d.weight_bps).expect("overflow calculating share");
+            let key = DataKey::Owed(token.clone(), d.account.clone());
+            set_amount(&env, &key, get_amount(&env, &key) + share);
+            credited += share;
+        }
+        set_amount(&env, &total_key, total_owed + credited);
+        env.storage().persistent().set(&last_key, &now);
+        env.storage()
+            .persistent()
+            .extend_ttl(&last_key, 17280, 120960);
+
+        env.events()
+            .publish((symbol_short!("distrib"), token), credited);
+        credited
+    }
+
+    /// Pay out everything credited to `account` in `token`.
+    /// Only `account` may call this. Returns the amount paid.
+    ///
+    /// # Panics
+    /// - If nothing is owed to `account` in `token`.
+    pub fn claim(env: Env, token: Address, account: Address) -> i128 {
+        account.require_auth();
+
+        let key = DataKey::Owed(token.clone(), account.clone());
+        let amount = get_amount(&env, &key);
+        assert!(amount > 0, "nothing to claim");
+
+        set_amount(&env, &key, 0);
+        let total_key = DataKey::TotalOwed(token.clone());
+        set_amount(&env, &total_key, get_amount(&env, &total_key) - amount);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &account,
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("claimed"), token, account), amount);
+        amount
+    }
+
+    /// Replace the destination set. Amounts already credited remain
+    /// claimable by their original destinations. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If weights are zero, duplicated, or do not sum to 10_000 bps.
+    pub fn set_destinations(env: Env, destinations: Vec<Destination>) {
+        fluxora_access::require_admin(&env);
+        set_destinations(&env, &destinations);
+
+        env.events()
+            .publish((symbol_short!("dests"),), destinations);
+    }
+
+    /// Change the minimum seconds between distributions.
+    /// Only the admin may call this.
+    pub fn set_epoch_length(env: Env, epoch_length: u64) {
+        fluxora_access::require_admin(&env);
+        let mut config = get_config(&env);
+        config.epoch_length = epoch_length;
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        env.events()
+            .publish((symbol_short!("epoch"),), epoch_length);
+    }
+
+    /// Return what `account` can currently claim in `token`.
+    pub fn claimable(env: Env, token: Address, account: Address) -> i128 {
+        get_amount(&env, &DataKey::Owed(token, account))
+    }
+
+    /// Return `token` fees received but not yet distributed.
+    pub fn pending(env: Env, token: Address) -> i128 {
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        balance - get_amount(&env, &DataKey::TotalOwed(token))
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the fee manager configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the current destination set.
+    pub fn get_destinations(env: Env) -> Vec<Destination> {
+        get_destinations(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
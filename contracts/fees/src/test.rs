@@ -0,0 +1,184 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, vec, Address, Env};
+
+use fluxora_stream::{FeeParams, FluxoraStreamClient, FEE_TIMELOCK_SECS};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{Destination, FluxoraFees, FluxoraFeesClient};
+
+const EPOCH: u64 = 86_400;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    fees_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    sender: Address,
+    treasury: Address,
+    stakers: Address,
+    insurance: Address,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream charging a 1% protocol fee into a fee manager
+    /// split 50/30/20 between treasury, stakers and insurance.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let treasury = Address::generate(&env);
+        let stakers = Address::generate(&env);
+        let insurance = Address::generate(&env);
+        let fees_id = env.register_contract(None, FluxoraFees);
+        FluxoraFeesClient::new(&env, &fees_id).init(
+            &Address::generate(&env),
+            &vec![
+                &env,
+                Destination {
+                    account: treasury.clone(),
+                    weight_bps: 5_000,
+                },
+                Destination {
+                    account: stakers.clone(),
+                    weight_bps: 3_000,
+                },
+                Destination {
+                    account: insurance.clone(),
+                    weight_bps: 2_000,
+                },
+            ],
+            &EPOCH,
+        );
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+        let stream = FluxoraStreamClient::new(&env, &stream_id);
+        stream.propose_fee_params(&FeeParams {
+            protocol_fee_bps: 100,
+            fee_collector: fees_id.clone(),
+            max_broker_fee_bps: 0,
+        });
+        set_time(&env, FEE_TIMELOCK_SECS);
+        stream.apply_fee_params();
+
+        let sender = Address::generate(&env);
+        mint(&env, &token_id, &sender, 100_000);
+
+        TestContext {
+            env,
+            fees_id,
+            stream_id,
+            token_id,
+            sender,
+            treasury,
+            stakers,
+            insurance,
+        }
+    }
+
+    fn fees(&self) -> FluxoraFeesClient<'_> {
+        FluxoraFeesClient::new(&self.env, &self.fees_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// Create a stream of `deposit`, paying a 1% fee to the fee manager.
+    fn create_stream(&self, deposit: i128) {
+        let now = self.env.ledger().timestamp();
+        FluxoraStreamClient::new(&self.env, &self.stream_id).create_stream(
+            &self.sender,
+            &Address::generate(&self.env),
+            &deposit,
+            &1,
+            &now,
+            &now,
+            &(now + deposit as u64),
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — distribution and claims
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fees_distributed_by_weight_and_claimed() {
+    let ctx = TestContext::setup();
+    ctx.create_stream(10_000);
+    assert_eq!(ctx.fees().pending(&ctx.token_id), 100);
+
+    assert_eq!(ctx.fees().distribute(&ctx.token_id), 100);
+    assert_eq!(ctx.fees().claimable(&ctx.token_id, &ctx.treasury), 50);
+    assert_eq!(ctx.fees().claimable(&ctx.token_id, &ctx.stakers), 30);
+    assert_eq!(ctx.fees().claimable(&ctx.token_id, &ctx.insurance), 20);
+    assert_eq!(ctx.fees().pending(&ctx.token_id), 0);
+
+    assert_eq!(ctx.fees().claim(&ctx.token_id, &ctx.stakers), 30);
+    assert_eq!(ctx.token().balance(&ctx.stakers), 30);
+    assert_eq!(ctx.fees().claimable(&ctx.token_id, &ctx.stakers), 0);
+}
+
+#[test]
+fn test_unclaimed_credit_excluded_from_next_epoch() {
+    let ctx = TestContext::setup();
+    ctx.create_stream(10_000);
+    ctx.fees().distribute(&ctx.token_id);
+
+    set_time(&ctx.env, FEE_TIMELOCK_SECS + EPOCH);
+    ctx.create_stream(20_000);
+    assert_eq!(ctx.fees().pending(&ctx.token_id), 200);
+    ctx.fees().distribute(&ctx.token_id);
+    assert_eq!(ctx.fees().claimable(&ctx.token_id, &ctx.treasury), 150);
+}
+
+#[test]
+#[should_panic(expected = "distribution not due")]
+fn test_distribute_twice_in_epoch_panics() {
+    let ctx = TestContext::setup();
+    ctx.create_stream(10_000);
+    ctx.fees().distribute(&ctx.token_id);
+    ctx.create_stream(10_000);
+    ctx.fees().distribute(&ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "weights must sum to 10000 bps")]
+fn test_destinations_must_cover_full_weight() {
+    let ctx = TestContext::setup();
+    ctx.fees().set_destinations(&vec![
+        &ctx.env,
+        Destination {
+            account: ctx.treasury.clone(),
+            weight_bps: 9_000,
+        },
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "nothing to claim")]
+fn test_claim_without_credit_panics() {
+    let ctx = TestContext::setup();
+    ctx.fees().claim(&ctx.token_id, &ctx.treasury);
+}
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.fees().propose_admin(&new_admin);
+    ctx.fees().accept_admin();
+    assert_eq!(ctx.fees().get_admin(), new_admin);
+
+    ctx.fees().set_epoch_length(&(EPOCH * 2));
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.fees().get_config().epoch_length, EPOCH * 2);
+}
@@ -0,0 +1,14 @@
+//! Interfaces of external contracts the fractions contract calls into.
+
+pub use fluxora_types::{Stream, StreamStatus};
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used to hold a stream.
+#[contractclient(name = "StreamClient")]
+pub trait StreamContract {
+    fn get_stream_state(env: Env, stream_id: u64) -> Stream;
+    fn transfer_recipient(env: Env, stream_id: u64, new_recipient: Address);
+    fn withdraw(env: Env, stream_id: u64) -> i128;
+    fn claim_credit(env: Env, account: Address, token: Address, to: Address) -> i128;
+    fn get_credit(env: Env, account: Address, token: Address) -> i128;
+}
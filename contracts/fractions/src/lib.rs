@@ -0,0 +1,488 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient, StreamContract, StreamStatus};
+
+use fluxora_types::Error;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, symbol_short,
+    token::{self, TokenInterface},
+    Address, Env, String,
+};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Fixed-point scale of the per-share collected amount.
+const ACC_PRECISION: i128 = 1_000_000_000_000;
+
+/// Ledgers below which the instance and holder entries' TTL is bumped.
+const TTL_THRESHOLD: u32 = 17_280;
+/// Ledgers the instance and holder entries' TTL is bumped to.
+const TTL_EXTEND_TO: u32 = 120_960;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// The stream this instance holds and how it is divided.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub stream_contract: Address,
+    pub stream_id: u64,
+    /// Token the stream pays out in, and the token claims are paid in.
+    pub token: Address,
+    /// Shares outstanding: minted once at creation, reduced by burns.
+    pub total_supply: i128,
+    /// SEP-41 metadata of the shares.
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// One share holder's balance and unclaimed proceeds.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Holder {
+    pub shares: i128,
+    /// Per-share collected amount when the holder was last settled.
+    pub acc_snapshot: i128,
+    /// Proceeds settled to the holder but not yet claimed.
+    pub owed: i128,
+}
+
+/// Shares a spender may move on an owner's behalf, until a ledger.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Allowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,                      // Instance storage for the locked stream and share supply.
+    AccPerShare, // Instance storage: collected amount per share, scaled by ACC_PRECISION.
+    Unlocked,    // Instance storage: set once the stream is handed back.
+    Holder(Address), // Persistent storage: shares and unclaimed proceeds per holder.
+    Allowance(Address, Address), // Temporary storage: allowance per (owner, spender).
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn get_acc_per_share(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AccPerShare)
+        .unwrap_or(0)
+}
+
+fn get_holder(env: &Env, account: &Address) -> Holder {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Holder(account.clone()))
+        .unwrap_or_default()
+}
+
+fn save_holder(env: &Env, account: &Address, holder: &Holder) {
+    let key = DataKey::Holder(account.clone());
+    env.storage().persistent().set(&key, holder);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Credit `holder` with their share of everything collected since they were
+/// last settled.
+///
+/// # Panics
+/// - With `Error::Overflow` if the holder's proceeds overflow.
+fn settle(env: &Env, holder: &mut Holder) {
+    let acc = get_acc_per_share(env);
+    holder.owed = acc
+        .checked_sub(holder.acc_snapshot)
+        .and_then(|delta| holder.shares.checked_mul(delta))
+        .and_then(|scaled| holder.owed.checked_add(scaled / ACC_PRECISION))
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow));
+    holder.acc_snapshot = acc;
+}
+
+/// Spread `amount` just received from the stream across all shares.
+///
+/// # Panics
+/// - With `Error::Overflow` if the per-share collected amount overflows.
+fn distribute(env: &Env, config: &Config, amount: i128) {
+    let acc = amount
+        .checked_mul(ACC_PRECISION)
+        .and_then(|scaled| get_acc_per_share(env).checked_add(scaled / config.total_supply))
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow));
+    env.storage().instance().set(&DataKey::AccPerShare, &acc);
+}
+
+fn get_allowance(env: &Env, from: &Address, spender: &Address) -> Allowance {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    match env.storage().temporary().get::<_, Allowance>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        _ => Allowance::default(),
+    }
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let mut allowance = get_allowance(env, from, spender);
+    assert!(allowance.amount >= amount, "insufficient allowance");
+    if amount > 0 {
+        allowance.amount -= amount;
+        env.storage().temporary().set(
+            &DataKey::Allowance(from.clone(), spender.clone()),
+            &allowance,
+        );
+    }
+}
+
+fn move_shares(env: &Env, from: &Address, to: &Address, amount: i128) {
+    assert!(amount >= 0, "amount must not be negative");
+    let mut sender = get_holder(env, from);
+    assert!(amount <= sender.shares, "insufficient shares");
+    settle(env, &mut sender);
+    sender.shares -= amount;
+    save_holder(env, from, &sender);
+
+    let mut receiver = get_holder(env, to);
+    settle(env, &mut receiver);
+    receiver.shares = receiver
+        .shares
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow));
+    save_holder(env, to, &receiver);
+
+    env.events().publish(
+        (symbol_short!("transfer"), from.clone(), to.clone()),
+        amount,
+    );
+}
+
+/// Burned shares stop accruing; their proceeds so far stay with `from`.
+fn burn_shares(env: &Env, from: &Address, amount: i128) {
+    assert!(amount >= 0, "amount must not be negative");
+    let mut config = get_config(env);
+    let mut holder = get_holder(env, from);
+    assert!(amount <= holder.shares, "insufficient shares");
+    assert!(
+        amount < config.total_supply,
+        "cannot burn every share; use unlock"
+    );
+    settle(env, &mut holder);
+    holder.shares -= amount;
+    save_holder(env, from, &holder);
+    config.total_supply -= amount;
+    env.storage().instance().set(&DataKey::Config, &config);
+
+    env.events()
+        .publish((symbol_short!("burn"), from.clone()), amount);
+}
+
+fn pay(env: &Env, config: &Config, to: &Address, amount: i128) {
+    if amount > 0 {
+        token::Client::new(env, &config.token).transfer(
+            &env.current_contract_address(),
+            to,
+            &amount,
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraFractions;
+
+#[contractimpl]
+impl FluxoraFractions {
+    /// Lock `stream_id` by making this contract its recipient, and mint
+    /// `total_supply` shares to `owner`, the current recipient. The shares
+    /// are a SEP-41 token described by `decimals`, `name` and `symbol`. Can
+    /// only be called once.
+    ///
+    /// # Panics
+    /// - If `total_supply` is not positive.
+    /// - If `owner` is not the stream's recipient.
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        env: Env,
+        owner: Address,
+        stream_contract: Address,
+        stream_id: u64,
+        total_supply: i128,
+        decimals: u32,
+        name: String,
+        symbol: String,
+    ) {
+        owner.require_auth();
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        assert!(total_supply > 0, "total_supply must be positive");
+
+        let stream = StreamClient::new(&env, &stream_contract);
+        let state = stream.get_stream_state(&stream_id);
+        assert!(state.recipient == owner, "owner is not the recipient");
+        stream.transfer_recipient(&stream_id, &env.current_contract_address());
+
+        env.storage().instance().set(
+            &DataKey::Config,
+            &Config {
+                stream_contract,
+                stream_id,
+                token: state.token,
+                total_supply,
+                decimals,
+                name,
+                symbol,
+            },
+        );
+        save_holder(
+            &env,
+            &owner,
+            &Holder {
+                shares: total_supply,
+                ..Default::default()
+            },
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.events()
+            .publish((symbol_short!("locked"), stream_id), (owner, total_supply));
+    }
+
+    /// Withdraw everything available from the locked stream and spread it
+    /// across all shares. Rounding dust stays in the contract. Callable by
+    /// anyone. Returns the amount collected.
+    ///
+    /// # Panics
+    /// - If the stream has been unlocked.
+    pub fn collect(env: Env) -> i128 {
+        assert!(
+            !env.storage().instance().has(&DataKey::Unlocked),
+            "stream has been unlocked"
+        );
+        let config = get_config(&env);
+        let amount = StreamClient::new(&env, &config.stream_contract).withdraw(&config.stream_id);
+        distribute(&env, &config, amount);
+
+        env.events()
+            .publish((symbol_short!("collected"), config.stream_id), amount);
+        amount
+    }
+
+    /// Claim the credit the stream contract recorded for this contract when
+    /// a `credit_refunds` stream was cancelled, and spread it across all
+    /// shares like `collect`. Callable by anyone. Returns the amount
+    /// collected.
+    ///
+    /// # Panics
+    /// - If the stream has been unlocked.
+    /// - If there is no credit to claim.
+    pub fn collect_credit(env: Env) -> i128 {
+        assert!(
+            !env.storage().instance().has(&DataKey::Unlocked),
+            "stream has been unlocked"
+        );
+        let config = get_config(&env);
+        let this = env.current_contract_address();
+        let amount = StreamClient::new(&env, &config.stream_contract).claim_credit(
+            &this,
+            &config.token,
+            &this,
+        );
+        distribute(&env, &config, amount);
+
+        env.events()
+            .publish((symbol_short!("collected"), config.stream_id), amount);
+        amount
+    }
+
+    /// Pay `holder` everything owed to them so far. Returns the amount paid.
+    pub fn claim(env: Env, holder: Address) -> i128 {
+        holder.require_auth();
+        let config = get_config(&env);
+        let mut record = get_holder(&env, &holder);
+        settle(&env, &mut record);
+        let amount = record.owed;
+        record.owed = 0;
+        save_holder(&env, &holder, &record);
+        pay(&env, &config, &holder, amount);
+
+        env.events()
+            .publish((symbol_short!("claimed"), holder), amount);
+        amount
+    }
+
+    /// Burn every share and hand the stream back to `holder`, who must own
+    /// the whole supply. Any credit from a cancellation is collected and,
+    /// with their other unclaimed proceeds, paid out first.
+    ///
+    /// # Panics
+    /// - If `holder` does not own every share.
+    pub fn unlock(env: Env, holder: Address) {
+        holder.require_auth();
+        let config = get_config(&env);
+        let mut record = get_holder(&env, &holder);
+        assert!(
+            record.shares == config.total_supply,
+            "holder must own every share"
+        );
+        let stream = StreamClient::new(&env, &config.stream_contract);
+        let this = env.current_contract_address();
+        if stream.get_credit(&this, &config.token) > 0 {
+            let credit = stream.claim_credit(&this, &config.token, &this);
+            distribute(&env, &config, credit);
+        }
+        settle(&env, &mut record);
+        pay(&env, &config, &holder, record.owed);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Holder(holder.clone()));
+        env.storage().instance().set(&DataKey::Unlocked, &true);
+
+        stream.transfer_recipient(&config.stream_id, &holder);
+
+        env.events()
+            .publish((symbol_short!("unlocked"), config.stream_id), holder);
+    }
+
+    /// Return the amount `account` could claim right now.
+    pub fn claimable(env: Env, account: Address) -> i128 {
+        let mut holder = get_holder(&env, &account);
+        settle(&env, &mut holder);
+        holder.owed
+    }
+
+    /// Fetches the locked stream and share supply.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SEP-41 share token
+// ---------------------------------------------------------------------------
+
+#[contractimpl]
+impl TokenInterface for FluxoraFractions {
+    /// Return how many of `from`'s shares `spender` may still move.
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        get_allowance(&env, &from, &spender).amount
+    }
+
+    /// Let `spender` move up to `amount` of `from`'s shares until
+    /// `expiration_ledger`, replacing any earlier allowance.
+    ///
+    /// # Panics
+    /// - If `amount` is negative.
+    /// - If `amount` is positive and `expiration_ledger` has passed.
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        assert!(amount >= 0, "amount must not be negative");
+        let ledger = env.ledger().sequence();
+        assert!(
+            amount == 0 || expiration_ledger >= ledger,
+            "expiration_ledger is in the past"
+        );
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &Allowance {
+                amount,
+                expiration_ledger,
+            },
+        );
+        if amount > 0 {
+            let live_for = expiration_ledger - ledger;
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
+        }
+
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    /// Return the number of shares `id` holds.
+    fn balance(env: Env, id: Address) -> i128 {
+        get_holder(&env, &id).shares
+    }
+
+    /// Move `amount` shares from `from` to `to`. Proceeds already collected
+    /// stay with `from`; `to` shares in everything collected afterwards.
+    ///
+    /// # Panics
+    /// - If `amount` is negative or exceeds `from`'s balance.
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        move_shares(&env, &from, &to, amount);
+    }
+
+    /// Move `amount` of `from`'s shares to `to` on `spender`'s allowance.
+    ///
+    /// # Panics
+    /// - If `amount` exceeds the allowance or `from`'s balance.
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        spend_allowance(&env, &from, &spender, amount);
+        move_shares(&env, &from, &to, amount);
+    }
+
+    /// Burn `amount` of `from`'s shares, raising every other share's cut of
+    /// future collections.
+    ///
+    /// # Panics
+    /// - If `amount` exceeds `from`'s balance, or is the whole supply.
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        burn_shares(&env, &from, amount);
+    }
+
+    /// Burn `amount` of `from`'s shares on `spender`'s allowance.
+    ///
+    /// # Panics
+    /// - If `amount` exceeds the allowance or `from`'s balance.
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        spend_allowance(&env, &from, &spender, amount);
+        burn_shares(&env, &from, amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        get_config(&env).decimals
+    }
+
+    fn name(env: Env) -> String {
+        get_config(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        get_config(&env).symbol
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,237 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::Client as TokenClient,
+    Address, Env, String,
+};
+
+use fluxora_stream::{FluxoraStreamClient, StreamOptions};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraFractions, FluxoraFractionsClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    fractions_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    owner: Address,
+    stream: u64,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream paying `owner` 1000 over 1000 seconds, then
+    /// fractionalize that stream into 100 shares held by `owner`.
+    fn setup() -> Self {
+        Self::setup_with(StreamOptions::default())
+    }
+
+    fn setup_with(options: StreamOptions) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+        let stream_client = FluxoraStreamClient::new(&env, &stream_id);
+
+        let sender = Address::generate(&env);
+        let owner = Address::generate(&env);
+        mint(&env, &token_id, &sender, 1_000);
+        let stream = stream_client.create_stream_with_options(
+            &sender, &owner, &token_id, &1_000, &1, &0, &0, &1_000, &options,
+        );
+
+        let fractions_id = env.register_contract(None, FluxoraFractions);
+        FluxoraFractionsClient::new(&env, &fractions_id).init(
+            &owner,
+            &stream_id,
+            &stream,
+            &100,
+            &0,
+            &String::from_str(&env, "Fluxora stream shares"),
+            &String::from_str(&env, "FLXS"),
+        );
+
+        TestContext {
+            env,
+            fractions_id,
+            stream_id,
+            token_id,
+            owner,
+            stream,
+        }
+    }
+
+    fn fractions(&self) -> FluxoraFractionsClient<'_> {
+        FluxoraFractionsClient::new(&self.env, &self.fractions_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — shares and claims
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_init_locks_stream_and_mints_shares() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.stream().get_stream_state(&ctx.stream).recipient,
+        ctx.fractions_id
+    );
+    assert_eq!(ctx.fractions().balance(&ctx.owner), 100);
+}
+
+#[test]
+fn test_holders_claim_pro_rata_across_transfers() {
+    let ctx = TestContext::setup();
+    let investor = Address::generate(&ctx.env);
+    ctx.fractions().transfer(&ctx.owner, &investor, &25);
+
+    set_time(&ctx.env, 400);
+    assert_eq!(ctx.fractions().collect(), 400);
+    assert_eq!(ctx.fractions().claimable(&investor), 100);
+
+    // Shares moved after a collection do not carry the proceeds with them.
+    ctx.fractions().transfer(&investor, &ctx.owner, &25);
+    set_time(&ctx.env, 1_000);
+    ctx.fractions().collect();
+
+    assert_eq!(ctx.fractions().claim(&investor), 100);
+    assert_eq!(ctx.fractions().claim(&ctx.owner), 900);
+    assert_eq!(ctx.token().balance(&investor), 100);
+    assert_eq!(ctx.token().balance(&ctx.fractions_id), 0);
+}
+
+#[test]
+fn test_unlock_returns_stream_to_sole_holder() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 300);
+    ctx.fractions().collect();
+
+    ctx.fractions().unlock(&ctx.owner);
+    assert_eq!(ctx.token().balance(&ctx.owner), 300);
+    assert_eq!(
+        ctx.stream().get_stream_state(&ctx.stream).recipient,
+        ctx.owner
+    );
+    assert_eq!(ctx.fractions().balance(&ctx.owner), 0);
+
+    set_time(&ctx.env, 500);
+    assert_eq!(ctx.stream().withdraw(&ctx.stream), 200);
+}
+
+#[test]
+#[should_panic(expected = "holder must own every share")]
+fn test_unlock_by_partial_holder_panics() {
+    let ctx = TestContext::setup();
+    let investor = Address::generate(&ctx.env);
+    ctx.fractions().transfer(&ctx.owner, &investor, &1);
+    ctx.fractions().unlock(&ctx.owner);
+}
+
+#[test]
+fn test_collect_credit_after_credit_refund_cancel() {
+    let ctx = TestContext::setup_with(StreamOptions {
+        credit_refunds: true,
+        ..Default::default()
+    });
+    let investor = Address::generate(&ctx.env);
+    ctx.fractions().transfer(&ctx.owner, &investor, &25);
+
+    set_time(&ctx.env, 400);
+    ctx.stream().cancel_stream(&ctx.stream);
+    assert_eq!(ctx.fractions().collect_credit(), 400);
+    assert_eq!(ctx.fractions().claim(&investor), 100);
+    assert_eq!(ctx.fractions().claimable(&ctx.owner), 300);
+}
+
+#[test]
+fn test_unlock_collects_outstanding_credit() {
+    let ctx = TestContext::setup_with(StreamOptions {
+        credit_refunds: true,
+        ..Default::default()
+    });
+    set_time(&ctx.env, 250);
+    ctx.stream().cancel_stream(&ctx.stream);
+
+    ctx.fractions().unlock(&ctx.owner);
+    assert_eq!(ctx.token().balance(&ctx.owner), 250);
+    assert_eq!(ctx.token().balance(&ctx.fractions_id), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — SEP-41 share token
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_share_metadata() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.fractions().decimals(), 0);
+    assert_eq!(
+        ctx.fractions().name(),
+        String::from_str(&ctx.env, "Fluxora stream shares")
+    );
+    assert_eq!(ctx.fractions().symbol(), String::from_str(&ctx.env, "FLXS"));
+}
+
+#[test]
+fn test_transfer_from_spends_allowance() {
+    let ctx = TestContext::setup();
+    let spender = Address::generate(&ctx.env);
+    let investor = Address::generate(&ctx.env);
+    ctx.fractions().approve(&ctx.owner, &spender, &30, &100);
+    assert_eq!(ctx.fractions().allowance(&ctx.owner, &spender), 30);
+
+    ctx.fractions()
+        .transfer_from(&spender, &ctx.owner, &investor, &20);
+    assert_eq!(ctx.fractions().allowance(&ctx.owner, &spender), 10);
+    assert_eq!(ctx.fractions().balance(&investor), 20);
+    assert!(ctx
+        .fractions()
+        .try_transfer_from(&spender, &ctx.owner, &investor, &11)
+        .is_err());
+
+    // Allowances lapse after their expiration ledger.
+    ctx.env.ledger().set_sequence_number(101);
+    assert_eq!(ctx.fractions().allowance(&ctx.owner, &spender), 0);
+}
+
+#[test]
+fn test_burn_raises_remaining_holders_cut() {
+    let ctx = TestContext::setup();
+    let investor = Address::generate(&ctx.env);
+    ctx.fractions().transfer(&ctx.owner, &investor, &50);
+    set_time(&ctx.env, 200);
+    ctx.fractions().collect();
+
+    ctx.fractions().burn(&investor, &50);
+    assert_eq!(ctx.fractions().get_config().total_supply, 50);
+    set_time(&ctx.env, 600);
+    ctx.fractions().collect();
+
+    // The investor keeps what was collected before the burn.
+    assert_eq!(ctx.fractions().claim(&investor), 100);
+    assert_eq!(ctx.fractions().claim(&ctx.owner), 500);
+}
+
+#[test]
+#[should_panic(expected = "cannot burn every share; use unlock")]
+fn test_burning_whole_supply_panics() {
+    let ctx = TestContext::setup();
+    ctx.fractions().burn(&ctx.owner, &100);
+}
@@ -0,0 +1,363 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{FeeParams, Stream, StreamClient};
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, BytesN, Env,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for a grant programme.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Approves or rejects grantee reports.
+    pub reviewer: Address,
+    /// FluxoraStream deployment that carries the grant streams.
+    pub stream_contract: Address,
+    /// Grant token; the funding pot is this contract's balance of it.
+    pub token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GrantStatus {
+    /// Streaming; the next report is due at `next_checkpoint`.
+    Active = 0,
+    /// A checkpoint passed without an approved report; the stream is paused.
+    Suspended = 1,
+    /// A report was rejected; unvested funds returned to the pot.
+    ClawedBack = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    pub grant_id: u64,
+    pub grantee: Address,
+    pub stream_id: u64,
+    /// Seconds between reporting checkpoints.
+    pub checkpoint_interval: u64,
+    /// Time by which the next report must be approved.
+    pub next_checkpoint: u64,
+    pub status: GrantStatus,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,             // Instance storage for the reviewer and stream contract.
+    NextGrantId,        // Instance storage for the auto-incrementing grant ID.
+    Grant(u64),         // Persistent storage for individual grants.
+    PendingReport(u64), // Persistent storage: hash of the report awaiting review, per grant.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn load_grant(env: &Env, grant_id: u64) -> Grant {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Grant(grant_id))
+        .expect("grant not found")
+}
+
+fn save_grant(env: &Env, grant: &Grant) {
+    let key = DataKey::Grant(grant.grant_id);
+    env.storage().persistent().set(&key, grant);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn take_pending_report(env: &Env, grant_id: u64) -> BytesN<32> {
+    let key = DataKey::PendingReport(grant_id);
+    let report = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .expect("no report pending");
+    env.storage().persistent().remove(&key);
+    report
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraGrants;
+
+#[contractimpl]
+impl FluxoraGrants {
+    /// Initialise a grant programme managed by `admin`, paying in `token`
+    /// through `stream_contract`. Can only be called once.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        reviewer: Address,
+        stream_contract: Address,
+        token: Address,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        let config = Config {
+            reviewer,
+            stream_contract,
+            token,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::NextGrantId, &0u64);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Move `amount` of the grant token from `from` into the funding pot.
+    pub fn fund(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        let config = get_config(&env);
+        token::Client::new(&env, &config.token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("funded"), from), amount);
+    }
+
+    /// Award `grantee` a grant of `amount`, streamed from the pot over
+    /// `duration` seconds from `start_time`, with a report due every
+    /// `checkpoint_interval` seconds. Only the admin may call this.
+    /// Returns the new grant id.
+    ///
+    /// # Panics
+    /// - If `duration` is zero or does not divide the amount evenly.
+    /// - If `checkpoint_interval` is zero.
+    /// - If the pot cannot cover the grant.
+    pub fn award(
+        env: Env,
+        grantee: Address,
+        amount: i128,
+        start_time: u64,
+        duration: u64,
+        checkpoint_interval: u64,
+    ) -> u64 {
+        fluxora_access::require_admin(&env);
+        let config = get_config(&env);
+        assert!(
+            duration > 0 && amount > 0 && amount % duration as i128 == 0,
+            "duration must divide the amount evenly"
+        );
+        assert!(
+            checkpoint_interval > 0,
+            "checkpoint_interval must be positive"
+        );
+
+        let stream = StreamClient::new(&env, &config.stream_contract);
+        let fee_params = stream.get_fee_params();
+        let pot = env.current_contract_address();
+
+        // The stream contract pulls the deposit (and any protocol fee) from
+        // the pot, one call below our direct invocation.
+        let mut auths = vec![
+            &env,
+            fluxora_access::transfer_auth(
+                &env,
+                &config.token,
+                &pot,
+                &config.stream_contract,
+                amount,
+            ),
+        ];
+        let fee = fluxora_math::bps(amount, fee_params.protocol_fee_bps)
+            .expect("overflow calculating protocol fee");
+        if fee > 0 {
+            auths.push_back(fluxora_access::transfer_auth(
+                &env,
+                &config.token,
+                &pot,
+                &fee_params.fee_collector,
+                fee,
+            ));
+        }
+        env.authorize_as_current_contract(auths);
+
+        let stream_id = stream.create_stream_with_token(
+            &pot,
+            &grantee,
+            &config.token,
+            &amount,
+            &(amount / duration as i128),
+            &start_time,
+            &start_time,
+            &(start_time + duration),
+        );
+
+        let grant_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextGrantId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextGrantId, &(grant_id + 1));
+
+        save_grant(
+            &env,
+            &Grant {
+                grant_id,
+                grantee: grantee.clone(),
+                stream_id,
+                checkpoint_interval,
+                next_checkpoint: start_time + checkpoint_interval,
+                status: GrantStatus::Active,
+            },
+        );
+
+        env.events()
+            .publish((symbol_short!("awarded"), grant_id), (grantee, amount));
+        grant_id
+    }
+
+    /// Submit the report for the grant's next checkpoint, identified by the
+    /// hash of its off-chain content. Replaces any report still awaiting
+    /// review. Only the grantee may call this.
+    ///
+    /// # Panics
+    /// - If the grant was clawed back.
+    pub fn submit_report(env: Env, grant_id: u64, report_hash: BytesN<32>) {
+        let grant = load_grant(&env, grant_id);
+        grant.grantee.require_auth();
+        assert!(
+            grant.status != GrantStatus::ClawedBack,
+            "grant was clawed back"
+        );
+
+        let key = DataKey::PendingReport(grant_id);
+        env.storage().persistent().set(&key, &report_hash);
+        env.storage().persistent().extend_ttl(&key, 17280, 120960);
+
+        env.events()
+            .publish((symbol_short!("reported"), grant_id), report_hash);
+    }
+
+    /// Approve the pending report. The checkpoint advances by one interval
+    /// and a suspended stream resumes. Only the reviewer may call this.
+    ///
+    /// # Panics
+    /// - If no report is pending.
+    pub fn approve_report(env: Env, grant_id: u64) {
+        let config = get_config(&env);
+        config.reviewer.require_auth();
+
+        let mut grant = load_grant(&env, grant_id);
+        let report = take_pending_report(&env, grant_id);
+        grant.next_checkpoint += grant.checkpoint_interval;
+        if grant.status == GrantStatus::Suspended {
+            StreamClient::new(&env, &config.stream_contract).resume_stream(&grant.stream_id);
+            grant.status = GrantStatus::Active;
+        }
+        save_grant(&env, &grant);
+
+        env.events()
+            .publish((symbol_short!("approved"), grant_id), report);
+    }
+
+    /// Reject the pending report and claw the grant back: the stream is
+    /// cancelled, the grantee keeps what already vested and the unvested
+    /// rest returns to the pot. Only the reviewer may call this.
+    ///
+    /// # Panics
+    /// - If no report is pending.
+    pub fn reject_report(env: Env, grant_id: u64) {
+        let config = get_config(&env);
+        config.reviewer.require_auth();
+
+        let mut grant = load_grant(&env, grant_id);
+        let report = take_pending_report(&env, grant_id);
+        StreamClient::new(&env, &config.stream_contract).cancel_stream(&grant.stream_id);
+        grant.status = GrantStatus::ClawedBack;
+        save_grant(&env, &grant);
+
+        env.events()
+            .publish((symbol_short!("clawback"), grant_id), report);
+    }
+
+    /// Suspend a grant whose checkpoint passed without an approved report,
+    /// pausing its stream until the reviewer approves one. Callable by anyone
+    /// (e.g. a keeper).
+    ///
+    /// # Panics
+    /// - If the grant is not active or its checkpoint has not passed.
+    pub fn enforce_checkpoint(env: Env, grant_id: u64) {
+        let mut grant = load_grant(&env, grant_id);
+        assert!(grant.status == GrantStatus::Active, "grant is not active");
+        assert!(
+            env.ledger().timestamp() >= grant.next_checkpoint,
+            "checkpoint not reached"
+        );
+
+        StreamClient::new(&env, &get_config(&env).stream_contract).pause_stream(&grant.stream_id);
+        grant.status = GrantStatus::Suspended;
+        save_grant(&env, &grant);
+
+        env.events().publish(
+            (symbol_short!("suspended"), grant_id),
+            grant.next_checkpoint,
+        );
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the programme configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the hash of the report awaiting review for `grant_id`, if any.
+    pub fn get_pending_report(env: Env, grant_id: u64) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingReport(grant_id))
+    }
+
+    /// Return the grant identified by `grant_id`.
+    pub fn get_grant(env: Env, grant_id: u64) -> Grant {
+        load_grant(&env, grant_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
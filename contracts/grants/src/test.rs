@@ -0,0 +1,172 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, BytesN, Env};
+
+use fluxora_stream::{FluxoraStreamClient, StreamStatus};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraGrants, FluxoraGrantsClient, GrantStatus};
+
+const INTERVAL: u64 = 250;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    grants_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    grantee: Address,
+    grant_id: u64,
+}
+
+impl TestContext {
+    /// A pot of 5000 units funding one 1000-unit grant over 1000 seconds,
+    /// reporting every 250 seconds.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+
+        let funder = Address::generate(&env);
+        let grantee = Address::generate(&env);
+        let grants_id = env.register_contract(None, FluxoraGrants);
+        let grants = FluxoraGrantsClient::new(&env, &grants_id);
+        grants.init(
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &stream_id,
+            &token_id,
+        );
+        mint(&env, &token_id, &funder, 5_000);
+        grants.fund(&funder, &5_000);
+        let grant_id = grants.award(&grantee, &1_000, &0, &1_000, &INTERVAL);
+
+        TestContext {
+            env,
+            grants_id,
+            stream_id,
+            token_id,
+            grantee,
+            grant_id,
+        }
+    }
+
+    fn grants(&self) -> FluxoraGrantsClient<'_> {
+        FluxoraGrantsClient::new(&self.env, &self.grants_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn report(&self) -> BytesN<32> {
+        BytesN::from_array(&self.env, &[7; 32])
+    }
+
+    fn stream_status(&self) -> StreamStatus {
+        let stream_id = self.grants().get_grant(&self.grant_id).stream_id;
+        self.stream().get_stream_state(&stream_id).status
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — checkpoints and clawback
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_award_streams_from_pot() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.token().balance(&ctx.grants_id), 4_000);
+
+    let grant = ctx.grants().get_grant(&ctx.grant_id);
+    assert_eq!(grant.next_checkpoint, INTERVAL);
+    set_time(&ctx.env, 200);
+    ctx.stream().withdraw(&grant.stream_id);
+    assert_eq!(ctx.token().balance(&ctx.grantee), 200);
+}
+
+#[test]
+fn test_approved_report_advances_checkpoint() {
+    let ctx = TestContext::setup();
+    ctx.grants().submit_report(&ctx.grant_id, &ctx.report());
+    ctx.grants().approve_report(&ctx.grant_id);
+
+    set_time(&ctx.env, INTERVAL + 1);
+    assert!(ctx.grants().try_enforce_checkpoint(&ctx.grant_id).is_err());
+    assert_eq!(
+        ctx.grants().get_grant(&ctx.grant_id).next_checkpoint,
+        2 * INTERVAL
+    );
+}
+
+#[test]
+fn test_missed_checkpoint_suspends_until_approval() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, INTERVAL);
+    ctx.grants().enforce_checkpoint(&ctx.grant_id);
+    assert_eq!(ctx.stream_status(), StreamStatus::Paused);
+    assert_eq!(
+        ctx.grants().get_grant(&ctx.grant_id).status,
+        GrantStatus::Suspended
+    );
+
+    ctx.grants().submit_report(&ctx.grant_id, &ctx.report());
+    ctx.grants().approve_report(&ctx.grant_id);
+    assert_eq!(ctx.stream_status(), StreamStatus::Active);
+    assert_eq!(
+        ctx.grants().get_grant(&ctx.grant_id).status,
+        GrantStatus::Active
+    );
+}
+
+#[test]
+fn test_rejected_report_claws_back_unvested() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 300);
+    ctx.grants().submit_report(&ctx.grant_id, &ctx.report());
+    ctx.grants().reject_report(&ctx.grant_id);
+
+    assert_eq!(ctx.stream_status(), StreamStatus::Cancelled);
+    assert_eq!(ctx.token().balance(&ctx.grants_id), 4_700);
+    assert_eq!(
+        ctx.grants().get_grant(&ctx.grant_id).status,
+        GrantStatus::ClawedBack
+    );
+}
+
+#[test]
+#[should_panic(expected = "no report pending")]
+fn test_approve_without_report_panics() {
+    let ctx = TestContext::setup();
+    ctx.grants().approve_report(&ctx.grant_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.grants().propose_admin(&new_admin);
+    assert_ne!(ctx.grants().get_admin(), new_admin);
+
+    ctx.grants().accept_admin();
+    assert_eq!(ctx.grants().get_admin(), new_admin);
+    ctx.grants()
+        .award(&ctx.grantee, &1_000, &0, &1_000, &INTERVAL);
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.token().balance(&ctx.grants_id), 3_000);
+}
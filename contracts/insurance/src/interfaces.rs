@@ -0,0 +1,11 @@
+//! Interfaces of external contracts the insurance contract calls into.
+
+pub use fluxora_types::{Stream, StreamStatus};
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used to assess claims.
+#[contractclient(name = "StreamClient")]
+pub trait StreamContract {
+    fn get_stream_state(env: Env, stream_id: u64) -> Stream;
+    fn get_surplus(env: Env, token: Address) -> i128;
+}
@@ -0,0 +1,382 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient, StreamContract, StreamStatus};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for an insurance pool.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Rules on claims; may be an account or a contract implementing its own
+    /// assessment logic.
+    pub adjudicator: Address,
+    /// FluxoraStream deployment whose streams can be covered.
+    pub stream_contract: Address,
+    /// Pool token; premiums are paid and claims settled in it.
+    pub token: Address,
+    /// Premium charged on cover, in bps of the stream deposit.
+    pub premium_bps: u32,
+    /// Maximum payout per covered stream, in bps of the deposit.
+    pub coverage_bps: u32,
+}
+
+/// Cover bought by a stream's sender for its recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Policy {
+    pub stream_id: u64,
+    pub recipient: Address,
+    /// Maximum the recipient can be paid out.
+    pub coverage: i128,
+    /// Paid out on claims so far.
+    pub paid: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClaimStatus {
+    Pending = 0,
+    Paid = 1,
+    Rejected = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub claim_id: u64,
+    pub stream_id: u64,
+    pub claimant: Address,
+    /// Loss claimed by the recipient.
+    pub amount: i128,
+    /// Amount paid on approval.
+    pub payout: i128,
+    pub status: ClaimStatus,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,            // Instance storage for the pool terms and adjudicator.
+    NextClaimId,       // Instance storage for the auto-incrementing claim ID.
+    Policy(u64),       // Persistent storage: cover per stream.
+    Claim(u64),        // Persistent storage for individual claims.
+    PendingClaim(u64), // Persistent storage: the open claim per stream, if any.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn set_config(env: &Env, config: &Config) {
+    assert!(
+        config.coverage_bps <= fluxora_math::BPS_DENOMINATOR,
+        "coverage_bps cannot exceed 10000"
+    );
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+fn load_policy(env: &Env, stream_id: u64) -> Policy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Policy(stream_id))
+        .expect("stream is not covered")
+}
+
+fn save_policy(env: &Env, policy: &Policy) {
+    let key = DataKey::Policy(policy.stream_id);
+    env.storage().persistent().set(&key, policy);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn load_claim(env: &Env, claim_id: u64) -> Claim {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Claim(claim_id))
+        .expect("claim not found")
+}
+
+fn save_claim(env: &Env, claim: &Claim) {
+    let key = DataKey::Claim(claim.claim_id);
+    env.storage().persistent().set(&key, claim);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn pool_balance(env: &Env, config: &Config) -> i128 {
+    token::Client::new(env, &config.token).balance(&env.current_contract_address())
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraInsurance;
+
+#[contractimpl]
+impl FluxoraInsurance {
+    /// Initialise an insurance pool for streams on `stream_contract`, with
+    /// `admin` able to replace the adjudicator. Can only be called once.
+    ///
+    /// # Panics
+    /// - If `coverage_bps` exceeds 10000.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        adjudicator: Address,
+        stream_contract: Address,
+        token: Address,
+        premium_bps: u32,
+        coverage_bps: u32,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        set_config(
+            &env,
+            &Config {
+                adjudicator,
+                stream_contract,
+                token,
+                premium_bps,
+                coverage_bps,
+            },
+        );
+        env.storage().instance().set(&DataKey::NextClaimId, &0u64);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Contribute `amount` to the pool, e.g. protocol-funded reserves.
+    pub fn contribute(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        let config = get_config(&env);
+        token::Client::new(&env, &config.token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("contrib"), from), amount);
+    }
+
+    /// Buy cover for `stream_id`, paying the premium from its sender.
+    /// Only the stream's sender may call this. Returns the policy.
+    ///
+    /// # Panics
+    /// - If the stream is not active, pays in another token, or is already
+    ///   covered.
+    pub fn cover(env: Env, stream_id: u64) -> Policy {
+        let config = get_config(&env);
+        let stream = StreamClient::new(&env, &config.stream_contract).get_stream_state(&stream_id);
+        stream.sender.require_auth();
+        assert!(
+            stream.status == StreamStatus::Active,
+            "stream is not active"
+        );
+        assert!(stream.token == config.token, "stream token not supported");
+        assert!(
+            !env.storage().persistent().has(&DataKey::Policy(stream_id)),
+            "stream already covered"
+        );
+
+        let premium = fluxora_math::bps(stream.deposit_amount, config.premium_bps)
+            .expect("overflow calculating premium");
+        if premium > 0 {
+            token::Client::new(&env, &config.token).transfer(
+                &stream.sender,
+                &env.current_contract_address(),
+                &premium,
+            );
+        }
+        let policy = Policy {
+            stream_id,
+            recipient: stream.recipient,
+            coverage: fluxora_math::bps(stream.deposit_amount, config.coverage_bps)
+                .expect("overflow calculating coverage"),
+            paid: 0,
+        };
+        save_policy(&env, &policy);
+
+        env.events().publish(
+            (symbol_short!("covered"), stream_id),
+            (premium, policy.coverage),
+        );
+        policy
+    }
+
+    /// File a claim for `amount` lost on a covered stream that was cancelled
+    /// or clawed back, or whose stream contract is insolvent in the token.
+    /// Only the covered recipient may call this. Returns the claim id.
+    ///
+    /// # Panics
+    /// - If the stream is not covered or not in default.
+    /// - If a claim on the stream is still pending.
+    /// - If `amount` is not positive or exceeds the remaining coverage.
+    pub fn file_claim(env: Env, stream_id: u64, amount: i128) -> u64 {
+        let config = get_config(&env);
+        let policy = load_policy(&env, stream_id);
+        policy.recipient.require_auth();
+        let pending_key = DataKey::PendingClaim(stream_id);
+        assert!(
+            !env.storage().persistent().has(&pending_key),
+            "a claim is already pending"
+        );
+        assert!(
+            amount > 0 && amount <= policy.coverage - policy.paid,
+            "amount exceeds remaining coverage"
+        );
+
+        let stream = fluxora_types::load_stream(&env, &config.stream_contract, stream_id);
+        let cancelled = matches!(
+            stream.status,
+            StreamStatus::Cancelled | StreamStatus::ClawedBack
+        );
+        let insolvent =
+            StreamClient::new(&env, &config.stream_contract).get_surplus(&stream.token) < 0;
+        assert!(cancelled || insolvent, "stream is not in default");
+
+        let claim_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextClaimId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextClaimId, &(claim_id + 1));
+        env.storage().persistent().set(&pending_key, &claim_id);
+        env.storage()
+            .persistent()
+            .extend_ttl(&pending_key, 17280, 120960);
+
+        save_claim(
+            &env,
+            &Claim {
+                claim_id,
+                stream_id,
+                claimant: policy.recipient.clone(),
+                amount,
+                payout: 0,
+                status: ClaimStatus::Pending,
+            },
+        );
+
+        env.events()
+            .publish((symbol_short!("claim"), claim_id), (stream_id, amount));
+        claim_id
+    }
+
+    /// Rule on a pending claim, paying `payout` (zero rejects it) to the
+    /// claimant. Only the adjudicator may call this.
+    ///
+    /// # Panics
+    /// - If the claim is not pending.
+    /// - If `payout` exceeds the claimed amount, the remaining coverage or
+    ///   the pool balance.
+    pub fn adjudicate(env: Env, claim_id: u64, payout: i128) {
+        let config = get_config(&env);
+        config.adjudicator.require_auth();
+
+        let mut claim = load_claim(&env, claim_id);
+        assert!(claim.status == ClaimStatus::Pending, "claim is not pending");
+        assert!(
+            payout >= 0 && payout <= claim.amount,
+            "payout exceeds claimed amount"
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingClaim(claim.stream_id));
+        if payout == 0 {
+            claim.status = ClaimStatus::Rejected;
+        } else {
+            assert!(
+                payout <= pool_balance(&env, &config),
+                "insufficient pool funds"
+            );
+            let mut policy = load_policy(&env, claim.stream_id);
+            policy.paid = policy.paid.checked_add(payout).expect("overflow");
+            assert!(
+                policy.paid <= policy.coverage,
+                "payout exceeds remaining coverage"
+            );
+            save_policy(&env, &policy);
+
+            token::Client::new(&env, &config.token).transfer(
+                &env.current_contract_address(),
+                &claim.claimant,
+                &payout,
+            );
+            claim.payout = payout;
+            claim.status = ClaimStatus::Paid;
+        }
+        save_claim(&env, &claim);
+
+        env.events()
+            .publish((symbol_short!("ruled"), claim_id), payout);
+    }
+
+    /// Replace the adjudicator. Only the admin may call this.
+    pub fn set_adjudicator(env: Env, adjudicator: Address) {
+        fluxora_access::require_admin(&env);
+        let mut config = get_config(&env);
+        config.adjudicator = adjudicator.clone();
+        set_config(&env, &config);
+
+        env.events()
+            .publish((symbol_short!("adjudic"),), adjudicator);
+    }
+
+    /// Return the cover on `stream_id`.
+    pub fn get_policy(env: Env, stream_id: u64) -> Policy {
+        load_policy(&env, stream_id)
+    }
+
+    /// Return the claim identified by `claim_id`.
+    pub fn get_claim(env: Env, claim_id: u64) -> Claim {
+        load_claim(&env, claim_id)
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the pool configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
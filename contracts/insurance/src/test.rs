@@ -0,0 +1,195 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, Env};
+
+use fluxora_stream::{FluxoraStreamClient, CLOSE_RETENTION_SECS};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{ClaimStatus, FluxoraInsurance, FluxoraInsuranceClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    insurance_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+    stream: u64,
+}
+
+impl TestContext {
+    /// A pool with 5000 units of reserves charging a 2% premium for 80%
+    /// coverage, and a 1000-unit stream from `sender` to `recipient`.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+        let stream_client = FluxoraStreamClient::new(&env, &stream_id);
+
+        let protocol = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &protocol, 5_000);
+        mint(&env, &token_id, &sender, 2_000);
+
+        let insurance_id = env.register_contract(None, FluxoraInsurance);
+        let insurance = FluxoraInsuranceClient::new(&env, &insurance_id);
+        insurance.init(
+            &protocol,
+            &Address::generate(&env),
+            &stream_id,
+            &token_id,
+            &200,
+            &8_000,
+        );
+        insurance.contribute(&protocol, &5_000);
+
+        let stream = stream_client.create_stream(&sender, &recipient, &1_000, &1, &0, &0, &1_000);
+
+        TestContext {
+            env,
+            insurance_id,
+            stream_id,
+            token_id,
+            sender,
+            recipient,
+            stream,
+        }
+    }
+
+    fn insurance(&self) -> FluxoraInsuranceClient<'_> {
+        FluxoraInsuranceClient::new(&self.env, &self.insurance_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn cancel(&self) {
+        FluxoraStreamClient::new(&self.env, &self.stream_id).cancel_stream(&self.stream);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — cover and claims
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cover_charges_premium() {
+    let ctx = TestContext::setup();
+    let policy = ctx.insurance().cover(&ctx.stream);
+    assert_eq!(policy.coverage, 800);
+    assert_eq!(policy.recipient, ctx.recipient);
+    assert_eq!(ctx.token().balance(&ctx.sender), 980);
+    assert_eq!(ctx.token().balance(&ctx.insurance_id), 5_020);
+}
+
+#[test]
+fn test_claim_after_early_cancellation_paid() {
+    let ctx = TestContext::setup();
+    ctx.insurance().cover(&ctx.stream);
+    set_time(&ctx.env, 200);
+    ctx.cancel();
+
+    let claim_id = ctx.insurance().file_claim(&ctx.stream, &800);
+    ctx.insurance().adjudicate(&claim_id, &600);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 600);
+    assert_eq!(
+        ctx.insurance().get_claim(&claim_id).status,
+        ClaimStatus::Paid
+    );
+
+    // Only the coverage left can still be claimed.
+    assert!(ctx.insurance().try_file_claim(&ctx.stream, &201).is_err());
+    ctx.insurance().file_claim(&ctx.stream, &200);
+}
+
+#[test]
+fn test_claim_on_closed_stream() {
+    let ctx = TestContext::setup();
+    ctx.insurance().cover(&ctx.stream);
+    set_time(&ctx.env, 200);
+    ctx.cancel();
+    let stream = FluxoraStreamClient::new(&ctx.env, &ctx.stream_id);
+    stream.withdraw(&ctx.stream);
+    set_time(&ctx.env, 200 + CLOSE_RETENTION_SECS);
+    stream.close_stream(&ctx.stream);
+
+    let claim_id = ctx.insurance().file_claim(&ctx.stream, &800);
+    assert_eq!(
+        ctx.insurance().get_claim(&claim_id).status,
+        ClaimStatus::Pending
+    );
+}
+
+#[test]
+fn test_rejected_claim_pays_nothing() {
+    let ctx = TestContext::setup();
+    ctx.insurance().cover(&ctx.stream);
+    ctx.cancel();
+    let claim_id = ctx.insurance().file_claim(&ctx.stream, &500);
+    ctx.insurance().adjudicate(&claim_id, &0);
+    assert_eq!(
+        ctx.insurance().get_claim(&claim_id).status,
+        ClaimStatus::Rejected
+    );
+    assert_eq!(ctx.insurance().get_policy(&ctx.stream).paid, 0);
+}
+
+#[test]
+#[should_panic(expected = "stream is not in default")]
+fn test_claim_on_healthy_stream_panics() {
+    let ctx = TestContext::setup();
+    ctx.insurance().cover(&ctx.stream);
+    ctx.insurance().file_claim(&ctx.stream, &100);
+}
+
+#[test]
+#[should_panic(expected = "a claim is already pending")]
+fn test_second_pending_claim_panics() {
+    let ctx = TestContext::setup();
+    ctx.insurance().cover(&ctx.stream);
+    ctx.cancel();
+    ctx.insurance().file_claim(&ctx.stream, &800);
+    ctx.insurance().file_claim(&ctx.stream, &800);
+}
+
+#[test]
+fn test_new_claim_allowed_once_previous_is_ruled() {
+    let ctx = TestContext::setup();
+    ctx.insurance().cover(&ctx.stream);
+    ctx.cancel();
+    let first = ctx.insurance().file_claim(&ctx.stream, &500);
+    ctx.insurance().adjudicate(&first, &0);
+
+    let second = ctx.insurance().file_claim(&ctx.stream, &800);
+    ctx.insurance().adjudicate(&second, &800);
+    assert_eq!(ctx.insurance().get_policy(&ctx.stream).paid, 800);
+    assert!(ctx.insurance().try_file_claim(&ctx.stream, &1).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.insurance().propose_admin(&new_admin);
+    ctx.insurance().accept_admin();
+    assert_eq!(ctx.insurance().get_admin(), new_admin);
+
+    let adjudicator = Address::generate(&ctx.env);
+    ctx.insurance().set_adjudicator(&adjudicator);
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.insurance().get_config().adjudicator, adjudicator);
+}
@@ -0,0 +1,22 @@
+//! Interfaces of external contracts the invoices contract calls into.
+
+pub use fluxora_types::{Stream, StreamStatus};
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used to settle invoices.
+#[contractclient(name = "StreamClient")]
+pub trait StreamContract {
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream_with_token(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64;
+    fn get_stream_state(env: Env, stream_id: u64) -> Stream;
+}
@@ -0,0 +1,285 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient, StreamContract, StreamStatus};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvoiceStatus {
+    /// Issued and awaiting payment.
+    Open = 0,
+    /// Accepted by the payer and being paid through a stream.
+    Streaming = 1,
+    /// Settled in full, instantly or by a completed stream.
+    Paid = 2,
+    /// Withdrawn by the issuer before payment.
+    Voided = 3,
+    /// The paying stream was cancelled before it finished.
+    Cancelled = 4,
+}
+
+/// How the payer settled, or is settling, an invoice.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Settlement {
+    None,
+    Instant,
+    /// Paid through `(stream_contract, stream_id)` on a FluxoraStream contract.
+    Stream(Address, u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invoice {
+    pub invoice_id: u64,
+    /// Contractor who issued the invoice and receives payment.
+    pub issuer: Address,
+    pub payer: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub due_date: u64,
+    pub settlement: Settlement,
+    pub status: InvoiceStatus,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    StreamContract, // Instance storage for the FluxoraStream deployment paying invoices.
+    NextInvoiceId,  // Instance storage for the auto-incrementing invoice ID.
+    Invoice(u64),   // Persistent storage for individual invoices.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_stream_contract(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::StreamContract)
+        .expect("not initialised")
+}
+
+fn load_invoice(env: &Env, invoice_id: u64) -> Invoice {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Invoice(invoice_id))
+        .expect("invoice not found")
+}
+
+fn save_invoice(env: &Env, invoice: &Invoice) {
+    let key = DataKey::Invoice(invoice.invoice_id);
+    env.storage().persistent().set(&key, invoice);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn assert_open(invoice: &Invoice) {
+    assert!(invoice.status == InvoiceStatus::Open, "invoice is not open");
+}
+
+/// Status of a streaming invoice, derived from its paying stream.
+fn live_status(env: &Env, invoice: &Invoice) -> InvoiceStatus {
+    match (&invoice.status, &invoice.settlement) {
+        (InvoiceStatus::Streaming, Settlement::Stream(stream_contract, stream_id)) => {
+            let stream = fluxora_types::load_stream(env, stream_contract, *stream_id);
+            match stream.status {
+                StreamStatus::Completed => InvoiceStatus::Paid,
+                StreamStatus::Cancelled | StreamStatus::ClawedBack => InvoiceStatus::Cancelled,
+                StreamStatus::Active | StreamStatus::Paused | StreamStatus::Distressed => {
+                    InvoiceStatus::Streaming
+                }
+            }
+        }
+        (status, _) => *status,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraInvoices;
+
+#[contractimpl]
+impl FluxoraInvoices {
+    /// Pin the FluxoraStream deployment that `pay_with_stream` funds. Can
+    /// only be called once.
+    ///
+    /// # Panics
+    /// - With `IncompatibleInterface` if `stream_contract` is not a
+    ///   compatible FluxoraStream deployment.
+    pub fn init(env: Env, stream_contract: Address) {
+        if env.storage().instance().has(&DataKey::StreamContract) {
+            panic!("already initialised");
+        }
+        fluxora_types::require_compatible(&env, &stream_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamContract, &stream_contract);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Issue an invoice for `amount` of `token` owed by `payer` by
+    /// `due_date`. Returns the new invoice id.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive.
+    pub fn issue(
+        env: Env,
+        issuer: Address,
+        payer: Address,
+        token: Address,
+        amount: i128,
+        due_date: u64,
+    ) -> u64 {
+        issuer.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        let invoice_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextInvoiceId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextInvoiceId, &(invoice_id + 1));
+
+        let invoice = Invoice {
+            invoice_id,
+            issuer: issuer.clone(),
+            payer: payer.clone(),
+            token,
+            amount,
+            due_date,
+            settlement: Settlement::None,
+            status: InvoiceStatus::Open,
+        };
+        save_invoice(&env, &invoice);
+
+        env.events().publish(
+            (symbol_short!("issued"), invoice_id),
+            (issuer, payer, amount),
+        );
+        invoice_id
+    }
+
+    /// Settle an open invoice instantly. Only the payer may call this.
+    pub fn pay(env: Env, invoice_id: u64) {
+        let mut invoice = load_invoice(&env, invoice_id);
+        invoice.payer.require_auth();
+        assert_open(&invoice);
+
+        token::Client::new(&env, &invoice.token).transfer(
+            &invoice.payer,
+            &invoice.issuer,
+            &invoice.amount,
+        );
+        invoice.settlement = Settlement::Instant;
+        invoice.status = InvoiceStatus::Paid;
+        save_invoice(&env, &invoice);
+
+        env.events()
+            .publish((symbol_short!("paid"), invoice_id), invoice.amount);
+    }
+
+    /// Accept an open invoice by funding a stream on the pinned FluxoraStream
+    /// deployment from the payer to the issuer for the full amount, running
+    /// `duration` seconds from `start_time`. Only the payer may call this.
+    /// Returns the stream id.
+    ///
+    /// # Panics
+    /// - If `duration` is zero or does not divide the amount evenly.
+    /// - If the stream created does not pay the invoice's amount and token
+    ///   to its issuer.
+    pub fn pay_with_stream(env: Env, invoice_id: u64, start_time: u64, duration: u64) -> u64 {
+        let mut invoice = load_invoice(&env, invoice_id);
+        invoice.payer.require_auth();
+        assert_open(&invoice);
+        assert!(
+            duration > 0 && invoice.amount % duration as i128 == 0,
+            "duration must divide the amount evenly"
+        );
+
+        let stream_contract = get_stream_contract(&env);
+        let client = StreamClient::new(&env, &stream_contract);
+        let stream_id = client.create_stream_with_token(
+            &invoice.payer,
+            &invoice.issuer,
+            &invoice.token,
+            &invoice.amount,
+            &(invoice.amount / duration as i128),
+            &start_time,
+            &start_time,
+            &(start_time + duration),
+        );
+        let stream = client.get_stream_state(&stream_id);
+        assert!(
+            stream.recipient == invoice.issuer
+                && stream.token == invoice.token
+                && stream.deposit_amount == invoice.amount,
+            "stream does not match the invoice"
+        );
+        invoice.settlement = Settlement::Stream(stream_contract, stream_id);
+        invoice.status = InvoiceStatus::Streaming;
+        save_invoice(&env, &invoice);
+
+        env.events()
+            .publish((symbol_short!("streamed"), invoice_id), stream_id);
+        stream_id
+    }
+
+    /// Void an unpaid invoice. Only the issuer may call this.
+    pub fn void(env: Env, invoice_id: u64) {
+        let mut invoice = load_invoice(&env, invoice_id);
+        invoice.issuer.require_auth();
+        assert_open(&invoice);
+
+        invoice.status = InvoiceStatus::Voided;
+        save_invoice(&env, &invoice);
+
+        env.events()
+            .publish((symbol_short!("voided"), invoice_id), ());
+    }
+
+    /// Persist the status derived from the paying stream, so indexers see the
+    /// change as an event. Anyone may call this. Returns the current status.
+    pub fn sync(env: Env, invoice_id: u64) -> InvoiceStatus {
+        let mut invoice = load_invoice(&env, invoice_id);
+        let status = live_status(&env, &invoice);
+        if status != invoice.status {
+            invoice.status = status;
+            save_invoice(&env, &invoice);
+            env.events()
+                .publish((symbol_short!("status"), invoice_id), status);
+        }
+        status
+    }
+
+    /// Return the invoice's current status, following its paying stream.
+    pub fn get_status(env: Env, invoice_id: u64) -> InvoiceStatus {
+        live_status(&env, &load_invoice(&env, invoice_id))
+    }
+
+    /// Return whether the invoice is still unpaid after its due date.
+    pub fn is_overdue(env: Env, invoice_id: u64) -> bool {
+        let invoice = load_invoice(&env, invoice_id);
+        invoice.status == InvoiceStatus::Open && env.ledger().timestamp() > invoice.due_date
+    }
+
+    /// Return the invoice identified by `invoice_id`, as last stored.
+    pub fn get_invoice(env: Env, invoice_id: u64) -> Invoice {
+        load_invoice(&env, invoice_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
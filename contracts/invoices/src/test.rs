@@ -0,0 +1,189 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, Env};
+
+use fluxora_stream::{FluxoraStreamClient, CLOSE_RETENTION_SECS};
+use fluxora_testutils::{
+    mint, register_outdated_stream, register_stream, register_token, set_time,
+};
+
+use crate::{FluxoraInvoices, FluxoraInvoicesClient, InvoiceStatus};
+
+const DUE: u64 = 5_000;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    invoices_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    contractor: Address,
+    client: Address,
+    invoice_id: u64,
+}
+
+impl TestContext {
+    /// A contractor invoices a client for 1000 units due at t=5000.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+
+        let contractor = Address::generate(&env);
+        let client = Address::generate(&env);
+        mint(&env, &token_id, &client, 5_000);
+
+        let invoices_id = env.register_contract(None, FluxoraInvoices);
+        let invoices = FluxoraInvoicesClient::new(&env, &invoices_id);
+        invoices.init(&stream_id);
+        let invoice_id = invoices.issue(&contractor, &client, &token_id, &1_000, &DUE);
+
+        TestContext {
+            env,
+            invoices_id,
+            stream_id,
+            token_id,
+            contractor,
+            client,
+            invoice_id,
+        }
+    }
+
+    fn invoices(&self) -> FluxoraInvoicesClient<'_> {
+        FluxoraInvoicesClient::new(&self.env, &self.invoices_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — settlement
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_instant_payment() {
+    let ctx = TestContext::setup();
+    ctx.invoices().pay(&ctx.invoice_id);
+    assert_eq!(ctx.token().balance(&ctx.contractor), 1_000);
+    assert_eq!(
+        ctx.invoices().get_status(&ctx.invoice_id),
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+fn test_stream_payment_status_follows_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.invoices().pay_with_stream(&ctx.invoice_id, &0, &1_000);
+    assert_eq!(
+        ctx.invoices().get_status(&ctx.invoice_id),
+        InvoiceStatus::Streaming
+    );
+
+    set_time(&ctx.env, 1_000);
+    ctx.stream().withdraw(&stream_id);
+    assert_eq!(
+        ctx.invoices().get_status(&ctx.invoice_id),
+        InvoiceStatus::Paid
+    );
+    // The stored record catches up once synced.
+    assert_eq!(
+        ctx.invoices().get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Streaming
+    );
+    assert_eq!(ctx.invoices().sync(&ctx.invoice_id), InvoiceStatus::Paid);
+    assert_eq!(
+        ctx.invoices().get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+fn test_cancelled_stream_marks_invoice_cancelled() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.invoices().pay_with_stream(&ctx.invoice_id, &0, &1_000);
+    set_time(&ctx.env, 400);
+    ctx.stream().cancel_stream(&stream_id);
+    assert_eq!(
+        ctx.invoices().sync(&ctx.invoice_id),
+        InvoiceStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_overdue_and_void() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.invoices().is_overdue(&ctx.invoice_id));
+    set_time(&ctx.env, DUE + 1);
+    assert!(ctx.invoices().is_overdue(&ctx.invoice_id));
+
+    ctx.invoices().void(&ctx.invoice_id);
+    assert!(ctx.invoices().try_pay(&ctx.invoice_id).is_err());
+    assert_eq!(ctx.token().balance(&ctx.client), 5_000);
+}
+
+#[test]
+#[should_panic(expected = "duration must divide the amount evenly")]
+fn test_uneven_stream_duration_rejected() {
+    let ctx = TestContext::setup();
+    ctx.invoices().pay_with_stream(&ctx.invoice_id, &0, &300);
+}
+
+#[test]
+fn test_stream_payment_uses_pinned_contract() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.invoices().pay_with_stream(&ctx.invoice_id, &0, &1_000);
+    let stream = ctx.stream().get_stream_state(&stream_id);
+    assert_eq!(stream.recipient, ctx.contractor);
+    assert_eq!(stream.token, ctx.token_id);
+    assert_eq!(stream.deposit_amount, 1_000);
+    assert_eq!(ctx.token().balance(&ctx.stream_id), 1_000);
+}
+
+#[test]
+fn test_status_survives_closing_the_paying_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.invoices().pay_with_stream(&ctx.invoice_id, &0, &1_000);
+    set_time(&ctx.env, 1_000);
+    ctx.stream().withdraw(&stream_id);
+    set_time(&ctx.env, 1_000 + CLOSE_RETENTION_SECS);
+    ctx.stream().close_stream(&stream_id);
+
+    assert_eq!(
+        ctx.invoices().get_status(&ctx.invoice_id),
+        InvoiceStatus::Paid
+    );
+    assert_eq!(ctx.invoices().sync(&ctx.invoice_id), InvoiceStatus::Paid);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — initialisation
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "already initialised")]
+fn test_init_twice_panics() {
+    let ctx = TestContext::setup();
+    ctx.invoices().init(&ctx.stream_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_init_rejects_incompatible_stream_contract() {
+    let env = Env::default();
+    let invoices_id = env.register_contract(None, FluxoraInvoices);
+    FluxoraInvoicesClient::new(&env, &invoices_id).init(&register_outdated_stream(&env));
+}
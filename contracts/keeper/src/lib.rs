@@ -0,0 +1,229 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol, Val, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// A recurring permissionless call, e.g. `withdraw` on a stream whose
+/// recipient enabled auto-withdraw, paid for by `funder`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Task {
+    pub task_id: u64,
+    pub funder: Address,
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    /// Token bounties are paid in.
+    pub bounty_token: Address,
+    /// Paid to the keeper on each successful run.
+    pub bounty: i128,
+    /// Runs still funded; the task is finished at zero.
+    pub runs_remaining: u32,
+    /// Earliest timestamp of the next run.
+    pub next_run_at: u64,
+    /// Seconds between runs.
+    pub interval: u64,
+}
+
+/// Functions a task may call. The keeper makes the call itself, so it must
+/// never be able to reach anything its own authorisation would unlock.
+pub const TASK_FUNCTIONS: [&str; 2] = ["withdraw", "extend_ttl_for"];
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    NextTaskId,           // Instance storage for the auto-incrementing task ID.
+    Task(u64),            // Persistent storage for individual tasks.
+    BountyToken(Address), // Persistent storage: tokens escrowed as bounties.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn load_task(env: &Env, task_id: u64) -> Task {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Task(task_id))
+        .expect("task not found")
+}
+
+/// Reject calls that would act with the keeper's own authority over the
+/// escrowed bounties: anything on this contract or a bounty token, and any
+/// function outside `TASK_FUNCTIONS`.
+fn assert_task_call(env: &Env, target: &Address, function: &Symbol) {
+    assert!(
+        *target != env.current_contract_address(),
+        "task cannot target the keeper"
+    );
+    assert!(
+        !env.storage()
+            .persistent()
+            .has(&DataKey::BountyToken(target.clone())),
+        "task cannot target a bounty token"
+    );
+    assert!(
+        TASK_FUNCTIONS
+            .iter()
+            .any(|name| Symbol::new(env, name) == *function),
+        "function not allowed for tasks"
+    );
+}
+
+fn save_task(env: &Env, task: &Task) {
+    let key = DataKey::Task(task.task_id);
+    env.storage().persistent().set(&key, task);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraKeeper;
+
+#[contractimpl]
+impl FluxoraKeeper {
+    /// Register `target.function(args)` to run up to `runs` times, first at
+    /// `first_run_at` and then every `interval` seconds, escrowing
+    /// `bounty × runs` of `bounty_token` from `funder`. The call must not
+    /// need anyone's authorisation. Returns the new task id.
+    ///
+    /// # Panics
+    /// - If `bounty` or `runs` is zero.
+    /// - If `function` is not in `TASK_FUNCTIONS`, or `target` is this
+    ///   contract or a token bounties are escrowed in.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_task(
+        env: Env,
+        funder: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+        bounty_token: Address,
+        bounty: i128,
+        runs: u32,
+        first_run_at: u64,
+        interval: u64,
+    ) -> u64 {
+        funder.require_auth();
+        assert!(bounty > 0, "bounty must be positive");
+        assert!(runs > 0, "runs must be positive");
+        assert!(target != bounty_token, "task cannot target a bounty token");
+        assert_task_call(&env, &target, &function);
+
+        let bounty_key = DataKey::BountyToken(bounty_token.clone());
+        env.storage().persistent().set(&bounty_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&bounty_key, 17280, 120960);
+
+        let total = bounty.checked_mul(runs as i128).expect("overflow");
+        token::Client::new(&env, &bounty_token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &total,
+        );
+
+        let task_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTaskId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTaskId, &(task_id + 1));
+
+        let task = Task {
+            task_id,
+            funder: funder.clone(),
+            target: target.clone(),
+            function: function.clone(),
+            args,
+            bounty_token,
+            bounty,
+            runs_remaining: runs,
+            next_run_at: first_run_at,
+            interval,
+        };
+        save_task(&env, &task);
+
+        env.events()
+            .publish((symbol_short!("task"), task_id), (funder, target, function));
+        task_id
+    }
+
+    /// Run a due task and pay its bounty to `keeper`. Anyone may call this.
+    /// Returns the target's result.
+    ///
+    /// # Panics
+    /// - If the task has no funded runs left or is not due yet.
+    /// - If its target has since become a bounty token.
+    /// - If the target call fails; no bounty is paid.
+    pub fn execute(env: Env, task_id: u64, keeper: Address) -> Val {
+        let mut task = load_task(&env, task_id);
+        assert!(task.runs_remaining > 0, "task is finished");
+        assert_task_call(&env, &task.target, &task.function);
+        let now = env.ledger().timestamp();
+        assert!(now >= task.next_run_at, "task not due");
+
+        // Update bookkeeping before the call so a re-entrant execute cannot
+        // run the same slot twice.
+        task.runs_remaining -= 1;
+        task.next_run_at = now.saturating_add(task.interval);
+        save_task(&env, &task);
+
+        let result: Val = env.invoke_contract(&task.target, &task.function, task.args.clone());
+        token::Client::new(&env, &task.bounty_token).transfer(
+            &env.current_contract_address(),
+            &keeper,
+            &task.bounty,
+        );
+
+        env.events()
+            .publish((symbol_short!("executed"), task_id), (keeper, task.bounty));
+        result
+    }
+
+    /// Cancel a task and refund the bounties of its remaining runs to the
+    /// funder. Only the funder may call this. Returns the amount refunded.
+    pub fn cancel_task(env: Env, task_id: u64) -> i128 {
+        let mut task = load_task(&env, task_id);
+        task.funder.require_auth();
+
+        let refund = task.bounty * task.runs_remaining as i128;
+        task.runs_remaining = 0;
+        save_task(&env, &task);
+        if refund > 0 {
+            token::Client::new(&env, &task.bounty_token).transfer(
+                &env.current_contract_address(),
+                &task.funder,
+                &refund,
+            );
+        }
+
+        env.events()
+            .publish((symbol_short!("cancelled"), task_id), refund);
+        refund
+    }
+
+    /// Return whether the task can be executed right now.
+    pub fn is_due(env: Env, task_id: u64) -> bool {
+        let task = load_task(&env, task_id);
+        task.runs_remaining > 0 && env.ledger().timestamp() >= task.next_run_at
+    }
+
+    /// Return the task identified by `task_id`.
+    pub fn get_task(env: Env, task_id: u64) -> Task {
+        load_task(&env, task_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,195 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::Address as _, token::Client as TokenClient, vec, Address, Env, IntoVal, Symbol,
+};
+
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{advance_time, mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraKeeper, FluxoraKeeperClient};
+
+const BOUNTY: i128 = 10;
+const INTERVAL: u64 = 100;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    keeper_id: Address,
+    token_id: Address,
+    recipient: Address,
+    task_id: u64,
+}
+
+impl TestContext {
+    /// A 1000-second stream whose recipient enabled auto-withdraw and funded
+    /// three keeper withdrawals, every 100 seconds from t=100.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_contract = register_stream(&env, &token_id);
+        let stream = FluxoraStreamClient::new(&env, &stream_contract);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &sender, 1_000);
+        mint(&env, &token_id, &recipient, 100);
+        let stream_id = stream.create_stream(&sender, &recipient, &1000, &1, &0, &0, &1000);
+        stream.set_auto_withdraw(&recipient, &true);
+
+        let keeper_id = env.register_contract(None, FluxoraKeeper);
+        let task_id = FluxoraKeeperClient::new(&env, &keeper_id).create_task(
+            &recipient,
+            &stream_contract,
+            &Symbol::new(&env, "withdraw"),
+            &vec![&env, stream_id.into_val(&env)],
+            &token_id,
+            &BOUNTY,
+            &3,
+            &INTERVAL,
+            &INTERVAL,
+        );
+
+        TestContext {
+            env,
+            keeper_id,
+            token_id,
+            recipient,
+            task_id,
+        }
+    }
+
+    fn keeper(&self) -> FluxoraKeeperClient<'_> {
+        FluxoraKeeperClient::new(&self.env, &self.keeper_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — execution and bounties
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_keeper_runs_due_withdrawal_and_earns_bounty() {
+    let ctx = TestContext::setup();
+    let bot = Address::generate(&ctx.env);
+    assert!(!ctx.keeper().is_due(&ctx.task_id));
+
+    set_time(&ctx.env, INTERVAL);
+    ctx.env.set_auths(&[]);
+    ctx.keeper().execute(&ctx.task_id, &bot);
+
+    // 100 streamed in, 30 of the original 100 escrowed as bounties.
+    assert_eq!(ctx.token().balance(&ctx.recipient), 170);
+    assert_eq!(ctx.token().balance(&bot), BOUNTY);
+    let task = ctx.keeper().get_task(&ctx.task_id);
+    assert_eq!(task.runs_remaining, 2);
+    assert_eq!(task.next_run_at, 2 * INTERVAL);
+}
+
+#[test]
+#[should_panic(expected = "task not due")]
+fn test_execute_before_due_panics() {
+    let ctx = TestContext::setup();
+    let bot = Address::generate(&ctx.env);
+    set_time(&ctx.env, INTERVAL);
+    ctx.keeper().execute(&ctx.task_id, &bot);
+    set_time(&ctx.env, 2 * INTERVAL - 1);
+    ctx.keeper().execute(&ctx.task_id, &bot);
+}
+
+#[test]
+#[should_panic(expected = "task is finished")]
+fn test_task_stops_after_funded_runs() {
+    let ctx = TestContext::setup();
+    let bot = Address::generate(&ctx.env);
+    for _ in 0..4 {
+        advance_time(&ctx.env, INTERVAL);
+        ctx.keeper().execute(&ctx.task_id, &bot);
+    }
+}
+
+#[test]
+fn test_cancel_refunds_unused_bounties() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, INTERVAL);
+    ctx.keeper()
+        .execute(&ctx.task_id, &Address::generate(&ctx.env));
+
+    assert_eq!(ctx.keeper().cancel_task(&ctx.task_id), 2 * BOUNTY);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 190);
+    assert!(!ctx.keeper().is_due(&ctx.task_id));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — task restrictions
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "task cannot target a bounty token")]
+fn test_task_cannot_drain_escrowed_bounties() {
+    let ctx = TestContext::setup();
+    let attacker = Address::generate(&ctx.env);
+    let balance = ctx.token().balance(&ctx.keeper_id);
+    ctx.keeper().create_task(
+        &attacker,
+        &ctx.token_id,
+        &Symbol::new(&ctx.env, "transfer"),
+        &vec![
+            &ctx.env,
+            ctx.keeper_id.into_val(&ctx.env),
+            attacker.into_val(&ctx.env),
+            balance.into_val(&ctx.env),
+        ],
+        &ctx.token_id,
+        &1,
+        &1,
+        &0,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "function not allowed for tasks")]
+fn test_task_function_must_be_allowlisted() {
+    let ctx = TestContext::setup();
+    let other_token = register_token(&ctx.env);
+    ctx.keeper().create_task(
+        &ctx.recipient,
+        &other_token,
+        &Symbol::new(&ctx.env, "transfer"),
+        &vec![&ctx.env],
+        &ctx.token_id,
+        &1,
+        &1,
+        &0,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "task cannot target the keeper")]
+fn test_task_cannot_target_keeper() {
+    let ctx = TestContext::setup();
+    ctx.keeper().create_task(
+        &ctx.recipient,
+        &ctx.keeper_id,
+        &Symbol::new(&ctx.env, "withdraw"),
+        &vec![&ctx.env],
+        &ctx.token_id,
+        &1,
+        &1,
+        &0,
+        &0,
+    );
+}
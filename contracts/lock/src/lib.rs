@@ -0,0 +1,167 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Tokens held until `unlock_at`, then releasable to `beneficiary`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lock {
+    pub lock_id: u64,
+    /// Account that funded the lock; the only one allowed to extend it.
+    pub owner: Address,
+    pub beneficiary: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Earliest ledger timestamp at which the tokens may be withdrawn.
+    pub unlock_at: u64,
+    /// Whether the owner may push `unlock_at` further out.
+    pub extendable: bool,
+    pub withdrawn: bool,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    NextLockId, // Instance storage for the auto-incrementing lock ID.
+    Lock(u64),  // Persistent storage for individual locks.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn load_lock(env: &Env, lock_id: u64) -> Lock {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Lock(lock_id))
+        .expect("lock not found")
+}
+
+fn save_lock(env: &Env, lock: &Lock) {
+    let key = DataKey::Lock(lock.lock_id);
+    env.storage().persistent().set(&key, lock);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraLock;
+
+#[contractimpl]
+impl FluxoraLock {
+    /// Lock `amount` of `token` from `owner` until `unlock_at`, after which
+    /// `beneficiary` may withdraw it. Returns the new lock id.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive or `unlock_at` is not in the future.
+    pub fn lock(
+        env: Env,
+        owner: Address,
+        beneficiary: Address,
+        token: Address,
+        amount: i128,
+        unlock_at: u64,
+        extendable: bool,
+    ) -> u64 {
+        owner.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        assert!(
+            unlock_at > env.ledger().timestamp(),
+            "unlock_at must be in the future"
+        );
+
+        token::Client::new(&env, &token).transfer(&owner, &env.current_contract_address(), &amount);
+
+        let lock_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextLockId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextLockId, &(lock_id + 1));
+
+        let lock = Lock {
+            lock_id,
+            owner,
+            beneficiary: beneficiary.clone(),
+            token,
+            amount,
+            unlock_at,
+            extendable,
+            withdrawn: false,
+        };
+        save_lock(&env, &lock);
+
+        env.events().publish(
+            (symbol_short!("locked"), lock_id),
+            (beneficiary, amount, unlock_at),
+        );
+        lock_id
+    }
+
+    /// Push the unlock time of an extendable lock out to `new_unlock_at`.
+    /// Only the owner may call this; the unlock time can never be reduced.
+    ///
+    /// # Panics
+    /// - If the lock is not extendable or was already withdrawn.
+    /// - If `new_unlock_at` is not later than the current unlock time.
+    pub fn extend(env: Env, lock_id: u64, new_unlock_at: u64) {
+        let mut lock = load_lock(&env, lock_id);
+        lock.owner.require_auth();
+        assert!(lock.extendable, "lock is not extendable");
+        assert!(!lock.withdrawn, "lock already withdrawn");
+        assert!(
+            new_unlock_at > lock.unlock_at,
+            "unlock time can only be extended"
+        );
+
+        lock.unlock_at = new_unlock_at;
+        save_lock(&env, &lock);
+
+        env.events()
+            .publish((symbol_short!("extended"), lock_id), new_unlock_at);
+    }
+
+    /// Release the locked tokens to the beneficiary once `unlock_at` has
+    /// passed. Only the beneficiary may call this.
+    ///
+    /// # Panics
+    /// - If the lock is still active or was already withdrawn.
+    pub fn withdraw(env: Env, lock_id: u64) {
+        let mut lock = load_lock(&env, lock_id);
+        lock.beneficiary.require_auth();
+        assert!(!lock.withdrawn, "lock already withdrawn");
+        assert!(
+            env.ledger().timestamp() >= lock.unlock_at,
+            "lock has not expired"
+        );
+
+        lock.withdrawn = true;
+        save_lock(&env, &lock);
+
+        token::Client::new(&env, &lock.token).transfer(
+            &env.current_contract_address(),
+            &lock.beneficiary,
+            &lock.amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("withdrew"), lock_id), lock.amount);
+    }
+
+    /// Return the lock identified by `lock_id`.
+    pub fn get_lock(env: Env, lock_id: u64) -> Lock {
+        load_lock(&env, lock_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
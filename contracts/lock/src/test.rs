@@ -0,0 +1,119 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, Env};
+
+use fluxora_testutils::{mint, register_token, set_time};
+
+use crate::{FluxoraLock, FluxoraLockClient};
+
+const UNLOCK_AT: u64 = 1_000;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    lock_contract: Address,
+    token_id: Address,
+    owner: Address,
+    beneficiary: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let owner = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        mint(&env, &token_id, &owner, 1_000);
+        let lock_contract = env.register_contract(None, FluxoraLock);
+
+        TestContext {
+            env,
+            lock_contract,
+            token_id,
+            owner,
+            beneficiary,
+        }
+    }
+
+    fn client(&self) -> FluxoraLockClient<'_> {
+        FluxoraLockClient::new(&self.env, &self.lock_contract)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn lock(&self, extendable: bool) -> u64 {
+        self.client().lock(
+            &self.owner,
+            &self.beneficiary,
+            &self.token_id,
+            &600,
+            &UNLOCK_AT,
+            &extendable,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — lock / withdraw
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_after_unlock() {
+    let ctx = TestContext::setup();
+    let id = ctx.lock(false);
+    assert_eq!(ctx.token().balance(&ctx.lock_contract), 600);
+
+    set_time(&ctx.env, UNLOCK_AT);
+    ctx.client().withdraw(&id);
+    assert_eq!(ctx.token().balance(&ctx.beneficiary), 600);
+    assert!(ctx.client().get_lock(&id).withdrawn);
+}
+
+#[test]
+#[should_panic(expected = "lock has not expired")]
+fn test_withdraw_before_unlock_panics() {
+    let ctx = TestContext::setup();
+    let id = ctx.lock(false);
+    set_time(&ctx.env, UNLOCK_AT - 1);
+    ctx.client().withdraw(&id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — extension
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_extend_pushes_unlock_time() {
+    let ctx = TestContext::setup();
+    let id = ctx.lock(true);
+    ctx.client().extend(&id, &(UNLOCK_AT * 2));
+    assert_eq!(ctx.client().get_lock(&id).unlock_at, UNLOCK_AT * 2);
+
+    set_time(&ctx.env, UNLOCK_AT);
+    assert!(ctx.client().try_withdraw(&id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "unlock time can only be extended")]
+fn test_extend_cannot_reduce() {
+    let ctx = TestContext::setup();
+    let id = ctx.lock(true);
+    ctx.client().extend(&id, &(UNLOCK_AT - 1));
+}
+
+#[test]
+#[should_panic(expected = "lock is not extendable")]
+fn test_extend_non_extendable_panics() {
+    let ctx = TestContext::setup();
+    let id = ctx.lock(false);
+    ctx.client().extend(&id, &(UNLOCK_AT * 2));
+}
@@ -0,0 +1,307 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MeteredStatus {
+    Open = 0,
+    /// The customer asked to close; usage reports are accepted until `closes_at`.
+    Closing = 1,
+    Closed = 2,
+}
+
+/// A prepaid balance released to the provider as usage is reported.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MeteredStream {
+    pub stream_id: u64,
+    pub customer: Address,
+    pub provider: Address,
+    /// Provider or oracle trusted to report usage.
+    pub reporter: Address,
+    pub token: Address,
+    /// Token amount billed per usage unit.
+    pub price_per_unit: i128,
+    /// Total prefunded by the customer, including withdrawn amounts.
+    pub prepaid: i128,
+    /// Usage units reported so far.
+    pub units_used: u64,
+    /// Amount already paid to the provider.
+    pub withdrawn: i128,
+    /// Seconds the customer must wait between asking to close and the refund.
+    pub notice_period: u64,
+    pub closes_at: u64,
+    pub status: MeteredStatus,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    NextStreamId, // Instance storage for the auto-incrementing stream ID.
+    Stream(u64),  // Persistent storage for individual metered streams.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn load_stream(env: &Env, stream_id: u64) -> MeteredStream {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Stream(stream_id))
+        .expect("stream not found")
+}
+
+fn save_stream(env: &Env, stream: &MeteredStream) {
+    let key = DataKey::Stream(stream.stream_id);
+    env.storage().persistent().set(&key, stream);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+/// Amount billed for reported usage, capped by the prepaid balance.
+fn billed(stream: &MeteredStream) -> i128 {
+    (stream.units_used as i128)
+        .checked_mul(stream.price_per_unit)
+        .expect("overflow")
+        .min(stream.prepaid)
+}
+
+/// Pay the provider everything billed but not yet withdrawn. Returns the
+/// amount paid.
+fn pay_provider(env: &Env, stream: &mut MeteredStream) -> i128 {
+    let amount = billed(stream) - stream.withdrawn;
+    if amount > 0 {
+        token::Client::new(env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &stream.provider,
+            &amount,
+        );
+        stream.withdrawn += amount;
+    }
+    amount
+}
+
+/// Settle the provider and refund the unused prepayment to the customer.
+fn close(env: &Env, stream: &mut MeteredStream) {
+    pay_provider(env, stream);
+    let refund = stream.prepaid - stream.withdrawn;
+    if refund > 0 {
+        token::Client::new(env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &stream.customer,
+            &refund,
+        );
+    }
+    stream.status = MeteredStatus::Closed;
+    save_stream(env, stream);
+
+    env.events()
+        .publish((symbol_short!("closed"), stream.stream_id), refund);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraMetered;
+
+#[contractimpl]
+impl FluxoraMetered {
+    /// Open a metered stream from `customer` to `provider`, prefunded with
+    /// `prepaid` of `token`. `reporter` reports usage billed at
+    /// `price_per_unit`. Returns the new stream id.
+    ///
+    /// # Panics
+    /// - If `price_per_unit` or `prepaid` is not positive.
+    #[allow(clippy::too_many_arguments)]
+    pub fn open(
+        env: Env,
+        customer: Address,
+        provider: Address,
+        reporter: Address,
+        token: Address,
+        price_per_unit: i128,
+        prepaid: i128,
+        notice_period: u64,
+    ) -> u64 {
+        customer.require_auth();
+        assert!(price_per_unit > 0, "price_per_unit must be positive");
+        assert!(prepaid > 0, "prepaid must be positive");
+
+        token::Client::new(&env, &token).transfer(
+            &customer,
+            &env.current_contract_address(),
+            &prepaid,
+        );
+
+        let stream_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextStreamId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextStreamId, &(stream_id + 1));
+
+        save_stream(
+            &env,
+            &MeteredStream {
+                stream_id,
+                customer: customer.clone(),
+                provider: provider.clone(),
+                reporter,
+                token,
+                price_per_unit,
+                prepaid,
+                units_used: 0,
+                withdrawn: 0,
+                notice_period,
+                closes_at: 0,
+                status: MeteredStatus::Open,
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("opened"), stream_id),
+            (customer, provider, prepaid),
+        );
+        stream_id
+    }
+
+    /// Add `amount` to the prepaid balance. Only the customer may call this.
+    ///
+    /// # Panics
+    /// - If the stream is not open.
+    pub fn top_up(env: Env, stream_id: u64, amount: i128) {
+        let mut stream = load_stream(&env, stream_id);
+        stream.customer.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        assert!(stream.status == MeteredStatus::Open, "stream is not open");
+
+        token::Client::new(&env, &stream.token).transfer(
+            &stream.customer,
+            &env.current_contract_address(),
+            &amount,
+        );
+        stream.prepaid += amount;
+        save_stream(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("top_up"), stream_id), amount);
+    }
+
+    /// Report `units` of additional usage. Only the reporter may call this.
+    ///
+    /// # Panics
+    /// - If the stream is closed.
+    pub fn report_usage(env: Env, stream_id: u64, units: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        stream.reporter.require_auth();
+        assert!(stream.status != MeteredStatus::Closed, "stream is closed");
+
+        stream.units_used = stream
+            .units_used
+            .checked_add(units)
+            .expect("overflow adding usage");
+        save_stream(&env, &stream);
+
+        env.events().publish(
+            (symbol_short!("usage"), stream_id),
+            (units, stream.units_used),
+        );
+    }
+
+    /// Pay the provider everything billed so far. Only the provider may call
+    /// this. Returns the amount paid.
+    ///
+    /// # Panics
+    /// - If there is nothing to withdraw.
+    pub fn withdraw(env: Env, stream_id: u64) -> i128 {
+        let mut stream = load_stream(&env, stream_id);
+        stream.provider.require_auth();
+
+        let amount = pay_provider(&env, &mut stream);
+        assert!(amount > 0, "nothing to withdraw");
+        save_stream(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("withdrew"), stream_id), amount);
+        amount
+    }
+
+    /// Ask to close the stream. Usage can still be reported during the
+    /// notice period; afterwards anyone may `finalize_close`. Only the
+    /// customer may call this.
+    ///
+    /// # Panics
+    /// - If the stream is not open.
+    pub fn request_close(env: Env, stream_id: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        stream.customer.require_auth();
+        assert!(stream.status == MeteredStatus::Open, "stream is not open");
+
+        stream.closes_at = env.ledger().timestamp() + stream.notice_period;
+        stream.status = MeteredStatus::Closing;
+        save_stream(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("closing"), stream_id), stream.closes_at);
+    }
+
+    /// Close a stream whose notice period has elapsed: the provider is paid
+    /// for billed usage and the unused prepayment is refunded. Callable by
+    /// anyone.
+    ///
+    /// # Panics
+    /// - If no close was requested or the notice period is still running.
+    pub fn finalize_close(env: Env, stream_id: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        assert!(
+            stream.status == MeteredStatus::Closing,
+            "close not requested"
+        );
+        assert!(
+            env.ledger().timestamp() >= stream.closes_at,
+            "notice period not elapsed"
+        );
+        close(&env, &mut stream);
+    }
+
+    /// Close the stream immediately, settling billed usage and refunding
+    /// the rest. Only the provider may call this.
+    ///
+    /// # Panics
+    /// - If the stream is already closed.
+    pub fn close_stream(env: Env, stream_id: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        stream.provider.require_auth();
+        assert!(stream.status != MeteredStatus::Closed, "stream is closed");
+        close(&env, &mut stream);
+    }
+
+    /// Return the amount the provider can withdraw now.
+    pub fn withdrawable(env: Env, stream_id: u64) -> i128 {
+        let stream = load_stream(&env, stream_id);
+        billed(&stream) - stream.withdrawn
+    }
+
+    /// Return the prepaid balance not yet consumed by usage.
+    pub fn remaining_balance(env: Env, stream_id: u64) -> i128 {
+        let stream = load_stream(&env, stream_id);
+        stream.prepaid - billed(&stream)
+    }
+
+    /// Return the stream identified by `stream_id`.
+    pub fn get_stream(env: Env, stream_id: u64) -> MeteredStream {
+        load_stream(&env, stream_id)
+    }
+}
+
+#[cfg(test)]
+mod test;
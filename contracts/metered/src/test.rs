@@ -0,0 +1,111 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, Env};
+
+use fluxora_testutils::{mint, register_token, set_time};
+
+use crate::{FluxoraMetered, FluxoraMeteredClient, MeteredStatus};
+
+const NOTICE: u64 = 3_600;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    metered_id: Address,
+    token_id: Address,
+    customer: Address,
+    provider: Address,
+    stream_id: u64,
+}
+
+impl TestContext {
+    /// A customer prepays 1000 units for usage billed at 2 per unit.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let customer = Address::generate(&env);
+        let provider = Address::generate(&env);
+        mint(&env, &token_id, &customer, 5_000);
+
+        let metered_id = env.register_contract(None, FluxoraMetered);
+        let stream_id = FluxoraMeteredClient::new(&env, &metered_id).open(
+            &customer, &provider, &provider, &token_id, &2, &1_000, &NOTICE,
+        );
+
+        TestContext {
+            env,
+            metered_id,
+            token_id,
+            customer,
+            provider,
+            stream_id,
+        }
+    }
+
+    fn metered(&self) -> FluxoraMeteredClient<'_> {
+        FluxoraMeteredClient::new(&self.env, &self.metered_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — usage and settlement
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_usage_becomes_withdrawable() {
+    let ctx = TestContext::setup();
+    ctx.metered().report_usage(&ctx.stream_id, &150);
+    assert_eq!(ctx.metered().withdrawable(&ctx.stream_id), 300);
+    assert_eq!(ctx.metered().withdraw(&ctx.stream_id), 300);
+    assert_eq!(ctx.token().balance(&ctx.provider), 300);
+    assert_eq!(ctx.metered().remaining_balance(&ctx.stream_id), 700);
+}
+
+#[test]
+fn test_billing_capped_by_prepaid_until_top_up() {
+    let ctx = TestContext::setup();
+    ctx.metered().report_usage(&ctx.stream_id, &800);
+    assert_eq!(ctx.metered().withdrawable(&ctx.stream_id), 1_000);
+
+    ctx.metered().top_up(&ctx.stream_id, &1_000);
+    assert_eq!(ctx.metered().withdrawable(&ctx.stream_id), 1_600);
+}
+
+#[test]
+fn test_customer_close_refunds_after_notice() {
+    let ctx = TestContext::setup();
+    ctx.metered().report_usage(&ctx.stream_id, &100);
+    ctx.metered().request_close(&ctx.stream_id);
+    assert!(ctx.metered().try_finalize_close(&ctx.stream_id).is_err());
+
+    // Late usage within the notice period is still billed.
+    ctx.metered().report_usage(&ctx.stream_id, &50);
+    set_time(&ctx.env, NOTICE);
+    ctx.metered().finalize_close(&ctx.stream_id);
+
+    assert_eq!(ctx.token().balance(&ctx.provider), 300);
+    assert_eq!(ctx.token().balance(&ctx.customer), 4_700);
+    assert_eq!(
+        ctx.metered().get_stream(&ctx.stream_id).status,
+        MeteredStatus::Closed
+    );
+}
+
+#[test]
+#[should_panic(expected = "stream is closed")]
+fn test_no_usage_after_close() {
+    let ctx = TestContext::setup();
+    ctx.metered().close_stream(&ctx.stream_id);
+    ctx.metered().report_usage(&ctx.stream_id, &1);
+}
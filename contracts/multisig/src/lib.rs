@@ -0,0 +1,282 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, Env, IntoVal, Symbol, TryFromVal,
+    Val, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Signer set and approval threshold.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// A cross-contract call awaiting `threshold` signer approvals.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub approvals: Vec<Address>,
+    pub executed: bool,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,         // Instance storage for signers and threshold.
+    NextProposalId, // Instance storage for the auto-incrementing proposal ID.
+    Proposal(u64),  // Persistent storage for individual proposals.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn set_config(env: &Env, config: &Config) {
+    assert!(
+        config.threshold >= 1 && config.threshold <= config.signers.len(),
+        "threshold must be between 1 and the number of signers"
+    );
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> Proposal {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .expect("proposal not found")
+}
+
+fn save_proposal(env: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.proposal_id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn assert_signer(config: &Config, who: &Address) {
+    assert!(config.signers.contains(who), "not a signer");
+}
+
+/// Approvals that still belong to the current signer set.
+fn valid_approvals(config: &Config, proposal: &Proposal) -> u32 {
+    proposal
+        .approvals
+        .iter()
+        .filter(|a| config.signers.contains(a))
+        .count() as u32
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraMultisig;
+
+#[contractimpl]
+impl FluxoraMultisig {
+    /// Initialise with the signer set and approval threshold.
+    /// Can only be called once.
+    ///
+    /// # Panics
+    /// - If `signers` contains duplicates.
+    /// - If `threshold` is zero or exceeds the number of signers.
+    pub fn init(env: Env, signers: Vec<Address>, threshold: u32) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            assert!(
+                signers.first_index_of(&signer) == Some(i as u32),
+                "duplicate signer"
+            );
+        }
+        set_config(&env, &Config { signers, threshold });
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &0u64);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Propose `target.function(args)`. The proposer's approval is recorded
+    /// immediately. Returns the new proposal id.
+    ///
+    /// # Panics
+    /// - If `proposer` is not a signer.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) -> u64 {
+        proposer.require_auth();
+        assert_signer(&get_config(&env), &proposer);
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        let proposal = Proposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            target: target.clone(),
+            function: function.clone(),
+            args,
+            approvals: Vec::from_array(&env, [proposer.clone()]),
+            executed: false,
+        };
+        save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("proposed"), proposal_id),
+            (proposer, target, function),
+        );
+        proposal_id
+    }
+
+    /// Record `signer`'s approval of a pending proposal.
+    ///
+    /// # Panics
+    /// - If `signer` is not a signer or has already approved.
+    /// - If the proposal was already executed.
+    pub fn confirm(env: Env, signer: Address, proposal_id: u64) {
+        signer.require_auth();
+        assert_signer(&get_config(&env), &signer);
+
+        let mut proposal = load_proposal(&env, proposal_id);
+        assert!(!proposal.executed, "proposal already executed");
+        assert!(!proposal.approvals.contains(&signer), "already confirmed");
+
+        proposal.approvals.push_back(signer.clone());
+        save_proposal(&env, &proposal);
+
+        env.events()
+            .publish((symbol_short!("confirmed"), proposal_id), signer);
+    }
+
+    /// Withdraw `signer`'s earlier approval of a pending proposal.
+    ///
+    /// # Panics
+    /// - If `signer` had not approved, or the proposal was already executed.
+    pub fn revoke(env: Env, signer: Address, proposal_id: u64) {
+        signer.require_auth();
+
+        let mut proposal = load_proposal(&env, proposal_id);
+        assert!(!proposal.executed, "proposal already executed");
+        let index = proposal
+            .approvals
+            .first_index_of(&signer)
+            .expect("not confirmed");
+        proposal.approvals.remove(index);
+        save_proposal(&env, &proposal);
+
+        env.events()
+            .publish((symbol_short!("revoked"), proposal_id), signer);
+    }
+
+    /// Execute a proposal that has reached the threshold. Anyone may trigger
+    /// execution once enough signers approved. Returns the target's result.
+    ///
+    /// # Panics
+    /// - If the proposal was already executed.
+    /// - If fewer than `threshold` current signers approved it.
+    pub fn execute(env: Env, proposal_id: u64) -> Val {
+        let config = get_config(&env);
+        let mut proposal = load_proposal(&env, proposal_id);
+        assert!(!proposal.executed, "proposal already executed");
+        assert!(
+            valid_approvals(&config, &proposal) >= config.threshold,
+            "threshold not reached"
+        );
+
+        // Mark executed before the call so a re-entrant execute cannot replay it.
+        proposal.executed = true;
+        save_proposal(&env, &proposal);
+
+        // Soroban forbids re-entrant self-calls, so signer-set changes
+        // targeting this contract are dispatched internally.
+        let result: Val = if proposal.target == env.current_contract_address() {
+            Self::apply_self_call(&env, &proposal.function, &proposal.args)
+        } else {
+            env.invoke_contract(&proposal.target, &proposal.function, proposal.args.clone())
+        };
+
+        env.events()
+            .publish((symbol_short!("executed"), proposal_id), ());
+        result
+    }
+
+    /// Fetches the signer set and threshold.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the proposal identified by `proposal_id`.
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
+        load_proposal(&env, proposal_id)
+    }
+
+    /// Signer administration, reachable only through an executed proposal
+    /// whose target is this contract:
+    /// - `add_signer(Address)`
+    /// - `remove_signer(Address)` — the threshold must remain satisfiable.
+    /// - `set_threshold(u32)`
+    fn apply_self_call(env: &Env, function: &Symbol, args: &Vec<Val>) -> Val {
+        let arg = args.get(0).expect("missing argument");
+        let mut config = get_config(env);
+
+        if *function == Symbol::new(env, "add_signer") {
+            let signer = Address::try_from_val(env, &arg).expect("invalid signer");
+            assert!(!config.signers.contains(&signer), "duplicate signer");
+            config.signers.push_back(signer.clone());
+            set_config(env, &config);
+            env.events().publish((symbol_short!("sig_add"),), signer);
+        } else if *function == Symbol::new(env, "remove_signer") {
+            let signer = Address::try_from_val(env, &arg).expect("invalid signer");
+            let index = config
+                .signers
+                .first_index_of(&signer)
+                .expect("not a signer");
+            config.signers.remove(index);
+            set_config(env, &config);
+            env.events().publish((symbol_short!("sig_rm"),), signer);
+        } else if *function == Symbol::new(env, "set_threshold") {
+            let threshold = u32::try_from_val(env, &arg).expect("invalid threshold");
+            config.threshold = threshold;
+            set_config(env, &config);
+            env.events()
+                .publish((symbol_short!("threshold"),), threshold);
+        } else {
+            panic!("unknown self-call");
+        }
+
+        ().into_val(env)
+    }
+}
+
+#[cfg(test)]
+mod test;
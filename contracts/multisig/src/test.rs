@@ -0,0 +1,217 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+use fluxora_stream::{FluxoraStreamClient, StreamStatus};
+use fluxora_testutils::{mint, register_stream_with_admin, register_token, set_time};
+
+use crate::{FluxoraMultisig, FluxoraMultisigClient};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    multisig_id: Address,
+    stream_id: Address,
+    signers: [Address; 3],
+    sender: Address,
+    recipient: Address,
+}
+
+impl TestContext {
+    /// Deploy a 2-of-3 multisig and a FluxoraStream whose admin is the multisig.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let signers = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        let multisig_id = env.register_contract(None, FluxoraMultisig);
+        FluxoraMultisigClient::new(&env, &multisig_id)
+            .init(&Vec::from_array(&env, signers.clone()), &2);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream_with_admin(&env, &token_id, &multisig_id);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &sender, 10_000);
+
+        TestContext {
+            env,
+            multisig_id,
+            stream_id,
+            signers,
+            sender,
+            recipient,
+        }
+    }
+
+    fn multisig(&self) -> FluxoraMultisigClient<'_> {
+        FluxoraMultisigClient::new(&self.env, &self.multisig_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    /// Propose a call on the multisig itself (signer administration).
+    fn propose_self(&self, function: &str, arg: Val) -> u64 {
+        let args: Vec<Val> = vec![&self.env, arg];
+        self.multisig().propose(
+            &self.signers[0],
+            &self.multisig_id,
+            &Symbol::new(&self.env, function),
+            &args,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — propose / confirm / execute
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_two_of_three_executes_admin_cancel() {
+    let ctx = TestContext::setup();
+    let id = ctx
+        .stream()
+        .create_stream(&ctx.sender, &ctx.recipient, &1000, &1, &0, &0, &1000);
+
+    let args: Vec<Val> = vec![&ctx.env, id.into_val(&ctx.env)];
+    let proposal_id = ctx.multisig().propose(
+        &ctx.signers[0],
+        &ctx.stream_id,
+        &Symbol::new(&ctx.env, "cancel_stream_as_admin"),
+        &args,
+    );
+    ctx.multisig().confirm(&ctx.signers[1], &proposal_id);
+    ctx.multisig().execute(&proposal_id);
+
+    assert_eq!(
+        ctx.stream().get_stream_state(&id).status,
+        StreamStatus::Cancelled
+    );
+    assert!(ctx.multisig().get_proposal(&proposal_id).executed);
+}
+
+#[test]
+#[should_panic(expected = "threshold not reached")]
+fn test_execute_below_threshold_panics() {
+    let ctx = TestContext::setup();
+    let proposal_id = ctx.propose_self("set_threshold", 1u32.into_val(&ctx.env));
+    ctx.multisig().execute(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "threshold not reached")]
+fn test_revoked_confirmation_not_counted() {
+    let ctx = TestContext::setup();
+    let proposal_id = ctx.propose_self("set_threshold", 1u32.into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[1], &proposal_id);
+    ctx.multisig().revoke(&ctx.signers[1], &proposal_id);
+    ctx.multisig().execute(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "not a signer")]
+fn test_non_signer_cannot_propose() {
+    let ctx = TestContext::setup();
+    let outsider = Address::generate(&ctx.env);
+    let args: Vec<Val> = vec![&ctx.env];
+    ctx.multisig().propose(
+        &outsider,
+        &ctx.stream_id,
+        &Symbol::new(&ctx.env, "emergency_unpause"),
+        &args,
+    );
+}
+
+#[test]
+#[should_panic(expected = "already confirmed")]
+fn test_double_confirm_panics() {
+    let ctx = TestContext::setup();
+    let proposal_id = ctx.propose_self("set_threshold", 1u32.into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[0], &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "proposal already executed")]
+fn test_execute_twice_panics() {
+    let ctx = TestContext::setup();
+    let proposal_id = ctx.propose_self("set_threshold", 1u32.into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[1], &proposal_id);
+    ctx.multisig().execute(&proposal_id);
+    ctx.multisig().execute(&proposal_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — signer rotation and threshold changes
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_add_and_remove_signer() {
+    let ctx = TestContext::setup();
+    let newcomer = Address::generate(&ctx.env);
+
+    let add = ctx.propose_self("add_signer", newcomer.into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[1], &add);
+    ctx.multisig().execute(&add);
+    assert!(ctx.multisig().get_config().signers.contains(&newcomer));
+
+    let remove = ctx.propose_self("remove_signer", ctx.signers[2].into_val(&ctx.env));
+    ctx.multisig().confirm(&newcomer, &remove);
+    ctx.multisig().execute(&remove);
+
+    let config = ctx.multisig().get_config();
+    assert_eq!(config.signers.len(), 3);
+    assert!(!config.signers.contains(&ctx.signers[2]));
+}
+
+#[test]
+fn test_removed_signer_approval_no_longer_counts() {
+    let ctx = TestContext::setup();
+    let pending = ctx.propose_self("set_threshold", 1u32.into_val(&ctx.env));
+
+    // Signer 0 approved `pending` at proposal time; now rotate signer 0 out.
+    let remove = ctx.propose_self("remove_signer", ctx.signers[0].into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[1], &remove);
+    ctx.multisig().execute(&remove);
+
+    assert!(ctx.multisig().try_execute(&pending).is_err());
+}
+
+#[test]
+fn test_set_threshold() {
+    let ctx = TestContext::setup();
+    let proposal_id = ctx.propose_self("set_threshold", 3u32.into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[1], &proposal_id);
+    ctx.multisig().execute(&proposal_id);
+    assert_eq!(ctx.multisig().get_config().threshold, 3);
+}
+
+#[test]
+#[should_panic(expected = "threshold must be between 1 and the number of signers")]
+fn test_threshold_above_signer_count_panics() {
+    let ctx = TestContext::setup();
+    let proposal_id = ctx.propose_self("set_threshold", 4u32.into_val(&ctx.env));
+    ctx.multisig().confirm(&ctx.signers[1], &proposal_id);
+    ctx.multisig().execute(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "duplicate signer")]
+fn test_init_with_duplicate_signers_panics() {
+    let env = Env::default();
+    let signer = Address::generate(&env);
+    let multisig_id = env.register_contract(None, FluxoraMultisig);
+    FluxoraMultisigClient::new(&env, &multisig_id)
+        .init(&Vec::from_array(&env, [signer.clone(), signer]), &1);
+}
@@ -0,0 +1,29 @@
+//! Interfaces of external contracts the adapter calls into.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Asset identifier used by Reflector-style feeds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    /// A Stellar token contract.
+    Stellar(Address),
+    /// An off-chain symbol such as `USD`.
+    Other(Symbol),
+}
+
+/// A single price observation as reported by the feed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Price of the asset in the feed's quote currency, scaled by `decimals()`.
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// The subset of a Reflector-style price feed used by the adapter.
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+    fn decimals(env: Env) -> u32;
+}
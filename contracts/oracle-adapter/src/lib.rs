@@ -0,0 +1,181 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Asset, PriceData, PriceFeed, PriceFeedClient};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Fixed-point precision of every price returned by the adapter, regardless
+/// of the underlying feed's own precision.
+pub const PRICE_DECIMALS: u32 = 14;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for the adapter.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Underlying Reflector-style price feed.
+    pub feed: Address,
+    /// Currency the feed quotes every asset in (e.g. `Other("USD")`).
+    pub quote: Asset,
+    /// Observations older than this many seconds are rejected.
+    pub max_age: u64,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config, // Instance storage for the feed and staleness bound.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn set_config(env: &Env, config: &Config) {
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+/// Fresh price of `asset` in the feed's quote currency, at `PRICE_DECIMALS`.
+fn quoted_price(env: &Env, config: &Config, asset: &Asset) -> i128 {
+    if *asset == config.quote {
+        return 10i128.pow(PRICE_DECIMALS);
+    }
+    let feed = PriceFeedClient::new(env, &config.feed);
+    let data = feed.lastprice(asset).expect("price unavailable");
+    assert!(
+        env.ledger().timestamp() <= data.timestamp.saturating_add(config.max_age),
+        "stale price"
+    );
+    assert!(data.price > 0, "price unavailable");
+
+    let decimals = feed.decimals();
+    let scaled = if decimals <= PRICE_DECIMALS {
+        data.price
+            .checked_mul(10i128.pow(PRICE_DECIMALS - decimals))
+            .expect("price overflow")
+    } else {
+        10i128
+            .checked_pow(decimals - PRICE_DECIMALS)
+            .map_or(0, |divisor| data.price / divisor)
+    };
+    // A price finer than `PRICE_DECIMALS` would otherwise divide by zero in
+    // cross rates, or read as free.
+    assert!(scaled > 0, "price below adapter precision");
+    scaled
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraOracleAdapter;
+
+#[contractimpl]
+impl FluxoraOracleAdapter {
+    /// Initialise with the admin, the underlying feed, the feed's quote
+    /// currency and the staleness bound. Can only be called once.
+    pub fn init(env: Env, admin: Address, feed: Address, quote: Asset, max_age: u64) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        set_config(
+            &env,
+            &Config {
+                feed,
+                quote,
+                max_age,
+            },
+        );
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Price of one unit of `token` expressed in `base`, scaled by
+    /// `PRICE_DECIMALS`. Cross rates are derived through the feed's quote
+    /// currency.
+    ///
+    /// # Panics
+    /// - If the feed has no price for either asset.
+    /// - If either observation is older than `max_age`.
+    pub fn price(env: Env, token: Asset, base: Asset) -> i128 {
+        let config = get_config(&env);
+        let token_price = quoted_price(&env, &config, &token);
+        let base_price = quoted_price(&env, &config, &base);
+        token_price
+            .checked_mul(10i128.pow(PRICE_DECIMALS))
+            .expect("price overflow")
+            / base_price
+    }
+
+    /// Return the precision of prices returned by `price`.
+    pub fn decimals(_env: Env) -> u32 {
+        PRICE_DECIMALS
+    }
+
+    /// Point the adapter at a new feed quoting in `quote`.
+    /// Only the admin may call this.
+    pub fn set_feed(env: Env, feed: Address, quote: Asset) {
+        fluxora_access::require_admin(&env);
+        let mut config = get_config(&env);
+        config.feed = feed.clone();
+        config.quote = quote;
+        set_config(&env, &config);
+
+        env.events().publish((symbol_short!("feed"),), feed);
+    }
+
+    /// Change how old an observation may be before it is rejected.
+    /// Only the admin may call this.
+    pub fn set_max_age(env: Env, max_age: u64) {
+        fluxora_access::require_admin(&env);
+        let mut config = get_config(&env);
+        config.max_age = max_age;
+        set_config(&env, &config);
+
+        env.events().publish((symbol_short!("max_age"),), max_age);
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+
+    /// Fetches the adapter configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
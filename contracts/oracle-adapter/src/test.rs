@@ -0,0 +1,174 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Symbol};
+
+use fluxora_testutils::set_time;
+
+use crate::{Asset, FluxoraOracleAdapter, FluxoraOracleAdapterClient, PriceData, PRICE_DECIMALS};
+
+const MAX_AGE: u64 = 300;
+
+// ---------------------------------------------------------------------------
+// Mock Reflector feed (7 decimals unless set, quoted in USD)
+// ---------------------------------------------------------------------------
+
+#[contract]
+struct MockFeed;
+
+#[contractimpl]
+impl MockFeed {
+    pub fn set_price(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&asset, &PriceData { price, timestamp });
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage().instance().get(&asset)
+    }
+
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&(), &decimals);
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&()).unwrap_or(7)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    adapter_id: Address,
+    feed_id: Address,
+    xlm: Asset,
+    eurc: Asset,
+    usd: Asset,
+}
+
+impl TestContext {
+    /// XLM at $0.10 and EURC at $1.25, both observed at t=1000.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 1_000);
+
+        let feed_id = env.register_contract(None, MockFeed);
+        let usd = Asset::Other(Symbol::new(&env, "USD"));
+        let xlm = Asset::Stellar(Address::generate(&env));
+        let eurc = Asset::Stellar(Address::generate(&env));
+        let feed = MockFeedClient::new(&env, &feed_id);
+        feed.set_price(&xlm, &1_000_000, &1_000);
+        feed.set_price(&eurc, &12_500_000, &1_000);
+
+        let adapter_id = env.register_contract(None, FluxoraOracleAdapter);
+        FluxoraOracleAdapterClient::new(&env, &adapter_id).init(
+            &Address::generate(&env),
+            &feed_id,
+            &usd,
+            &MAX_AGE,
+        );
+
+        TestContext {
+            env,
+            adapter_id,
+            feed_id,
+            xlm,
+            eurc,
+            usd,
+        }
+    }
+
+    fn adapter(&self) -> FluxoraOracleAdapterClient<'_> {
+        FluxoraOracleAdapterClient::new(&self.env, &self.adapter_id)
+    }
+}
+
+const ONE: i128 = 10i128.pow(PRICE_DECIMALS);
+
+// ---------------------------------------------------------------------------
+// Tests — prices
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_price_in_quote_currency_is_rescaled() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.adapter().price(&ctx.xlm, &ctx.usd), ONE / 10);
+    assert_eq!(ctx.adapter().price(&ctx.usd, &ctx.usd), ONE);
+    assert_eq!(ctx.adapter().decimals(), PRICE_DECIMALS);
+}
+
+#[test]
+fn test_cross_rate_through_quote() {
+    let ctx = TestContext::setup();
+    // 1 EURC = 12.5 XLM; 1 XLM = 0.08 EURC.
+    assert_eq!(ctx.adapter().price(&ctx.eurc, &ctx.xlm), ONE * 125 / 10);
+    assert_eq!(ctx.adapter().price(&ctx.xlm, &ctx.eurc), ONE * 8 / 100);
+}
+
+#[test]
+#[should_panic(expected = "stale price")]
+fn test_stale_price_rejected() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 1_000 + MAX_AGE + 1);
+    ctx.adapter().price(&ctx.xlm, &ctx.usd);
+}
+
+#[test]
+#[should_panic(expected = "price unavailable")]
+fn test_missing_price_rejected() {
+    let ctx = TestContext::setup();
+    let unknown = Asset::Stellar(Address::generate(&ctx.env));
+    ctx.adapter().price(&unknown, &ctx.usd);
+}
+
+#[test]
+#[should_panic(expected = "price overflow")]
+fn test_rescaling_overflow_rejected() {
+    let ctx = TestContext::setup();
+    MockFeedClient::new(&ctx.env, &ctx.feed_id).set_price(&ctx.xlm, &(i128::MAX / 10), &1_000);
+    ctx.adapter().price(&ctx.xlm, &ctx.usd);
+}
+
+#[test]
+#[should_panic(expected = "price below adapter precision")]
+fn test_price_finer_than_precision_rejected() {
+    let ctx = TestContext::setup();
+    let feed = MockFeedClient::new(&ctx.env, &ctx.feed_id);
+    feed.set_decimals(&(PRICE_DECIMALS + 8));
+    ctx.adapter().price(&ctx.usd, &ctx.xlm);
+}
+
+#[test]
+fn test_max_age_can_be_relaxed() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 1_000 + MAX_AGE + 1);
+    ctx.adapter().set_max_age(&(MAX_AGE * 2));
+    assert_eq!(ctx.adapter().price(&ctx.xlm, &ctx.usd), ONE / 10);
+
+    // A newer observation is picked up immediately.
+    MockFeedClient::new(&ctx.env, &ctx.feed_id).set_price(&ctx.xlm, &2_000_000, &1_301);
+    assert_eq!(ctx.adapter().price(&ctx.xlm, &ctx.usd), ONE / 5);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.adapter().propose_admin(&new_admin);
+    assert_ne!(ctx.adapter().get_admin(), new_admin);
+
+    ctx.adapter().accept_admin();
+    assert_eq!(ctx.adapter().get_admin(), new_admin);
+    ctx.adapter().set_max_age(&(MAX_AGE * 2));
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.adapter().get_config().max_age, MAX_AGE * 2);
+}
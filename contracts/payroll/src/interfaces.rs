@@ -0,0 +1,23 @@
+//! Interfaces of external contracts the payroll contract calls into.
+
+pub use fluxora_types::FeeParams;
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used by payroll.
+#[contractclient(name = "StreamClient")]
+pub trait Stream {
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream_with_token(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64;
+    fn cancel_stream(env: Env, stream_id: u64);
+    fn get_fee_params(env: Env) -> FeeParams;
+}
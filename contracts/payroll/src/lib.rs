@@ -0,0 +1,311 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{FeeParams, Stream, StreamClient};
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, Env, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for a payroll instance.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Manages the roster and funds the treasury.
+    pub employer: Address,
+    /// FluxoraStream deployment that carries the salary streams.
+    pub stream_contract: Address,
+    /// Salary token; the treasury is this contract's balance of it.
+    pub token: Address,
+    /// Length of one pay period, in seconds.
+    pub period: u64,
+    /// Start of the next pay period to be opened by `run_payroll`.
+    pub next_period_start: u64,
+}
+
+/// A rostered employee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Employee {
+    /// Salary expressed as a per-second rate; applies from the next run.
+    pub rate_per_second: i128,
+    /// Stream opened for the most recent pay period, if any.
+    pub current_stream: Option<u64>,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,            // Instance storage for the employer, stream contract and schedule.
+    Employees,         // Instance storage: roster in joining order.
+    Employee(Address), // Persistent storage: salary and current stream per employee.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn get_employees(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Employees)
+        .unwrap_or(Vec::new(env))
+}
+
+fn load_employee(env: &Env, employee: &Address) -> Employee {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Employee(employee.clone()))
+        .expect("employee not found")
+}
+
+fn save_employee(env: &Env, address: &Address, employee: &Employee) {
+    let key = DataKey::Employee(address.clone());
+    env.storage().persistent().set(&key, employee);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraPayroll;
+
+#[contractimpl]
+impl FluxoraPayroll {
+    /// Initialise payroll for `employer`, paying in `token` through
+    /// `stream_contract` in periods of `period` seconds starting at
+    /// `first_period_start`. Can only be called once.
+    ///
+    /// # Panics
+    /// - If `period` is zero.
+    /// - With `IncompatibleInterface` if `stream_contract` is not a
+    ///   compatible FluxoraStream deployment.
+    pub fn init(
+        env: Env,
+        employer: Address,
+        stream_contract: Address,
+        token: Address,
+        period: u64,
+        first_period_start: u64,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        assert!(period > 0, "period must be positive");
+        fluxora_types::require_compatible(&env, &stream_contract);
+
+        let config = Config {
+            employer,
+            stream_contract,
+            token,
+            period,
+            next_period_start: first_period_start,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Move `amount` of the salary token from `from` into the treasury.
+    pub fn fund(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        let config = get_config(&env);
+        token::Client::new(&env, &config.token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("funded"), from), amount);
+    }
+
+    /// Add a joiner at `rate_per_second`. They are paid from the next
+    /// `run_payroll`. Only the employer may call this.
+    ///
+    /// # Panics
+    /// - If `rate_per_second` is not positive.
+    /// - If `employee` is already on the roster.
+    pub fn add_employee(env: Env, employee: Address, rate_per_second: i128) {
+        get_config(&env).employer.require_auth();
+        assert!(rate_per_second > 0, "rate_per_second must be positive");
+
+        let mut employees = get_employees(&env);
+        assert!(!employees.contains(&employee), "employee already added");
+        employees.push_back(employee.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Employees, &employees);
+
+        save_employee(
+            &env,
+            &employee,
+            &Employee {
+                rate_per_second,
+                current_stream: None,
+            },
+        );
+
+        env.events()
+            .publish((symbol_short!("joined"), employee), rate_per_second);
+    }
+
+    /// Change an employee's salary from the next pay period onwards.
+    /// Only the employer may call this.
+    ///
+    /// # Panics
+    /// - If `rate_per_second` is not positive, or `employee` is not rostered.
+    pub fn set_salary(env: Env, employee: Address, rate_per_second: i128) {
+        get_config(&env).employer.require_auth();
+        assert!(rate_per_second > 0, "rate_per_second must be positive");
+
+        let mut record = load_employee(&env, &employee);
+        record.rate_per_second = rate_per_second;
+        save_employee(&env, &employee, &record);
+
+        env.events()
+            .publish((symbol_short!("salary"), employee), rate_per_second);
+    }
+
+    /// Remove a leaver. If their current pay period is still running, its
+    /// stream is cancelled: they keep what accrued and the unstreamed rest
+    /// returns to the treasury. Only the employer may call this.
+    ///
+    /// # Panics
+    /// - If `employee` is not rostered.
+    pub fn remove_employee(env: Env, employee: Address) {
+        let config = get_config(&env);
+        config.employer.require_auth();
+
+        let record = load_employee(&env, &employee);
+        // Streams opened by the last run end at `next_period_start`.
+        if let Some(stream_id) = record.current_stream {
+            if env.ledger().timestamp() < config.next_period_start {
+                StreamClient::new(&env, &config.stream_contract).cancel_stream(&stream_id);
+            }
+        }
+
+        let mut employees = get_employees(&env);
+        let index = employees
+            .first_index_of(&employee)
+            .expect("employee not found");
+        employees.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Employees, &employees);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Employee(employee.clone()));
+
+        env.events().publish((symbol_short!("left"), employee), ());
+    }
+
+    /// Open the next pay period: one stream per rostered employee, funded
+    /// from the treasury, covering `[next_period_start, next_period_start + period)`.
+    /// Callable by anyone (e.g. a keeper) once the period has started.
+    /// Returns the number of streams opened.
+    ///
+    /// # Panics
+    /// - If the next pay period has not started yet.
+    /// - If the treasury cannot cover every salary (nothing is opened).
+    pub fn run_payroll(env: Env) -> u32 {
+        let mut config = get_config(&env);
+        let start = config.next_period_start;
+        assert!(
+            env.ledger().timestamp() >= start,
+            "pay period has not started"
+        );
+        let end = start + config.period;
+
+        let stream = StreamClient::new(&env, &config.stream_contract);
+        let fee_params = stream.get_fee_params();
+        let payroll = env.current_contract_address();
+
+        let employees = get_employees(&env);
+        for address in employees.iter() {
+            let mut record = load_employee(&env, &address);
+            let deposit = record
+                .rate_per_second
+                .checked_mul(config.period as i128)
+                .expect("overflow calculating salary");
+
+            // The stream contract pulls the deposit (and any protocol fee)
+            // from this contract, one call below our direct invocation.
+            let mut auths = vec![
+                &env,
+                fluxora_access::transfer_auth(
+                    &env,
+                    &config.token,
+                    &payroll,
+                    &config.stream_contract,
+                    deposit,
+                ),
+            ];
+            let fee = fluxora_math::bps(deposit, fee_params.protocol_fee_bps)
+                .expect("overflow calculating protocol fee");
+            if fee > 0 {
+                auths.push_back(fluxora_access::transfer_auth(
+                    &env,
+                    &config.token,
+                    &payroll,
+                    &fee_params.fee_collector,
+                    fee,
+                ));
+            }
+            env.authorize_as_current_contract(auths);
+
+            let stream_id = stream.create_stream_with_token(
+                &payroll,
+                &address,
+                &config.token,
+                &deposit,
+                &record.rate_per_second,
+                &start,
+                &start,
+                &end,
+            );
+            record.current_stream = Some(stream_id);
+            save_employee(&env, &address, &record);
+        }
+
+        config.next_period_start = end;
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        env.events()
+            .publish((symbol_short!("payroll"), start), employees.len());
+        employees.len()
+    }
+
+    /// Fetches the payroll configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the roster in joining order.
+    pub fn get_employees(env: Env) -> Vec<Address> {
+        get_employees(&env)
+    }
+
+    /// Return the salary record of `employee`.
+    pub fn get_employee(env: Env, employee: Address) -> Employee {
+        load_employee(&env, &employee)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,245 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, Address, Env};
+
+use fluxora_stream::{FeeParams, FluxoraStreamClient, StreamStatus, FEE_TIMELOCK_SECS};
+
+use fluxora_testutils::{
+    mint, register_outdated_stream, register_stream_with_admin, register_token, set_time,
+};
+
+use crate::{FluxoraPayroll, FluxoraPayrollClient};
+
+const PERIOD: u64 = 1000;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    payroll_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream and a payroll whose treasury holds 10_000 units,
+    /// with two employees paid 1/s and 2/s.
+    fn setup(first_period_start: u64) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let admin = Address::generate(&env);
+        let stream_id = register_stream_with_admin(&env, &token_id, &admin);
+
+        let employer = Address::generate(&env);
+        let payroll_id = env.register_contract(None, FluxoraPayroll);
+        let payroll = FluxoraPayrollClient::new(&env, &payroll_id);
+        payroll.init(
+            &employer,
+            &stream_id,
+            &token_id,
+            &PERIOD,
+            &first_period_start,
+        );
+
+        mint(&env, &token_id, &employer, 10_000);
+        payroll.fund(&employer, &10_000);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        payroll.add_employee(&alice, &1);
+        payroll.add_employee(&bob, &2);
+
+        TestContext {
+            env,
+            payroll_id,
+            stream_id,
+            token_id,
+            alice,
+            bob,
+        }
+    }
+
+    fn payroll(&self) -> FluxoraPayrollClient<'_> {
+        FluxoraPayrollClient::new(&self.env, &self.payroll_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn current_stream(&self, employee: &Address) -> u64 {
+        self.payroll()
+            .get_employee(employee)
+            .current_stream
+            .unwrap()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — pay periods
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_run_payroll_opens_one_stream_per_employee() {
+    let ctx = TestContext::setup(0);
+
+    // Keepers need no signature; the payroll contract authorises the deposits.
+    ctx.env.set_auths(&[]);
+    assert_eq!(ctx.payroll().run_payroll(), 2);
+
+    let alice_stream = ctx
+        .stream()
+        .get_stream_state(&ctx.current_stream(&ctx.alice));
+    assert_eq!(alice_stream.sender, ctx.payroll_id);
+    assert_eq!(alice_stream.deposit_amount, 1000);
+    assert_eq!(
+        (alice_stream.start_time, alice_stream.end_time),
+        (0, PERIOD)
+    );
+    let bob_stream = ctx.stream().get_stream_state(&ctx.current_stream(&ctx.bob));
+    assert_eq!(bob_stream.deposit_amount, 2000);
+
+    assert_eq!(ctx.token().balance(&ctx.payroll_id), 7_000);
+    assert_eq!(ctx.payroll().get_config().next_period_start, PERIOD);
+}
+
+#[test]
+fn test_payroll_renews_each_period() {
+    let ctx = TestContext::setup(0);
+    ctx.payroll().run_payroll();
+    let first = ctx.current_stream(&ctx.alice);
+
+    set_time(&ctx.env, PERIOD);
+    ctx.payroll().set_salary(&ctx.alice, &3);
+    ctx.payroll().run_payroll();
+    let second = ctx.current_stream(&ctx.alice);
+    assert_ne!(first, second);
+
+    let renewed = ctx.stream().get_stream_state(&second);
+    assert_eq!(renewed.deposit_amount, 3000);
+    assert_eq!((renewed.start_time, renewed.end_time), (PERIOD, 2 * PERIOD));
+    assert_eq!(ctx.stream().withdraw(&first), 1000);
+}
+
+#[test]
+#[should_panic(expected = "pay period has not started")]
+fn test_run_payroll_before_period_start_panics() {
+    let ctx = TestContext::setup(500);
+    ctx.payroll().run_payroll();
+}
+
+#[test]
+fn test_insufficient_treasury_opens_nothing() {
+    let ctx = TestContext::setup(0);
+    ctx.payroll().set_salary(&ctx.bob, &10);
+    assert!(ctx.payroll().try_run_payroll().is_err());
+    assert_eq!(ctx.token().balance(&ctx.payroll_id), 10_000);
+    assert_eq!(ctx.payroll().get_employee(&ctx.alice).current_stream, None);
+}
+
+#[test]
+fn test_run_payroll_covers_protocol_fee() {
+    let ctx = TestContext::setup(FEE_TIMELOCK_SECS);
+    let collector = Address::generate(&ctx.env);
+    ctx.stream().propose_fee_params(&FeeParams {
+        protocol_fee_bps: 100,
+        fee_collector: collector.clone(),
+        max_broker_fee_bps: 0,
+    });
+    set_time(&ctx.env, FEE_TIMELOCK_SECS);
+    ctx.stream().apply_fee_params();
+
+    ctx.env.set_auths(&[]);
+    ctx.payroll().run_payroll();
+    assert_eq!(ctx.token().balance(&collector), 30);
+    assert_eq!(ctx.token().balance(&ctx.payroll_id), 10_000 - 3_000 - 30);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — joiners and leavers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_leaver_mid_period_refunds_treasury() {
+    let ctx = TestContext::setup(0);
+    ctx.payroll().run_payroll();
+    let bob_stream = ctx.current_stream(&ctx.bob);
+
+    set_time(&ctx.env, 250);
+    ctx.payroll().remove_employee(&ctx.bob);
+
+    assert_eq!(
+        ctx.stream().get_stream_state(&bob_stream).status,
+        StreamStatus::Cancelled
+    );
+    // 500 accrued to Bob stays in the stream; 1500 returns to the treasury.
+    assert_eq!(ctx.token().balance(&ctx.payroll_id), 7_000 + 1_500);
+    assert_eq!(ctx.stream().withdraw(&bob_stream), 500);
+    assert!(!ctx.payroll().get_employees().contains(&ctx.bob));
+}
+
+#[test]
+fn test_joiner_paid_from_next_run() {
+    let ctx = TestContext::setup(0);
+    ctx.payroll().run_payroll();
+
+    let carol = Address::generate(&ctx.env);
+    ctx.payroll().add_employee(&carol, &1);
+    assert_eq!(ctx.payroll().get_employee(&carol).current_stream, None);
+
+    set_time(&ctx.env, PERIOD);
+    assert_eq!(ctx.payroll().run_payroll(), 3);
+    assert!(ctx.payroll().get_employee(&carol).current_stream.is_some());
+}
+
+#[test]
+#[should_panic(expected = "employee already added")]
+fn test_add_employee_twice_panics() {
+    let ctx = TestContext::setup(0);
+    ctx.payroll().add_employee(&ctx.alice, &5);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream compatibility
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_init_rejects_outdated_stream_contract() {
+    let env = Env::default();
+    let payroll_id = env.register_contract(None, FluxoraPayroll);
+    FluxoraPayrollClient::new(&env, &payroll_id).init(
+        &Address::generate(&env),
+        &register_outdated_stream(&env),
+        &register_token(&env),
+        &PERIOD,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_init_rejects_non_stream_contract() {
+    let env = Env::default();
+    let token_id = register_token(&env);
+    let payroll_id = env.register_contract(None, FluxoraPayroll);
+    FluxoraPayrollClient::new(&env, &payroll_id).init(
+        &Address::generate(&env),
+        &token_id,
+        &token_id,
+        &PERIOD,
+        &0,
+    );
+}
@@ -0,0 +1,18 @@
+//! Interfaces of external contracts the pegged stream contract calls into.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Mirror of `fluxora_oracle_adapter::Asset`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// The subset of the Fluxora oracle adapter used for conversions.
+#[contractclient(name = "OracleClient")]
+pub trait Oracle {
+    fn price(env: Env, token: Asset, base: Asset) -> i128;
+    fn decimals(env: Env) -> u32;
+}
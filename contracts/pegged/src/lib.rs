@@ -0,0 +1,402 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Asset, Oracle, OracleClient};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for the pegged stream contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Fluxora oracle adapter used for every conversion.
+    pub oracle: Address,
+    /// Currency stream rates are denominated in (e.g. `Other("USD")`).
+    pub reference: Asset,
+    /// Collateral must exceed the outstanding obligation by this margin, in
+    /// bps, at creation and whenever excess collateral is released.
+    pub min_buffer_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PeggedStatus {
+    Active = 0,
+    Cancelled = 1,
+}
+
+/// A stream denominated in the reference currency and paid in `token`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeggedStream {
+    pub stream_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    /// Token the stream is settled in.
+    pub token: Address,
+    /// Reference-currency units accrued per second.
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Reference-currency units already paid out.
+    pub withdrawn: i128,
+    /// Token units held to back the remaining obligation.
+    pub collateral: i128,
+    pub status: PeggedStatus,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,       // Instance storage for oracle, reference currency and buffer.
+    NextStreamId, // Instance storage for the auto-incrementing stream ID.
+    Stream(u64),  // Persistent storage for individual pegged streams.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn load_stream(env: &Env, stream_id: u64) -> PeggedStream {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Stream(stream_id))
+        .expect("stream not found")
+}
+
+fn save_stream(env: &Env, stream: &PeggedStream) {
+    let key = DataKey::Stream(stream.stream_id);
+    env.storage().persistent().set(&key, stream);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Conversion helpers
+// ---------------------------------------------------------------------------
+
+/// Current price of one token unit in reference units, and its scale.
+fn token_price(env: &Env, config: &Config, token: &Address) -> (i128, i128) {
+    let oracle = OracleClient::new(env, &config.oracle);
+    let price = oracle.price(&Asset::Stellar(token.clone()), &config.reference);
+    (price, 10i128.pow(oracle.decimals()))
+}
+
+fn to_token(amount: i128, (price, scale): (i128, i128)) -> i128 {
+    assert!(price > 0, "oracle price must be positive");
+    amount.checked_mul(scale).expect("overflow") / price
+}
+
+fn to_reference(amount: i128, (price, scale): (i128, i128)) -> i128 {
+    amount.checked_mul(price).expect("overflow") / scale
+}
+
+fn accrued(stream: &PeggedStream, now: u64) -> i128 {
+    let elapsed = now.clamp(stream.start_time, stream.end_time) - stream.start_time;
+    stream
+        .rate_per_second
+        .checked_mul(elapsed as i128)
+        .expect("overflow")
+}
+
+fn total_obligation(stream: &PeggedStream) -> i128 {
+    stream
+        .rate_per_second
+        .checked_mul((stream.end_time - stream.start_time) as i128)
+        .expect("overflow")
+}
+
+/// Whether `collateral` token units cover `outstanding` reference units plus
+/// the configured buffer at `price`.
+fn is_covered(config: &Config, collateral: i128, outstanding: i128, price: (i128, i128)) -> bool {
+    let required_bps = 10_000i128
+        .checked_add(config.min_buffer_bps as i128)
+        .expect("overflow");
+    to_reference(collateral, price)
+        .checked_mul(10_000)
+        .expect("overflow")
+        >= outstanding.checked_mul(required_bps).expect("overflow")
+}
+
+/// Pay the recipient what has accrued, converted at `price` and capped at the
+/// collateral held. Only the reference value of the whole tokens paid is
+/// recorded, so rounding dust stays owed. Returns the token amount paid.
+fn settle(env: &Env, stream: &mut PeggedStream, price: (i128, i128)) -> i128 {
+    let owed = accrued(stream, env.ledger().timestamp()) - stream.withdrawn;
+    if owed <= 0 {
+        return 0;
+    }
+    // Undercollateralised, everything left is paid out.
+    let amount = to_token(owed, price).min(stream.collateral);
+    stream.withdrawn = stream
+        .withdrawn
+        .checked_add(to_reference(amount, price))
+        .expect("overflow");
+    stream.collateral -= amount;
+    if amount > 0 {
+        token::Client::new(env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &stream.recipient,
+            &amount,
+        );
+    }
+    amount
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraPegged;
+
+#[contractimpl]
+impl FluxoraPegged {
+    /// Initialise with the oracle adapter, the reference currency and the
+    /// minimum collateral buffer. Can only be called once.
+    pub fn init(env: Env, oracle: Address, reference: Asset, min_buffer_bps: u32) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        env.storage().instance().set(
+            &DataKey::Config,
+            &Config {
+                oracle,
+                reference,
+                min_buffer_bps,
+            },
+        );
+        env.storage().instance().set(&DataKey::NextStreamId, &0u64);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Create a stream paying `rate_per_second` reference units between
+    /// `start_time` and `end_time`, settled in `token`. `collateral` token
+    /// units are pulled from `sender` and must cover the full obligation plus
+    /// the buffer at the current price. Returns the new stream id.
+    ///
+    /// # Panics
+    /// - If `sender` and `recipient` are the same address.
+    /// - If `rate_per_second` or `collateral` is not positive, or
+    ///   `start_time >= end_time`.
+    /// - If `collateral` does not cover the obligation plus the buffer.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        rate_per_second: i128,
+        start_time: u64,
+        end_time: u64,
+        collateral: i128,
+    ) -> u64 {
+        sender.require_auth();
+        assert!(sender != recipient, "sender and recipient must differ");
+        assert!(rate_per_second > 0, "rate_per_second must be positive");
+        assert!(collateral > 0, "collateral must be positive");
+        assert!(start_time < end_time, "start_time must be before end_time");
+
+        let config = get_config(&env);
+        let mut stream = PeggedStream {
+            stream_id: 0,
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            token,
+            rate_per_second,
+            start_time,
+            end_time,
+            withdrawn: 0,
+            collateral,
+            status: PeggedStatus::Active,
+        };
+        let price = token_price(&env, &config, &stream.token);
+        assert!(
+            is_covered(&config, collateral, total_obligation(&stream), price),
+            "insufficient collateral"
+        );
+
+        token::Client::new(&env, &stream.token).transfer(
+            &sender,
+            &env.current_contract_address(),
+            &collateral,
+        );
+
+        stream.stream_id = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextStreamId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextStreamId, &(stream.stream_id + 1));
+        save_stream(&env, &stream);
+
+        env.events().publish(
+            (symbol_short!("created"), stream.stream_id),
+            (sender, recipient, rate_per_second),
+        );
+        stream.stream_id
+    }
+
+    /// Pay the recipient everything accrued so far, converted to the stream
+    /// token at the current oracle price. If the collateral no longer covers
+    /// the amount owed, all of it is paid and the shortfall stays owed.
+    /// Only the recipient may call this. Returns the token amount paid.
+    ///
+    /// # Panics
+    /// - If the stream was cancelled.
+    pub fn withdraw(env: Env, stream_id: u64) -> i128 {
+        let mut stream = load_stream(&env, stream_id);
+        stream.recipient.require_auth();
+        assert!(
+            stream.status == PeggedStatus::Active,
+            "stream is not active"
+        );
+
+        let price = token_price(&env, &get_config(&env), &stream.token);
+        let amount = settle(&env, &mut stream, price);
+        save_stream(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("withdrew"), stream_id), amount);
+        amount
+    }
+
+    /// Add `amount` of the stream token to the collateral, e.g. after the
+    /// token's price fell. Anyone may top up a stream.
+    pub fn top_up(env: Env, stream_id: u64, from: Address, amount: i128) {
+        from.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        let mut stream = load_stream(&env, stream_id);
+        assert!(
+            stream.status == PeggedStatus::Active,
+            "stream is not active"
+        );
+
+        token::Client::new(&env, &stream.token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+        stream.collateral = stream.collateral.checked_add(amount).expect("overflow");
+        save_stream(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("top_up"), stream_id), (from, amount));
+    }
+
+    /// Return `amount` of collateral to the sender, e.g. after the token's
+    /// price rose. Only the sender may call this.
+    ///
+    /// # Panics
+    /// - If the remaining collateral would no longer cover the outstanding
+    ///   obligation plus the buffer.
+    pub fn release_excess(env: Env, stream_id: u64, amount: i128) {
+        let mut stream = load_stream(&env, stream_id);
+        stream.sender.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        assert!(
+            stream.status == PeggedStatus::Active,
+            "stream is not active"
+        );
+
+        let config = get_config(&env);
+        let price = token_price(&env, &config, &stream.token);
+        let outstanding = total_obligation(&stream) - stream.withdrawn;
+        assert!(
+            amount <= stream.collateral
+                && is_covered(&config, stream.collateral - amount, outstanding, price),
+            "insufficient collateral"
+        );
+
+        stream.collateral -= amount;
+        save_stream(&env, &stream);
+        token::Client::new(&env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &stream.sender,
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("released"), stream_id), amount);
+    }
+
+    /// Cancel the stream: the recipient is paid what has accrued (at the
+    /// current price) and the remaining collateral returns to the sender.
+    /// Only the sender may call this.
+    pub fn cancel_stream(env: Env, stream_id: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        stream.sender.require_auth();
+        assert!(
+            stream.status == PeggedStatus::Active,
+            "stream is not active"
+        );
+
+        let price = token_price(&env, &get_config(&env), &stream.token);
+        settle(&env, &mut stream, price);
+        let refund = stream.collateral;
+        if refund > 0 {
+            token::Client::new(&env, &stream.token).transfer(
+                &env.current_contract_address(),
+                &stream.sender,
+                &refund,
+            );
+        }
+        stream.collateral = 0;
+        stream.status = PeggedStatus::Cancelled;
+        save_stream(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("cancelled"), stream_id), refund);
+    }
+
+    /// Return the token amount the recipient could withdraw right now.
+    pub fn withdrawable(env: Env, stream_id: u64) -> i128 {
+        let stream = load_stream(&env, stream_id);
+        if stream.status != PeggedStatus::Active {
+            return 0;
+        }
+        let owed = accrued(&stream, env.ledger().timestamp()) - stream.withdrawn;
+        let price = token_price(&env, &get_config(&env), &stream.token);
+        to_token(owed, price).min(stream.collateral)
+    }
+
+    /// Return whether the collateral still covers the outstanding obligation
+    /// plus the buffer at the current price.
+    pub fn is_covered(env: Env, stream_id: u64) -> bool {
+        let stream = load_stream(&env, stream_id);
+        let config = get_config(&env);
+        let price = token_price(&env, &config, &stream.token);
+        is_covered(
+            &config,
+            stream.collateral,
+            total_obligation(&stream) - stream.withdrawn,
+            price,
+        )
+    }
+
+    /// Return the stream identified by `stream_id`.
+    pub fn get_stream(env: Env, stream_id: u64) -> PeggedStream {
+        load_stream(&env, stream_id)
+    }
+
+    /// Fetches the contract configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,219 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, token::Client as TokenClient, Address, Env,
+    Symbol,
+};
+
+use fluxora_testutils::{mint, register_token, set_time};
+
+use crate::{Asset, FluxoraPegged, FluxoraPeggedClient, PeggedStatus};
+
+/// Price scale of the mock oracle, matching the Fluxora oracle adapter.
+const ONE: i128 = 100_000_000_000_000;
+
+// ---------------------------------------------------------------------------
+// Mock oracle adapter: a single settable price for every token
+// ---------------------------------------------------------------------------
+
+#[contract]
+struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, price: i128) {
+        env.storage().instance().set(&0u32, &price);
+    }
+
+    pub fn price(env: Env, _token: Asset, _base: Asset) -> i128 {
+        env.storage().instance().get(&0u32).unwrap()
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        14
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    pegged_id: Address,
+    oracle_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl TestContext {
+    /// Token priced at 2 USD, with a 20% collateral buffer.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let oracle_id = env.register_contract(None, MockOracle);
+        MockOracleClient::new(&env, &oracle_id).set_price(&(2 * ONE));
+        let pegged_id = env.register_contract(None, FluxoraPegged);
+        FluxoraPeggedClient::new(&env, &pegged_id).init(
+            &oracle_id,
+            &Asset::Other(Symbol::new(&env, "USD")),
+            &2_000,
+        );
+
+        let token_id = register_token(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &sender, 10_000);
+
+        TestContext {
+            env,
+            pegged_id,
+            oracle_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn pegged(&self) -> FluxoraPeggedClient<'_> {
+        FluxoraPeggedClient::new(&self.env, &self.pegged_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn set_price(&self, price: i128) {
+        MockOracleClient::new(&self.env, &self.oracle_id).set_price(&price);
+    }
+
+    /// 2 USD/s for 1000 s (2000 USD), backed by `collateral` tokens.
+    fn create(&self, collateral: i128) -> u64 {
+        self.pegged().create_stream(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &2,
+            &0,
+            &1_000,
+            &collateral,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — creation and conversion
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "insufficient collateral")]
+fn test_collateral_below_buffer_rejected() {
+    let ctx = TestContext::setup();
+    // 2000 USD + 20% = 2400 USD = 1200 tokens at 2 USD.
+    ctx.create(1_199);
+}
+
+#[test]
+#[should_panic(expected = "sender and recipient must differ")]
+fn test_stream_to_self_rejected() {
+    let ctx = TestContext::setup();
+    ctx.pegged().create_stream(
+        &ctx.sender,
+        &ctx.sender,
+        &ctx.token_id,
+        &2,
+        &0,
+        &1_000,
+        &1_200,
+    );
+}
+
+#[test]
+#[should_panic(expected = "collateral must be positive")]
+fn test_zero_collateral_rejected() {
+    let ctx = TestContext::setup();
+    ctx.create(0);
+}
+
+#[test]
+#[should_panic(expected = "oracle price must be positive")]
+fn test_withdraw_at_zero_price_panics() {
+    let ctx = TestContext::setup();
+    let id = ctx.create(1_200);
+
+    ctx.set_price(0);
+    set_time(&ctx.env, 100);
+    ctx.pegged().withdraw(&id);
+}
+
+#[test]
+fn test_withdraw_converts_at_current_price() {
+    let ctx = TestContext::setup();
+    let id = ctx.create(1_200);
+
+    set_time(&ctx.env, 100);
+    assert_eq!(ctx.pegged().withdraw(&id), 100); // 200 USD at 2 USD
+
+    ctx.set_price(ONE); // token halves in value
+    set_time(&ctx.env, 200);
+    assert_eq!(ctx.pegged().withdraw(&id), 200); // 200 USD at 1 USD
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(ctx.pegged().get_stream(&id).withdrawn, 400);
+}
+
+#[test]
+fn test_rounding_dust_stays_owed() {
+    let ctx = TestContext::setup();
+    let id = ctx.create(1_200);
+    ctx.set_price(3 * ONE);
+
+    set_time(&ctx.env, 2); // 4 USD owed buys one 3 USD token
+    assert_eq!(ctx.pegged().withdraw(&id), 1);
+    assert_eq!(ctx.pegged().get_stream(&id).withdrawn, 3);
+
+    set_time(&ctx.env, 3); // 1 USD carried over plus 2 accrued
+    assert_eq!(ctx.pegged().withdraw(&id), 1);
+    assert_eq!(ctx.pegged().get_stream(&id).withdrawn, 6);
+}
+
+#[test]
+fn test_shortfall_stays_owed_until_topped_up() {
+    let ctx = TestContext::setup();
+    let id = ctx.create(1_200);
+
+    ctx.set_price(ONE / 4);
+    set_time(&ctx.env, 500); // 1000 USD owed = 4000 tokens
+    assert!(!ctx.pegged().is_covered(&id));
+    assert_eq!(ctx.pegged().withdraw(&id), 1_200);
+    assert_eq!(ctx.pegged().get_stream(&id).withdrawn, 300);
+
+    ctx.pegged().top_up(&id, &ctx.sender, &4_000);
+    assert_eq!(ctx.pegged().withdraw(&id), 2_800); // remaining 700 USD
+}
+
+#[test]
+fn test_release_excess_after_price_rise() {
+    let ctx = TestContext::setup();
+    let id = ctx.create(1_200);
+
+    ctx.set_price(4 * ONE); // 2400 USD now needs only 600 tokens
+    ctx.pegged().release_excess(&id, &600);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_400);
+    assert!(ctx.pegged().try_release_excess(&id, &1).is_err());
+}
+
+#[test]
+fn test_cancel_pays_accrued_and_refunds_rest() {
+    let ctx = TestContext::setup();
+    let id = ctx.create(1_200);
+
+    set_time(&ctx.env, 250); // 500 USD = 250 tokens
+    ctx.pegged().cancel_stream(&id);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 250);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_750);
+    assert_eq!(ctx.pegged().get_stream(&id).status, PeggedStatus::Cancelled);
+}
@@ -0,0 +1,140 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+// ---------------------------------------------------------------------------
+// Well-known keys
+// ---------------------------------------------------------------------------
+
+/// Receiver of protocol fees.
+pub const FEE_COLLECTOR: Symbol = symbol_short!("fee_coll");
+/// Price-feed adapter.
+pub const ORACLE: Symbol = symbol_short!("oracle");
+/// Transfer-restriction policy hook.
+pub const POLICY: Symbol = symbol_short!("policy");
+/// Protocol treasury.
+pub const TREASURY: Symbol = symbol_short!("treasury");
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Keys,          // Instance storage: enumeration of registered keys.
+    Entry(Symbol), // Persistent storage: address registered under a key.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_keys(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Keys)
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_entry(env: &Env, key: &Symbol) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Entry(key.clone()))
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraRegistry;
+
+#[contractimpl]
+impl FluxoraRegistry {
+    /// Initialise the registry with its admin. Can only be called once.
+    pub fn init(env: Env, admin: Address) {
+        fluxora_access::init_admin(&env, &admin);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Register (or replace) the address stored under `key`.
+    /// Only the admin may call this.
+    pub fn set(env: Env, key: Symbol, address: Address) {
+        fluxora_access::require_admin(&env);
+
+        let entry = DataKey::Entry(key.clone());
+        env.storage().persistent().set(&entry, &address);
+        env.storage().persistent().extend_ttl(&entry, 17280, 120960);
+
+        let mut keys = get_keys(&env);
+        if !keys.contains(&key) {
+            keys.push_back(key.clone());
+            env.storage().instance().set(&DataKey::Keys, &keys);
+        }
+
+        env.events().publish((symbol_short!("set"), key), address);
+    }
+
+    /// Remove the entry stored under `key`. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If nothing is registered under `key`.
+    pub fn remove(env: Env, key: Symbol) {
+        fluxora_access::require_admin(&env);
+
+        let mut keys = get_keys(&env);
+        let index = keys.first_index_of(&key).expect("key not registered");
+        keys.remove(index);
+        env.storage().instance().set(&DataKey::Keys, &keys);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Entry(key.clone()));
+
+        env.events().publish((symbol_short!("removed"), key), ());
+    }
+
+    /// Return the address stored under `key`, if any.
+    pub fn get(env: Env, key: Symbol) -> Option<Address> {
+        get_entry(&env, &key)
+    }
+
+    /// Return the address stored under `key`.
+    ///
+    /// # Panics
+    /// - If nothing is registered under `key`.
+    pub fn resolve(env: Env, key: Symbol) -> Address {
+        get_entry(&env, &key).expect("key not registered")
+    }
+
+    /// List all registered keys, in registration order.
+    pub fn keys(env: Env) -> Vec<Symbol> {
+        get_keys(&env)
+    }
+
+    /// Hand the registry to a new admin. Only the current admin may call this.
+    pub fn set_admin(env: Env, new_admin: Address) {
+        fluxora_access::set_admin(&env, &new_admin);
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the registry admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
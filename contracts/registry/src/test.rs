@@ -0,0 +1,127 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal,
+};
+
+use crate::{FluxoraRegistry, FluxoraRegistryClient, FEE_COLLECTOR, ORACLE, TREASURY};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    registry_id: Address,
+    admin: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let registry_id = env.register_contract(None, FluxoraRegistry);
+        FluxoraRegistryClient::new(&env, &registry_id).init(&admin);
+
+        TestContext {
+            env,
+            registry_id,
+            admin,
+        }
+    }
+
+    fn registry(&self) -> FluxoraRegistryClient<'_> {
+        FluxoraRegistryClient::new(&self.env, &self.registry_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — entries
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_resolve_and_enumerate() {
+    let ctx = TestContext::setup();
+    let collector = Address::generate(&ctx.env);
+    let treasury = Address::generate(&ctx.env);
+
+    ctx.registry().set(&FEE_COLLECTOR, &collector);
+    ctx.registry().set(&TREASURY, &treasury);
+    assert_eq!(ctx.registry().resolve(&FEE_COLLECTOR), collector);
+    assert_eq!(ctx.registry().get(&ORACLE), None);
+
+    // Replacing an entry does not duplicate its key.
+    let new_collector = Address::generate(&ctx.env);
+    ctx.registry().set(&FEE_COLLECTOR, &new_collector);
+    assert_eq!(ctx.registry().get(&FEE_COLLECTOR), Some(new_collector));
+    assert_eq!(ctx.registry().keys().len(), 2);
+}
+
+#[test]
+fn test_remove_entry() {
+    let ctx = TestContext::setup();
+    ctx.registry().set(&ORACLE, &Address::generate(&ctx.env));
+    ctx.registry().remove(&ORACLE);
+    assert_eq!(ctx.registry().get(&ORACLE), None);
+    assert!(ctx.registry().keys().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "key not registered")]
+fn test_resolve_missing_key_panics() {
+    let ctx = TestContext::setup();
+    ctx.registry().resolve(&TREASURY);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_only_admin_can_set() {
+    let ctx = TestContext::setup();
+    let outsider = Address::generate(&ctx.env);
+    let target = Address::generate(&ctx.env);
+    ctx.env.mock_auths(&[MockAuth {
+        address: &outsider,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.registry_id,
+            fn_name: "set",
+            args: (TREASURY, target.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(ctx.registry().try_set(&TREASURY, &target).is_err());
+}
+
+#[test]
+fn test_set_admin() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.registry().set_admin(&new_admin);
+    assert_eq!(ctx.registry().get_admin(), new_admin);
+    assert_ne!(ctx.registry().get_admin(), ctx.admin);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.registry().propose_admin(&new_admin);
+    assert_eq!(ctx.registry().get_admin(), ctx.admin);
+
+    ctx.registry().accept_admin();
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.registry().get_admin(), new_admin);
+}
+
+#[test]
+#[should_panic(expected = "no pending admin")]
+fn test_accept_admin_without_proposal_panics() {
+    let ctx = TestContext::setup();
+    ctx.registry().accept_admin();
+}
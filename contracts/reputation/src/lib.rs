@@ -0,0 +1,156 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Score of a sender whose every finished stream ran cleanly.
+pub const MAX_SCORE: u32 = 10_000;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Outcome counts for one sender, as reported by the stream contract.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SenderStats {
+    /// Streams that ran to their end.
+    pub completed: u32,
+    /// Streams the sender cancelled before their end.
+    pub cancelled_early: u32,
+    /// Disputed streams settled by an arbiter.
+    pub disputes: u32,
+    /// Disputes in which the arbiter awarded the recipient beyond what had accrued.
+    pub disputes_lost: u32,
+    /// Total amount streamed to recipients across all finished streams.
+    pub volume: i128,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    StreamContract, // Instance storage: the only contract allowed to report outcomes.
+    Stats(Address), // Persistent storage: outcome counts per sender.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_stream_contract(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::StreamContract)
+        .expect("contract not initialised: missing stream contract")
+}
+
+fn get_stats(env: &Env, sender: &Address) -> SenderStats {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Stats(sender.clone()))
+        .unwrap_or_default()
+}
+
+/// Apply `update` to `sender`'s stats on behalf of the stream contract.
+fn record(env: &Env, sender: &Address, volume: i128, update: impl FnOnce(&mut SenderStats)) {
+    get_stream_contract(env).require_auth();
+    assert!(volume >= 0, "volume must not be negative");
+
+    let mut stats = get_stats(env, sender);
+    update(&mut stats);
+    stats.volume = stats
+        .volume
+        .checked_add(volume)
+        .expect("overflow adding volume");
+
+    let key = DataKey::Stats(sender.clone());
+    env.storage().persistent().set(&key, &stats);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraReputation;
+
+#[contractimpl]
+impl FluxoraReputation {
+    /// Initialise the registry to accept reports only from `stream_contract`,
+    /// which must in turn list this contract via `set_reputation_registry`.
+    /// Can only be called once.
+    pub fn init(env: Env, stream_contract: Address) {
+        if env.storage().instance().has(&DataKey::StreamContract) {
+            panic!("already initialised");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamContract, &stream_contract);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Record a stream from `sender` that ran to its end, streaming `volume`.
+    /// Only the stream contract may call this.
+    pub fn record_completed(env: Env, sender: Address, volume: i128) {
+        record(&env, &sender, volume, |stats| stats.completed += 1);
+
+        env.events()
+            .publish((symbol_short!("completed"), sender), volume);
+    }
+
+    /// Record a stream `sender` cancelled before its end, after streaming
+    /// `volume`. Only the stream contract may call this.
+    pub fn record_cancelled(env: Env, sender: Address, volume: i128) {
+        record(&env, &sender, volume, |stats| stats.cancelled_early += 1);
+
+        env.events()
+            .publish((symbol_short!("cancelled"), sender), volume);
+    }
+
+    /// Record an arbiter's settlement of a disputed stream from `sender`.
+    /// Only the stream contract may call this.
+    pub fn record_dispute(env: Env, sender: Address, volume: i128, sender_lost: bool) {
+        record(&env, &sender, volume, |stats| {
+            stats.disputes += 1;
+            if sender_lost {
+                stats.disputes_lost += 1;
+            }
+        });
+
+        env.events()
+            .publish((symbol_short!("disputed"), sender), sender_lost);
+    }
+
+    /// Return `sender`'s outcome counts; all zero for an unknown sender.
+    pub fn get_stats(env: Env, sender: Address) -> SenderStats {
+        get_stats(&env, &sender)
+    }
+
+    /// Return `sender`'s score out of `MAX_SCORE`: the share of finished
+    /// streams that ended cleanly (completed, or a dispute the sender won),
+    /// with each lost dispute weighed twice. `None` while the sender has no
+    /// finished streams.
+    pub fn score(env: Env, sender: Address) -> Option<u32> {
+        let stats = get_stats(&env, &sender);
+        let lost = stats.disputes_lost as u64;
+        let good = stats.completed as u64 + (stats.disputes as u64 - lost);
+        let total = good + stats.cancelled_early as u64 + 2 * lost;
+        if total == 0 {
+            return None;
+        }
+        Some((good * MAX_SCORE as u64 / total) as u32)
+    }
+
+    /// Return the stream contract whose reports this registry accepts.
+    pub fn get_stream_contract(env: Env) -> Address {
+        get_stream_contract(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,155 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use fluxora_stream::{FluxoraStreamClient, StreamOptions};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraReputation, FluxoraReputationClient, SenderStats};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    reputation_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream reporting to a fresh reputation registry.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+        let stream = FluxoraStreamClient::new(&env, &stream_id);
+
+        let reputation_id = env.register_contract(None, FluxoraReputation);
+        FluxoraReputationClient::new(&env, &reputation_id).init(&stream_id);
+        stream.set_reputation_registry(&Some(reputation_id.clone()));
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &sender, 10_000);
+
+        TestContext {
+            env,
+            reputation_id,
+            stream_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn reputation(&self) -> FluxoraReputationClient<'_> {
+        FluxoraReputationClient::new(&self.env, &self.reputation_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    /// A 1000-unit stream over the next 1000 seconds, optionally under `arbiter`.
+    fn open(&self, arbiter: Option<Address>) -> u64 {
+        let now = self.env.ledger().timestamp();
+        self.stream().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &1000,
+            &1,
+            &now,
+            &now,
+            &(now + 1000),
+            &StreamOptions {
+                arbiter,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — outcomes reported by the stream contract
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_completed_and_cancelled_streams_are_recorded() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.reputation().score(&ctx.sender), None);
+
+    let finished = ctx.open(None);
+    let cancelled = ctx.open(None);
+    set_time(&ctx.env, 250);
+    ctx.stream().cancel_stream(&cancelled);
+    set_time(&ctx.env, 1000);
+    ctx.stream().withdraw(&finished);
+
+    assert_eq!(
+        ctx.reputation().get_stats(&ctx.sender),
+        SenderStats {
+            completed: 1,
+            cancelled_early: 1,
+            disputes: 0,
+            disputes_lost: 0,
+            volume: 1250,
+        }
+    );
+    assert_eq!(ctx.reputation().score(&ctx.sender), Some(5_000));
+}
+
+#[test]
+fn test_sender_losing_dispute_is_weighed_twice() {
+    let ctx = TestContext::setup();
+    let arbiter = Address::generate(&ctx.env);
+    let id = ctx.open(Some(arbiter));
+
+    set_time(&ctx.env, 100);
+    ctx.stream().open_dispute(&id, &ctx.recipient);
+    ctx.stream().resolve_dispute(&id, &600);
+
+    let stats = ctx.reputation().get_stats(&ctx.sender);
+    assert_eq!((stats.disputes, stats.disputes_lost), (1, 1));
+    assert_eq!(stats.volume, 600);
+    assert_eq!(ctx.reputation().score(&ctx.sender), Some(0));
+
+    set_time(&ctx.env, 200);
+    let next = ctx.open(None);
+    set_time(&ctx.env, 1200);
+    ctx.stream().withdraw(&next);
+    assert_eq!(ctx.reputation().score(&ctx.sender), Some(3_333));
+}
+
+#[test]
+fn test_dispute_settled_within_accrual_is_not_lost() {
+    let ctx = TestContext::setup();
+    let id = ctx.open(Some(Address::generate(&ctx.env)));
+
+    set_time(&ctx.env, 500);
+    ctx.stream().open_dispute(&id, &ctx.sender);
+    ctx.stream().resolve_dispute(&id, &400);
+
+    let stats = ctx.reputation().get_stats(&ctx.sender);
+    assert_eq!((stats.disputes, stats.disputes_lost), (1, 0));
+    assert_eq!(ctx.reputation().score(&ctx.sender), Some(10_000));
+}
+
+#[test]
+fn test_reports_from_other_callers_are_rejected() {
+    let ctx = TestContext::setup();
+    ctx.env.set_auths(&[]);
+    assert!(ctx
+        .reputation()
+        .try_record_completed(&ctx.sender, &1_000_000)
+        .is_err());
+    assert_eq!(ctx.reputation().score(&ctx.sender), None);
+}
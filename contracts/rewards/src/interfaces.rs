@@ -0,0 +1,11 @@
+//! Interfaces of external contracts the rewards contract calls into.
+
+pub use fluxora_types::{Stream, StreamStatus};
+use soroban_sdk::{contractclient, Env};
+
+/// The subset of the FluxoraStream contract read to award points.
+#[contractclient(name = "StreamClient")]
+pub trait StreamContract {
+    fn get_stream_state(env: Env, stream_id: u64) -> Stream;
+    fn calculate_accrued(env: Env, stream_id: u64) -> i128;
+}
@@ -0,0 +1,202 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient, StreamContract, StreamStatus};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Token units a per-token rate is quoted against: one whole unit of a
+/// 7-decimal Stellar asset.
+pub const RATE_SCALE: i128 = 10_000_000;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for the rewards module.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// FluxoraStream deployment whose streams earn points.
+    pub stream_contract: Address,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,          // Instance storage for the stream contract.
+    Rate(Address),   // Persistent storage: points per RATE_SCALE units streamed, per token.
+    Index(u64),      // Persistent storage: amount streamed already rewarded, per stream.
+    Points(Address), // Persistent storage: claimed point balance per account.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn get_rate(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Rate(token.clone()))
+        .unwrap_or(0)
+}
+
+fn get_index(env: &Env, stream_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Index(stream_id))
+        .unwrap_or(0)
+}
+
+fn get_points(env: &Env, account: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Points(account.clone()))
+        .unwrap_or(0)
+}
+
+fn set_persistent(env: &Env, key: &DataKey, value: i128) {
+    env.storage().persistent().set(key, &value);
+    env.storage().persistent().extend_ttl(key, 17280, 120960);
+}
+
+/// Amount a stream has paid out or may still pay out. Closed streams only
+/// count what was withdrawn, since nothing more can reach the recipient.
+fn streamed(env: &Env, stream: &Stream) -> i128 {
+    match stream.status {
+        StreamStatus::Cancelled | StreamStatus::ClawedBack => stream.withdrawn_amount,
+        _ => fluxora_types::accrued(env, &get_config(env).stream_contract, stream.stream_id),
+    }
+}
+
+/// Points earned on `stream` since its index, and the amount to move the
+/// index to. Points are floored on the cumulative amount, so no dust is lost
+/// between claims.
+fn pending(env: &Env, stream: &Stream) -> (i128, i128) {
+    let index = get_index(env, stream.stream_id);
+    let total = streamed(env, stream).max(index);
+    let rate = get_rate(env, &stream.token);
+    let earned =
+        fluxora_math::mul_div_floor(total, rate, RATE_SCALE).expect("overflow calculating points");
+    let credited =
+        fluxora_math::mul_div_floor(index, rate, RATE_SCALE).expect("overflow calculating points");
+    let points = earned - credited;
+    (points, total)
+}
+
+fn load_stream(env: &Env, stream_id: u64) -> Stream {
+    fluxora_types::load_stream(env, &get_config(env).stream_contract, stream_id)
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraRewards;
+
+#[contractimpl]
+impl FluxoraRewards {
+    /// Initialise points for streams on `stream_contract`, with `admin`
+    /// setting the rates. Can only be called once.
+    pub fn init(env: Env, admin: Address, stream_contract: Address) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Config, &Config { stream_contract });
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Award `rate` points per `RATE_SCALE` units of `token` streamed; zero
+    /// stops emission for the token. Amounts streamed but not yet claimed
+    /// are valued at the rate in force when they are claimed.
+    /// Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If `rate` is negative.
+    pub fn set_rate(env: Env, token: Address, rate: i128) {
+        fluxora_access::require_admin(&env);
+        assert!(rate >= 0, "rate must not be negative");
+        set_persistent(&env, &DataKey::Rate(token.clone()), rate);
+
+        env.events().publish((symbol_short!("rate"), token), rate);
+    }
+
+    /// Credit the stream's recipient with the points it earned since the
+    /// last claim and move its index forward. Only the current recipient
+    /// may call this. Returns the points credited.
+    pub fn claim(env: Env, stream_id: u64) -> i128 {
+        let stream = load_stream(&env, stream_id);
+        stream.recipient.require_auth();
+
+        let (points, total) = pending(&env, &stream);
+        set_persistent(&env, &DataKey::Index(stream_id), total);
+        if points > 0 {
+            let key = DataKey::Points(stream.recipient.clone());
+            set_persistent(&env, &key, get_points(&env, &stream.recipient) + points);
+        }
+
+        env.events().publish(
+            (symbol_short!("points"), stream_id),
+            (stream.recipient, points),
+        );
+        points
+    }
+
+    /// Return the points `claim` would credit on `stream_id` right now.
+    pub fn pending(env: Env, stream_id: u64) -> i128 {
+        pending(&env, &load_stream(&env, stream_id)).0
+    }
+
+    /// Return `account`'s claimed point balance.
+    pub fn points(env: Env, account: Address) -> i128 {
+        get_points(&env, &account)
+    }
+
+    /// Return the point rate for `token`.
+    pub fn get_rate(env: Env, token: Address) -> i128 {
+        get_rate(&env, &token)
+    }
+
+    /// Return the amount streamed on `stream_id` already rewarded.
+    pub fn get_index(env: Env, stream_id: u64) -> i128 {
+        get_index(&env, stream_id)
+    }
+
+    /// Propose `new_admin` as the next admin; the transfer completes when
+    /// they call `accept_admin`. Only the current admin may call this.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Take over as admin after being proposed with `propose_admin`.
+    ///
+    /// # Panics
+    /// - If no transfer is pending.
+    pub fn accept_admin(env: Env) {
+        fluxora_access::accept_admin(&env);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        fluxora_access::admin(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
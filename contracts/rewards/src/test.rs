@@ -0,0 +1,146 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use fluxora_stream::{FluxoraStreamClient, CLOSE_RETENTION_SECS};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraRewards, FluxoraRewardsClient, RATE_SCALE};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    rewards_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    recipient: Address,
+    stream: u64,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream with a 1000-unit stream over 1000 seconds, and
+    /// a rewards module paying 2 points per unit streamed.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+        let stream_client = FluxoraStreamClient::new(&env, &stream_id);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &sender, 1_000);
+        let stream = stream_client.create_stream(&sender, &recipient, &1_000, &1, &0, &0, &1_000);
+
+        let rewards_id = env.register_contract(None, FluxoraRewards);
+        let rewards = FluxoraRewardsClient::new(&env, &rewards_id);
+        rewards.init(&Address::generate(&env), &stream_id);
+        rewards.set_rate(&token_id, &(2 * RATE_SCALE));
+
+        TestContext {
+            env,
+            rewards_id,
+            stream_id,
+            token_id,
+            recipient,
+            stream,
+        }
+    }
+
+    fn rewards(&self) -> FluxoraRewardsClient<'_> {
+        FluxoraRewardsClient::new(&self.env, &self.rewards_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — accrual and claims
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_claim_credits_points_since_last_index() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 300);
+    assert_eq!(ctx.rewards().pending(&ctx.stream), 600);
+    assert_eq!(ctx.rewards().claim(&ctx.stream), 600);
+
+    set_time(&ctx.env, 500);
+    assert_eq!(ctx.rewards().claim(&ctx.stream), 400);
+    assert_eq!(ctx.rewards().points(&ctx.recipient), 1_000);
+    assert_eq!(ctx.rewards().get_index(&ctx.stream), 500);
+}
+
+#[test]
+fn test_fractional_points_are_not_lost_between_claims() {
+    let ctx = TestContext::setup();
+    ctx.rewards().set_rate(&ctx.token_id, &(RATE_SCALE / 2));
+    for t in [1, 2, 3, 4, 5, 6] {
+        set_time(&ctx.env, t);
+        ctx.rewards().claim(&ctx.stream);
+    }
+    assert_eq!(ctx.rewards().points(&ctx.recipient), 3);
+}
+
+#[test]
+fn test_cancelled_stream_stops_earning_after_final_withdrawal() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 250);
+    ctx.stream().cancel_stream(&ctx.stream);
+    ctx.stream().withdraw(&ctx.stream);
+
+    set_time(&ctx.env, 900);
+    assert_eq!(ctx.rewards().claim(&ctx.stream), 500);
+    assert_eq!(ctx.rewards().pending(&ctx.stream), 0);
+}
+
+#[test]
+fn test_closed_stream_can_still_be_claimed() {
+    let ctx = TestContext::setup();
+    set_time(&ctx.env, 400);
+    assert_eq!(ctx.rewards().claim(&ctx.stream), 800);
+
+    set_time(&ctx.env, 1_000);
+    ctx.stream().withdraw(&ctx.stream);
+    set_time(&ctx.env, 1_000 + CLOSE_RETENTION_SECS);
+    ctx.stream().close_stream(&ctx.stream);
+
+    assert_eq!(ctx.rewards().pending(&ctx.stream), 1_200);
+    assert_eq!(ctx.rewards().claim(&ctx.stream), 1_200);
+    assert_eq!(ctx.rewards().points(&ctx.recipient), 2_000);
+}
+
+#[test]
+fn test_unsupported_token_earns_nothing() {
+    let ctx = TestContext::setup();
+    ctx.rewards().set_rate(&ctx.token_id, &0);
+    set_time(&ctx.env, 1_000);
+    assert_eq!(ctx.rewards().claim(&ctx.stream), 0);
+    assert_eq!(ctx.rewards().points(&ctx.recipient), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — administration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_handover_is_two_step() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.rewards().propose_admin(&new_admin);
+    assert_ne!(ctx.rewards().get_admin(), new_admin);
+
+    ctx.rewards().accept_admin();
+    assert_eq!(ctx.rewards().get_admin(), new_admin);
+    ctx.rewards().set_rate(&ctx.token_id, &RATE_SCALE);
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+    assert_eq!(ctx.rewards().get_rate(&ctx.token_id), RATE_SCALE);
+}
@@ -0,0 +1,21 @@
+//! Interfaces of external contracts the router calls into.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract used by the typed router helpers.
+#[contractclient(name = "StreamClient")]
+pub trait Stream {
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64;
+    fn cancel_stream(env: Env, stream_id: u64);
+    fn withdraw(env: Env, stream_id: u64) -> i128;
+}
@@ -0,0 +1,128 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient};
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Val, Vec};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// One call in a batch: `contract.function(args)`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Call {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// A stream on a specific FluxoraStream instance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamRef {
+    pub contract: Address,
+    pub stream_id: u64,
+}
+
+/// Terms of a stream created in the deployment's default token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamTerms {
+    pub recipient: Address,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+/// Stateless batching periphery. The router holds no funds and no
+/// privileges: the signer authorises the router invocation, and that single
+/// authorisation also covers the `require_auth` checks performed by every
+/// target call beneath it.
+///
+/// The typed helpers check each stream instance's interface version before
+/// their first call to it; `multicall` targets arbitrary contracts and does
+/// not.
+#[contract]
+pub struct FluxoraRouter;
+
+#[contractimpl]
+impl FluxoraRouter {
+    /// Execute `calls` in order, atomically, on behalf of `caller`.
+    /// Returns each call's result.
+    ///
+    /// # Panics
+    /// - If any call fails; the whole batch is reverted.
+    pub fn multicall(env: Env, caller: Address, calls: Vec<Call>) -> Vec<Val> {
+        caller.require_auth();
+        let mut results = Vec::new(&env);
+        for call in calls.iter() {
+            let result: Val = env.invoke_contract(&call.contract, &call.function, call.args);
+            results.push_back(result);
+        }
+        results
+    }
+
+    /// Withdraw from several of `recipient`'s streams, possibly on different
+    /// instances, in one transaction. Returns the total withdrawn.
+    ///
+    /// # Panics
+    /// - If any withdrawal fails (e.g. nothing to withdraw).
+    /// - If the total overflows.
+    /// - With `IncompatibleInterface` if any instance is not a compatible
+    ///   FluxoraStream deployment.
+    pub fn withdraw_many(env: Env, recipient: Address, streams: Vec<StreamRef>) -> i128 {
+        recipient.require_auth();
+        let mut checked = Vec::new(&env);
+        let mut total: i128 = 0;
+        for s in streams.iter() {
+            if !checked.contains(&s.contract) {
+                fluxora_types::require_compatible(&env, &s.contract);
+                checked.push_back(s.contract.clone());
+            }
+            let withdrawn = StreamClient::new(&env, &s.contract).withdraw(&s.stream_id);
+            total = total.checked_add(withdrawn).expect("overflow");
+        }
+        total
+    }
+
+    /// Cancel `stream_id` and immediately create a replacement stream from
+    /// `sender` with new `terms` on the same instance. The cancellation
+    /// refund returns to `sender` first, so it can fund the new deposit.
+    /// Returns the new stream id.
+    ///
+    /// # Panics
+    /// - With `IncompatibleInterface` if `stream_contract` is not a
+    ///   compatible FluxoraStream deployment.
+    pub fn cancel_and_recreate(
+        env: Env,
+        stream_contract: Address,
+        stream_id: u64,
+        sender: Address,
+        terms: StreamTerms,
+    ) -> u64 {
+        sender.require_auth();
+        fluxora_types::require_compatible(&env, &stream_contract);
+        let stream = StreamClient::new(&env, &stream_contract);
+        stream.cancel_stream(&stream_id);
+        stream.create_stream(
+            &sender,
+            &terms.recipient,
+            &terms.deposit_amount,
+            &terms.rate_per_second,
+            &terms.start_time,
+            &terms.cliff_time,
+            &terms.end_time,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test;
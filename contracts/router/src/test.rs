@@ -0,0 +1,249 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    token::Client as TokenClient,
+    vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+use fluxora_stream::{FluxoraStreamClient, StreamStatus};
+use fluxora_testutils::{
+    mint, register_outdated_stream, register_stream, register_token, set_time,
+};
+
+use crate::{Call, FluxoraRouter, FluxoraRouterClient, StreamRef, StreamTerms};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    router_id: Address,
+    streams: [Address; 2],
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl TestContext {
+    /// Deploy a router and two FluxoraStream instances sharing one token.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let streams = [
+            register_stream(&env, &token_id),
+            register_stream(&env, &token_id),
+        ];
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token_id, &sender, 10_000);
+
+        TestContext {
+            router_id: env.register_contract(None, FluxoraRouter),
+            env,
+            streams,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn router(&self) -> FluxoraRouterClient<'_> {
+        FluxoraRouterClient::new(&self.env, &self.router_id)
+    }
+
+    fn stream(&self, i: usize) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.streams[i])
+    }
+
+    /// A 1000-unit stream over 1000 seconds on instance `i`.
+    fn create(&self, i: usize) -> u64 {
+        self.stream(i)
+            .create_stream(&self.sender, &self.recipient, &1000, &1, &0, &0, &1000)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — batching
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_many_across_instances() {
+    let ctx = TestContext::setup();
+    let first = ctx.create(0);
+    let second = ctx.create(1);
+    set_time(&ctx.env, 300);
+
+    let refs = vec![
+        &ctx.env,
+        StreamRef {
+            contract: ctx.streams[0].clone(),
+            stream_id: first,
+        },
+        StreamRef {
+            contract: ctx.streams[1].clone(),
+            stream_id: second,
+        },
+    ];
+
+    // The recipient signs once, covering both nested withdrawals.
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.router_id,
+            fn_name: "withdraw_many",
+            args: (ctx.recipient.clone(), refs.clone()).into_val(&ctx.env),
+            sub_invokes: &[
+                MockAuthInvoke {
+                    contract: &ctx.streams[0],
+                    fn_name: "withdraw",
+                    args: (first,).into_val(&ctx.env),
+                    sub_invokes: &[],
+                },
+                MockAuthInvoke {
+                    contract: &ctx.streams[1],
+                    fn_name: "withdraw",
+                    args: (second,).into_val(&ctx.env),
+                    sub_invokes: &[],
+                },
+            ],
+        },
+    }]);
+    assert_eq!(ctx.router().withdraw_many(&ctx.recipient, &refs), 600);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &ctx.token_id).balance(&ctx.recipient),
+        600
+    );
+}
+
+#[test]
+fn test_cancel_and_recreate_with_new_terms() {
+    let ctx = TestContext::setup();
+    let old = ctx.create(0);
+    set_time(&ctx.env, 100);
+
+    let terms = StreamTerms {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 2_000,
+        rate_per_second: 2,
+        start_time: 100,
+        cliff_time: 100,
+        end_time: 1_100,
+    };
+    let new = ctx
+        .router()
+        .cancel_and_recreate(&ctx.streams[0], &old, &ctx.sender, &terms);
+
+    assert_eq!(
+        ctx.stream(0).get_stream_state(&old).status,
+        StreamStatus::Cancelled
+    );
+    let state = ctx.stream(0).get_stream_state(&new);
+    assert_eq!(state.rate_per_second, 2);
+    assert_eq!(state.deposit_amount, 2_000);
+}
+
+#[test]
+fn test_multicall_runs_heterogeneous_calls() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(0);
+
+    let create_args: Vec<Val> = (
+        ctx.sender.clone(),
+        ctx.recipient.clone(),
+        500_i128,
+        1_i128,
+        0u64,
+        0u64,
+        500u64,
+    )
+        .into_val(&ctx.env);
+    let calls = vec![
+        &ctx.env,
+        Call {
+            contract: ctx.streams[1].clone(),
+            function: Symbol::new(&ctx.env, "create_stream"),
+            args: create_args,
+        },
+        Call {
+            contract: ctx.streams[0].clone(),
+            function: Symbol::new(&ctx.env, "pause_stream"),
+            args: vec![&ctx.env, stream_id.into_val(&ctx.env)],
+        },
+    ];
+    let results = ctx.router().multicall(&ctx.sender, &calls);
+    assert_eq!(results.len(), 2);
+
+    let created: u64 = results.get_unchecked(0).into_val(&ctx.env);
+    assert_eq!(ctx.stream(1).get_stream_state(&created).deposit_amount, 500);
+    assert_eq!(
+        ctx.stream(0).get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+}
+
+#[test]
+fn test_failing_call_reverts_batch() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(0);
+
+    let pause = Call {
+        contract: ctx.streams[0].clone(),
+        function: Symbol::new(&ctx.env, "pause_stream"),
+        args: vec![&ctx.env, stream_id.into_val(&ctx.env)],
+    };
+    // Pausing twice fails, so the first pause must not persist either.
+    let calls = vec![&ctx.env, pause.clone(), pause];
+    assert!(ctx.router().try_multicall(&ctx.sender, &calls).is_err());
+    assert_eq!(
+        ctx.stream(0).get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream compatibility
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_withdraw_many_rejects_outdated_instance() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(0);
+    set_time(&ctx.env, 500);
+
+    let streams = vec![
+        &ctx.env,
+        StreamRef {
+            contract: ctx.streams[0].clone(),
+            stream_id,
+        },
+        StreamRef {
+            contract: register_outdated_stream(&ctx.env),
+            stream_id: 0,
+        },
+    ];
+    ctx.router().withdraw_many(&ctx.recipient, &streams);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_cancel_and_recreate_rejects_outdated_instance() {
+    let ctx = TestContext::setup();
+    let terms = StreamTerms {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 1000,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+    };
+    ctx.router()
+        .cancel_and_recreate(&register_outdated_stream(&ctx.env), &0, &ctx.sender, &terms);
+}
@@ -0,0 +1,26 @@
+//! Interfaces of external contracts the adapter calls into.
+
+use fluxora_types::{Clock, Stream, StreamOptions};
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The subset of the FluxoraStream contract the adapter translates to.
+#[contractclient(name = "StreamClient")]
+pub trait FluxoraStream {
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream_scaled(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        scaled_rate: u128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+    ) -> u64;
+    fn withdraw_to(env: Env, stream_id: u64, to: Address) -> i128;
+    fn cancel_stream(env: Env, stream_id: u64);
+    fn get_stream_state(env: Env, stream_id: u64) -> Stream;
+    fn get_stream_clock(env: Env, stream_id: u64) -> Clock;
+}
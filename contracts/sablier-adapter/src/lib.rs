@@ -0,0 +1,415 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{FluxoraStream, StreamClient};
+
+use fluxora_math::{FlowRate, RATE_SCALE};
+use fluxora_types::{Clock, Stream, StreamOptions, StreamStatus};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Offsets from the creation time, as in Sablier's `LockupLinear.Durations`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Durations {
+    /// Seconds before anything can be withdrawn; zero for no cliff.
+    pub cliff: u64,
+    /// Seconds the stream runs for.
+    pub total: u64,
+}
+
+/// Absolute schedule, as in Sablier's `LockupLinear.Timestamps`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Timestamps {
+    pub start: u64,
+    /// Nothing can be withdrawn before this; equal to `start` for no cliff.
+    pub cliff: u64,
+    pub end: u64,
+}
+
+/// Parameters of `create_with_durations`, after Sablier's
+/// `CreateWithDurations`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreateWithDurations {
+    pub sender: Address,
+    pub recipient: Address,
+    /// Streamed in full by the end, whatever the duration.
+    pub total_amount: i128,
+    pub asset: Address,
+    /// Must be `true`: Fluxora senders can always cancel.
+    pub cancelable: bool,
+    pub durations: Durations,
+}
+
+/// Parameters of `create_with_timestamps`, after Sablier's
+/// `CreateWithTimestamps`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreateWithTimestamps {
+    pub sender: Address,
+    pub recipient: Address,
+    /// Streamed in full by the end, whatever the duration.
+    pub total_amount: i128,
+    pub asset: Address,
+    /// Must be `true`: Fluxora senders can always cancel.
+    pub cancelable: bool,
+    pub timestamps: Timestamps,
+}
+
+/// Sablier's `Lockup.Status`, derived from the Fluxora stream.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LockupStatus {
+    /// Not started yet.
+    Pending = 0,
+    /// Accruing, or paused part-way.
+    Streaming = 1,
+    /// Everything has accrued and some is still to be withdrawn.
+    Settled = 2,
+    /// Cancelled with an accrued balance still to be withdrawn.
+    Canceled = 3,
+    /// Nothing is left to withdraw.
+    Depleted = 4,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    StreamContract, // Instance storage for the FluxoraStream instance adapted.
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn stream_contract(env: &Env) -> StreamClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::StreamContract)
+        .expect("not initialised");
+    StreamClient::new(env, &address)
+}
+
+/// The stream's state, or its final state once `close_stream` deleted it.
+fn load_stream(env: &Env, stream_id: u64) -> Stream {
+    fluxora_types::load_stream(env, &stream_contract(env).address, stream_id)
+}
+
+/// A scaled rate streaming exactly `total` over `duration` seconds. The
+/// rate is rounded up so the last unit is reached by the end, unless that
+/// would stream more than `total` (only on very long schedules).
+fn scaled_rate(total: i128, duration: u64) -> u128 {
+    let scaled = (total as u128)
+        .checked_mul(RATE_SCALE)
+        .expect("overflow calculating rate")
+        .div_ceil(duration as u128);
+    match FlowRate::Scaled(scaled).streamed(duration) {
+        Some(streamed) if streamed <= total => scaled,
+        _ => scaled - 1,
+    }
+}
+
+/// A stream with its accrued amount, read in one place for the views.
+struct Snapshot {
+    stream: Stream,
+    accrued: i128,
+    now: u64,
+}
+
+impl Snapshot {
+    fn load(env: &Env, stream_id: u64) -> Self {
+        let client = stream_contract(env);
+        let now = match client.try_get_stream_clock(&stream_id) {
+            Ok(Ok(Clock::LedgerSequence)) => env.ledger().sequence() as u64,
+            // A closed stream has no clock left, and is settled, so its
+            // status no longer depends on the time.
+            _ => env.ledger().timestamp(),
+        };
+        Snapshot {
+            stream: load_stream(env, stream_id),
+            accrued: fluxora_types::accrued(env, &client.address, stream_id),
+            now,
+        }
+    }
+
+    fn withdrawable(&self) -> i128 {
+        let open = match self.stream.status {
+            StreamStatus::Active => true,
+            StreamStatus::Cancelled => !self.stream.credit_refunds,
+            _ => false,
+        };
+        if open {
+            (self.accrued - self.stream.withdrawn_amount).max(0)
+        } else {
+            0
+        }
+    }
+
+    fn status(&self) -> LockupStatus {
+        match self.stream.status {
+            StreamStatus::Completed | StreamStatus::ClawedBack => LockupStatus::Depleted,
+            StreamStatus::Cancelled if self.withdrawable() > 0 => LockupStatus::Canceled,
+            StreamStatus::Cancelled => LockupStatus::Depleted,
+            StreamStatus::Active | StreamStatus::Paused | StreamStatus::Distressed => {
+                if self.now < self.stream.start_time {
+                    LockupStatus::Pending
+                } else if self.accrued < self.stream.deposit_amount {
+                    LockupStatus::Streaming
+                } else if self.stream.withdrawn_amount < self.stream.deposit_amount {
+                    LockupStatus::Settled
+                } else {
+                    LockupStatus::Depleted
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+/// Sablier lockup-style entrypoints over one FluxoraStream instance, so
+/// tooling and integrations written against Sablier's `SablierLockupLinear`
+/// port with a change of names only: `createWithDurations` becomes
+/// `create_with_durations`, `withdrawMax` becomes `withdraw_max`, and so on.
+///
+/// The adapter holds no funds or state beyond the instance address. Streams
+/// live in, and are paid out by, the stream contract, with its ids, and
+/// remain fully usable through it directly. Differences from Sablier:
+///
+/// - withdrawals always pay out everything withdrawable, so there is
+///   `withdraw_max` but no partial `withdraw`, and only the recipient may
+///   call it;
+/// - every stream is cancelable by its sender, so `cancelable` must be
+///   `true` and there is no `renounce`;
+/// - recipients are moved with the stream contract's `transfer_recipient`,
+///   not an NFT transfer.
+#[contract]
+pub struct FluxoraSablierAdapter;
+
+#[contractimpl]
+impl FluxoraSablierAdapter {
+    /// Bind the adapter to `stream_contract`. Callable once.
+    ///
+    /// # Panics
+    /// - If already initialised.
+    /// - With `IncompatibleInterface` if `stream_contract` is not a
+    ///   compatible FluxoraStream deployment.
+    pub fn init(env: Env, stream_contract: Address) {
+        if env.storage().instance().has(&DataKey::StreamContract) {
+            panic!("already initialised");
+        }
+        fluxora_types::require_compatible(&env, &stream_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamContract, &stream_contract);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Return the FluxoraStream instance this adapter creates streams on.
+    pub fn get_stream_contract(env: Env) -> Address {
+        stream_contract(&env).address
+    }
+
+    /// Stream `total_amount` of `asset` from `sender` to `recipient`,
+    /// starting now, over `durations.total` seconds with an optional cliff.
+    /// `sender` must authorise. Returns the stream id.
+    ///
+    /// # Panics
+    /// - If `cancelable` is `false`.
+    /// - If the total duration is zero or the cliff is after the end.
+    /// - Any of the stream contract's creation failures.
+    pub fn create_with_durations(env: Env, params: CreateWithDurations) -> u64 {
+        let start = env.ledger().timestamp();
+        let durations = &params.durations;
+        assert!(
+            durations.cliff <= durations.total,
+            "cliff must not be after the end"
+        );
+        let timestamps = Timestamps {
+            start,
+            cliff: start
+                .checked_add(durations.cliff)
+                .expect("overflow calculating cliff"),
+            end: start
+                .checked_add(durations.total)
+                .expect("overflow calculating end"),
+        };
+        Self::create(
+            &env,
+            params.sender,
+            params.recipient,
+            params.total_amount,
+            params.asset,
+            params.cancelable,
+            timestamps,
+        )
+    }
+
+    /// Stream `total_amount` of `asset` from `sender` to `recipient` on an
+    /// absolute schedule. `sender` must authorise. Returns the stream id.
+    ///
+    /// # Panics
+    /// - If `cancelable` is `false`.
+    /// - If `start` is not before `end`.
+    /// - Any of the stream contract's creation failures.
+    pub fn create_with_timestamps(env: Env, params: CreateWithTimestamps) -> u64 {
+        Self::create(
+            &env,
+            params.sender,
+            params.recipient,
+            params.total_amount,
+            params.asset,
+            params.cancelable,
+            params.timestamps,
+        )
+    }
+
+    fn create(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        total_amount: i128,
+        asset: Address,
+        cancelable: bool,
+        timestamps: Timestamps,
+    ) -> u64 {
+        sender.require_auth();
+        assert!(cancelable, "non-cancelable streams are not supported");
+        assert!(total_amount > 0, "total_amount must be positive");
+        assert!(
+            timestamps.start < timestamps.end,
+            "start must be before end"
+        );
+        stream_contract(env).create_stream_scaled(
+            &sender,
+            &recipient,
+            &asset,
+            &total_amount,
+            &scaled_rate(total_amount, timestamps.end - timestamps.start),
+            &timestamps.start,
+            &timestamps.cliff,
+            &timestamps.end,
+            &StreamOptions::default(),
+        )
+    }
+
+    /// Withdraw everything withdrawable from `stream_id` to `to`. The
+    /// recipient must authorise. Returns the amount withdrawn.
+    ///
+    /// # Panics
+    /// - Any of the stream contract's `withdraw_to` failures, including
+    ///   when nothing is withdrawable.
+    pub fn withdraw_max(env: Env, stream_id: u64, to: Address) -> i128 {
+        let stream = stream_contract(&env);
+        stream.get_stream_state(&stream_id).recipient.require_auth();
+        stream.withdraw_to(&stream_id, &to)
+    }
+
+    /// Cancel `stream_id`, refunding the unstreamed amount to the sender.
+    /// The sender must authorise.
+    ///
+    /// # Panics
+    /// - Any of the stream contract's `cancel_stream` failures.
+    pub fn cancel(env: Env, stream_id: u64) {
+        let stream = stream_contract(&env);
+        stream.get_stream_state(&stream_id).sender.require_auth();
+        stream.cancel_stream(&stream_id);
+    }
+
+    /// Return the Sablier status of `stream_id`.
+    pub fn status_of(env: Env, stream_id: u64) -> LockupStatus {
+        Snapshot::load(&env, stream_id).status()
+    }
+
+    /// Return the amount streamed to the recipient so far, withdrawn or not.
+    pub fn streamed_amount_of(env: Env, stream_id: u64) -> i128 {
+        Snapshot::load(&env, stream_id).accrued
+    }
+
+    /// Return what `withdraw_max` would pay out now.
+    pub fn withdrawable_amount_of(env: Env, stream_id: u64) -> i128 {
+        Snapshot::load(&env, stream_id).withdrawable()
+    }
+
+    /// Return what the sender would get back if they cancelled now.
+    pub fn refundable_amount_of(env: Env, stream_id: u64) -> i128 {
+        let snapshot = Snapshot::load(&env, stream_id);
+        match snapshot.stream.status {
+            StreamStatus::Active | StreamStatus::Paused => {
+                snapshot.stream.deposit_amount - snapshot.accrued
+            }
+            _ => 0,
+        }
+    }
+
+    /// Return whether the sender can still cancel `stream_id` with an
+    /// effect: it is running and has not fully accrued.
+    pub fn is_cancelable(env: Env, stream_id: u64) -> bool {
+        matches!(
+            Snapshot::load(&env, stream_id).status(),
+            LockupStatus::Pending | LockupStatus::Streaming
+        )
+    }
+
+    /// Return whether `stream_id` was stopped early, by its sender or an
+    /// admin clawback.
+    pub fn was_canceled(env: Env, stream_id: u64) -> bool {
+        matches!(
+            load_stream(&env, stream_id).status,
+            StreamStatus::Cancelled | StreamStatus::ClawedBack
+        )
+    }
+
+    /// Return whether nothing is left to withdraw from `stream_id`.
+    pub fn is_depleted(env: Env, stream_id: u64) -> bool {
+        Snapshot::load(&env, stream_id).status() == LockupStatus::Depleted
+    }
+
+    /// Return the address that funded `stream_id`.
+    pub fn get_sender(env: Env, stream_id: u64) -> Address {
+        load_stream(&env, stream_id).sender
+    }
+
+    /// Return the address `stream_id` currently pays.
+    pub fn get_recipient(env: Env, stream_id: u64) -> Address {
+        load_stream(&env, stream_id).recipient
+    }
+
+    /// Return the token `stream_id` is paid in.
+    pub fn get_asset(env: Env, stream_id: u64) -> Address {
+        load_stream(&env, stream_id).token
+    }
+
+    /// Return the stream's deposit. Unlike Sablier, cancellation reduces it
+    /// to the amount streamed before the cancellation.
+    pub fn get_deposited_amount(env: Env, stream_id: u64) -> i128 {
+        load_stream(&env, stream_id).deposit_amount
+    }
+
+    /// Return the amount withdrawn from `stream_id` so far.
+    pub fn get_withdrawn_amount(env: Env, stream_id: u64) -> i128 {
+        load_stream(&env, stream_id).withdrawn_amount
+    }
+
+    /// Return the schedule of `stream_id`.
+    pub fn get_timestamps(env: Env, stream_id: u64) -> Timestamps {
+        let stream = load_stream(&env, stream_id);
+        Timestamps {
+            start: stream.start_time,
+            cliff: stream.cliff_time,
+            end: stream.end_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
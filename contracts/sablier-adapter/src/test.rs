@@ -0,0 +1,232 @@
+#[cfg(test)]
+extern crate std;
+
+use fluxora_stream::{FluxoraStreamClient, CLOSE_RETENTION_SECS};
+use fluxora_testutils::{
+    balance, mint, register_outdated_stream, register_stream, register_token, set_time,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use crate::{
+    CreateWithDurations, CreateWithTimestamps, Durations, FluxoraSablierAdapter,
+    FluxoraSablierAdapterClient, LockupStatus, Timestamps,
+};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    adapter_id: Address,
+    stream_id: Address,
+    token: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl TestContext {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 100);
+
+        let token = register_token(&env);
+        let stream_id = register_stream(&env, &token);
+        let adapter_id = env.register_contract(None, FluxoraSablierAdapter);
+        FluxoraSablierAdapterClient::new(&env, &adapter_id).init(&stream_id);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        mint(&env, &token, &sender, 10_000);
+
+        TestContext {
+            env,
+            adapter_id,
+            stream_id,
+            token,
+            sender,
+            recipient,
+        }
+    }
+
+    fn adapter(&self) -> FluxoraSablierAdapterClient<'_> {
+        FluxoraSablierAdapterClient::new(&self.env, &self.adapter_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    /// Open a stream of `total` over `duration` seconds from now, with no
+    /// cliff, through the adapter.
+    fn create(&self, total: i128, duration: u64) -> u64 {
+        self.adapter().create_with_durations(&CreateWithDurations {
+            sender: self.sender.clone(),
+            recipient: self.recipient.clone(),
+            total_amount: total,
+            asset: self.token.clone(),
+            cancelable: true,
+            durations: Durations {
+                cliff: 0,
+                total: duration,
+            },
+        })
+    }
+
+    fn set_time(&self, timestamp: u64) {
+        set_time(&self.env, timestamp);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — creation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_with_durations_opens_stream_on_fluxora() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(1_000, 1_000);
+
+    let state = ctx.stream().get_stream_state(&stream_id);
+    assert_eq!(state.sender, ctx.sender);
+    assert_eq!(state.recipient, ctx.recipient);
+    assert_eq!(state.deposit_amount, 1_000);
+    assert_eq!(state.start_time, 100);
+    assert_eq!(state.end_time, 1_100);
+    assert_eq!(balance(&ctx.env, &ctx.token, &ctx.sender), 9_000);
+
+    assert_eq!(ctx.adapter().get_sender(&stream_id), ctx.sender);
+    assert_eq!(ctx.adapter().get_asset(&stream_id), ctx.token);
+    assert_eq!(ctx.adapter().get_deposited_amount(&stream_id), 1_000);
+}
+
+#[test]
+fn test_total_amount_streams_in_full_when_not_divisible() {
+    let ctx = TestContext::setup();
+    // 1_000 over 300 seconds is not a whole rate per second.
+    let stream_id = ctx.create(1_000, 300);
+
+    ctx.set_time(250);
+    assert_eq!(ctx.adapter().streamed_amount_of(&stream_id), 500);
+
+    ctx.set_time(400);
+    assert_eq!(ctx.adapter().streamed_amount_of(&stream_id), 1_000);
+    assert_eq!(ctx.adapter().status_of(&stream_id), LockupStatus::Settled);
+    assert_eq!(
+        ctx.adapter().withdraw_max(&stream_id, &ctx.recipient),
+        1_000
+    );
+    assert_eq!(ctx.adapter().status_of(&stream_id), LockupStatus::Depleted);
+}
+
+#[test]
+fn test_create_with_timestamps_honours_cliff() {
+    let ctx = TestContext::setup();
+    let timestamps = Timestamps {
+        start: 200,
+        cliff: 500,
+        end: 1_200,
+    };
+    let stream_id = ctx.adapter().create_with_timestamps(&CreateWithTimestamps {
+        sender: ctx.sender.clone(),
+        recipient: ctx.recipient.clone(),
+        total_amount: 1_000,
+        asset: ctx.token.clone(),
+        cancelable: true,
+        timestamps: timestamps.clone(),
+    });
+    assert_eq!(ctx.adapter().get_timestamps(&stream_id), timestamps);
+    assert_eq!(ctx.adapter().status_of(&stream_id), LockupStatus::Pending);
+
+    ctx.set_time(400);
+    assert_eq!(ctx.adapter().status_of(&stream_id), LockupStatus::Streaming);
+    assert_eq!(ctx.adapter().withdrawable_amount_of(&stream_id), 0);
+
+    ctx.set_time(500);
+    assert_eq!(ctx.adapter().withdrawable_amount_of(&stream_id), 300);
+}
+
+#[test]
+#[should_panic(expected = "non-cancelable streams are not supported")]
+fn test_non_cancelable_streams_are_rejected() {
+    let ctx = TestContext::setup();
+    ctx.adapter().create_with_durations(&CreateWithDurations {
+        sender: ctx.sender.clone(),
+        recipient: ctx.recipient.clone(),
+        total_amount: 1_000,
+        asset: ctx.token.clone(),
+        cancelable: false,
+        durations: Durations {
+            cliff: 0,
+            total: 1_000,
+        },
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_init_rejects_incompatible_stream_contract() {
+    let env = Env::default();
+    let adapter_id = env.register_contract(None, FluxoraSablierAdapter);
+    FluxoraSablierAdapterClient::new(&env, &adapter_id).init(&register_outdated_stream(&env));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdrawal and cancellation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_max_pays_out_everything_withdrawable() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(1_000, 1_000);
+    let to = Address::generate(&ctx.env);
+
+    ctx.set_time(350);
+    assert_eq!(ctx.adapter().withdrawable_amount_of(&stream_id), 250);
+    assert_eq!(ctx.adapter().withdraw_max(&stream_id, &to), 250);
+    assert_eq!(balance(&ctx.env, &ctx.token, &to), 250);
+    assert_eq!(ctx.adapter().get_withdrawn_amount(&stream_id), 250);
+    assert_eq!(ctx.adapter().withdrawable_amount_of(&stream_id), 0);
+}
+
+#[test]
+fn test_cancel_refunds_sender_and_reports_canceled() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(1_000, 1_000);
+
+    ctx.set_time(500);
+    assert!(ctx.adapter().is_cancelable(&stream_id));
+    assert_eq!(ctx.adapter().refundable_amount_of(&stream_id), 600);
+    ctx.adapter().cancel(&stream_id);
+
+    assert_eq!(balance(&ctx.env, &ctx.token, &ctx.sender), 9_600);
+    assert!(ctx.adapter().was_canceled(&stream_id));
+    assert!(!ctx.adapter().is_cancelable(&stream_id));
+    assert_eq!(ctx.adapter().refundable_amount_of(&stream_id), 0);
+    assert_eq!(ctx.adapter().status_of(&stream_id), LockupStatus::Canceled);
+
+    ctx.adapter().withdraw_max(&stream_id, &ctx.recipient);
+    assert_eq!(balance(&ctx.env, &ctx.token, &ctx.recipient), 400);
+    assert!(ctx.adapter().is_depleted(&stream_id));
+}
+
+#[test]
+fn test_views_survive_closing_the_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create(1_000, 1_000);
+    ctx.set_time(1_100);
+    ctx.adapter().withdraw_max(&stream_id, &ctx.recipient);
+    ctx.set_time(1_100 + CLOSE_RETENTION_SECS);
+    ctx.stream().close_stream(&stream_id);
+
+    assert_eq!(ctx.adapter().get_sender(&stream_id), ctx.sender);
+    assert_eq!(ctx.adapter().get_recipient(&stream_id), ctx.recipient);
+    assert_eq!(ctx.adapter().get_asset(&stream_id), ctx.token);
+    assert_eq!(ctx.adapter().get_withdrawn_amount(&stream_id), 1_000);
+    assert_eq!(ctx.adapter().streamed_amount_of(&stream_id), 1_000);
+    assert_eq!(ctx.adapter().withdrawable_amount_of(&stream_id), 0);
+    assert_eq!(ctx.adapter().status_of(&stream_id), LockupStatus::Depleted);
+    assert!(!ctx.adapter().was_canceled(&stream_id));
+    assert_eq!(ctx.adapter().get_timestamps(&stream_id).end, 1_100);
+}
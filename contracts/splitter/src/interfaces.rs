@@ -0,0 +1,9 @@
+//! Interfaces of external contracts the splitter calls into.
+
+use soroban_sdk::{contractclient, Env};
+
+/// The subset of the FluxoraStream contract used to collect proceeds.
+#[contractclient(name = "StreamClient")]
+pub trait Stream {
+    fn withdraw(env: Env, stream_id: u64) -> i128;
+}
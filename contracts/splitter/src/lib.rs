@@ -0,0 +1,176 @@
+#![no_std]
+
+mod interfaces;
+pub use interfaces::{Stream, StreamClient};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// A payee and their weight in every split.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payee {
+    pub account: Address,
+    pub shares: u32,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Payees,                     // Instance storage: fixed payee set.
+    TotalShares,                // Instance storage: sum of all payee shares.
+    TotalReleased(Address),     // Persistent storage: paid out so far, per token.
+    Released(Address, Address), // Persistent storage: paid out so far, per (token, payee).
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_payees(env: &Env) -> Vec<Payee> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Payees)
+        .expect("contract not initialised: missing payees")
+}
+
+fn get_total_shares(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalShares)
+        .expect("contract not initialised: missing shares")
+}
+
+fn get_amount(env: &Env, key: &DataKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn set_amount(env: &Env, key: &DataKey, amount: i128) {
+    env.storage().persistent().set(key, &amount);
+    env.storage().persistent().extend_ttl(key, 17280, 120960);
+}
+
+fn find_payee(env: &Env, account: &Address) -> Payee {
+    get_payees(env)
+        .iter()
+        .find(|p| p.account == *account)
+        .expect("not a payee")
+}
+
+/// Share of everything ever received in `token` still owed to `payee`.
+fn releasable(env: &Env, token: &Address, payee: &Payee) -> i128 {
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    let received = balance
+        .checked_add(get_amount(env, &DataKey::TotalReleased(token.clone())))
+        .expect("overflow calculating amount received");
+    let entitled = fluxora_math::mul_div_floor(
+        received,
+        payee.shares as i128,
+        get_total_shares(env) as i128,
+    )
+    .expect("overflow calculating entitlement");
+    entitled
+        - get_amount(
+            env,
+            &DataKey::Released(token.clone(), payee.account.clone()),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraSplitter;
+
+#[contractimpl]
+impl FluxoraSplitter {
+    /// Initialise with a fixed set of weighted payees. Can only be called once.
+    ///
+    /// # Panics
+    /// - If `payees` is empty, lists an account twice, or has a zero weight.
+    pub fn init(env: Env, payees: Vec<Payee>) {
+        if env.storage().instance().has(&DataKey::Payees) {
+            panic!("already initialised");
+        }
+        assert!(!payees.is_empty(), "no payees");
+
+        let mut total: u32 = 0;
+        for (i, payee) in payees.iter().enumerate() {
+            assert!(payee.shares > 0, "shares must be positive");
+            assert!(
+                payees.iter().position(|p| p.account == payee.account) == Some(i),
+                "duplicate payee"
+            );
+            total = total
+                .checked_add(payee.shares)
+                .expect("overflow summing shares");
+        }
+        env.storage().instance().set(&DataKey::Payees, &payees);
+        env.storage().instance().set(&DataKey::TotalShares, &total);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    /// Pull everything withdrawable from a stream whose recipient is this
+    /// splitter. Callable by anyone. Returns the amount collected.
+    pub fn collect(env: Env, stream_contract: Address, stream_id: u64) -> i128 {
+        let amount = StreamClient::new(&env, &stream_contract).withdraw(&stream_id);
+
+        env.events()
+            .publish((symbol_short!("collected"), stream_id), amount);
+        amount
+    }
+
+    /// Pay `payee` their outstanding share of `token`. Callable by anyone.
+    /// Returns the amount released.
+    ///
+    /// # Panics
+    /// - If `payee` is not a payee, or nothing is owed to them.
+    pub fn release(env: Env, token: Address, payee: Address) -> i128 {
+        let entry = find_payee(&env, &payee);
+        let amount = releasable(&env, &token, &entry);
+        assert!(amount > 0, "nothing to release");
+
+        let released_key = DataKey::Released(token.clone(), payee.clone());
+        set_amount(
+            &env,
+            &released_key,
+            get_amount(&env, &released_key) + amount,
+        );
+        let total_key = DataKey::TotalReleased(token.clone());
+        set_amount(&env, &total_key, get_amount(&env, &total_key) + amount);
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &payee, &amount);
+
+        env.events()
+            .publish((symbol_short!("released"), token, payee), amount);
+        amount
+    }
+
+    /// Return the amount of `token` currently owed to `payee`.
+    pub fn releasable(env: Env, token: Address, payee: Address) -> i128 {
+        releasable(&env, &token, &find_payee(&env, &payee))
+    }
+
+    /// Return the amount of `token` already paid to `payee`.
+    pub fn released(env: Env, token: Address, payee: Address) -> i128 {
+        get_amount(&env, &DataKey::Released(token, payee))
+    }
+
+    /// Return the amount of `token` already paid out across all payees.
+    pub fn total_released(env: Env, token: Address) -> i128 {
+        get_amount(&env, &DataKey::TotalReleased(token))
+    }
+
+    /// Return the payee set.
+    pub fn get_payees(env: Env) -> Vec<Payee> {
+        get_payees(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,138 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token::Client as TokenClient, vec, Address, Env};
+
+use fluxora_stream::FluxoraStreamClient;
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraSplitter, FluxoraSplitterClient, Payee};
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    splitter_id: Address,
+    stream_id: Address,
+    token_id: Address,
+    sender: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream and a splitter paying Alice 3 shares and Bob 1.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let token_id = register_token(&env);
+        let stream_id = register_stream(&env, &token_id);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let splitter_id = env.register_contract(None, FluxoraSplitter);
+        FluxoraSplitterClient::new(&env, &splitter_id).init(&vec![
+            &env,
+            Payee {
+                account: alice.clone(),
+                shares: 3,
+            },
+            Payee {
+                account: bob.clone(),
+                shares: 1,
+            },
+        ]);
+
+        let sender = Address::generate(&env);
+        mint(&env, &token_id, &sender, 10_000);
+
+        TestContext {
+            env,
+            splitter_id,
+            stream_id,
+            token_id,
+            sender,
+            alice,
+            bob,
+        }
+    }
+
+    fn splitter(&self) -> FluxoraSplitterClient<'_> {
+        FluxoraSplitterClient::new(&self.env, &self.splitter_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn stream_to(&self, recipient: &Address) -> u64 {
+        self.stream()
+            .create_stream(&self.sender, recipient, &1000, &1, &0, &0, &1000)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — collecting and releasing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_collect_stream_and_release_by_weight() {
+    let ctx = TestContext::setup();
+    let id = ctx.stream_to(&ctx.splitter_id);
+
+    set_time(&ctx.env, 400);
+    ctx.env.set_auths(&[]);
+    assert_eq!(ctx.splitter().collect(&ctx.stream_id, &id), 400);
+
+    assert_eq!(ctx.splitter().release(&ctx.token_id, &ctx.alice), 300);
+    assert_eq!(ctx.splitter().release(&ctx.token_id, &ctx.bob), 100);
+    assert_eq!(ctx.token().balance(&ctx.alice), 300);
+    assert_eq!(ctx.splitter().total_released(&ctx.token_id), 400);
+}
+
+#[test]
+fn test_withdraw_to_splitter_accumulates_across_releases() {
+    let ctx = TestContext::setup();
+    let lead = Address::generate(&ctx.env);
+    let id = ctx.stream_to(&lead);
+
+    set_time(&ctx.env, 200);
+    ctx.stream().withdraw_to(&id, &ctx.splitter_id);
+    assert_eq!(ctx.splitter().release(&ctx.token_id, &ctx.alice), 150);
+
+    set_time(&ctx.env, 1000);
+    ctx.stream().withdraw_to(&id, &ctx.splitter_id);
+    assert_eq!(ctx.splitter().releasable(&ctx.token_id, &ctx.alice), 600);
+    assert_eq!(ctx.splitter().releasable(&ctx.token_id, &ctx.bob), 250);
+    assert_eq!(ctx.token().balance(&lead), 0);
+}
+
+#[test]
+#[should_panic(expected = "nothing to release")]
+fn test_release_twice_panics() {
+    let ctx = TestContext::setup();
+    mint(&ctx.env, &ctx.token_id, &ctx.splitter_id, 40);
+    ctx.splitter().release(&ctx.token_id, &ctx.bob);
+    ctx.splitter().release(&ctx.token_id, &ctx.bob);
+}
+
+#[test]
+#[should_panic(expected = "duplicate payee")]
+fn test_init_with_duplicate_payee_panics() {
+    let env = Env::default();
+    let payee = Address::generate(&env);
+    let splitter_id = env.register_contract(None, FluxoraSplitter);
+    let entry = Payee {
+        account: payee,
+        shares: 1,
+    };
+    FluxoraSplitterClient::new(&env, &splitter_id).init(&vec![&env, entry.clone(), entry]);
+}
@@ -0,0 +1,416 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol, Val, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Longest allowed lock. A lock this long carries voting power equal to the
+/// locked amount; shorter locks carry proportionally less.
+pub const MAX_LOCK_SECS: u64 = 126_144_000; // 4 years
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Global configuration for the staking contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Protocol governance token that is locked for voting power.
+    pub token: Address,
+    /// Stream contract governed by this contract (its `governor`).
+    pub stream_contract: Address,
+    /// Seconds a proposal stays open for voting.
+    pub voting_period: u64,
+    /// Minimum voting power in favour for a proposal to pass.
+    pub quorum: i128,
+}
+
+/// Tokens locked by one account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VeLock {
+    pub amount: i128,
+    pub unlock_at: u64,
+}
+
+/// A call on the governed stream contract put to a vote.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub voting_ends: u64,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub executed: bool,
+}
+
+/// Namespace for all contract storage keys.
+#[contracttype]
+pub enum DataKey {
+    Config,              // Instance storage for token, governed contract and voting rules.
+    NextProposalId,      // Instance storage for the auto-incrementing proposal ID.
+    Lock(Address),       // Persistent storage: locked balance per account.
+    Proposal(u64),       // Persistent storage for individual proposals.
+    Voted(u64, Address), // Persistent storage: accounts that voted on a proposal.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+fn get_lock(env: &Env, account: &Address) -> Option<VeLock> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Lock(account.clone()))
+}
+
+fn save_lock(env: &Env, account: &Address, lock: &VeLock) {
+    let key = DataKey::Lock(account.clone());
+    env.storage().persistent().set(&key, lock);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> Proposal {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .expect("proposal not found")
+}
+
+fn save_proposal(env: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.proposal_id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+}
+
+/// Voting power decays linearly to zero as the lock approaches `unlock_at`.
+fn voting_power(env: &Env, account: &Address) -> i128 {
+    voting_power_at(env, account, env.ledger().timestamp())
+}
+
+/// Voting power `account`'s lock carries at time `at`.
+fn voting_power_at(env: &Env, account: &Address, at: u64) -> i128 {
+    match get_lock(env, account) {
+        Some(lock) if lock.unlock_at > at => fluxora_math::mul_div_floor(
+            lock.amount,
+            (lock.unlock_at - at) as i128,
+            MAX_LOCK_SECS as i128,
+        )
+        .expect("overflow calculating voting power"),
+        _ => 0,
+    }
+}
+
+fn assert_unlock_time(env: &Env, unlock_at: u64) {
+    let now = env.ledger().timestamp();
+    assert!(unlock_at > now, "unlock time must be in the future");
+    assert!(
+        unlock_at - now <= MAX_LOCK_SECS,
+        "lock exceeds the maximum duration"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraStaking;
+
+#[contractimpl]
+impl FluxoraStaking {
+    /// Initialise with the governance token, the governed stream contract
+    /// and the voting rules. Install this contract as the stream contract's
+    /// governor to hand parameter control to stakers. Can only be called once.
+    pub fn init(
+        env: Env,
+        token: Address,
+        stream_contract: Address,
+        voting_period: u64,
+        quorum: i128,
+    ) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        env.storage().instance().set(
+            &DataKey::Config,
+            &Config {
+                token,
+                stream_contract,
+                voting_period,
+                quorum,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &0u64);
+
+        env.storage().instance().extend_ttl(17280, 120960);
+    }
+
+    // -----------------------------------------------------------------------
+    // Locks
+    // -----------------------------------------------------------------------
+
+    /// Lock `amount` of the governance token until `unlock_at`.
+    ///
+    /// # Panics
+    /// - If `owner` already has a lock; use `increase_amount` or
+    ///   `increase_unlock_time` instead.
+    /// - If `amount` is not positive, or `unlock_at` is in the past or more
+    ///   than `MAX_LOCK_SECS` away.
+    pub fn create_lock(env: Env, owner: Address, amount: i128, unlock_at: u64) {
+        owner.require_auth();
+        assert!(get_lock(&env, &owner).is_none(), "lock already exists");
+        assert!(amount > 0, "amount must be positive");
+        assert_unlock_time(&env, unlock_at);
+
+        token::Client::new(&env, &get_config(&env).token).transfer(
+            &owner,
+            &env.current_contract_address(),
+            &amount,
+        );
+        save_lock(&env, &owner, &VeLock { amount, unlock_at });
+
+        env.events()
+            .publish((symbol_short!("locked"), owner), (amount, unlock_at));
+    }
+
+    /// Add `amount` to an existing, unexpired lock.
+    ///
+    /// # Panics
+    /// - If `owner` has no lock, or it has expired.
+    pub fn increase_amount(env: Env, owner: Address, amount: i128) {
+        owner.require_auth();
+        assert!(amount > 0, "amount must be positive");
+        let mut lock = get_lock(&env, &owner).expect("lock not found");
+        assert!(
+            lock.unlock_at > env.ledger().timestamp(),
+            "lock has expired"
+        );
+
+        token::Client::new(&env, &get_config(&env).token).transfer(
+            &owner,
+            &env.current_contract_address(),
+            &amount,
+        );
+        lock.amount = lock.amount.checked_add(amount).expect("overflow");
+        save_lock(&env, &owner, &lock);
+
+        env.events().publish(
+            (symbol_short!("locked"), owner),
+            (lock.amount, lock.unlock_at),
+        );
+    }
+
+    /// Push the unlock time of an existing lock out to `unlock_at`.
+    ///
+    /// # Panics
+    /// - If `unlock_at` is not later than the current unlock time, or more than
+    ///   `MAX_LOCK_SECS` away.
+    pub fn increase_unlock_time(env: Env, owner: Address, unlock_at: u64) {
+        owner.require_auth();
+        let mut lock = get_lock(&env, &owner).expect("lock not found");
+        assert!(
+            unlock_at > lock.unlock_at,
+            "unlock time can only be extended"
+        );
+        assert_unlock_time(&env, unlock_at);
+
+        lock.unlock_at = unlock_at;
+        save_lock(&env, &owner, &lock);
+
+        env.events()
+            .publish((symbol_short!("locked"), owner), (lock.amount, unlock_at));
+    }
+
+    /// Return the locked tokens to `owner` once the lock has expired.
+    ///
+    /// # Panics
+    /// - If the lock has not expired yet.
+    pub fn withdraw(env: Env, owner: Address) -> i128 {
+        owner.require_auth();
+        let lock = get_lock(&env, &owner).expect("lock not found");
+        assert!(
+            env.ledger().timestamp() >= lock.unlock_at,
+            "lock has not expired"
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Lock(owner.clone()));
+        token::Client::new(&env, &get_config(&env).token).transfer(
+            &env.current_contract_address(),
+            &owner,
+            &lock.amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("withdrew"), owner), lock.amount);
+        lock.amount
+    }
+
+    // -----------------------------------------------------------------------
+    // Governance
+    // -----------------------------------------------------------------------
+
+    /// Propose `stream_contract.function(args)`, e.g. `set_creation_limits`.
+    /// Returns the new proposal id.
+    ///
+    /// # Panics
+    /// - If `proposer` holds no voting power.
+    pub fn propose(env: Env, proposer: Address, function: Symbol, args: Vec<Val>) -> u64 {
+        proposer.require_auth();
+        assert!(voting_power(&env, &proposer) > 0, "no voting power");
+        let config = get_config(&env);
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        let proposal = Proposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            function: function.clone(),
+            args,
+            voting_ends: env.ledger().timestamp() + config.voting_period,
+            votes_for: 0,
+            votes_against: 0,
+            executed: false,
+        };
+        save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("proposed"), proposal_id),
+            (proposer, function),
+        );
+        proposal_id
+    }
+
+    /// Cast `voter`'s voting power for or against a proposal. The weight is
+    /// the power `voter`'s lock still carries when voting closes, and the
+    /// lock must not expire before then, so the same tokens cannot be
+    /// withdrawn, re-locked and counted twice. Returns the weight counted.
+    ///
+    /// # Panics
+    /// - If voting has closed, `voter` already voted, or holds no power.
+    /// - If `voter`'s lock expires before voting closes.
+    pub fn vote(env: Env, voter: Address, proposal_id: u64, support: bool) -> i128 {
+        voter.require_auth();
+        let mut proposal = load_proposal(&env, proposal_id);
+        assert!(
+            env.ledger().timestamp() < proposal.voting_ends,
+            "voting has closed"
+        );
+        let voted_key = DataKey::Voted(proposal_id, voter.clone());
+        assert!(!env.storage().persistent().has(&voted_key), "already voted");
+        let lock = get_lock(&env, &voter).expect("no voting power");
+        assert!(
+            lock.unlock_at >= proposal.voting_ends,
+            "lock expires before voting closes"
+        );
+        let weight = voting_power_at(&env, &voter, proposal.voting_ends);
+        assert!(weight > 0, "no voting power");
+
+        env.storage().persistent().set(&voted_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&voted_key, 17280, 120960);
+        if support {
+            proposal.votes_for = proposal.votes_for.checked_add(weight).expect("overflow");
+        } else {
+            proposal.votes_against = proposal
+                .votes_against
+                .checked_add(weight)
+                .expect("overflow");
+        }
+        save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("voted"), proposal_id, voter),
+            (support, weight),
+        );
+        weight
+    }
+
+    /// Execute a proposal that passed. Anyone may trigger execution once
+    /// voting has closed. Returns the stream contract's result.
+    ///
+    /// # Panics
+    /// - If voting is still open or the proposal was already executed.
+    /// - If votes in favour do not exceed votes against and reach quorum.
+    pub fn execute(env: Env, proposal_id: u64) -> Val {
+        let config = get_config(&env);
+        let mut proposal = load_proposal(&env, proposal_id);
+        assert!(!proposal.executed, "proposal already executed");
+        assert!(
+            env.ledger().timestamp() >= proposal.voting_ends,
+            "voting is still open"
+        );
+        assert!(
+            proposal.votes_for > proposal.votes_against && proposal.votes_for >= config.quorum,
+            "proposal did not pass"
+        );
+
+        // Mark executed before the call so a re-entrant execute cannot replay it.
+        proposal.executed = true;
+        save_proposal(&env, &proposal);
+
+        let result: Val = env.invoke_contract(
+            &config.stream_contract,
+            &proposal.function,
+            proposal.args.clone(),
+        );
+
+        env.events()
+            .publish((symbol_short!("executed"), proposal_id), ());
+        result
+    }
+
+    // -----------------------------------------------------------------------
+    // Views
+    // -----------------------------------------------------------------------
+
+    /// Return `account`'s current voting power.
+    pub fn voting_power(env: Env, account: Address) -> i128 {
+        voting_power(&env, &account)
+    }
+
+    /// Return `account`'s lock, if any.
+    pub fn get_lock(env: Env, account: Address) -> Option<VeLock> {
+        get_lock(&env, &account)
+    }
+
+    /// Return the proposal identified by `proposal_id`.
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
+        load_proposal(&env, proposal_id)
+    }
+
+    /// Fetches the staking configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+}
+
+#[cfg(test)]
+mod test;
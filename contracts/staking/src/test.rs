@@ -0,0 +1,213 @@
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::Address as _, token::Client as TokenClient, vec, Address, Env, IntoVal, Symbol, Val,
+    Vec,
+};
+
+use fluxora_stream::{CreationLimits, FluxoraStreamClient};
+use fluxora_testutils::{mint, register_stream, register_token, set_time};
+
+use crate::{FluxoraStaking, FluxoraStakingClient, MAX_LOCK_SECS};
+
+const VOTING_PERIOD: u64 = 3 * 86_400;
+const QUORUM: i128 = 500;
+
+// ---------------------------------------------------------------------------
+// Test helpers
+// ---------------------------------------------------------------------------
+
+struct TestContext {
+    env: Env,
+    staking_id: Address,
+    stream_id: Address,
+    gov_token: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl TestContext {
+    /// Deploy a FluxoraStream governed by a staking contract, and fund two
+    /// stakers with 1_000 governance tokens each.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        set_time(&env, 0);
+
+        let gov_token = register_token(&env);
+        let stream_token = register_token(&env);
+
+        let stream_id = register_stream(&env, &stream_token);
+        let staking_id = env.register_contract(None, FluxoraStaking);
+        FluxoraStakingClient::new(&env, &staking_id).init(
+            &gov_token,
+            &stream_id,
+            &VOTING_PERIOD,
+            &QUORUM,
+        );
+        FluxoraStreamClient::new(&env, &stream_id).set_governor(&Some(staking_id.clone()));
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        mint(&env, &gov_token, &alice, 1_000);
+        mint(&env, &gov_token, &bob, 1_000);
+
+        TestContext {
+            env,
+            staking_id,
+            stream_id,
+            gov_token,
+            alice,
+            bob,
+        }
+    }
+
+    fn staking(&self) -> FluxoraStakingClient<'_> {
+        FluxoraStakingClient::new(&self.env, &self.staking_id)
+    }
+
+    fn stream(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.stream_id)
+    }
+
+    /// Alice proposes `set_creation_limits` with `min_deposit = 42`.
+    fn propose_limits(&self) -> u64 {
+        let limits = CreationLimits {
+            min_deposit: 42,
+            ..Default::default()
+        };
+        let args: Vec<Val> = vec![&self.env, limits.into_val(&self.env)];
+        self.staking().propose(
+            &self.alice,
+            &Symbol::new(&self.env, "set_creation_limits"),
+            &args,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — locks and voting power
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_longer_lock_carries_more_weight() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &MAX_LOCK_SECS);
+    ctx.staking()
+        .create_lock(&ctx.bob, &1_000, &(MAX_LOCK_SECS / 4));
+
+    assert_eq!(ctx.staking().voting_power(&ctx.alice), 1_000);
+    assert_eq!(ctx.staking().voting_power(&ctx.bob), 250);
+
+    // Power decays linearly towards the unlock time.
+    set_time(&ctx.env, MAX_LOCK_SECS / 2);
+    assert_eq!(ctx.staking().voting_power(&ctx.alice), 500);
+    assert_eq!(ctx.staking().voting_power(&ctx.bob), 0);
+}
+
+#[test]
+fn test_withdraw_after_unlock() {
+    let ctx = TestContext::setup();
+    ctx.staking().create_lock(&ctx.alice, &600, &1_000);
+    assert!(ctx.staking().try_withdraw(&ctx.alice).is_err());
+
+    set_time(&ctx.env, 1_000);
+    assert_eq!(ctx.staking().withdraw(&ctx.alice), 600);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &ctx.gov_token).balance(&ctx.alice),
+        1_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "lock exceeds the maximum duration")]
+fn test_lock_beyond_maximum_panics() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &(MAX_LOCK_SECS + 1));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — governance of the stream contract
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_passed_proposal_updates_stream_params() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &MAX_LOCK_SECS);
+    ctx.staking()
+        .create_lock(&ctx.bob, &1_000, &(MAX_LOCK_SECS / 4));
+    let proposal_id = ctx.propose_limits();
+
+    ctx.staking().vote(&ctx.alice, &proposal_id, &true);
+    ctx.staking().vote(&ctx.bob, &proposal_id, &false);
+    set_time(&ctx.env, VOTING_PERIOD);
+
+    // No mocked signatures: the staking contract authorises as governor.
+    ctx.env.set_auths(&[]);
+    ctx.staking().execute(&proposal_id);
+    assert_eq!(ctx.stream().get_creation_limits().min_deposit, 42);
+}
+
+#[test]
+#[should_panic(expected = "proposal did not pass")]
+fn test_proposal_below_quorum_fails() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &(MAX_LOCK_SECS / 10));
+    let proposal_id = ctx.propose_limits();
+    ctx.staking().vote(&ctx.alice, &proposal_id, &true);
+
+    set_time(&ctx.env, VOTING_PERIOD);
+    ctx.staking().execute(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "already voted")]
+fn test_double_vote_panics() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &MAX_LOCK_SECS);
+    let proposal_id = ctx.propose_limits();
+    ctx.staking().vote(&ctx.alice, &proposal_id, &true);
+    ctx.staking().vote(&ctx.alice, &proposal_id, &true);
+}
+
+#[test]
+#[should_panic(expected = "lock expires before voting closes")]
+fn test_vote_with_lock_expiring_before_close_panics() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &MAX_LOCK_SECS);
+    ctx.staking()
+        .create_lock(&ctx.bob, &1_000, &(VOTING_PERIOD - 1));
+    let proposal_id = ctx.propose_limits();
+    ctx.staking().vote(&ctx.bob, &proposal_id, &false);
+}
+
+#[test]
+fn test_relocked_tokens_cannot_vote_twice() {
+    let ctx = TestContext::setup();
+    ctx.staking()
+        .create_lock(&ctx.alice, &1_000, &MAX_LOCK_SECS);
+    ctx.staking().create_lock(&ctx.bob, &1_000, &VOTING_PERIOD);
+    let proposal_id = ctx.propose_limits();
+
+    // Bob's lock only just lasts the voting period, so it carries no power
+    // at close and cannot be withdrawn and re-locked mid-vote.
+    assert!(ctx
+        .staking()
+        .try_vote(&ctx.bob, &proposal_id, &false)
+        .is_err());
+    assert!(ctx.staking().try_withdraw(&ctx.bob).is_err());
+
+    // Weight is the power the lock carries when voting closes.
+    let weight = ctx.staking().vote(&ctx.alice, &proposal_id, &true);
+    assert_eq!(
+        weight,
+        1_000 * (MAX_LOCK_SECS - VOTING_PERIOD) as i128 / MAX_LOCK_SECS as i128
+    );
+}
@@ -0,0 +1,61 @@
+//! Interfaces of external contracts the stream contract calls into.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Attestation / KYC registry consulted before withdrawals when configured.
+#[contractclient(name = "AttestationRegistryClient")]
+pub trait AttestationRegistry {
+    /// Return whether `account` has a valid attestation.
+    fn is_verified(env: Env, account: Address) -> bool;
+}
+
+/// Jurisdiction-specific transfer policy consulted on creation and withdrawal.
+#[contractclient(name = "TransferPolicyClient")]
+pub trait TransferPolicy {
+    /// Return whether `amount` of `token` may move from `sender`'s stream to
+    /// `recipient`.
+    fn can_transfer(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+    ) -> bool;
+}
+
+/// AMM route used by swap-on-withdraw. The router is pre-funded with
+/// `amount_in` of `token_in` before the call and must deliver at least
+/// `min_out` of `token_out` to `to`, returning the amount delivered.
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouter {
+    fn swap_exact_in(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
+/// Savings or yield vault receiving a recipient's withdrawals. The vault is
+/// pre-funded with `amount` of `token` before the call and credits it to
+/// `beneficiary`.
+#[contractclient(name = "VaultClient")]
+pub trait Vault {
+    fn deposit(env: Env, token: Address, amount: i128, beneficiary: Address);
+}
+
+/// Sender reputation registry told how each stream ends, when configured.
+/// The registry should only accept these calls from this contract.
+#[contractclient(name = "ReputationRegistryClient")]
+pub trait ReputationRegistry {
+    /// A stream from `sender` ran to its end, streaming `volume`.
+    fn record_completed(env: Env, sender: Address, volume: i128);
+    /// `sender` cancelled a stream before its end, after streaming `volume`.
+    fn record_cancelled(env: Env, sender: Address, volume: i128);
+    /// An arbiter settled a disputed stream from `sender`, awarding the
+    /// recipient `volume` in total; `sender_lost` when the award exceeded
+    /// what had accrued.
+    fn record_dispute(env: Env, sender: Address, volume: i128, sender_lost: bool);
+}
@@ -0,0 +1,72 @@
+//! Accounting invariants of the stream contract.
+//!
+//! - **Solvency**: the contract's balance of a token covers its principal,
+//!   which in turn covers the unwithdrawn deposits of every open stream in
+//!   that token.
+//! - **Per stream**: `withdrawn ≤ deposit`, and while the stream is open,
+//!   `withdrawn ≤ accrued ≤ deposit`. Closed streams may have paid out more
+//!   than accrued, e.g. after an arbiter ruling.
+//!
+//! `assert_invariants` checks all of them for one token. Debug builds also
+//! check each stream as it is saved and a token's solvency whenever its
+//! custody changes.
+
+use soroban_sdk::{token, Address, Env};
+
+use crate::{
+    accrued_now, get_stream_count, math, try_load_stream, vault, AccrualBasis, Stream, StreamStatus,
+};
+
+fn is_open(stream: &Stream) -> bool {
+    matches!(stream.status, StreamStatus::Active | StreamStatus::Paused)
+}
+
+pub(crate) fn check_stream(env: &Env, stream: &Stream, basis: &AccrualBasis) {
+    assert!(
+        stream.withdrawn_amount >= 0 && stream.withdrawn_amount <= stream.deposit_amount,
+        "invariant violated: withdrawn exceeds deposit"
+    );
+    if is_open(stream) {
+        let accrued = accrued_now(env, stream, basis);
+        assert!(
+            accrued <= stream.deposit_amount,
+            "invariant violated: accrued exceeds deposit"
+        );
+        assert!(
+            stream.withdrawn_amount <= accrued,
+            "invariant violated: withdrawn exceeds accrued"
+        );
+    }
+}
+
+pub(crate) fn check_solvency(env: &Env, token: &Address) {
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    assert!(
+        balance >= vault::custody(env, token).principal,
+        "invariant violated: balance below principal"
+    );
+}
+
+/// Check every invariant for `token`, walking all of its streams.
+pub(crate) fn check_all(env: &Env, token: &Address) {
+    check_solvency(env, token);
+
+    let mut open_remaining: i128 = 0;
+    for stream_id in 0..get_stream_count(env) {
+        let Some((stream, basis)) = try_load_stream(env, stream_id) else {
+            continue;
+        };
+        if stream.token != *token {
+            continue;
+        }
+        check_stream(env, &stream, &basis);
+        if is_open(&stream) {
+            let remaining = math::sub(env, stream.deposit_amount, stream.withdrawn_amount);
+            open_remaining = math::add(env, open_remaining, remaining);
+        }
+    }
+    assert!(
+        vault::custody(env, token).principal >= open_remaining,
+        "invariant violated: principal below open stream balances"
+    );
+}
@@ -1,373 +1,4623 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+mod interfaces;
+mod invariants;
+mod math;
+mod migration;
+mod roundup;
+mod vault;
+
+pub use interfaces::{
+    AttestationRegistry, AttestationRegistryClient, ReputationRegistry, ReputationRegistryClient,
+    SwapRouter, SwapRouterClient, TransferPolicy, TransferPolicyClient, Vault, VaultClient,
+};
+pub use roundup::RoundUp;
+pub use vault::Custody;
+
+use fluxora_core::Timestamp;
+use fluxora_math::{FlowRate, Schedule};
+pub use fluxora_types::{
+    Clock, Config, Error, FeeParams, OperatorAllowance, Stream, StreamOptions, StreamStatus,
+    INTERFACE_VERSION,
+};
+use soroban_sdk::{
+    contract, contractimpl, contractmeta, contracttype, panic_with_error, symbol_short, token, vec,
+    Address, Bytes, BytesN, Env, IntoVal, String, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Basis-point denominator (100% = 10_000 bps).
+pub const BPS_DENOMINATOR: u32 = fluxora_core::BPS_DENOMINATOR;
+
+/// Hard ceiling on the protocol fee. Governance cannot exceed this.
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 100;
+
+/// Hard ceiling on the share of protocol fees paid to referrers.
+pub const MAX_REFERRAL_SHARE_BPS: u32 = 5_000;
+
+/// Hard ceiling on the broker fee cap. Governance cannot exceed this.
+pub const MAX_BROKER_FEE_BPS: u32 = 500;
+
+/// Minimum delay between proposing and applying new fee parameters (2 days).
+pub const FEE_TIMELOCK_SECS: u64 = 172_800;
+
+/// Notice period between queuing and executing an admin clawback (3 days).
+pub const CLAWBACK_DELAY_SECS: u64 = 259_200;
+
+/// How long a settled stream's records are kept before `close_stream` may
+/// delete them (30 days).
+pub const CLOSE_RETENTION_SECS: u64 = 2_592_000;
+
+/// Default TTL (in ledgers) below which storage entries are bumped (~1 day).
+pub const DEFAULT_TTL_THRESHOLD: u32 = 17_280;
+
+/// Default TTL (in ledgers) storage entries are bumped to (~7 days).
+pub const DEFAULT_TTL_EXTEND_TO: u32 = 120_960;
+
+/// Default lifetime of an unconfirmed guardian pause on a single stream (1 day).
+pub const DEFAULT_GUARDIAN_PAUSE_SECS: u64 = 86_400;
+
+/// Scaled rates are expressed in `1/RATE_SCALE` base units per tick.
+pub const RATE_SCALE: u128 = fluxora_math::RATE_SCALE;
+
+/// Largest deposit one stream may hold. Keeps every bps share of a
+/// withdrawal (`amount × bps`) inside `i128`, so payouts cannot trap later.
+pub const MAX_DEPOSIT: i128 = i128::MAX / BPS_DENOMINATOR as i128;
+
+/// Largest whole-unit rate a stream may flow at.
+pub const MAX_RATE_PER_SECOND: i128 = MAX_DEPOSIT;
+
+/// Furthest a stream may end from its creation, in ticks of its clock
+/// (100 years of seconds).
+pub const MAX_HORIZON: u64 = 3_155_760_000;
+
+/// Most streams a single `extend_ttl_for` call may bump.
+pub const MAX_TTL_BATCH: u32 = 50;
+
+/// Most streams a single `get_stream_views` call may read.
+pub const MAX_VIEW_BATCH: u32 = 50;
+
+/// Most tokens a single basket stream may carry.
+pub const MAX_BASKET_COMPONENTS: u32 = 5;
+
+/// Fixed-point scale of index values: an index of 1.0 is `INDEX_SCALE`.
+pub const INDEX_SCALE: u128 = 1_000_000_000;
+
+/// Highest escalation an index-linked stream may be created with, in basis
+/// points over its nominal rate (i.e. the rate may at most double).
+pub const MAX_INDEX_CAP_BPS: u32 = 10_000;
+
+/// Decimals of native XLM: amounts are in stroops, 10^7 to the lumen.
+pub const NATIVE_DECIMALS: u32 = 7;
+
+/// XDR encoding of `Asset::Native`, from which its asset contract id derives.
+const NATIVE_ASSET_XDR: [u8; 4] = [0; 4];
+
+/// Declares each build identity value once, as both a constant returned by
+/// `meta` and an entry in the WASM's `contractmetav0` section, which
+/// `contractmeta!` only accepts as a literal.
+macro_rules! build_identity {
+    ($($(#[$doc:meta])* $name:ident = $key:tt: $val:tt;)*) => {
+        $(
+            $(#[$doc])*
+            pub const $name: &str = $val;
+            contractmeta!(key = $key, val = $val);
+        )*
+    };
+}
+
+build_identity! {
+    CONTRACT_NAME = "name": "fluxora-stream";
+    /// Semantic version of this build; matches the crate version.
+    CONTRACT_VERSION = "version": "0.1.0";
+    SOURCE_REPOSITORY = "source_repo": "https://github.com/Caritajoe18/Fluxora-Contracts";
+    /// Audit report covering this build, or `unaudited`.
+    AUDIT_REFERENCE = "audit": "unaudited";
+}
 
 // ---------------------------------------------------------------------------
 // Data types
 // ---------------------------------------------------------------------------
 
-/// Global configuration for the Fluxora protocol.
+/// Admin-settable floors and caps applied in `create_stream`.
+/// A zero value disables the corresponding check.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct Config {
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CreationLimits {
+    pub min_deposit: i128,
+    pub min_duration: u64,
+    /// Maximum distance of `end_time` from the current ledger time, in seconds.
+    pub max_horizon: u64,
+    /// Maximum number of Active/Paused streams a single sender may hold.
+    pub max_active_per_sender: u32,
+}
+
+/// Fee parameters queued behind the timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeParams {
+    pub params: FeeParams,
+    /// Earliest ledger timestamp at which `apply_fee_params` succeeds.
+    pub eta: u64,
+}
+
+/// Attribution of one referrer in one token.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferralAccount {
+    /// Streams created with this referrer.
+    pub streams: u32,
+    /// Total deposits of those streams.
+    pub volume: i128,
+    /// Protocol fee share earned but not yet claimed.
+    pub owed: i128,
+}
+
+/// A dispute opened on a stream with an arbiter. The stream stays paused
+/// until the arbiter rules.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub opened_by: Address,
+    pub opened_at: u64,
+}
+
+/// Guardian hold on a single stream. Unless the admin confirms it, the hold
+/// lapses at `expires_at` and the stream resumes on its next interaction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianHold {
+    pub expires_at: u64,
+    pub confirmed: bool,
+}
+
+/// Withholding fixed by the sender at creation, with the running total for
+/// reporting.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Withholding {
+    pub destination: Address,
+    /// Share of each withdrawal withheld, in bps.
+    pub share_bps: u32,
+    /// Total withheld from the stream so far.
+    pub withheld: i128,
+}
+
+/// Standing instruction to set aside part of every withdrawal from a
+/// stream, e.g. for savings or a tax reserve.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawSplit {
+    /// Account or contract receiving the set-aside share.
+    pub destination: Address,
+    /// Share of each withdrawal sent to `destination`, in bps.
+    pub share_bps: u32,
+}
+
+/// Spending cap a sender grants an operator contract for creating streams
+/// on their behalf in one token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorAllowance {
+    /// Deposits plus protocol fees the operator may still commit.
+    pub remaining: i128,
+    /// Ledger timestamp after which the allowance can no longer be used.
+    pub expiry: u64,
+}
+
+/// Timing of one payroll entry. The rate is the entry's amount spread evenly
+/// over `[start_time, end_time)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayrollSchedule {
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+/// One salary stream in an `import_payroll` batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayrollEntry {
+    pub recipient: Address,
+    pub amount: i128,
+    pub schedule: PayrollSchedule,
+    /// Identifier in the caller's HR system, echoed in the creation event.
+    pub external_id: String,
+}
+
+/// One token of a basket stream and how much of it streams over the
+/// basket's schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BasketComponent {
     pub token: Address,
-    pub admin: Address,
+    pub amount: i128,
+}
+
+/// Streams in several tokens that share one schedule and are withdrawn and
+/// cancelled together.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Basket {
+    pub sender: Address,
+    pub recipient: Address,
+    /// One component stream per token, in the order given at creation.
+    pub streams: Vec<u64>,
 }
 
+/// Why a payroll entry was rejected.
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum StreamStatus {
-    Active = 0,
-    Paused = 1,
-    Completed = 2,
-    Cancelled = 3,
+pub enum PayrollEntryError {
+    /// Not positive or below the configured minimum deposit.
+    InvalidAmount,
+    /// Times out of order, or outside the configured duration and horizon.
+    InvalidSchedule,
+    /// The amount does not divide evenly over the schedule.
+    UnevenRate,
+    /// The recipient is the sender.
+    SelfPayment,
+    /// The configured transfer policy refused the payment.
+    PolicyRejected,
+    /// Another entry in the batch has the same `external_id`.
+    DuplicateExternalId,
 }
 
+/// Per-entry outcome of `import_payroll`.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct Stream {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayrollResult {
+    /// The stream was created with this id.
+    Created(u64),
+    /// The entry is valid but nothing was created because another was not.
+    Valid,
+    Invalid(PayrollEntryError),
+}
+
+/// Reusable stream shape, stored once by `create_template`. Times are
+/// relative to the `start_time` supplied when a stream is opened from it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamTemplate {
+    pub token: Address,
+    pub rate_per_second: i128,
+    /// Seconds from `start_time` until the cliff (0 = no cliff).
+    pub cliff_offset: u64,
+    /// Total stream length in seconds; the deposit is `rate_per_second × duration`.
+    pub duration: u64,
+    pub options: StreamOptions,
+}
+
+/// A clawback queued against a stream, executable once `eta` is reached.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingClawback {
+    /// Hash of the off-chain justification (e.g. the compliance order).
+    pub reason_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+/// Bounds on publishing the rate index (e.g. a CPI series), set by the admin.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexParams {
+    /// Who besides the admin may publish index values, such as an oracle.
+    pub oracle: Option<Address>,
+    /// Largest change one update may make, in basis points of the previous
+    /// value.
+    pub max_step_bps: u32,
+    /// Minimum seconds between updates.
+    pub min_interval: u64,
+}
+
+/// The latest published index value, scaled by `INDEX_SCALE`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexValue {
+    pub value: u128,
+    pub updated_at: u64,
+}
+
+/// How an index-linked stream's payouts follow the index.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexLink {
+    /// Index value at creation. Each withdrawal pays the nominal amount
+    /// scaled by the prevailing index over this base.
+    pub base_index: u128,
+    /// Most a withdrawal may be escalated by, in basis points of its
+    /// nominal amount.
+    pub cap_bps: u32,
+    /// Escalation funds deposited at creation and not yet paid out or
+    /// refunded.
+    pub reserve: i128,
+}
+
+/// Why a stream is `Distressed`, recorded when a payout found its token
+/// unusable.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Distress {
+    /// Status the stream returns to on recovery.
+    pub previous: StreamStatus,
+    /// Ledger timestamp at which the stream became distressed.
+    pub since: u64,
+}
+
+/// Which build of the contract is deployed, as returned by `meta`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractMeta {
+    pub name: String,
+    /// Semantic version of the `fluxora_stream` crate.
+    pub version: String,
+    pub repository: String,
+    pub audit: String,
+}
+
+/// A stream's stored fields alongside the values clients would otherwise
+/// derive themselves, all as of the ledger the view was read in. Times and
+/// durations are on the stream's `clock`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamView {
     pub stream_id: u64,
     pub sender: Address,
     pub recipient: Address,
+    pub token: Address,
     pub deposit_amount: i128,
     pub rate_per_second: i128,
     pub start_time: u64,
     pub cliff_time: u64,
     pub end_time: u64,
     pub withdrawn_amount: i128,
+    /// Status as stored.
     pub status: StreamStatus,
+    /// Status as `get_stream_state` reports it.
+    pub effective_status: StreamStatus,
+    pub clock: Clock,
+    pub admin_cancelable: bool,
+    pub credit_refunds: bool,
+    /// What `calculate_accrued` returns.
+    pub accrued: i128,
+    /// What `withdraw` would pay out now, or zero while the stream's status,
+    /// a dispute, a recipient freeze or an emergency pause blocks it.
+    pub withdrawable_now: i128,
+    /// Share of the deposit accrued, in whole percent rounded down.
+    pub streamed_pct: u32,
+    /// Ticks until `end_time`, or zero once it has passed.
+    pub seconds_remaining: u64,
+}
+
+/// The part of a `Stream` fixed at creation or changed only by rare actions
+/// (cancellation, recipient transfer). Stored apart from `StreamProgress` so
+/// withdrawals and status changes never rewrite it. Older layouts are
+/// upgraded on read; see `migration`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+struct StreamTerms {
+    version: u32,
+    sender: Address,
+    recipient: Address,
+    token: Address,
+    deposit_amount: i128,
+    rate_per_second: i128,
+    start_time: u64,
+    cliff_time: u64,
+    end_time: u64,
+    admin_cancelable: bool,
+    credit_refunds: bool,
+    basis: AccrualBasis,
+}
+
+/// The terms of a stream that shape its accrual but have no field on
+/// `Stream`.
+#[contracttype(export = false)]
+#[derive(Clone, Copy, Debug)]
+struct AccrualBasis {
+    clock: Clock,
+    /// Full-precision rate in `1/RATE_SCALE` base units per tick, or zero
+    /// when `rate_per_second` is exact.
+    scaled_rate: u128,
+}
+
+impl Default for AccrualBasis {
+    fn default() -> Self {
+        AccrualBasis {
+            clock: Clock::Timestamp,
+            scaled_rate: 0,
+        }
+    }
+}
+
+/// The hot part of a `Stream`, rewritten on every withdrawal.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+struct StreamProgress {
+    version: u32,
+    withdrawn_amount: i128,
+    status: StreamStatus,
+    /// Ledger time the stream was last saved with nothing left to pay out,
+    /// or zero while funds are still owed. Starts the `close_stream`
+    /// retention delay.
+    settled_at: u64,
 }
 
 /// Namespace for all contract storage keys.
 #[contracttype]
 pub enum DataKey {
-    Config,       // Instance storage for global settings (admin/token).
-    NextStreamId, // Instance storage for the auto-incrementing ID counter.
-    Stream(u64),  // Persistent storage for individual stream data (O(1) lookup).
+    Config,                             // Instance storage for global settings (admin/token).
+    NextStreamId,                       // Persistent storage for the auto-incrementing ID counter.
+    Stream(u64), // Persistent storage: stream terms (parties, schedule, deposit).
+    AllowedToken(Address), // Persistent storage: tokens vetted for streaming.
+    BlockedToken(Address), // Persistent storage: tokens frozen for new activity.
+    Sep41Token(Address), // Persistent storage: tokens that passed the SEP-41 probe.
+    Limits,      // Instance storage for creation floors/caps.
+    ActiveCount(Address), // Persistent storage: open (Active/Paused) streams per sender.
+    FeeParams,   // Instance storage for the active fee parameters.
+    PendingFeeParams, // Instance storage for timelocked fee parameters.
+    Clawback(u64), // Persistent storage: clawbacks queued per stream.
+    Distress(u64), // Persistent storage: why and since when a stream is Distressed.
+    NextBasketId, // Persistent storage: id the next basket stream receives.
+    Basket(u64), // Persistent storage: component streams per basket.
+    BasketOf(u64), // Persistent storage: basket each component stream belongs to.
+    IndexParams, // Instance storage: bounds on index updates.
+    Index,       // Instance storage: latest published index value.
+    IndexLink(u64), // Persistent storage: index linkage per index-linked stream.
+    GlobalPause, // Instance storage: emergency pause flag.
+    PendingGuardian, // Instance storage: guardian nominated via two-step transfer.
+    Custody(Address), // Persistent storage: custody buckets (principal, fees) per token.
+    Frozen(Address), // Persistent storage: compliance hold on a recipient.
+    FrozenList,  // Persistent storage: enumeration of frozen recipients.
+    AttestationRegistry, // Instance storage: KYC registry gating withdrawals.
+    TransferPolicy, // Instance storage: policy hook for creation/withdrawal.
+    NextTemplateId, // Instance storage for the auto-incrementing template ID.
+    Template(u64), // Persistent storage: reusable stream shapes.
+    GuardianPauseWindow, // Instance storage: lifetime of unconfirmed guardian holds.
+    GuardianHold(u64), // Persistent storage: guardian pause on a single stream.
+    Sunset,      // Instance storage: permanent stop on new streams.
+    SwapRouter,  // Instance storage: AMM route for swap-on-withdraw.
+    ReputationRegistry, // Instance storage: sender reputation registry told of stream outcomes.
+    Vault(Address), // Persistent storage: auto-deposit destination per recipient.
+    AutoWithdraw(Address), // Persistent storage: recipients allowing anyone to trigger withdraw.
+    Credit(Address, Address), // Persistent storage: claimable cancellation credit per (account, token).
+    Operator(u64), // Persistent storage: withdrawal allowance pledged to an operator, per stream.
+    Creator(Address, Address, Address), // Persistent storage: creation allowance per (sender, operator, token).
+    Arbiter(u64),  // Persistent storage: dispute arbiter chosen at creation, per stream.
+    Dispute(u64),  // Persistent storage: open dispute per stream.
+    ReferralShare, // Instance storage: share of protocol fees paid to referrers, in bps.
+    Referral(Address, Address), // Persistent storage: attribution per (referrer, token).
+    Split(u64),    // Persistent storage: recipient's withdrawal split, per stream.
+    Withholding(u64), // Persistent storage: sender-fixed withholding and total withheld, per stream.
+    Withheld(Address, Address), // Persistent storage: total withheld per (destination, token).
+    RoundUp(Address), // Persistent storage: charity round-up instruction per recipient.
+    Donated(Address, Address), // Persistent storage: total rounded off per (recipient, token).
+    StreamProgress(u64), // Persistent storage: withdrawn amount and status, per stream.
+    Tombstone(u64),   // Persistent storage: final state of a stream removed by close_stream.
+}
+
+// ---------------------------------------------------------------------------
+// Storage helpers
+// ---------------------------------------------------------------------------
+
+fn get_config(env: &Env) -> Config {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .expect("contract not initialised: missing config")
+}
+
+/// Bump a persistent entry using the configured TTL parameters.
+fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+    let config = get_config(env);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, config.ttl_threshold, config.ttl_extend_to);
+}
+
+/// Bump the contract instance (config, counters) using the configured TTL parameters.
+fn extend_instance_ttl(env: &Env) {
+    let config = get_config(env);
+    env.storage()
+        .instance()
+        .extend_ttl(config.ttl_threshold, config.ttl_extend_to);
+}
+
+/// Every persistent key that can hold state for a single stream. Only the
+/// first two are always present; TTL bumps skip the rest when absent.
+fn stream_keys(stream_id: u64) -> [DataKey; 12] {
+    [
+        DataKey::Stream(stream_id),
+        DataKey::StreamProgress(stream_id),
+        DataKey::Clawback(stream_id),
+        DataKey::GuardianHold(stream_id),
+        DataKey::Operator(stream_id),
+        DataKey::Arbiter(stream_id),
+        DataKey::Dispute(stream_id),
+        DataKey::Split(stream_id),
+        DataKey::Withholding(stream_id),
+        DataKey::IndexLink(stream_id),
+        DataKey::Distress(stream_id),
+        DataKey::BasketOf(stream_id),
+    ]
+}
+
+fn get_token(env: &Env) -> Address {
+    get_config(env).token
+}
+
+/// Deployments initialised before the admin moved to `fluxora_access` hold
+/// it only in `Config` until their first admin transfer.
+fn get_admin(env: &Env) -> Address {
+    fluxora_access::try_admin(env).unwrap_or_else(|| get_config(env).admin)
+}
+
+fn get_guardian(env: &Env) -> Address {
+    get_config(env).guardian
+}
+
+/// Require auth from whoever currently controls protocol parameters:
+/// the governor contract if one is configured, otherwise the admin.
+fn require_params_authority(env: &Env) {
+    let config = get_config(env);
+    match config.governor {
+        Some(governor) => governor.require_auth(),
+        None => get_admin(env).require_auth(),
+    }
+}
+
+fn is_globally_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::GlobalPause)
+        .unwrap_or(false)
+}
+
+fn assert_not_globally_paused(env: &Env) {
+    assert!(!is_globally_paused(env), "protocol is paused");
+}
+
+fn is_sunset(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Sunset)
+        .unwrap_or(false)
+}
+
+fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AllowedToken(token.clone()))
+        .unwrap_or(false)
+}
+
+fn set_token_allowed(env: &Env, token: &Address, allowed: bool) {
+    let key = DataKey::AllowedToken(token.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+        extend_persistent_ttl(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+fn is_token_blocked(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BlockedToken(token.clone()))
+        .unwrap_or(false)
+}
+
+fn set_token_blocked(env: &Env, token: &Address, blocked: bool) {
+    let key = DataKey::BlockedToken(token.clone());
+    if blocked {
+        env.storage().persistent().set(&key, &true);
+        extend_persistent_ttl(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Address of the native XLM asset contract on this network, whether or not
+/// it has been deployed yet.
+fn native_token(env: &Env) -> Address {
+    env.deployer()
+        .with_stellar_asset(Bytes::from_array(env, &NATIVE_ASSET_XDR))
+        .deployed_address()
+}
+
+/// Gate for every path that moves new funds into the contract.
+/// Withdrawals and refunds deliberately bypass this check.
+fn assert_token_usable(env: &Env, token: &Address) {
+    assert!(is_token_allowed(env, token), "token is not allowed");
+    assert!(!is_token_blocked(env, token), "token is blocked");
+}
+
+/// Panic with `NonConformingToken` unless `token` answers the SEP-41 calls
+/// streaming relies on: `decimals`, `balance` and a zero-amount `transfer`
+/// from this contract to itself. Try-calls keep a missing or failing
+/// function from surfacing as an opaque host error deep inside a deposit.
+/// A token is probed once and remembered.
+fn assert_sep41(env: &Env, token: &Address) {
+    let key = DataKey::Sep41Token(token.clone());
+    if env.storage().persistent().has(&key) {
+        extend_persistent_ttl(env, &key);
+        return;
+    }
+    let this = env.current_contract_address();
+    let decimals =
+        env.try_invoke_contract::<u32, Error>(token, &symbol_short!("decimals"), Vec::new(env));
+    let balance = env.try_invoke_contract::<i128, Error>(
+        token,
+        &symbol_short!("balance"),
+        vec![env, this.to_val()],
+    );
+    let transfer = env.try_invoke_contract::<(), Error>(
+        token,
+        &symbol_short!("transfer"),
+        vec![env, this.to_val(), this.to_val(), 0_i128.into_val(env)],
+    );
+    let conforming = matches!(decimals, Ok(Ok(_)))
+        && matches!(balance, Ok(Ok(_)))
+        && matches!(transfer, Ok(Ok(())));
+    if !conforming {
+        panic_with_error!(env, Error::NonConformingToken);
+    }
+    env.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Whether `token` can no longer back payouts from this contract to `to`:
+/// the contract's balance was clawed back below what it owes, or the
+/// contract's or `to`'s balance is frozen so a zero-amount transfer fails.
+fn asset_distressed(env: &Env, token: &Address, to: &Address) -> bool {
+    let this = env.current_contract_address();
+    let client = token::Client::new(env, token);
+    let owed = vault::custody(env, token).principal;
+    let covered = matches!(client.try_balance(&this), Ok(Ok(balance)) if balance >= owed);
+    let transferable = matches!(client.try_transfer(&this, to, &0), Ok(Ok(())));
+    !(covered && transferable)
+}
+
+/// Move `stream` into `Distressed` instead of trapping when its token can no
+/// longer reach `to`, remembering the status to restore. Returns whether the
+/// stream is now distressed.
+fn enter_distress(env: &Env, stream: &mut Stream, to: &Address) -> bool {
+    if !asset_distressed(env, &stream.token, to) {
+        return false;
+    }
+    let distress = Distress {
+        previous: stream.status,
+        since: env.ledger().timestamp(),
+    };
+    let key = DataKey::Distress(stream.stream_id);
+    env.storage().persistent().set(&key, &distress);
+    extend_persistent_ttl(env, &key);
+    stream.status = StreamStatus::Distressed;
+    save_stream_progress(env, stream);
+    env.events().publish(
+        (symbol_short!("distress"), stream.stream_id),
+        distress.previous,
+    );
+    true
+}
+
+fn get_limits(env: &Env) -> CreationLimits {
+    env.storage()
+        .instance()
+        .get(&DataKey::Limits)
+        .unwrap_or_default()
+}
+
+fn set_limits(env: &Env, limits: &CreationLimits) {
+    env.storage().instance().set(&DataKey::Limits, limits);
+}
+
+/// Always zero without the `indexes` feature, where no counts are kept.
+fn get_active_count(env: &Env, sender: &Address) -> u32 {
+    if !cfg!(feature = "indexes") {
+        return 0;
+    }
+    env.storage()
+        .persistent()
+        .get(&DataKey::ActiveCount(sender.clone()))
+        .unwrap_or(0)
+}
+
+fn set_active_count(env: &Env, sender: &Address, count: u32) {
+    if !cfg!(feature = "indexes") {
+        return;
+    }
+    let key = DataKey::ActiveCount(sender.clone());
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &count);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Called whenever a stream leaves the open (Active/Paused) states.
+fn release_active_slot(env: &Env, sender: &Address) {
+    let count = get_active_count(env, sender);
+    set_active_count(env, sender, count.saturating_sub(1));
+}
+
+/// Without the `fees` feature nothing can set fee parameters, so this is
+/// always the zero-fee default.
+fn get_fee_params(env: &Env) -> FeeParams {
+    let stored = if cfg!(feature = "fees") {
+        env.storage().instance().get(&DataKey::FeeParams)
+    } else {
+        None
+    };
+    stored.unwrap_or_else(|| FeeParams {
+        protocol_fee_bps: 0,
+        fee_collector: get_admin(env),
+        max_broker_fee_bps: 0,
+    })
+}
+
+#[cfg(feature = "fees")]
+fn validate_fee_params(params: &FeeParams) {
+    assert!(
+        params.protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+        "protocol fee exceeds hard maximum"
+    );
+    assert!(
+        params.max_broker_fee_bps <= MAX_BROKER_FEE_BPS,
+        "broker fee cap exceeds hard maximum"
+    );
+}
+
+/// Fee owed on a deposit of `amount`, rounded down.
+fn protocol_fee_for(env: &Env, params: &FeeParams, amount: i128) -> i128 {
+    math::bps(env, amount, params.protocol_fee_bps)
+}
+
+fn is_recipient_frozen(env: &Env, recipient: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Frozen(recipient.clone()))
+}
+
+fn get_frozen_list(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FrozenList)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn set_frozen_list(env: &Env, list: &Vec<Address>) {
+    env.storage().persistent().set(&DataKey::FrozenList, list);
+    extend_persistent_ttl(env, &DataKey::FrozenList);
+}
+
+/// Address of the hook contract stored under `key`. Without the `hooks`
+/// feature none can be registered, so every hook call site compiles away.
+fn get_hook(env: &Env, key: &DataKey) -> Option<Address> {
+    if !cfg!(feature = "hooks") {
+        return None;
+    }
+    env.storage().instance().get(key)
+}
+
+fn get_attestation_registry(env: &Env) -> Option<Address> {
+    get_hook(env, &DataKey::AttestationRegistry)
+}
+
+/// When an attestation registry is configured, require `account` to be verified.
+fn assert_attested(env: &Env, account: &Address) {
+    if let Some(registry) = get_attestation_registry(env) {
+        let verified = AttestationRegistryClient::new(env, &registry).is_verified(account);
+        assert!(verified, "recipient is not verified");
+    }
+}
+
+fn get_swap_router(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::SwapRouter)
+}
+
+fn get_recipient_vault(env: &Env, recipient: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Vault(recipient.clone()))
+}
+
+fn get_credit(env: &Env, account: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Credit(account.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+fn add_credit(env: &Env, account: &Address, token: &Address, amount: i128) {
+    let key = DataKey::Credit(account.clone(), token.clone());
+    env.storage().persistent().set(
+        &key,
+        &math::add(env, get_credit(env, account, token), amount),
+    );
+    extend_persistent_ttl(env, &key);
+}
+
+fn is_auto_withdraw(env: &Env, recipient: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AutoWithdraw(recipient.clone()))
+}
+
+fn get_operator(env: &Env, stream_id: u64) -> Option<OperatorAllowance> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Operator(stream_id))
+}
+
+fn get_withholding(env: &Env, stream_id: u64) -> Option<Withholding> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Withholding(stream_id))
+}
+
+fn get_withheld_total(env: &Env, destination: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Withheld(destination.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+fn get_withdraw_split(env: &Env, stream_id: u64) -> Option<WithdrawSplit> {
+    env.storage().persistent().get(&DataKey::Split(stream_id))
+}
+
+fn get_creator_allowance(
+    env: &Env,
+    sender: &Address,
+    operator: &Address,
+    token: &Address,
+) -> Option<CreatorAllowance> {
+    env.storage().persistent().get(&DataKey::Creator(
+        sender.clone(),
+        operator.clone(),
+        token.clone(),
+    ))
+}
+
+fn get_referral_share(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReferralShare)
+        .unwrap_or(0)
+}
+
+fn get_referral(env: &Env, referrer: &Address, token: &Address) -> ReferralAccount {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Referral(referrer.clone(), token.clone()))
+        .unwrap_or_default()
+}
+
+fn save_referral(env: &Env, referrer: &Address, token: &Address, account: &ReferralAccount) {
+    let key = DataKey::Referral(referrer.clone(), token.clone());
+    env.storage().persistent().set(&key, account);
+    extend_persistent_ttl(env, &key);
+}
+
+fn get_arbiter(env: &Env, stream_id: u64) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Arbiter(stream_id))
+}
+
+fn get_dispute(env: &Env, stream_id: u64) -> Option<Dispute> {
+    env.storage().persistent().get(&DataKey::Dispute(stream_id))
+}
+
+fn assert_not_disputed(env: &Env, stream_id: u64) {
+    assert!(
+        get_dispute(env, stream_id).is_none(),
+        "stream is under dispute"
+    );
+}
+
+/// Who authorises a stream's creation, and so how its deposit is pulled.
+enum Creator {
+    /// The sender signs; the deposit is transferred from them directly.
+    Sender,
+    /// As `Sender`, but the sender's signature was already checked by the
+    /// enclosing call (e.g. a basket creating several streams).
+    #[cfg_attr(not(feature = "baskets"), allow(dead_code))]
+    Signed,
+    /// An approved operator signs; the deposit is pulled from the sender
+    /// with `transfer_from`, against their token allowance to this contract.
+    Operator(Address),
+    /// The sender signed a batch whose deposits and fees were already
+    /// transferred in aggregate.
+    Prefunded,
+}
+
+/// Where a withdrawal is delivered.
+enum Payout {
+    /// Transfer the stream token to the recipient, or into the recipient's
+    /// vault when one is configured.
+    Recipient,
+    /// Transfer the stream token to this address.
+    To(Address),
+    /// Swap through the configured router into `token_out`, delivering at
+    /// least `min_out` to the recipient.
+    Swap { token_out: Address, min_out: i128 },
+    /// Pay `fee` to the relayer that submitted the transaction and the rest
+    /// to the recipient.
+    Relayed { relayer: Address, fee: i128 },
+}
+
+impl Payout {
+    /// The address the stream token itself is delivered to, which is where a
+    /// frozen balance would stop the payout.
+    fn destination(&self, env: &Env, recipient: &Address) -> Address {
+        match self {
+            Payout::Recipient => {
+                get_recipient_vault(env, recipient).unwrap_or_else(|| recipient.clone())
+            }
+            Payout::To(to) => to.clone(),
+            Payout::Swap { .. } => get_swap_router(env).expect("swap router not configured"),
+            Payout::Relayed { .. } => recipient.clone(),
+        }
+    }
+}
+
+/// Pre-fund the swap router with `amount_in` of `token_in` and have it
+/// deliver `token_out` to `to`. The minimum is enforced on the observed
+/// balance change, not on the router's word. Returns the amount received.
+fn swap_to(
+    env: &Env,
+    token_in: &Address,
+    amount_in: i128,
+    token_out: &Address,
+    min_out: i128,
+    to: &Address,
+) -> i128 {
+    let router = get_swap_router(env).expect("swap router not configured");
+    assert!(
+        token_out != token_in,
+        "token_out must differ from the stream token"
+    );
+
+    let out_client = token::Client::new(env, token_out);
+    let before = out_client.balance(to);
+    vault::send(env, token_in, &router, amount_in);
+    SwapRouterClient::new(env, &router)
+        .swap_exact_in(token_in, token_out, &amount_in, &min_out, to);
+    let received = out_client.balance(to) - before;
+    assert!(received >= min_out, "swap output below minimum");
+    received
+}
+
+fn get_transfer_policy(env: &Env) -> Option<Address> {
+    get_hook(env, &DataKey::TransferPolicy)
+}
+
+/// When a transfer policy is configured, require it to approve the movement.
+fn assert_transfer_permitted(
+    env: &Env,
+    sender: &Address,
+    recipient: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    if let Some(policy) = get_transfer_policy(env) {
+        let permitted =
+            TransferPolicyClient::new(env, &policy).can_transfer(sender, recipient, token, &amount);
+        assert!(permitted, "transfer rejected by policy");
+    }
+}
+
+/// Check a payroll entry against everything `create_stream_internal` would
+/// assert, so a batch can be rejected without panicking.
+fn validate_payroll_entry(
+    env: &Env,
+    sender: &Address,
+    token: &Address,
+    limits: &CreationLimits,
+    entry: &PayrollEntry,
+) -> Option<PayrollEntryError> {
+    let PayrollSchedule {
+        start_time,
+        cliff_time,
+        end_time,
+    } = entry.schedule;
+    if entry.amount <= 0 || entry.amount < limits.min_deposit || entry.amount > MAX_DEPOSIT {
+        return Some(PayrollEntryError::InvalidAmount);
+    }
+    if entry.recipient == *sender {
+        return Some(PayrollEntryError::SelfPayment);
+    }
+    let now = Timestamp(env.ledger().timestamp());
+    let end = Timestamp(end_time);
+    let horizon_ok = end <= now.saturating_add(MAX_HORIZON)
+        && (limits.max_horizon == 0 || end <= now.saturating_add(limits.max_horizon));
+    if start_time >= end_time
+        || cliff_time < start_time
+        || cliff_time > end_time
+        || end_time - start_time < limits.min_duration
+        || !horizon_ok
+    {
+        return Some(PayrollEntryError::InvalidSchedule);
+    }
+    let duration = (end_time - start_time) as i128;
+    if entry.amount % duration != 0 {
+        return Some(PayrollEntryError::UnevenRate);
+    }
+    if let Some(policy) = get_transfer_policy(env) {
+        let permitted = TransferPolicyClient::new(env, &policy).can_transfer(
+            sender,
+            &entry.recipient,
+            token,
+            &entry.amount,
+        );
+        if !permitted {
+            return Some(PayrollEntryError::PolicyRejected);
+        }
+    }
+    None
+}
+
+fn get_reputation_registry(env: &Env) -> Option<Address> {
+    get_hook(env, &DataKey::ReputationRegistry)
+}
+
+fn get_guardian_pause_window(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::GuardianPauseWindow)
+        .unwrap_or(DEFAULT_GUARDIAN_PAUSE_SECS)
+}
+
+fn get_guardian_hold(env: &Env, stream_id: u64) -> Option<GuardianHold> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GuardianHold(stream_id))
+}
+
+/// Pause `stream` under a fresh, unconfirmed guardian hold.
+fn place_guardian_hold(env: &Env, stream: &mut Stream) {
+    stream.status = StreamStatus::Paused;
+    save_stream_progress(env, stream);
+
+    let hold = GuardianHold {
+        expires_at: env
+            .ledger()
+            .timestamp()
+            .saturating_add(get_guardian_pause_window(env)),
+        confirmed: false,
+    };
+    let key = DataKey::GuardianHold(stream.stream_id);
+    env.storage().persistent().set(&key, &hold);
+    extend_persistent_ttl(env, &key);
+
+    env.events().publish(
+        (symbol_short!("grd_pause"), stream.stream_id),
+        hold.expires_at,
+    );
+}
+
+/// Drop an unconfirmed guardian hold whose window has lapsed. Returns whether
+/// a hold was lifted.
+fn lift_expired_guardian_hold(env: &Env, stream_id: u64) -> bool {
+    match get_guardian_hold(env, stream_id) {
+        Some(hold) if !hold.confirmed && env.ledger().timestamp() >= hold.expires_at => {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::GuardianHold(stream_id));
+            env.events()
+                .publish((symbol_short!("hold_exp"), stream_id), ());
+            true
+        }
+        _ => false,
+    }
+}
+
+fn load_template(env: &Env, template_id: u64) -> StreamTemplate {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Template(template_id))
+        .expect("template not found")
+}
+
+/// The ID counter lives in its own persistent entry, so creating a stream
+/// doesn't rewrite the instance entry that every call reads. Deployments
+/// initialised before the move still hold it in instance storage until
+/// their first creation, which moves it across.
+fn get_stream_count(env: &Env) -> u64 {
+    let key = DataKey::NextStreamId;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .or_else(|| env.storage().instance().get(&key))
+        .unwrap_or(0u64)
+}
+
+fn set_stream_count(env: &Env, count: u64) {
+    let key = DataKey::NextStreamId;
+    env.storage().persistent().set(&key, &count);
+    extend_persistent_ttl(env, &key);
+    if env.storage().instance().has(&key) {
+        env.storage().instance().remove(&key);
+    }
+}
+
+fn get_index(env: &Env) -> Option<IndexValue> {
+    env.storage().instance().get(&DataKey::Index)
+}
+
+fn get_index_link(env: &Env, stream_id: u64) -> Option<IndexLink> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLink(stream_id))
+}
+
+fn save_index_link(env: &Env, stream_id: u64, link: &IndexLink) {
+    let key = DataKey::IndexLink(stream_id);
+    env.storage().persistent().set(&key, link);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Escalation owed on top of a `nominal` payout from an index-linked stream
+/// at the prevailing index: `nominal × (index − base) / base`, capped at the
+/// stream's `cap_bps` and by its remaining reserve. A falling index never
+/// cuts the nominal rate. The amount is taken out of the reserve, and what
+/// is left is refunded to the sender once `paid_out` says nothing more is
+/// owed. Returns 0 for streams that are not index-linked.
+fn take_escalation(env: &Env, stream: &Stream, nominal: i128, paid_out: bool) -> i128 {
+    let Some(mut link) = get_index_link(env, stream.stream_id) else {
+        return 0;
+    };
+    let index = get_index(env).map_or(link.base_index, |index| index.value);
+    let growth = index.saturating_sub(link.base_index);
+    let escalation = (math::mul(env, nominal, growth as i128) / link.base_index as i128)
+        .min(math::bps(env, nominal, link.cap_bps))
+        .min(link.reserve);
+    link.reserve -= escalation;
+    if paid_out {
+        release_index_reserve(env, stream, &mut link);
+    } else {
+        save_index_link(env, stream.stream_id, &link);
+    }
+    escalation
+}
+
+/// Refund `link`'s remaining reserve to the stream's sender and unlink it.
+fn release_index_reserve(env: &Env, stream: &Stream, link: &mut IndexLink) {
+    if link.reserve > 0 {
+        vault::pay(env, &stream.token, &stream.sender, link.reserve);
+        env.events()
+            .publish((symbol_short!("idx_rfnd"), stream.stream_id), link.reserve);
+    }
+    link.reserve = 0;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::IndexLink(stream.stream_id));
+}
+
+/// On cancellation, keep only the reserve the recipient's unwithdrawn
+/// balance of `owed` could still be escalated by and refund the rest.
+fn settle_index_reserve(env: &Env, stream: &Stream, owed: i128) {
+    let Some(mut link) = get_index_link(env, stream.stream_id) else {
+        return;
+    };
+    let kept = math::bps(env, owed, link.cap_bps).min(link.reserve);
+    if kept == 0 {
+        release_index_reserve(env, stream, &mut link);
+        return;
+    }
+    let refund = link.reserve - kept;
+    if refund == 0 {
+        return;
+    }
+    vault::pay(env, &stream.token, &stream.sender, refund);
+    link.reserve = kept;
+    save_index_link(env, stream.stream_id, &link);
+    env.events()
+        .publish((symbol_short!("idx_rfnd"), stream.stream_id), refund);
+}
+
+fn get_basket_of(env: &Env, stream_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BasketOf(stream_id))
+}
+
+/// Keep a basket's components from diverging: they cannot be paused,
+/// cancelled or reassigned one at a time.
+fn assert_not_in_basket(env: &Env, stream_id: u64) {
+    assert!(
+        get_basket_of(env, stream_id).is_none(),
+        "stream is part of a basket"
+    );
+}
+
+/// Every component of the basket `stream_id` belongs to, or just `stream_id`
+/// if it is not part of one.
+fn basket_streams(env: &Env, stream_id: u64) -> Vec<u64> {
+    match get_basket_of(env, stream_id) {
+        Some(basket_id) => {
+            let basket: Basket = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Basket(basket_id))
+                .expect("basket not found");
+            basket.streams
+        }
+        None => vec![env, stream_id],
+    }
+}
+
+/// Drop a closed component from its basket, and the basket itself once its
+/// last component is gone, so basket calls never meet a deleted stream.
+fn leave_basket(env: &Env, stream_id: u64) {
+    let Some(basket_id) = get_basket_of(env, stream_id) else {
+        return;
+    };
+    let key = DataKey::Basket(basket_id);
+    let storage = env.storage().persistent();
+    let mut basket: Basket = storage.get(&key).expect("basket not found");
+    if let Some(i) = basket.streams.first_index_of(stream_id) {
+        basket.streams.remove(i);
+    }
+    if basket.streams.is_empty() {
+        storage.remove(&key);
+    } else {
+        storage.set(&key, &basket);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Reassemble a stream from its terms and progress records, together with
+/// how it accrues, or `None` if it was never created.
+fn try_load_stream(env: &Env, stream_id: u64) -> Option<(Stream, AccrualBasis)> {
+    let terms = migration::load_terms(env, stream_id)?;
+    let progress = migration::load_progress(env, stream_id).expect("stream progress missing");
+    let stream = Stream {
+        stream_id,
+        sender: terms.sender,
+        recipient: terms.recipient,
+        token: terms.token,
+        deposit_amount: terms.deposit_amount,
+        rate_per_second: terms.rate_per_second,
+        start_time: terms.start_time,
+        cliff_time: terms.cliff_time,
+        end_time: terms.end_time,
+        withdrawn_amount: progress.withdrawn_amount,
+        status: progress.status,
+        admin_cancelable: terms.admin_cancelable,
+        credit_refunds: terms.credit_refunds,
+    };
+    Some((stream, terms.basis))
+}
+
+fn load_stream(env: &Env, stream_id: u64) -> Stream {
+    load_stream_with_basis(env, stream_id).0
+}
+
+/// `load_stream` for callers that accrue, sparing a second read of the terms.
+fn load_stream_with_basis(env: &Env, stream_id: u64) -> (Stream, AccrualBasis) {
+    try_load_stream(env, stream_id).expect("stream not found")
+}
+
+/// How `stream_id` accrues: its clock and full-precision rate.
+fn stream_basis(env: &Env, stream_id: u64) -> AccrualBasis {
+    migration::load_terms(env, stream_id)
+        .expect("stream not found")
+        .basis
+}
+
+/// The current position on `clock`.
+fn clock_now(env: &Env, clock: Clock) -> u64 {
+    match clock {
+        Clock::Timestamp => env.ledger().timestamp(),
+        Clock::LedgerSequence => env.ledger().sequence() as u64,
+    }
+}
+
+/// Amount of `stream` accrued to the recipient right now, on its own clock.
+fn accrued_now(env: &Env, stream: &Stream, basis: &AccrualBasis) -> i128 {
+    accrued(env, stream, basis, clock_now(env, basis.clock))
+}
+
+/// Write both records of `stream`. Only needed when a term changed; paths
+/// that touch just the withdrawn amount or status use
+/// `save_stream_progress`.
+fn save_stream(env: &Env, stream: &Stream, basis: AccrualBasis) {
+    let terms = StreamTerms {
+        version: migration::STREAM_RECORD_VERSION,
+        sender: stream.sender.clone(),
+        recipient: stream.recipient.clone(),
+        token: stream.token.clone(),
+        deposit_amount: stream.deposit_amount,
+        rate_per_second: stream.rate_per_second,
+        start_time: stream.start_time,
+        cliff_time: stream.cliff_time,
+        end_time: stream.end_time,
+        admin_cancelable: stream.admin_cancelable,
+        credit_refunds: stream.credit_refunds,
+        basis,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Stream(stream.stream_id), &terms);
+    save_stream_progress(env, stream);
+}
+
+/// Write only the withdrawn amount and status of `stream`, leaving its
+/// terms record untouched apart from a TTL bump.
+fn save_stream_progress(env: &Env, stream: &Stream) {
+    let terms_key = DataKey::Stream(stream.stream_id);
+    let progress_key = DataKey::StreamProgress(stream.stream_id);
+    let progress = StreamProgress {
+        version: migration::STREAM_RECORD_VERSION,
+        withdrawn_amount: stream.withdrawn_amount,
+        status: stream.status,
+        settled_at: if is_paid_out(stream) {
+            env.ledger().timestamp()
+        } else {
+            0
+        },
+    };
+    env.storage().persistent().set(&progress_key, &progress);
+    #[cfg(debug_assertions)]
+    invariants::check_stream(env, stream, &stream_basis(env, stream.stream_id));
+
+    // Requirement from Issue #1: extend TTL on stream save to ensure persistence.
+    // All bumps share one config read.
+    let config = get_config(env);
+    for key in [terms_key, progress_key] {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, config.ttl_threshold, config.ttl_extend_to);
+    }
+    env.storage()
+        .instance()
+        .extend_ttl(config.ttl_threshold, config.ttl_extend_to);
+}
+
+/// Whether `stream` has nothing left to pay out: completed, or with its
+/// whole deposit withdrawn.
+fn is_paid_out(stream: &Stream) -> bool {
+    stream.status == StreamStatus::Completed || stream.withdrawn_amount == stream.deposit_amount
+}
+
+/// Whether `stream` is closed with nothing left to pay out: completed, or
+/// cancelled with the recipient's share fully withdrawn.
+fn is_settled(stream: &Stream) -> bool {
+    matches!(
+        stream.status,
+        StreamStatus::Completed | StreamStatus::Cancelled
+    ) && is_paid_out(stream)
+}
+
+/// Status of `stream` as of now. An active stream past its end with its
+/// whole deposit withdrawn is complete, even while the stored status still
+/// reads active because no transaction has flipped it.
+fn effective_status(env: &Env, stream: &Stream, basis: &AccrualBasis) -> StreamStatus {
+    if stream.status == StreamStatus::Active
+        && clock_now(env, basis.clock) >= stream.end_time
+        && stream.withdrawn_amount == stream.deposit_amount
+    {
+        StreamStatus::Completed
+    } else {
+        stream.status
+    }
+}
+
+/// `stream` with its derived fields, as the view entrypoints return it.
+fn stream_view(env: &Env, stream: Stream, basis: &AccrualBasis) -> StreamView {
+    let now = clock_now(env, basis.clock);
+    let effective_status = effective_status(env, &stream, basis);
+    let accrued = accrued(env, &stream, basis, now);
+
+    // Mirror the checks `withdraw` makes before paying out. An unconfirmed
+    // guardian hold that has expired is lifted by the withdrawal itself.
+    let hold_expired = get_guardian_hold(env, stream.stream_id)
+        .is_some_and(|hold| !hold.confirmed && env.ledger().timestamp() >= hold.expires_at);
+    let open = match effective_status {
+        StreamStatus::Active => true,
+        StreamStatus::Paused => hold_expired,
+        StreamStatus::Cancelled => !stream.credit_refunds,
+        _ => false,
+    };
+    let withdrawable_now = if open
+        && !is_globally_paused(env)
+        && get_dispute(env, stream.stream_id).is_none()
+        && !is_recipient_frozen(env, &stream.recipient)
+    {
+        math::sub(env, accrued, stream.withdrawn_amount).max(0)
+    } else {
+        0
+    };
+
+    StreamView {
+        stream_id: stream.stream_id,
+        sender: stream.sender,
+        recipient: stream.recipient,
+        token: stream.token,
+        deposit_amount: stream.deposit_amount,
+        rate_per_second: stream.rate_per_second,
+        start_time: stream.start_time,
+        cliff_time: stream.cliff_time,
+        end_time: stream.end_time,
+        withdrawn_amount: stream.withdrawn_amount,
+        status: stream.status,
+        effective_status,
+        clock: basis.clock,
+        admin_cancelable: stream.admin_cancelable,
+        credit_refunds: stream.credit_refunds,
+        accrued,
+        withdrawable_now,
+        streamed_pct: streamed_pct(accrued, stream.deposit_amount),
+        seconds_remaining: stream.end_time.saturating_sub(now),
+    }
+}
+
+/// `accrued` as whole percent of `deposit`, rounded down. Accrual never
+/// exceeds the deposit, so a product too large for `i128` can divide first.
+fn streamed_pct(accrued: i128, deposit: i128) -> u32 {
+    let pct = match accrued.checked_mul(100) {
+        Some(scaled) => scaled / deposit,
+        None => accrued / (deposit / 100),
+    };
+    pct as u32
+}
+
+/// Amount of `stream` accrued to the recipient at `now`, computed on the
+/// already-loaded struct so callers holding it avoid a second storage read.
+fn accrued(env: &Env, stream: &Stream, basis: &AccrualBasis, now: u64) -> i128 {
+    let schedule = Schedule {
+        start: stream.start_time,
+        cliff: stream.cliff_time,
+        end: stream.end_time,
+    };
+    let rate = flow_rate(stream.rate_per_second, basis.scaled_rate);
+    math::accrued(env, &schedule, rate, stream.deposit_amount, now)
+}
+
+/// The rate a stream accrues at: its scaled rate when it has one, so
+/// sub-unit remainders carry over between withdrawals instead of being lost.
+fn flow_rate(rate_per_second: i128, scaled_rate: u128) -> FlowRate {
+    if scaled_rate == 0 {
+        FlowRate::Whole(rate_per_second)
+    } else {
+        FlowRate::Scaled(scaled_rate)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraStream;
+
+#[contractimpl]
+impl FluxoraStream {
+    /// Initialise the contract with the streaming token, admin and guardian
+    /// (emergency council) addresses.
+    /// Can only be called once. Sets up global Config and ID counter.
+    pub fn init(env: Env, token: Address, admin: Address, guardian: Address) {
+        if env.storage().instance().has(&DataKey::Config) {
+            panic!("already initialised");
+        }
+        fluxora_access::init_admin(&env, &admin);
+        let config = Config {
+            token,
+            admin,
+            guardian,
+            governor: None,
+            ttl_threshold: DEFAULT_TTL_THRESHOLD,
+            ttl_extend_to: DEFAULT_TTL_EXTEND_TO,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        set_stream_count(&env, 0);
+
+        // The deployment token is vetted by construction.
+        set_token_allowed(&env, &config.token, true);
+
+        // Ensure instance storage (Config/ID) doesn't expire quickly
+        extend_instance_ttl(&env);
+    }
+
+    /// Create a new payment stream in the deployment's default token.
+    ///
+    /// Transfers `deposit_amount` of the stream token from `sender` to this
+    /// contract and stores all stream parameters. Returns the new stream id.
+    /// If a protocol fee is configured, `deposit_amount × protocol_fee_bps / 10_000`
+    /// is additionally transferred from `sender` to the fee collector.
+    /// For tokens that take a fee on transfer, the stream's deposit is the
+    /// amount the contract actually received, not `deposit_amount`.
+    ///
+    /// # Panics
+    /// - If the protocol is under an emergency pause.
+    /// - If the contract has been sunset.
+    /// - If the default token has been removed from the allowlist or is blocked.
+    /// - If `deposit_amount` or `rate_per_second` is not positive.
+    /// - If `sender` and `recipient` are the same address.
+    /// - If `start_time >= end_time`.
+    /// - If `cliff_time` is not in `[start_time, end_time]`.
+    /// - If `deposit_amount < rate_per_second * (end_time - start_time)` (insufficient deposit).
+    /// - With `Error::DepositTooLarge`, `Error::RateTooLarge` or
+    ///   `Error::HorizonTooFar` past `MAX_DEPOSIT`, `MAX_RATE_PER_SECOND` or
+    ///   `MAX_HORIZON`.
+    /// - With `Error::Overflow` if the streamable amount or the fee overflows.
+    /// - If the stream violates the configured `CreationLimits` (floors, horizon,
+    ///   or per-sender open-stream cap).
+    /// - If a transfer policy is configured and rejects the deposit.
+    /// - If token transfer fails (e.g., insufficient balance or allowance).
+    /// - If the amount received after a token's transfer fee no longer
+    ///   covers `rate_per_second * (end_time - start_time)`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64 {
+        let token = get_token(&env);
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            FlowRate::Whole(rate_per_second),
+            start_time,
+            cliff_time,
+            end_time,
+            StreamOptions::default(),
+            Creator::Sender,
+        )
+    }
+
+    /// Create a new payment stream in any allowlisted `token`.
+    ///
+    /// Identical to `create_stream` otherwise.
+    ///
+    /// # Panics
+    /// - If `token` is not on the allowlist or is blocked.
+    /// - Any of the `create_stream` validation failures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_with_token(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64 {
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            FlowRate::Whole(rate_per_second),
+            start_time,
+            cliff_time,
+            end_time,
+            StreamOptions::default(),
+            Creator::Sender,
+        )
+    }
+
+    /// Create a new payment stream in native XLM, resolving its asset contract
+    /// for this network. Amounts and the rate are in stroops
+    /// (`NATIVE_DECIMALS`). The native asset contract must be allowlisted
+    /// like any other token; see `get_native_token`.
+    ///
+    /// Identical to `create_stream_with_token` otherwise.
+    ///
+    /// # Panics
+    /// - Any of the `create_stream_with_token` validation failures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_native_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64 {
+        let token = native_token(&env);
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            FlowRate::Whole(rate_per_second),
+            start_time,
+            cliff_time,
+            end_time,
+            StreamOptions::default(),
+            Creator::Sender,
+        )
+    }
+
+    /// Return the address of the native XLM asset contract on this network,
+    /// e.g. to pass to `allow_token`.
+    pub fn get_native_token(env: Env) -> Address {
+        native_token(&env)
+    }
+
+    /// Create a new payment stream in an allowlisted `token` with explicit
+    /// per-stream `options` (e.g. opting out of admin cancellation). With
+    /// `Clock::LedgerSequence` the schedule, the rate and any configured
+    /// `max_horizon` are read in ledgers rather than seconds.
+    ///
+    /// # Panics
+    /// - Any of the `create_stream_with_token` validation failures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_with_options(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+    ) -> u64 {
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            FlowRate::Whole(rate_per_second),
+            start_time,
+            cliff_time,
+            end_time,
+            options,
+            Creator::Sender,
+        )
+    }
+
+    /// Create a stream flowing `scaled_rate / RATE_SCALE` base units per
+    /// tick, for rates below one base unit per second (e.g. 0.3 stroops/s is
+    /// `300_000_000`). Accrual is rounded down over the whole elapsed span,
+    /// so no fraction is lost between withdrawals. `deposit_amount` must
+    /// cover what accrues by `end_time`.
+    ///
+    /// # Panics
+    /// - If `scaled_rate` is zero.
+    /// - Any of the `create_stream_with_options` validation failures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_scaled(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        scaled_rate: u128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+    ) -> u64 {
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            FlowRate::Scaled(scaled_rate),
+            start_time,
+            cliff_time,
+            end_time,
+            options,
+            Creator::Sender,
+        )
+    }
+
+    /// Allow `operator` (e.g. a payroll or grants contract) to create streams
+    /// on `sender`'s behalf in `token`, committing at most `max_total` in
+    /// deposits and protocol fees until `expiry`. Replaces any earlier
+    /// allowance; a `max_total` of zero revokes it. Deposits are pulled with
+    /// `transfer_from`, so `sender` must also approve this contract on the
+    /// token. Only the sender may call this.
+    ///
+    /// # Panics
+    /// - If `max_total` is negative.
+    pub fn approve_creator(
+        env: Env,
+        sender: Address,
+        operator: Address,
+        token: Address,
+        max_total: i128,
+        expiry: u64,
+    ) {
+        sender.require_auth();
+        assert!(max_total >= 0, "max_total must not be negative");
+
+        let key = DataKey::Creator(sender.clone(), operator.clone(), token.clone());
+        if max_total == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(
+                &key,
+                &CreatorAllowance {
+                    remaining: max_total,
+                    expiry,
+                },
+            );
+            extend_persistent_ttl(&env, &key);
+        }
+
+        env.events().publish(
+            (symbol_short!("creator"), sender, operator),
+            (token, max_total, expiry),
+        );
+    }
+
+    /// Return the creation allowance `sender` granted `operator` in `token`.
+    pub fn get_creator_allowance(
+        env: Env,
+        sender: Address,
+        operator: Address,
+        token: Address,
+    ) -> Option<CreatorAllowance> {
+        get_creator_allowance(&env, &sender, &operator, &token)
+    }
+
+    /// Create a stream from `sender` as an approved `operator`, without the
+    /// sender's signature. The deposit and protocol fee count against the
+    /// operator's creation allowance. Only `operator` may call this.
+    ///
+    /// # Panics
+    /// - If `operator` has no allowance from `sender` in `token`, it has
+    ///   expired, or it cannot cover the deposit and fee.
+    /// - If `sender`'s token allowance to this contract is insufficient.
+    /// - Any of the `create_stream_with_token` validation failures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_as_operator(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> u64 {
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            FlowRate::Whole(rate_per_second),
+            start_time,
+            cliff_time,
+            end_time,
+            StreamOptions::default(),
+            Creator::Operator(operator),
+        )
+    }
+
+    /// Create one stream in the default token per payroll entry, all or
+    /// nothing, with a single aggregated deposit transfer and a single
+    /// protocol fee transfer. Each entry streams its `amount` evenly over its
+    /// schedule. Returns one result per entry, in order: `Created` when the
+    /// whole batch went through, otherwise `Invalid` for each rejected entry
+    /// and `Valid` for the rest, with nothing created or transferred.
+    /// Only the sender may call this.
+    ///
+    /// # Panics
+    /// - If `entries` is empty.
+    /// - If the batch would exceed the per-sender open-stream cap.
+    /// - If the contract is paused or sunset, or the token is unusable.
+    /// - If the aggregated transfer fails (e.g. insufficient balance).
+    /// - If the token takes a fee on transfer, as the shortfall cannot be
+    ///   attributed to individual entries.
+    pub fn import_payroll(
+        env: Env,
+        sender: Address,
+        entries: Vec<PayrollEntry>,
+    ) -> Vec<PayrollResult> {
+        sender.require_auth();
+        assert!(!entries.is_empty(), "no payroll entries");
+        assert_not_globally_paused(&env);
+        assert!(!is_sunset(&env), "contract is sunset");
+        let token = get_token(&env);
+        assert_token_usable(&env, &token);
+
+        let limits = get_limits(&env);
+        if limits.max_active_per_sender > 0 {
+            assert!(
+                get_active_count(&env, &sender) + entries.len() <= limits.max_active_per_sender,
+                "sender has too many active streams"
+            );
+        }
+
+        let mut results = Vec::new(&env);
+        let mut rejected = false;
+        for (i, entry) in entries.iter().enumerate() {
+            let duplicate = entries
+                .iter()
+                .position(|e| e.external_id == entry.external_id)
+                != Some(i);
+            let error = if duplicate {
+                Some(PayrollEntryError::DuplicateExternalId)
+            } else {
+                validate_payroll_entry(&env, &sender, &token, &limits, &entry)
+            };
+            match error {
+                Some(error) => {
+                    rejected = true;
+                    results.push_back(PayrollResult::Invalid(error));
+                }
+                None => results.push_back(PayrollResult::Valid),
+            }
+        }
+        if rejected {
+            return results;
+        }
+
+        let fee_params = get_fee_params(&env);
+        let mut total_deposit: i128 = 0;
+        let mut total_fee: i128 = 0;
+        for entry in entries.iter() {
+            total_deposit = math::add(&env, total_deposit, entry.amount);
+            total_fee = math::add(
+                &env,
+                total_fee,
+                protocol_fee_for(&env, &fee_params, entry.amount),
+            );
+        }
+        let received = vault::deposit(&env, &token, &sender, total_deposit);
+        assert!(
+            received == total_deposit,
+            "batch deposits do not support fee-on-transfer tokens"
+        );
+        vault::charge_fee(
+            &env,
+            &token,
+            &sender,
+            &fee_params.fee_collector,
+            total_fee,
+            false,
+        );
+
+        let mut results = Vec::new(&env);
+        for entry in entries.iter() {
+            let schedule = entry.schedule;
+            let rate = entry.amount / (schedule.end_time - schedule.start_time) as i128;
+            let stream_id = Self::create_stream_internal(
+                &env,
+                sender.clone(),
+                entry.recipient,
+                token.clone(),
+                entry.amount,
+                FlowRate::Whole(rate),
+                schedule.start_time,
+                schedule.cliff_time,
+                schedule.end_time,
+                StreamOptions::default(),
+                Creator::Prefunded,
+            );
+            env.events()
+                .publish((symbol_short!("payroll"), stream_id), entry.external_id);
+            results.push_back(PayrollResult::Created(stream_id));
+        }
+
+        env.events().publish(
+            (symbol_short!("imported"), sender),
+            (entries.len(), total_deposit),
+        );
+        results
+    }
+
+    /// Pause an active stream. Only the sender or admin may call this.
+    /// # Panics
+    /// - If the stream is not in `Active` state.
+    /// - If the stream is part of a basket.
+    pub fn pause_stream(env: Env, stream_id: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        assert_not_in_basket(&env, stream_id);
+
+        // Corrected Auth Check
+        Self::require_sender_or_admin(&env, &stream.sender);
+
+        assert!(
+            stream.status == StreamStatus::Active,
+            "stream is not active"
+        );
+
+        stream.status = StreamStatus::Paused;
+        save_stream_progress(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("paused"), stream_id), ());
+    }
+
+    /// Resume a paused stream. Only the sender or admin may call this.
+    /// # Panics
+    /// - If the stream is not in `Paused` state.
+    /// - If the stream is part of a basket.
+    /// - If the stream is under a live guardian hold (see `release_guardian_hold`).
+    pub fn resume_stream(env: Env, stream_id: u64) {
+        let mut stream = load_stream(&env, stream_id);
+        assert_not_in_basket(&env, stream_id);
+        Self::require_sender_or_admin(&env, &stream.sender);
+
+        assert_not_disputed(&env, stream_id);
+        lift_expired_guardian_hold(&env, stream_id);
+        assert!(
+            get_guardian_hold(&env, stream_id).is_none(),
+            "stream is held by the guardian"
+        );
+
+        assert!(
+            stream.status == StreamStatus::Paused,
+            "stream is not paused"
+        );
+
+        stream.status = StreamStatus::Active;
+        save_stream_progress(&env, &stream);
+
+        env.events()
+            .publish((symbol_short!("resumed"), stream_id), ());
+    }
+
+    /// Add `amount` to an active stream's deposit and push its end back by
+    /// the ticks that amount funds at the stream's rate, so the recipient is
+    /// paid at the same rate for longer. Of what actually arrives, as at
+    /// creation, only whole ticks are added to the deposit and the remainder
+    /// is refunded; no protocol fee is charged. Only the sender may call
+    /// this. Returns the new end time.
+    ///
+    /// # Panics
+    /// - If the protocol is under an emergency pause or the contract is sunset.
+    /// - If the stream is paused, has ended or is otherwise not active.
+    /// - If the stream is part of a basket, index-linked or accrues at a
+    ///   scaled rate.
+    /// - If the recipient is frozen.
+    /// - If the token is blocked or no longer allowed.
+    /// - If `amount` funds less than one tick.
+    /// - If the new deposit or end time exceeds `MAX_DEPOSIT` or `MAX_HORIZON`.
+    pub fn top_up(env: Env, stream_id: u64, amount: i128) -> u64 {
+        let (mut stream, basis) = load_stream_with_basis(&env, stream_id);
+        stream.sender.require_auth();
+
+        assert_not_globally_paused(&env);
+        assert!(!is_sunset(&env), "contract is sunset");
+        assert!(
+            stream.status != StreamStatus::Paused,
+            "cannot top up paused stream"
+        );
+        assert!(
+            stream.status == StreamStatus::Active,
+            "stream is not active"
+        );
+        let now = clock_now(&env, basis.clock);
+        assert!(now < stream.end_time, "stream has ended");
+        assert_not_in_basket(&env, stream_id);
+        assert!(
+            basis.scaled_rate == 0,
+            "scaled-rate streams cannot be topped up"
+        );
+        // Its reserve was sized for the original deposit.
+        assert!(
+            get_index_link(&env, stream_id).is_none(),
+            "index-linked streams cannot be topped up"
+        );
+        assert!(
+            !is_recipient_frozen(&env, &stream.recipient),
+            "recipient is frozen"
+        );
+        assert_token_usable(&env, &stream.token);
+        assert!(amount > 0, "amount must be positive");
+        assert_transfer_permitted(
+            &env,
+            &stream.sender,
+            &stream.recipient,
+            &stream.token,
+            amount,
+        );
+
+        let received = vault::deposit(&env, &stream.token, &stream.sender, amount);
+        let extension = received / stream.rate_per_second;
+        assert!(extension > 0, "top-up funds less than one tick");
+        let added = math::mul(&env, extension, stream.rate_per_second);
+        vault::pay(&env, &stream.token, &stream.sender, received - added);
+        stream.deposit_amount = math::add(&env, stream.deposit_amount, added);
+        if stream.deposit_amount > MAX_DEPOSIT {
+            panic_with_error!(&env, Error::DepositTooLarge);
+        }
+        let extension = u64::try_from(extension).unwrap_or(u64::MAX);
+        stream.end_time = stream.end_time.saturating_add(extension);
+        if Timestamp(stream.end_time) > Timestamp(now).saturating_add(MAX_HORIZON) {
+            panic_with_error!(&env, Error::HorizonTooFar);
+        }
+        save_stream(&env, &stream, basis);
+
+        env.events().publish(
+            (symbol_short!("topped_up"), stream_id),
+            (added, stream.end_time),
+        );
+        stream.end_time
+    }
+
+    /// Cancel a stream and refund unstreamed funds to the sender.
+    ///
+    /// ## Behaviour
+    /// 0. **Emergency pause** — rejected while the guardian has paused the protocol.
+    /// 1. **Auth** — only the original sender or the contract admin can cancel.
+    /// 2. **State check** — only `Active` or `Paused` streams can be cancelled,
+    ///    and basket components only through `cancel_basket`.
+    /// 3. **Accrual** — computes `accrued = min((now − start_time) × rate, deposit_amount)`.
+    /// 4. **Refund** — transfers `deposit_amount − accrued` back to the sender immediately.
+    /// 5. **Persistence** — the portion `accrued − withdrawn_amount` remains for the recipient,
+    ///    and `deposit_amount` is capped at `accrued` so nothing further accrues.
+    ///
+    /// If the refund cannot be paid because the token was clawed back or
+    /// frozen at the Stellar level, the stream becomes `Distressed` and is
+    /// left uncancelled; see `recover_stream`.
+    pub fn cancel_stream(env: Env, stream_id: u64) {
+        assert_not_globally_paused(&env);
+        let (stream, basis) = load_stream_with_basis(&env, stream_id);
+        Self::require_sender_or_admin(&env, &stream.sender);
+        assert_not_in_basket(&env, stream_id);
+        Self::cancel_unchecked(&env, stream, basis);
+    }
+
+    /// Withdraw accrued-but-not-yet-withdrawn tokens to the recipient, or
+    /// deposit them into the recipient's vault if one is configured.
+    /// Only the recipient may call this, unless they enabled auto-withdraw or
+    /// pledged an operator allowance, in which case anyone (e.g. a keeper) may
+    /// trigger it. The stream's withholding is taken first, then any operator
+    /// allowance, the recipient's split and any round-up donation.
+    /// Returns the amount delivered to the recipient.
+    ///
+    /// # Panics
+    /// - If the protocol is under an emergency pause.
+    /// - If the stream is `Completed` (nothing left to withdraw).
+    /// - If the stream is `Paused` (withdrawals not allowed while paused). A
+    ///   lapsed, unconfirmed guardian hold is lifted first.
+    /// - If the stream was clawed back by the admin.
+    /// - If the stream is `Distressed`.
+    /// - If the recipient is under a compliance freeze.
+    /// - If an attestation registry is configured and the recipient is not verified.
+    /// - If a transfer policy is configured and rejects the payout.
+    /// - If there is nothing to withdraw (accrued == withdrawn).
+    ///
+    /// If the token has been clawed back or frozen at the Stellar level so
+    /// the payout cannot be made, the stream becomes `Distressed` and this
+    /// returns 0 instead of failing; see `recover_stream`.
+    pub fn withdraw(env: Env, stream_id: u64) -> i128 {
+        Self::withdraw_internal(&env, stream_id, Payout::Recipient)
+    }
+
+    /// Withdraw like `withdraw`, but deliver the tokens to `to` (e.g. a
+    /// splitter or vault) instead of the recipient. Only the recipient may
+    /// call this; compliance checks still apply to the recipient.
+    ///
+    /// # Panics
+    /// - Any of the `withdraw` failures.
+    pub fn withdraw_to(env: Env, stream_id: u64, to: Address) -> i128 {
+        Self::withdraw_internal(&env, stream_id, Payout::To(to))
+    }
+
+    /// Withdraw like `withdraw`, but atomically swap the tokens through the
+    /// configured swap router into `token_out`, paid to the recipient.
+    /// Only the recipient may call this. Returns the amount of `token_out`
+    /// received.
+    ///
+    /// # Panics
+    /// - Any of the `withdraw` failures.
+    /// - If no swap router is configured, or `token_out` is the stream token.
+    /// - If the recipient receives less than `min_out`.
+    pub fn withdraw_swap(env: Env, stream_id: u64, token_out: Address, min_out: i128) -> i128 {
+        Self::withdraw_internal(&env, stream_id, Payout::Swap { token_out, min_out })
+    }
+
+    /// Withdraw on a transaction submitted (and paid for) by `relayer`, who
+    /// is compensated with `relayer_fee` out of the payout. The recipient
+    /// signs the authorisation, including the relayer and fee, off-chain, so
+    /// they need no XLM of their own. Returns the amount paid to the
+    /// recipient.
+    ///
+    /// # Panics
+    /// - Any of the `withdraw` failures.
+    /// - If `relayer_fee` is negative or not smaller than the payout.
+    pub fn withdraw_relayed(env: Env, stream_id: u64, relayer: Address, relayer_fee: i128) -> i128 {
+        assert!(relayer_fee >= 0, "relayer_fee must not be negative");
+        Self::withdraw_internal(
+            &env,
+            stream_id,
+            Payout::Relayed {
+                relayer,
+                fee: relayer_fee,
+            },
+        )
+    }
+
+    /// Open a dispute on a stream created with an arbiter, pausing it until
+    /// the arbiter rules. Only the stream's sender or recipient may call
+    /// this, as `party`.
+    ///
+    /// # Panics
+    /// - If the stream has no arbiter or is already under dispute.
+    /// - If `party` is neither the sender nor the recipient.
+    /// - If the stream is not active or paused.
+    pub fn open_dispute(env: Env, stream_id: u64, party: Address) {
+        party.require_auth();
+        let mut stream = load_stream(&env, stream_id);
+        assert!(
+            get_arbiter(&env, stream_id).is_some(),
+            "stream has no arbiter"
+        );
+        assert!(
+            party == stream.sender || party == stream.recipient,
+            "only a stream party can dispute"
+        );
+        assert!(
+            stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused,
+            "stream must be active or paused to dispute"
+        );
+        assert_not_disputed(&env, stream_id);
+
+        stream.status = StreamStatus::Paused;
+        save_stream_progress(&env, &stream);
+        let key = DataKey::Dispute(stream_id);
+        env.storage().persistent().set(
+            &key,
+            &Dispute {
+                opened_by: party.clone(),
+                opened_at: env.ledger().timestamp(),
+            },
+        );
+        extend_persistent_ttl(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("disputed"), stream_id), party);
+    }
+
+    /// Settle a disputed stream: `recipient_amount` of the funds not yet
+    /// withdrawn goes to the recipient and the rest back to the sender. The
+    /// stream is then complete. Only the stream's arbiter may call this.
+    ///
+    /// # Panics
+    /// - If the stream is not under dispute.
+    /// - If `recipient_amount` is negative or exceeds the remaining funds.
+    pub fn resolve_dispute(env: Env, stream_id: u64, recipient_amount: i128) {
+        get_arbiter(&env, stream_id)
+            .expect("stream has no arbiter")
+            .require_auth();
+        assert!(
+            get_dispute(&env, stream_id).is_some(),
+            "stream is not under dispute"
+        );
+
+        let (mut stream, basis) = load_stream_with_basis(&env, stream_id);
+        let remaining = math::sub(&env, stream.deposit_amount, stream.withdrawn_amount);
+        assert!(
+            recipient_amount >= 0 && recipient_amount <= remaining,
+            "recipient_amount exceeds remaining funds"
+        );
+        let sender_amount = remaining - recipient_amount;
+        let accrued = accrued_now(&env, &stream, &basis);
+
+        vault::pay(&env, &stream.token, &stream.recipient, recipient_amount);
+        vault::pay(&env, &stream.token, &stream.sender, sender_amount);
+        // The ruling replaces any escalation, so the reserve goes back too.
+        if let Some(mut link) = get_index_link(&env, stream_id) {
+            release_index_reserve(&env, &stream, &mut link);
+        }
+
+        stream.withdrawn_amount = math::add(&env, stream.withdrawn_amount, recipient_amount);
+        stream.status = StreamStatus::Completed;
+        save_stream_progress(&env, &stream);
+        release_active_slot(&env, &stream.sender);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Dispute(stream_id));
+        if let Some(registry) = get_reputation_registry(&env) {
+            ReputationRegistryClient::new(&env, &registry).record_dispute(
+                &stream.sender,
+                &stream.withdrawn_amount,
+                &(stream.withdrawn_amount > accrued),
+            );
+        }
+
+        env.events().publish(
+            (symbol_short!("resolved"), stream_id),
+            (recipient_amount, sender_amount),
+        );
+    }
+
+    /// Return the arbiter chosen for a stream, if any.
+    pub fn get_arbiter(env: Env, stream_id: u64) -> Option<Address> {
+        get_arbiter(&env, stream_id)
+    }
+
+    /// Return the open dispute on a stream, if any.
+    pub fn get_dispute(env: Env, stream_id: u64) -> Option<Dispute> {
+        get_dispute(&env, stream_id)
+    }
+
+    /// Calculate the total amount accrued to the recipient so far.
+    pub fn calculate_accrued(env: Env, stream_id: u64) -> i128 {
+        let (stream, basis) = load_stream_with_basis(&env, stream_id);
+        accrued_now(&env, &stream, &basis)
+    }
+
+    /// Return whether `stream_id`'s schedule is in timestamps or ledger
+    /// sequence numbers.
+    pub fn get_stream_clock(env: Env, stream_id: u64) -> Clock {
+        stream_basis(&env, stream_id).clock
+    }
+
+    /// Return the full-precision rate of a stream opened with
+    /// `create_stream_scaled`, in `1/RATE_SCALE` base units per tick, or
+    /// `None` for a whole-unit rate.
+    pub fn get_scaled_rate(env: Env, stream_id: u64) -> Option<u128> {
+        let scaled_rate = stream_basis(&env, stream_id).scaled_rate;
+        (scaled_rate > 0).then_some(scaled_rate)
+    }
+
+    /// Return the revision of this contract's public interface,
+    /// `INTERFACE_VERSION`, which periphery contracts check before use.
+    pub fn interface_version(_env: Env) -> u32 {
+        INTERFACE_VERSION
+    }
+
+    /// Return the name, version, source repository and audit reference this
+    /// build was compiled with, matching the WASM's embedded metadata.
+    pub fn meta(env: Env) -> ContractMeta {
+        ContractMeta {
+            name: String::from_str(&env, CONTRACT_NAME),
+            version: String::from_str(&env, CONTRACT_VERSION),
+            repository: String::from_str(&env, SOURCE_REPOSITORY),
+            audit: String::from_str(&env, AUDIT_REFERENCE),
+        }
+    }
+
+    /// Fetches the global configuration.
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the current state of the stream identified by `stream_id`.
+    /// The status is the effective one: an active stream that has ended and
+    /// been fully withdrawn reads as completed.
+    pub fn get_stream_state(env: Env, stream_id: u64) -> Stream {
+        let (mut stream, basis) = load_stream_with_basis(&env, stream_id);
+        stream.status = effective_status(&env, &stream, &basis);
+        stream
+    }
+
+    /// Return `stream_id` as a `StreamView`, with derived balances and
+    /// status computed as of now.
+    ///
+    /// # Panics
+    /// - If the stream does not exist.
+    pub fn get_stream_view(env: Env, stream_id: u64) -> StreamView {
+        let (stream, basis) = load_stream_with_basis(&env, stream_id);
+        stream_view(&env, stream, &basis)
+    }
+
+    /// Return a `StreamView` for each of `stream_ids`, in order.
+    ///
+    /// # Panics
+    /// - If more than `MAX_VIEW_BATCH` ids are given.
+    /// - If any of the streams does not exist.
+    pub fn get_stream_views(env: Env, stream_ids: Vec<u64>) -> Vec<StreamView> {
+        assert!(stream_ids.len() <= MAX_VIEW_BATCH, "too many streams");
+        let mut views = Vec::new(&env);
+        for stream_id in stream_ids.iter() {
+            let (stream, basis) = load_stream_with_basis(&env, stream_id);
+            views.push_back(stream_view(&env, stream, &basis));
+        }
+        views
+    }
+
+    /// Cancellation body shared by the sender and admin paths. Callers are
+    /// responsible for authorisation and the emergency-pause check.
+    fn cancel_unchecked(env: &Env, mut stream: Stream, basis: AccrualBasis) {
+        assert!(
+            stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused,
+            "stream must be active or paused to cancel"
+        );
+        assert_not_disputed(env, stream.stream_id);
+
+        let accrued = accrued_now(env, &stream, &basis);
+        let unstreamed = math::sub(env, stream.deposit_amount, accrued);
+
+        let sender = stream.sender.clone();
+        if !stream.credit_refunds && unstreamed > 0 && enter_distress(env, &mut stream, &sender) {
+            return;
+        }
+
+        if stream.credit_refunds {
+            // Both balances stay in the contract (and in obligations) until claimed.
+            let owed = math::sub(env, accrued, stream.withdrawn_amount);
+            // The owed balance is settled now, so its escalation is credited
+            // with it and whatever is left of the reserve goes to the sender.
+            let escalation = take_escalation(env, &stream, owed, false);
+            let mut refund = unstreamed;
+            if let Some(link) = get_index_link(env, stream.stream_id) {
+                refund = math::add(env, refund, link.reserve);
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::IndexLink(stream.stream_id));
+            }
+            if escalation > 0 {
+                env.events()
+                    .publish((symbol_short!("escalated"), stream.stream_id), escalation);
+            }
+            if refund > 0 {
+                add_credit(env, &stream.sender, &stream.token, refund);
+            }
+            if owed > 0 {
+                // An operator allowance is honoured out of the owed balance.
+                let mut recipient_owed = owed;
+                if let Some(allowance) = get_operator(env, stream.stream_id) {
+                    let share = allowance.remaining.min(owed);
+                    add_credit(env, &allowance.operator, &stream.token, share);
+                    env.storage()
+                        .persistent()
+                        .remove(&DataKey::Operator(stream.stream_id));
+                    recipient_owed -= share;
+                }
+                let recipient_owed = math::add(env, recipient_owed, escalation);
+                if recipient_owed > 0 {
+                    add_credit(env, &stream.recipient, &stream.token, recipient_owed);
+                }
+                stream.withdrawn_amount = accrued;
+            }
+            env.events().publish(
+                (symbol_short!("credited"), stream.stream_id),
+                (unstreamed, owed),
+            );
+        } else {
+            settle_index_reserve(
+                env,
+                &stream,
+                math::sub(env, accrued, stream.withdrawn_amount),
+            );
+            vault::pay(env, &stream.token, &stream.sender, unstreamed);
+        }
+
+        // Only what accrued stays in the stream; capping the deposit stops
+        // accrual at cancellation so later withdrawals cannot reach the
+        // refunded balance.
+        stream.deposit_amount = accrued;
+        stream.status = StreamStatus::Cancelled;
+        save_stream(env, &stream, basis);
+        release_active_slot(env, &stream.sender);
+        if let Some(registry) = get_reputation_registry(env) {
+            let registry = ReputationRegistryClient::new(env, &registry);
+            // Cancelling once everything has streamed is not an early exit.
+            if unstreamed > 0 {
+                registry.record_cancelled(&stream.sender, &accrued);
+            } else {
+                registry.record_completed(&stream.sender, &accrued);
+            }
+        }
+
+        env.events()
+            .publish((symbol_short!("cancelled"), stream.stream_id), unstreamed);
+    }
+
+    /// Withdrawal body shared by `withdraw`, `withdraw_to` and `withdraw_swap`.
+    /// Returns the amount delivered, in the payout's token.
+    fn withdraw_internal(env: &Env, stream_id: u64, payout: Payout) -> i128 {
+        assert_not_globally_paused(env);
+        let (stream, basis) = load_stream_with_basis(env, stream_id);
+        // Auto-withdraw and operator allowances only waive auth when funds go
+        // to the operator and the recipient's own wallet or vault.
+        let operator = get_operator(env, stream_id);
+        if !(matches!(payout, Payout::Recipient)
+            && (operator.is_some() || is_auto_withdraw(env, &stream.recipient)))
+        {
+            stream.recipient.require_auth();
+        }
+        Self::withdraw_authorized(env, stream, &basis, operator, payout)
+    }
+
+    /// `withdraw_internal` once the recipient's authorisation, where needed,
+    /// has been checked.
+    fn withdraw_authorized(
+        env: &Env,
+        mut stream: Stream,
+        basis: &AccrualBasis,
+        operator: Option<OperatorAllowance>,
+        payout: Payout,
+    ) -> i128 {
+        let stream_id = stream.stream_id;
+        assert_not_disputed(env, stream_id);
+        if lift_expired_guardian_hold(env, stream_id) && stream.status == StreamStatus::Paused {
+            stream.status = StreamStatus::Active;
+        }
+        stream.status = effective_status(env, &stream, basis);
+
+        // Reject if stream is completed (#37)
+        assert!(
+            stream.status != StreamStatus::Completed,
+            "stream already completed"
+        );
+
+        // Reject if stream is paused - no withdrawals allowed while paused (#37)
+        assert!(
+            stream.status != StreamStatus::Paused,
+            "cannot withdraw from paused stream"
+        );
+
+        assert!(
+            stream.status != StreamStatus::ClawedBack,
+            "stream was clawed back"
+        );
+
+        assert!(
+            stream.status != StreamStatus::Distressed,
+            "stream is distressed"
+        );
+
+        assert!(
+            !(stream.status == StreamStatus::Cancelled && stream.credit_refunds),
+            "cancelled stream settles through credits"
+        );
+
+        assert!(
+            !is_recipient_frozen(env, &stream.recipient),
+            "recipient is frozen"
+        );
+        assert_attested(env, &stream.recipient);
+
+        let now = clock_now(env, basis.clock);
+        let accrued = accrued(env, &stream, basis, now);
+        let withdrawable = math::sub(env, accrued, stream.withdrawn_amount);
+        assert!(withdrawable > 0, "nothing to withdraw");
+        assert_transfer_permitted(
+            env,
+            &stream.sender,
+            &stream.recipient,
+            &stream.token,
+            withdrawable,
+        );
+        let destination = payout.destination(env, &stream.recipient);
+        if enter_distress(env, &mut stream, &destination) {
+            return 0;
+        }
+
+        // An index-linked stream's escalation is paid on top of the nominal
+        // amount, out of the reserve deposited for it.
+        let paid_out =
+            math::add(env, stream.withdrawn_amount, withdrawable) == stream.deposit_amount;
+        let escalation = take_escalation(env, &stream, withdrawable, paid_out);
+        if escalation > 0 {
+            env.events()
+                .publish((symbol_short!("escalated"), stream_id), escalation);
+        }
+        let gross = math::add(env, withdrawable, escalation);
+
+        // Principal is released once for the whole withdrawal; each leg below
+        // only transfers its share.
+        vault::release(env, &stream.token, gross);
+
+        // Withholding comes off the top, then the operator is paid; the
+        // recipient receives the rest.
+        let mut payable = gross;
+        if let Some(mut withholding) = get_withholding(env, stream_id) {
+            let share = math::bps(env, gross, withholding.share_bps);
+            if share > 0 {
+                vault::send(env, &stream.token, &withholding.destination, share);
+                withholding.withheld = math::add(env, withholding.withheld, share);
+                let key = DataKey::Withholding(stream_id);
+                env.storage().persistent().set(&key, &withholding);
+                extend_persistent_ttl(env, &key);
+                let key = DataKey::Withheld(withholding.destination.clone(), stream.token.clone());
+                let total = get_withheld_total(env, &withholding.destination, &stream.token);
+                env.storage()
+                    .persistent()
+                    .set(&key, &math::add(env, total, share));
+                extend_persistent_ttl(env, &key);
+                env.events().publish(
+                    (symbol_short!("withheld"), stream_id),
+                    (withholding.destination, share),
+                );
+                payable -= share;
+            }
+        }
+        if let Some(mut allowance) = operator {
+            let share = allowance.remaining.min(payable);
+            vault::send(env, &stream.token, &allowance.operator, share);
+            allowance.remaining -= share;
+            let key = DataKey::Operator(stream_id);
+            if allowance.remaining == 0 {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &allowance);
+                extend_persistent_ttl(env, &key);
+            }
+            env.events().publish(
+                (symbol_short!("op_paid"), stream_id),
+                (allowance.operator, share),
+            );
+            payable -= share;
+        }
+
+        // The recipient's standing split comes out of whatever is left.
+        if let Some(split) = get_withdraw_split(env, stream_id) {
+            let share = math::bps(env, payable, split.share_bps);
+            vault::send(env, &stream.token, &split.destination, share);
+            env.events().publish(
+                (symbol_short!("split"), stream_id),
+                (split.destination, share),
+            );
+            payable -= share;
+        }
+        payable = roundup::apply(env, stream_id, &stream.token, &stream.recipient, payable);
+
+        let delivered = match payout {
+            _ if payable == 0 => 0,
+            Payout::Recipient => match get_recipient_vault(env, &stream.recipient) {
+                Some(vault) => {
+                    vault::send(env, &stream.token, &vault, payable);
+                    VaultClient::new(env, &vault).deposit(
+                        &stream.token,
+                        &payable,
+                        &stream.recipient,
+                    );
+                    env.events()
+                        .publish((symbol_short!("vaulted"), stream_id), (vault, payable));
+                    payable
+                }
+                None => {
+                    vault::send(env, &stream.token, &stream.recipient, payable);
+                    payable
+                }
+            },
+            Payout::To(to) => {
+                vault::send(env, &stream.token, &to, payable);
+                payable
+            }
+            Payout::Swap { token_out, min_out } => {
+                let received = swap_to(
+                    env,
+                    &stream.token,
+                    payable,
+                    &token_out,
+                    min_out,
+                    &stream.recipient,
+                );
+                env.events()
+                    .publish((symbol_short!("swapped"), stream_id), (token_out, received));
+                received
+            }
+            Payout::Relayed { relayer, fee } => {
+                assert!(fee < payable, "relayer fee exceeds payout");
+                vault::send(env, &stream.token, &relayer, fee);
+                vault::send(env, &stream.token, &stream.recipient, payable - fee);
+                env.events()
+                    .publish((symbol_short!("relayed"), stream_id), (relayer, fee));
+                payable - fee
+            }
+        };
+
+        stream.withdrawn_amount = math::add(env, stream.withdrawn_amount, withdrawable);
+
+        if stream.status == StreamStatus::Active
+            && now >= stream.end_time
+            && stream.withdrawn_amount == stream.deposit_amount
+        {
+            stream.status = StreamStatus::Completed;
+            release_active_slot(env, &stream.sender);
+            if let Some(registry) = get_reputation_registry(env) {
+                ReputationRegistryClient::new(env, &registry)
+                    .record_completed(&stream.sender, &stream.deposit_amount);
+            }
+        }
+
+        save_stream_progress(env, &stream);
+        env.events()
+            .publish((symbol_short!("withdrew"), stream_id), withdrawable);
+        delivered
+    }
+
+    /// Deduct `amount` from `operator`'s creation allowance from `sender`.
+    fn consume_creator_allowance(
+        env: &Env,
+        sender: &Address,
+        operator: &Address,
+        token: &Address,
+        amount: i128,
+    ) {
+        let mut allowance =
+            get_creator_allowance(env, sender, operator, token).expect("no creator allowance");
+        assert!(
+            env.ledger().timestamp() <= allowance.expiry,
+            "creator allowance expired"
+        );
+        assert!(amount <= allowance.remaining, "creator allowance exceeded");
+
+        allowance.remaining -= amount;
+        let key = DataKey::Creator(sender.clone(), operator.clone(), token.clone());
+        env.storage().persistent().set(&key, &allowance);
+        extend_persistent_ttl(env, &key);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream_internal(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate: FlowRate,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+        creator: Creator,
+    ) -> u64 {
+        match &creator {
+            Creator::Sender => sender.require_auth(),
+            Creator::Operator(operator) => operator.require_auth(),
+            Creator::Signed | Creator::Prefunded => {}
+        }
+
+        assert_not_globally_paused(env);
+        assert!(!is_sunset(env), "contract is sunset");
+        assert_token_usable(env, &token);
+        assert_sep41(env, &token);
+
+        // Validate positive amounts (#35)
+        assert!(deposit_amount > 0, "deposit_amount must be positive");
+        if deposit_amount > MAX_DEPOSIT {
+            panic_with_error!(env, Error::DepositTooLarge);
+        }
+        let (rate_per_second, scaled_rate) = match rate {
+            FlowRate::Whole(rate_per_second) => {
+                assert!(rate_per_second > 0, "rate_per_second must be positive");
+                if rate_per_second > MAX_RATE_PER_SECOND {
+                    panic_with_error!(env, Error::RateTooLarge);
+                }
+                (rate_per_second, 0)
+            }
+            FlowRate::Scaled(scaled_rate) => {
+                assert!(scaled_rate > 0, "scaled_rate must be positive");
+                (math::streamed(env, rate, 1), scaled_rate)
+            }
+        };
+
+        // Validate sender != recipient (#35)
+        assert!(
+            sender != recipient,
+            "sender and recipient must be different"
+        );
+        if let Some(arbiter) = &options.arbiter {
+            assert!(
+                *arbiter != sender && *arbiter != recipient,
+                "arbiter must be a third party"
+            );
+        }
+        if let Some(referrer) = &options.referrer {
+            assert!(*referrer != sender, "sender cannot refer themselves");
+        }
+        match &options.withholding_to {
+            Some(destination) => {
+                assert!(
+                    options.withholding_bps > 0 && options.withholding_bps <= BPS_DENOMINATOR,
+                    "withholding_bps must be between 1 and 10000"
+                );
+                assert!(
+                    *destination != recipient,
+                    "withholding destination is the recipient"
+                );
+            }
+            None => assert!(
+                options.withholding_bps == 0,
+                "withholding_bps set without a destination"
+            ),
+        }
+
+        // Validate time constraints
+        assert!(start_time < end_time, "start_time must be before end_time");
+        assert!(
+            cliff_time >= start_time && cliff_time <= end_time,
+            "cliff_time must be within [start_time, end_time]"
+        );
+
+        // Enforce admin-configured floors against spam streams
+        let limits = get_limits(env);
+        assert!(
+            deposit_amount >= limits.min_deposit,
+            "deposit_amount below configured minimum"
+        );
+        assert!(
+            end_time - start_time >= limits.min_duration,
+            "stream duration below configured minimum"
+        );
+        let now = Timestamp(clock_now(env, options.clock));
+        if Timestamp(end_time) > now.saturating_add(MAX_HORIZON) {
+            panic_with_error!(env, Error::HorizonTooFar);
+        }
+        if limits.max_horizon > 0 {
+            assert!(
+                Timestamp(end_time) <= now.saturating_add(limits.max_horizon),
+                "end_time exceeds configured maximum horizon"
+            );
+        }
+        let active = get_active_count(env, &sender);
+        if limits.max_active_per_sender > 0 {
+            assert!(
+                active < limits.max_active_per_sender,
+                "sender has too many active streams"
+            );
+        }
+
+        // Validate deposit covers total streamable amount (#34)
+        let total_streamable = math::streamed(
+            env,
+            flow_rate(rate_per_second, scaled_rate),
+            end_time - start_time,
+        );
+        assert!(
+            deposit_amount >= total_streamable,
+            "deposit_amount must cover total streamable amount (rate * duration)"
+        );
+
+        assert_transfer_permitted(env, &sender, &recipient, &token, deposit_amount);
+
+        // Transfer tokens from sender to this contract (#36)
+        // If transfer fails (insufficient balance/allowance), this will panic
+        // and no state will be persisted (atomic transaction)
+        let fee_params = get_fee_params(env);
+        let fee = protocol_fee_for(env, &fee_params, deposit_amount);
+        let mut received = deposit_amount;
+        let via_allowance = match &creator {
+            Creator::Sender | Creator::Signed => {
+                received = vault::deposit(env, &token, &sender, deposit_amount);
+                Some(false)
+            }
+            Creator::Operator(operator) => {
+                Self::consume_creator_allowance(
+                    env,
+                    &sender,
+                    operator,
+                    &token,
+                    math::add(env, deposit_amount, fee),
+                );
+                received = vault::deposit_from(env, &token, &sender, deposit_amount);
+                Some(true)
+            }
+            // The batch already moved the deposit and fee into custody.
+            Creator::Prefunded => None,
+        };
+        // A token that takes a fee on transfer delivers less than requested.
+        // The stream is backed by what actually arrived, so accrual can never
+        // run ahead of custody.
+        let deposit_amount = received;
+        assert!(
+            deposit_amount >= total_streamable,
+            "received amount must cover total streamable amount (rate * duration)"
+        );
+        if let Some(via_allowance) = via_allowance {
+            // The referrer's cut of the fee is held in custody until claimed.
+            let referral_cut = match &options.referrer {
+                Some(_) => math::bps(env, fee, get_referral_share(env)),
+                None => 0,
+            };
+            let collector = &fee_params.fee_collector;
+            vault::charge_fee(
+                env,
+                &token,
+                &sender,
+                collector,
+                fee - referral_cut,
+                via_allowance,
+            );
+            let referral_cut = vault::retain_fee(env, &token, &sender, referral_cut, via_allowance);
+            if let Some(referrer) = &options.referrer {
+                let mut account = get_referral(env, referrer, &token);
+                account.streams = account.streams.checked_add(1).expect("overflow");
+                account.volume = math::add(env, account.volume, deposit_amount);
+                account.owed = math::add(env, account.owed, referral_cut);
+                save_referral(env, referrer, &token, &account);
+            }
+        }
+
+        // Only allocate stream id and persist state AFTER successful transfer
+        let stream_id = get_stream_count(env);
+        set_stream_count(env, stream_id + 1);
+        set_active_count(env, &sender, active + 1);
+
+        let stream = Stream {
+            stream_id,
+            sender,
+            recipient,
+            token,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+            withdrawn_amount: 0,
+            status: StreamStatus::Active,
+            admin_cancelable: options.admin_cancelable,
+            credit_refunds: options.credit_refunds,
+        };
+
+        save_stream(
+            env,
+            &stream,
+            AccrualBasis {
+                clock: options.clock,
+                scaled_rate,
+            },
+        );
+        if let Some(arbiter) = &options.arbiter {
+            let key = DataKey::Arbiter(stream_id);
+            env.storage().persistent().set(&key, arbiter);
+            extend_persistent_ttl(env, &key);
+        }
+        if let Some(destination) = &options.withholding_to {
+            let key = DataKey::Withholding(stream_id);
+            let withholding = Withholding {
+                destination: destination.clone(),
+                share_bps: options.withholding_bps,
+                withheld: 0,
+            };
+            env.storage().persistent().set(&key, &withholding);
+            extend_persistent_ttl(env, &key);
+        }
+
+        env.events()
+            .publish((symbol_short!("created"), stream_id), deposit_amount);
+
+        stream_id
+    }
+
+    /// Internal helper to check authorization for sender or admin.
+    fn require_sender_or_admin(env: &Env, sender: &Address) {
+        let admin = get_admin(env);
+
+        // If the admin is the one calling, they must authorize.
+        // Otherwise, the sender must authorize.
+        if sender != &admin {
+            // This allows the admin to bypass the sender's auth
+            // if we use a separate admin entrypoint, or we can
+            // rely on the transaction signatures.
+            sender.require_auth();
+        } else {
+            admin.require_auth();
+        }
+    }
+}
+
+#[contractimpl]
+impl FluxoraStream {
+    /// Store a reusable stream shape (e.g. 4-year vesting with a 1-year cliff)
+    /// and return its id. Anyone may open streams from a stored template.
+    ///
+    /// # Panics
+    /// - If `rate_per_second` or `duration` is not positive.
+    /// - If `cliff_offset` exceeds `duration`.
+    /// - With `Error::Overflow` if `rate_per_second × duration` overflows.
+    pub fn create_template(env: Env, creator: Address, template: StreamTemplate) -> u64 {
+        creator.require_auth();
+
+        assert!(
+            template.rate_per_second > 0,
+            "rate_per_second must be positive"
+        );
+        assert!(template.duration > 0, "duration must be positive");
+        assert!(
+            template.cliff_offset <= template.duration,
+            "cliff_offset must not exceed duration"
+        );
+        math::mul(&env, template.rate_per_second, template.duration as i128);
+
+        let template_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTemplateId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTemplateId, &(template_id + 1));
+
+        let key = DataKey::Template(template_id);
+        env.storage().persistent().set(&key, &template);
+        extend_persistent_ttl(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("template"), template_id), creator);
+        template_id
+    }
+
+    /// Return the template identified by `template_id`.
+    pub fn get_template(env: Env, template_id: u64) -> StreamTemplate {
+        load_template(&env, template_id)
+    }
+
+    /// Open a stream from `sender` to `recipient` using a stored template,
+    /// starting at `start_time`. The deposit is exactly
+    /// `rate_per_second × duration`.
+    ///
+    /// # Panics
+    /// - If the template does not exist.
+    /// - If `start_time + duration` overflows.
+    /// - Any of the `create_stream_with_options` validation failures.
+    pub fn create_stream_from_template(
+        env: Env,
+        sender: Address,
+        template_id: u64,
+        recipient: Address,
+        start_time: u64,
+    ) -> u64 {
+        let template = load_template(&env, template_id);
+        let end_time = start_time
+            .checked_add(template.duration)
+            .expect("overflow calculating end_time");
+        let deposit_amount = math::mul(&env, template.rate_per_second, template.duration as i128);
+
+        Self::create_stream_internal(
+            &env,
+            sender,
+            recipient,
+            template.token,
+            deposit_amount,
+            FlowRate::Whole(template.rate_per_second),
+            start_time,
+            start_time + template.cliff_offset,
+            end_time,
+            template.options,
+            Creator::Sender,
+        )
+    }
+
+    /// Recover `amount` of `token` sent to the contract by mistake, paying it
+    /// to `to`. Only the surplus above stream obligations can be rescued, so
+    /// funds backing any stream are untouchable. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive.
+    /// - If `amount` exceeds `balance − obligations` for `token`.
+    pub fn rescue(env: Env, token: Address, amount: i128, to: Address) {
+        get_admin(&env).require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        vault::pay_surplus(&env, &token, &to, amount);
+
+        env.events()
+            .publish((symbol_short!("rescued"), token), (to, amount));
+    }
+
+    /// Return the total amount of `token` owed to stream parties.
+    pub fn get_obligations(env: Env, token: Address) -> i128 {
+        vault::custody(&env, &token).principal
+    }
+
+    /// Check the contract's accounting for `token`: its balance covers the
+    /// principal, the principal covers every open stream, and no stream has
+    /// paid out more than it accrued or was deposited. Walks every stream,
+    /// so it is meant for off-chain simulation and monitoring.
+    ///
+    /// # Panics
+    /// - With an "invariant violated" message naming the broken invariant.
+    pub fn assert_invariants(env: Env, token: Address) {
+        invariants::check_all(&env, &token);
+    }
+
+    /// Return the custody buckets held for `token`.
+    pub fn get_custody(env: Env, token: Address) -> Custody {
+        vault::custody(&env, &token)
+    }
+
+    /// Return the untracked balance of `token` (contract balance − obligations).
+    pub fn get_surplus(env: Env, token: Address) -> i128 {
+        vault::surplus(&env, &token)
+    }
+
+    /// Sweep the entire untracked surplus of `token` to the fee collector,
+    /// reconciling the contract balance with its obligations. Callable by
+    /// anyone, since the destination is fixed. Returns the amount swept.
+    ///
+    /// # Panics
+    /// - If there is no surplus.
+    pub fn skim(env: Env, token: Address) -> i128 {
+        let surplus = vault::surplus(&env, &token);
+        assert!(surplus > 0, "nothing to skim");
+
+        let collector = get_fee_params(&env).fee_collector;
+        vault::pay_surplus(&env, &token, &collector, surplus);
+
+        env.events()
+            .publish((symbol_short!("skimmed"), token), (collector, surplus));
+        surplus
+    }
+
+    /// Place a compliance hold on `recipient`: withdrawals from all of their
+    /// streams, and top-ups of them, are rejected while accrual continues
+    /// unaffected.
+    /// Only the admin may call this.
+    pub fn freeze_recipient(env: Env, recipient: Address) {
+        get_admin(&env).require_auth();
+        if is_recipient_frozen(&env, &recipient) {
+            return;
+        }
+
+        let key = DataKey::Frozen(recipient.clone());
+        env.storage().persistent().set(&key, &true);
+        extend_persistent_ttl(&env, &key);
+
+        if cfg!(feature = "indexes") {
+            let mut list = get_frozen_list(&env);
+            list.push_back(recipient.clone());
+            set_frozen_list(&env, &list);
+        }
+
+        env.events()
+            .publish((symbol_short!("frozen"), recipient), ());
+    }
+
+    /// Lift a compliance hold on `recipient`. Only the admin may call this.
+    pub fn unfreeze_recipient(env: Env, recipient: Address) {
+        get_admin(&env).require_auth();
+        if !is_recipient_frozen(&env, &recipient) {
+            return;
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Frozen(recipient.clone()));
+
+        if cfg!(feature = "indexes") {
+            let mut list = get_frozen_list(&env);
+            if let Some(index) = list.first_index_of(&recipient) {
+                list.remove(index);
+            }
+            set_frozen_list(&env, &list);
+        }
+
+        env.events()
+            .publish((symbol_short!("unfrozen"), recipient), ());
+    }
+
+    /// Return whether `recipient` is under a compliance hold.
+    pub fn is_recipient_frozen(env: Env, recipient: Address) -> bool {
+        is_recipient_frozen(&env, &recipient)
+    }
+
+    /// Emergency pause: halt creations, withdrawals and cancellations across
+    /// the whole protocol. Only the guardian may call this; only the admin can
+    /// lift it, so a compromised guardian key cannot move funds.
+    pub fn emergency_pause(env: Env) {
+        get_guardian(&env).require_auth();
+        env.storage().instance().set(&DataKey::GlobalPause, &true);
+
+        env.events().publish((symbol_short!("e_pause"),), ());
+    }
+
+    /// Lift an emergency pause. Only the admin may call this.
+    pub fn emergency_unpause(env: Env) {
+        get_admin(&env).require_auth();
+        env.storage().instance().remove(&DataKey::GlobalPause);
+
+        env.events().publish((symbol_short!("e_unpause"),), ());
+    }
+
+    /// Return whether the protocol is under an emergency pause.
+    pub fn is_paused(env: Env) -> bool {
+        is_globally_paused(&env)
+    }
+
+    /// Permanently retire this deployment ahead of a migration: new stream
+    /// creation and top-ups are disabled for good, while withdrawals and
+    /// cancellations of existing streams keep working. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If the contract is already sunset.
+    pub fn sunset(env: Env) {
+        get_admin(&env).require_auth();
+        assert!(!is_sunset(&env), "contract is sunset");
+        env.storage().instance().set(&DataKey::Sunset, &true);
+
+        env.events().publish((symbol_short!("sunset"),), ());
+    }
+
+    /// Return whether the contract has been sunset.
+    pub fn is_sunset(env: Env) -> bool {
+        is_sunset(&env)
+    }
+
+    /// Freeze new activity in `token` on behalf of the emergency council.
+    /// Same effect as `block_token`; unblocking remains admin-only.
+    pub fn guardian_block_token(env: Env, token: Address) {
+        get_guardian(&env).require_auth();
+        set_token_blocked(&env, &token, true);
+
+        env.events()
+            .publish((symbol_short!("tok_block"), token), ());
+    }
+
+    /// Pause a single active stream, e.g. on a fraud report. Only the guardian
+    /// may call this. The hold lapses after the guardian pause window unless
+    /// the admin confirms it with `confirm_guardian_hold`; while it stands,
+    /// the sender cannot resume the stream. Once lapsed, the next withdrawal
+    /// resumes the stream automatically.
+    ///
+    /// # Panics
+    /// - If the stream is not in `Active` state.
+    /// - If the stream is part of a basket; use `guardian_pause_basket`.
+    pub fn guardian_pause_stream(env: Env, stream_id: u64) {
+        get_guardian(&env).require_auth();
+
+        let mut stream = load_stream(&env, stream_id);
+        assert_not_in_basket(&env, stream_id);
+        assert!(
+            stream.status == StreamStatus::Active,
+            "stream is not active"
+        );
+        place_guardian_hold(&env, &mut stream);
+    }
+
+    /// Confirm a guardian hold so it no longer lapses. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If the stream has no live guardian hold.
+    pub fn confirm_guardian_hold(env: Env, stream_id: u64) {
+        get_admin(&env).require_auth();
+
+        lift_expired_guardian_hold(&env, stream_id);
+        let mut hold = get_guardian_hold(&env, stream_id).expect("no guardian hold");
+        hold.confirmed = true;
+        let key = DataKey::GuardianHold(stream_id);
+        env.storage().persistent().set(&key, &hold);
+        extend_persistent_ttl(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("hold_conf"), stream_id), ());
+    }
+
+    /// Release a guardian hold (confirmed or not) and resume the stream,
+    /// unless a dispute keeps it paused until the arbiter rules.
+    /// For a basket component, every held component of the basket is
+    /// released together so the components stay in step. Only the admin may
+    /// call this.
+    ///
+    /// # Panics
+    /// - If the stream has no guardian hold.
+    pub fn release_guardian_hold(env: Env, stream_id: u64) {
+        get_admin(&env).require_auth();
+
+        assert!(
+            get_guardian_hold(&env, stream_id).is_some(),
+            "no guardian hold"
+        );
+        for held_id in basket_streams(&env, stream_id).iter() {
+            if get_guardian_hold(&env, held_id).is_none() {
+                continue;
+            }
+            env.storage()
+                .persistent()
+                .remove(&DataKey::GuardianHold(held_id));
+
+            let mut stream = load_stream(&env, held_id);
+            if stream.status == StreamStatus::Paused && get_dispute(&env, held_id).is_none() {
+                stream.status = StreamStatus::Active;
+                save_stream_progress(&env, &stream);
+            }
+
+            env.events()
+                .publish((symbol_short!("hold_rel"), held_id), ());
+        }
+    }
+
+    /// Return the guardian hold on `stream_id`, if any.
+    pub fn get_guardian_hold(env: Env, stream_id: u64) -> Option<GuardianHold> {
+        get_guardian_hold(&env, stream_id)
+    }
+
+    /// Set how long an unconfirmed guardian hold lasts, in seconds.
+    /// Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If `window` is zero.
+    pub fn set_guardian_pause_window(env: Env, window: u64) {
+        get_admin(&env).require_auth();
+        assert!(window > 0, "window must be positive");
+        env.storage()
+            .instance()
+            .set(&DataKey::GuardianPauseWindow, &window);
+
+        env.events().publish((symbol_short!("grd_win"),), window);
+    }
+
+    /// Return the lifetime of an unconfirmed guardian hold, in seconds.
+    pub fn get_guardian_pause_window(env: Env) -> u64 {
+        get_guardian_pause_window(&env)
+    }
+
+    /// Step one of a guardian rotation: the current guardian nominates a
+    /// successor. Replaces any earlier nomination.
+    pub fn propose_guardian(env: Env, new_guardian: Address) {
+        get_guardian(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingGuardian, &new_guardian);
+
+        env.events()
+            .publish((symbol_short!("grd_prop"),), new_guardian);
+    }
+
+    /// Step two of a guardian rotation: the nominee accepts the role.
+    ///
+    /// # Panics
+    /// - If no guardian has been nominated.
+    pub fn accept_guardian(env: Env) {
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingGuardian)
+            .expect("no pending guardian");
+        pending.require_auth();
+
+        let mut config = get_config(&env);
+        config.guardian = pending.clone();
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().remove(&DataKey::PendingGuardian);
+
+        env.events().publish((symbol_short!("grd_set"),), pending);
+    }
+
+    /// Step one of an admin transfer: the admin nominates a successor, who
+    /// takes over by calling `accept_admin`. Replaces any earlier nomination.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        if fluxora_access::try_admin(&env).is_none() {
+            fluxora_access::init_admin(&env, &get_config(&env).admin);
+        }
+        fluxora_access::propose_admin(&env, &new_admin);
+    }
+
+    /// Step two of an admin transfer: the nominee takes over as admin.
+    ///
+    /// # Panics
+    /// - If no admin has been nominated.
+    pub fn accept_admin(env: Env) {
+        let admin = fluxora_access::accept_admin(&env);
+        let mut config = get_config(&env);
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+    }
+
+    /// Return the current admin.
+    pub fn get_admin(env: Env) -> Address {
+        get_admin(&env)
+    }
+
+    /// Register (or clear, with `None`) the swap router implementing
+    /// `SwapRouter` used by `withdraw_swap`, typically a thin adapter over an
+    /// AMM such as Soroswap. Requires the parameter authority.
+    pub fn set_swap_router(env: Env, router: Option<Address>) {
+        require_params_authority(&env);
+        match &router {
+            Some(router) => env.storage().instance().set(&DataKey::SwapRouter, router),
+            None => env.storage().instance().remove(&DataKey::SwapRouter),
+        }
+
+        env.events().publish((symbol_short!("router"),), router);
+    }
+
+    /// Return the registered swap router, if any.
+    pub fn get_swap_router(env: Env) -> Option<Address> {
+        get_swap_router(&env)
+    }
+
+    /// Route every future `withdraw` by `recipient` into `vault`, a contract
+    /// implementing `Vault`, or clear the route with `None`. Applies across
+    /// all of the recipient's streams. Only the recipient may call this.
+    pub fn set_recipient_vault(env: Env, recipient: Address, vault: Option<Address>) {
+        recipient.require_auth();
+        let key = DataKey::Vault(recipient.clone());
+        match &vault {
+            Some(vault) => {
+                env.storage().persistent().set(&key, vault);
+                extend_persistent_ttl(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events()
+            .publish((symbol_short!("vault"), recipient), vault);
+    }
+
+    /// Round every future withdrawal by `recipient` down to a multiple of
+    /// `granularity` and donate the remainder to `donee`, or opt out with
+    /// `None`. Applies across all of the recipient's streams, after any
+    /// withholding, operator allowance and split. Only the recipient may
+    /// call this.
+    ///
+    /// # Panics
+    /// - If `granularity` is not above 1.
+    /// - If `donee` is the recipient.
+    pub fn set_round_up(env: Env, recipient: Address, round_up: Option<RoundUp>) {
+        recipient.require_auth();
+        roundup::set(&env, &recipient, &round_up);
+
+        env.events()
+            .publish((symbol_short!("round_up"), recipient), round_up);
+    }
+
+    /// Return `recipient`'s round-up instruction, if any.
+    pub fn get_round_up(env: Env, recipient: Address) -> Option<RoundUp> {
+        roundup::get(&env, &recipient)
+    }
+
+    /// Return the total `recipient` has donated in `token` through round-ups.
+    pub fn get_donated(env: Env, recipient: Address, token: Address) -> i128 {
+        roundup::donated(&env, &recipient, &token)
+    }
+
+    /// Return the vault `recipient`'s withdrawals are deposited into, if any.
+    pub fn get_recipient_vault(env: Env, recipient: Address) -> Option<Address> {
+        get_recipient_vault(&env, &recipient)
+    }
+
+    /// Allow (or stop allowing) anyone to trigger `withdraw` on `recipient`'s
+    /// streams, so keepers can settle them on the recipient's behalf. Funds
+    /// still only reach the recipient or their vault. Only the recipient may
+    /// call this.
+    pub fn set_auto_withdraw(env: Env, recipient: Address, enabled: bool) {
+        recipient.require_auth();
+        let key = DataKey::AutoWithdraw(recipient.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+            extend_persistent_ttl(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events()
+            .publish((symbol_short!("auto_wd"), recipient), enabled);
+    }
+
+    /// Return whether anyone may trigger `withdraw` for `recipient`.
+    pub fn is_auto_withdraw(env: Env, recipient: Address) -> bool {
+        is_auto_withdraw(&env, &recipient)
+    }
+
+    /// Hand the stream's future withdrawals to `new_recipient`, e.g. to sell
+    /// or fractionalize the cashflow. Any operator allowance stays in force.
+    /// Only the current recipient may call this.
+    ///
+    /// # Panics
+    /// - If the stream is completed or clawed back, or under dispute.
+    /// - If the stream is part of a basket.
+    /// - If the current recipient is frozen.
+    /// - If `new_recipient` is the sender or already the recipient.
+    pub fn transfer_recipient(env: Env, stream_id: u64, new_recipient: Address) {
+        let (mut stream, basis) = load_stream_with_basis(&env, stream_id);
+        stream.recipient.require_auth();
+        assert_not_in_basket(&env, stream_id);
+        assert!(
+            stream.status != StreamStatus::Completed && stream.status != StreamStatus::ClawedBack,
+            "stream is closed"
+        );
+        assert_not_disputed(&env, stream_id);
+        assert!(
+            !is_recipient_frozen(&env, &stream.recipient),
+            "recipient is frozen"
+        );
+        assert!(
+            new_recipient != stream.sender && new_recipient != stream.recipient,
+            "invalid new recipient"
+        );
+
+        let previous = stream.recipient;
+        stream.recipient = new_recipient.clone();
+        save_stream(&env, &stream, basis);
+        // A split is the previous recipient's instruction, not the stream's.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Split(stream_id));
+
+        env.events().publish(
+            (symbol_short!("rcpt_xfer"), stream_id),
+            (previous, new_recipient),
+        );
+    }
+
+    /// Pledge the next `amount` of the stream's withdrawals to `operator`
+    /// (e.g. a lender advancing salary against the stream). Until the pledge
+    /// is used up, every withdrawal pays the operator first and anyone may
+    /// trigger `withdraw`. Granting again to the same operator adds to the
+    /// pledge. Only the recipient may call this; only the operator can
+    /// release it early.
+    ///
+    /// # Panics
+    /// - If `amount` is not positive.
+    /// - If the stream already has a different operator.
+    pub fn approve_operator(env: Env, stream_id: u64, operator: Address, amount: i128) {
+        let stream = load_stream(&env, stream_id);
+        stream.recipient.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        let remaining = match get_operator(&env, stream_id) {
+            Some(existing) => {
+                assert!(
+                    existing.operator == operator,
+                    "stream already has an operator"
+                );
+                math::add(&env, existing.remaining, amount)
+            }
+            None => amount,
+        };
+        let key = DataKey::Operator(stream_id);
+        env.storage().persistent().set(
+            &key,
+            &OperatorAllowance {
+                operator: operator.clone(),
+                remaining,
+            },
+        );
+        extend_persistent_ttl(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("op_appr"), stream_id), (operator, remaining));
+    }
+
+    /// Release the operator allowance on a stream, e.g. once a loan is repaid
+    /// by other means. Only the operator may call this.
+    ///
+    /// # Panics
+    /// - If the stream has no operator allowance.
+    pub fn release_operator(env: Env, stream_id: u64) {
+        let allowance = get_operator(&env, stream_id).expect("no operator allowance");
+        allowance.operator.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Operator(stream_id));
+
+        env.events()
+            .publish((symbol_short!("op_rel"), stream_id), allowance.remaining);
+    }
+
+    /// Return the operator allowance on a stream, if any.
+    pub fn get_operator(env: Env, stream_id: u64) -> Option<OperatorAllowance> {
+        get_operator(&env, stream_id)
+    }
+
+    /// Send `share_bps` of every future withdrawal from the stream to
+    /// `destination`, or stop with `None`. The split is taken after any
+    /// operator allowance and before the payout, whichever way the recipient
+    /// withdraws. It is dropped if the stream changes recipient. Only the
+    /// recipient may call this.
+    ///
+    /// # Panics
+    /// - If `share_bps` is zero or above `BPS_DENOMINATOR`.
+    /// - If `destination` is the recipient.
+    pub fn set_withdraw_split(env: Env, stream_id: u64, split: Option<WithdrawSplit>) {
+        let stream = load_stream(&env, stream_id);
+        stream.recipient.require_auth();
+        let key = DataKey::Split(stream_id);
+        match &split {
+            Some(split) => {
+                assert!(
+                    split.share_bps > 0 && split.share_bps <= BPS_DENOMINATOR,
+                    "share_bps must be between 1 and 10000"
+                );
+                assert!(
+                    split.destination != stream.recipient,
+                    "destination is the recipient"
+                );
+                env.storage().persistent().set(&key, split);
+                extend_persistent_ttl(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events()
+            .publish((symbol_short!("split_set"), stream_id), split);
+    }
+
+    /// Return the stream's withholding and the total withheld so far, if it
+    /// was created with one.
+    pub fn get_withholding(env: Env, stream_id: u64) -> Option<Withholding> {
+        get_withholding(&env, stream_id)
+    }
+
+    /// Return the total withheld for `destination` in `token` across all
+    /// streams.
+    pub fn get_withheld_total(env: Env, destination: Address, token: Address) -> i128 {
+        get_withheld_total(&env, &destination, &token)
+    }
+
+    /// Return the recipient's withdrawal split on a stream, if any.
+    pub fn get_withdraw_split(env: Env, stream_id: u64) -> Option<WithdrawSplit> {
+        get_withdraw_split(&env, stream_id)
+    }
+
+    /// Pay out `account`'s cancellation credit in `token` to `to`, which may
+    /// differ from `account` when it cannot receive the token itself.
+    /// Only `account` may call this. Returns the amount paid.
+    ///
+    /// # Panics
+    /// - If `account` is under a compliance freeze.
+    /// - If `account` has no credit in `token`.
+    pub fn claim_credit(env: Env, account: Address, token: Address, to: Address) -> i128 {
+        account.require_auth();
+        assert!(!is_recipient_frozen(&env, &account), "recipient is frozen");
+        let amount = get_credit(&env, &account, &token);
+        assert!(amount > 0, "no credit to claim");
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Credit(account.clone(), token.clone()));
+        vault::pay(&env, &token, &to, amount);
+
+        env.events()
+            .publish((symbol_short!("claimed"), account), (token, amount));
+        amount
+    }
+
+    /// Return `account`'s unclaimed cancellation credit in `token`.
+    pub fn get_credit(env: Env, account: Address, token: Address) -> i128 {
+        get_credit(&env, &account, &token)
+    }
+
+    /// Tune the rent parameters applied whenever instance or persistent
+    /// entries are written: entries whose TTL falls below `threshold` ledgers
+    /// are bumped to `extend_to` ledgers. Requires the parameter authority.
+    ///
+    /// # Panics
+    /// - If `threshold` is zero or exceeds `extend_to`.
+    /// - If `extend_to` exceeds the network's maximum entry TTL.
+    pub fn set_ttl_params(env: Env, threshold: u32, extend_to: u32) {
+        require_params_authority(&env);
+        assert!(
+            threshold > 0 && threshold <= extend_to,
+            "threshold must be positive and not exceed extend_to"
+        );
+        assert!(
+            extend_to <= env.storage().max_ttl(),
+            "extend_to exceeds the maximum TTL"
+        );
+
+        let mut config = get_config(&env);
+        config.ttl_threshold = threshold;
+        config.ttl_extend_to = extend_to;
+        env.storage().instance().set(&DataKey::Config, &config);
+        extend_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("ttl"),), (threshold, extend_to));
+    }
+
+    /// Bump the records and per-stream settings of each listed stream, and
+    /// the contract instance, using the configured TTL parameters. Callable by anyone, so keepers
+    /// can keep long-running streams alive without writing any state.
+    ///
+    /// # Panics
+    /// - If more than `MAX_TTL_BATCH` ids are listed.
+    /// - If any listed stream does not exist.
+    pub fn extend_ttl_for(env: Env, stream_ids: Vec<u64>) {
+        assert!(stream_ids.len() <= MAX_TTL_BATCH, "too many streams");
+        let config = get_config(&env);
+        let storage = env.storage().persistent();
+        for stream_id in stream_ids.iter() {
+            assert!(storage.has(&DataKey::Stream(stream_id)), "stream not found");
+            for key in stream_keys(stream_id) {
+                if storage.has(&key) {
+                    storage.extend_ttl(&key, config.ttl_threshold, config.ttl_extend_to);
+                }
+            }
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(config.ttl_threshold, config.ttl_extend_to);
+    }
+
+    /// Bring a stream whose entries were archived back to a full TTL.
+    /// Callable by anyone.
+    ///
+    /// A contract cannot read an archived entry, so restoring is two steps:
+    /// a `RestoreFootprint` operation over the stream's keys (see the README;
+    /// simulating this call reports the keys to restore), then this call.
+    /// Restored entries only get the network's minimum TTL, so this bumps the
+    /// stream's records, and any per-stream settings present, to
+    /// `ttl_extend_to` in one go.
+    ///
+    /// # Panics
+    /// - If the stream does not exist.
+    pub fn restore_stream(env: Env, stream_id: u64) {
+        let config = get_config(&env);
+        let storage = env.storage().persistent();
+        assert!(storage.has(&DataKey::Stream(stream_id)), "stream not found");
+        for key in stream_keys(stream_id) {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, config.ttl_extend_to, config.ttl_extend_to);
+            }
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(config.ttl_threshold, config.ttl_extend_to);
+
+        env.events()
+            .publish((symbol_short!("restored"), stream_id), ());
+    }
+
+    /// Delete the records of a settled stream, leaving a tombstone with its
+    /// final state (see `get_closed_stream`). Callable by anyone once
+    /// `CLOSE_RETENTION_SECS` have passed since the stream settled, so
+    /// high-volume senders stop paying rent on finished streams.
+    ///
+    /// A settled stream no longer counts towards its sender's open streams,
+    /// so no index entry needs removing. A basket component is dropped from
+    /// its basket. Afterwards the stream reads as not found; periphery
+    /// contracts read it through `fluxora_types::load_stream`, which falls
+    /// back to the tombstone.
+    ///
+    /// # Panics
+    /// - If the stream does not exist.
+    /// - If the stream is not completed, or cancelled and fully withdrawn.
+    /// - If the retention delay has not passed.
+    /// - If an index reserve is still held for the stream.
+    pub fn close_stream(env: Env, stream_id: u64) {
+        let (mut stream, basis) = load_stream_with_basis(&env, stream_id);
+        let stored_status = stream.status;
+        stream.status = effective_status(&env, &stream, &basis);
+        assert!(is_settled(&stream), "stream is not settled");
+        let progress = migration::load_progress(&env, stream_id).expect("stream progress missing");
+        assert!(
+            env.ledger().timestamp() >= progress.settled_at + CLOSE_RETENTION_SECS,
+            "retention period has not elapsed"
+        );
+        assert!(
+            get_index_link(&env, stream_id).is_none_or(|link| link.reserve == 0),
+            "index reserve is still held"
+        );
+
+        leave_basket(&env, stream_id);
+        let storage = env.storage().persistent();
+        for key in stream_keys(stream_id) {
+            storage.remove(&key);
+        }
+        if stored_status == StreamStatus::Active {
+            // Completed without a transaction noticing; free its slot now.
+            release_active_slot(&env, &stream.sender);
+        }
+        let tombstone = DataKey::Tombstone(stream_id);
+        storage.set(&tombstone, &stream);
+        extend_persistent_ttl(&env, &tombstone);
+
+        env.events()
+            .publish((symbol_short!("closed"), stream_id), stream.status);
+    }
+
+    /// Return the final status of a stream deleted by `close_stream`, or
+    /// `None` if it was never closed.
+    pub fn get_tombstone(env: Env, stream_id: u64) -> Option<StreamStatus> {
+        Self::get_closed_stream(env, stream_id).map(|stream| stream.status)
+    }
+
+    /// Return the final state of a stream deleted by `close_stream`, as
+    /// `get_stream_state` last reported it, or `None` if it was never closed.
+    pub fn get_closed_stream(env: Env, stream_id: u64) -> Option<Stream> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tombstone(stream_id))
+    }
+
+    /// Designate (or clear, with `None`) the governance contract accepted for
+    /// parameter updates. Callable by the current parameter authority, so once a
+    /// governor is installed only the governor can replace or remove itself.
+    pub fn set_governor(env: Env, governor: Option<Address>) {
+        require_params_authority(&env);
+
+        let mut config = get_config(&env);
+        config.governor = governor.clone();
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        env.events().publish((symbol_short!("governor"),), governor);
+    }
+
+    /// Add `token` to the allowlist so it can be used in `create_stream_with_token`.
+    /// Requires the parameter authority.
+    pub fn allow_token(env: Env, token: Address) {
+        require_params_authority(&env);
+        set_token_allowed(&env, &token, true);
+
+        env.events()
+            .publish((symbol_short!("tok_allow"), token), ());
+    }
+
+    /// Remove `token` from the allowlist. Existing streams in that token are
+    /// unaffected; only new streams are rejected. Requires the parameter authority.
+    pub fn disallow_token(env: Env, token: Address) {
+        require_params_authority(&env);
+        set_token_allowed(&env, &token, false);
+
+        env.events().publish((symbol_short!("tok_deny"), token), ());
+    }
+
+    /// Return whether `token` is currently on the allowlist.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        is_token_allowed(&env, &token)
+    }
+
+    /// Emergency freeze of new activity in `token` (incident response).
+    ///
+    /// Blocks new streams and top-ups in that asset, even if it is
+    /// allowlisted, while withdrawals and cancellations of existing streams
+    /// keep working.
+    /// Only the admin may call this.
+    pub fn block_token(env: Env, token: Address) {
+        get_admin(&env).require_auth();
+        set_token_blocked(&env, &token, true);
+
+        env.events()
+            .publish((symbol_short!("tok_block"), token), ());
+    }
+
+    /// Lift an emergency block on `token`. Only the admin may call this.
+    pub fn unblock_token(env: Env, token: Address) {
+        get_admin(&env).require_auth();
+        set_token_blocked(&env, &token, false);
+
+        env.events()
+            .publish((symbol_short!("tok_unblk"), token), ());
+    }
+
+    /// Return whether `token` is currently blocked.
+    pub fn is_token_blocked(env: Env, token: Address) -> bool {
+        is_token_blocked(&env, &token)
+    }
+
+    /// Replace the creation floors enforced in `create_stream`.
+    /// Existing streams are unaffected. Requires the parameter authority.
+    ///
+    /// # Panics
+    /// - If `min_deposit` is negative.
+    /// - If `max_active_per_sender` is set in a build without the `indexes`
+    ///   feature, which keeps no per-sender counts.
+    pub fn set_creation_limits(env: Env, limits: CreationLimits) {
+        require_params_authority(&env);
+        assert!(limits.min_deposit >= 0, "min_deposit must not be negative");
+        assert!(
+            cfg!(feature = "indexes") || limits.max_active_per_sender == 0,
+            "max_active_per_sender requires the indexes feature"
+        );
+        set_limits(&env, &limits);
+
+        env.events().publish((symbol_short!("limits"),), limits);
+    }
+
+    /// Return the creation floors and caps currently enforced.
+    pub fn get_creation_limits(env: Env) -> CreationLimits {
+        get_limits(&env)
+    }
+
+    /// Return the fee parameters currently in force.
+    pub fn get_fee_params(env: Env) -> FeeParams {
+        get_fee_params(&env)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Distressed assets
+// ---------------------------------------------------------------------------
+
+#[contractimpl]
+impl FluxoraStream {
+    /// Return a `Distressed` stream to the status it had before, once its
+    /// token is usable again: transfers from this contract to the sender and
+    /// the recipient succeed and the contract's balance covers everything it
+    /// owes. A Stellar-level clawback leaves a shortfall, which can be made
+    /// good by transferring tokens to the contract. Anyone may call this.
+    /// Returns the restored status.
+    ///
+    /// # Panics
+    /// - If the stream is not `Distressed`.
+    /// - If the token is still unusable.
+    pub fn recover_stream(env: Env, stream_id: u64) -> StreamStatus {
+        let mut stream = load_stream(&env, stream_id);
+        let key = DataKey::Distress(stream_id);
+        let distress: Distress = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("stream is not distressed");
+        assert!(
+            !asset_distressed(&env, &stream.token, &stream.sender)
+                && !asset_distressed(&env, &stream.token, &stream.recipient),
+            "token is still unusable"
+        );
+
+        stream.status = distress.previous;
+        save_stream_progress(&env, &stream);
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("recovered"), stream_id), stream.status);
+        stream.status
+    }
+
+    /// Return why `stream_id` is `Distressed`, if it is.
+    pub fn get_distress(env: Env, stream_id: u64) -> Option<Distress> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Distress(stream_id))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Admin cancellation and clawbacks (`admin-cancel` feature)
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "admin-cancel")]
+#[contractimpl]
+impl FluxoraStream {
+    /// Cancel a stream as the contract admin. Identical logic to cancel_stream.
+    ///
+    /// # Panics
+    /// - If the sender created the stream with `admin_cancelable: false`.
+    /// - If the stream is part of a basket; see `cancel_basket`.
+    pub fn cancel_stream_as_admin(env: Env, stream_id: u64) {
+        get_admin(&env).require_auth();
+        assert_not_globally_paused(&env);
+
+        let (stream, basis) = load_stream_with_basis(&env, stream_id);
+        assert!(stream.admin_cancelable, "stream is not admin-cancelable");
+        assert_not_in_basket(&env, stream_id);
+        Self::cancel_unchecked(&env, stream, basis);
+    }
+
+    /// Phase one of an admin clawback: record the justification and start the
+    /// `CLAWBACK_DELAY_SECS` notice period. The recipient keeps withdrawing
+    /// normally until execution. Returns the earliest execution time.
+    ///
+    /// # Panics
+    /// - If the sender created the stream with `admin_cancelable: false`.
+    /// - If the stream is not `Active` or `Paused`.
+    /// - If the stream is part of a basket or under dispute.
+    /// - If a clawback is already queued for the stream.
+    pub fn queue_clawback(env: Env, stream_id: u64, reason_hash: BytesN<32>) -> u64 {
+        get_admin(&env).require_auth();
+
+        let stream = load_stream(&env, stream_id);
+        assert!(stream.admin_cancelable, "stream is not admin-cancelable");
+        assert!(
+            stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused,
+            "stream must be active or paused to claw back"
+        );
+        assert_not_in_basket(&env, stream_id);
+        assert_not_disputed(&env, stream_id);
+
+        let key = DataKey::Clawback(stream_id);
+        assert!(
+            !env.storage().persistent().has(&key),
+            "clawback already queued"
+        );
+
+        let eta = env.ledger().timestamp() + CLAWBACK_DELAY_SECS;
+        let pending = PendingClawback {
+            reason_hash: reason_hash.clone(),
+            eta,
+        };
+        env.storage().persistent().set(&key, &pending);
+        extend_persistent_ttl(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("claw_q"), stream_id), (reason_hash, eta));
+        eta
+    }
+
+    /// Phase two of an admin clawback: transfer every unwithdrawn token of the
+    /// stream (accrued or not, plus any index escalation reserve) to the admin
+    /// and mark it `ClawedBack`.
+    /// Returns the amount reclaimed.
+    ///
+    /// # Panics
+    /// - If no clawback is queued or the notice period has not elapsed.
+    /// - If the stream is not admin-cancelable.
+    /// - If the stream is no longer `Active` or `Paused`.
+    /// - If the stream has since joined a basket or come under dispute.
+    pub fn execute_clawback(env: Env, stream_id: u64) -> i128 {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        let key = DataKey::Clawback(stream_id);
+        let pending: PendingClawback = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("no clawback queued");
+        assert!(
+            env.ledger().timestamp() >= pending.eta,
+            "clawback delay has not elapsed"
+        );
+
+        let mut stream = load_stream(&env, stream_id);
+        assert!(stream.admin_cancelable, "stream is not admin-cancelable");
+        assert!(
+            stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused,
+            "stream must be active or paused to claw back"
+        );
+        assert_not_in_basket(&env, stream_id);
+        assert_not_disputed(&env, stream_id);
+
+        let mut amount = math::sub(&env, stream.deposit_amount, stream.withdrawn_amount);
+        // An index-linked stream's unspent escalation reserve goes with it.
+        if let Some(link) = get_index_link(&env, stream_id) {
+            amount = math::add(&env, amount, link.reserve);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::IndexLink(stream_id));
+        }
+        vault::pay(&env, &stream.token, &admin, amount);
+
+        stream.status = StreamStatus::ClawedBack;
+        save_stream_progress(&env, &stream);
+        release_active_slot(&env, &stream.sender);
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("claw_x"), stream_id),
+            (pending.reason_hash, amount),
+        );
+        amount
+    }
+
+    /// Abandon a queued clawback. Only the admin may call this.
+    pub fn cancel_clawback(env: Env, stream_id: u64) {
+        get_admin(&env).require_auth();
+
+        let key = DataKey::Clawback(stream_id);
+        assert!(env.storage().persistent().has(&key), "no clawback queued");
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("claw_c"), stream_id), ());
+    }
+
+    /// Return the clawback queued against `stream_id`, if any.
+    pub fn get_pending_clawback(env: Env, stream_id: u64) -> Option<PendingClawback> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Clawback(stream_id))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Protocol fees and referrals (`fees` feature)
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fees")]
+#[contractimpl]
+impl FluxoraStream {
+    /// Queue new fee parameters behind the `FEE_TIMELOCK_SECS` delay.
+    /// Replaces any previously queued proposal. Requires the parameter authority.
+    ///
+    /// # Panics
+    /// - If any value exceeds its hard-coded maximum.
+    pub fn propose_fee_params(env: Env, params: FeeParams) -> u64 {
+        require_params_authority(&env);
+        validate_fee_params(&params);
+
+        let eta = env.ledger().timestamp() + FEE_TIMELOCK_SECS;
+        let pending = PendingFeeParams {
+            params: params.clone(),
+            eta,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingFeeParams, &pending);
+
+        env.events()
+            .publish((symbol_short!("fee_prop"),), (params, eta));
+        eta
+    }
+
+    /// Activate the queued fee parameters once the timelock has elapsed.
+    /// Requires the parameter authority.
+    ///
+    /// # Panics
+    /// - If nothing is queued or the delay has not elapsed.
+    pub fn apply_fee_params(env: Env) {
+        require_params_authority(&env);
+
+        let pending: PendingFeeParams = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingFeeParams)
+            .expect("no pending fee params");
+        assert!(
+            env.ledger().timestamp() >= pending.eta,
+            "fee timelock has not elapsed"
+        );
+        // Re-check in case the hard limits changed in an upgrade since queuing.
+        validate_fee_params(&pending.params);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeParams, &pending.params);
+        env.storage().instance().remove(&DataKey::PendingFeeParams);
+
+        env.events()
+            .publish((symbol_short!("fee_set"),), pending.params);
+    }
+
+    /// Discard the queued fee parameters. Requires the parameter authority.
+    pub fn cancel_fee_params(env: Env) {
+        require_params_authority(&env);
+        assert!(
+            env.storage().instance().has(&DataKey::PendingFeeParams),
+            "no pending fee params"
+        );
+        env.storage().instance().remove(&DataKey::PendingFeeParams);
+
+        env.events().publish((symbol_short!("fee_canc"),), ());
+    }
+
+    /// Return the fee parameters waiting on the timelock, if any.
+    pub fn get_pending_fee_params(env: Env) -> Option<PendingFeeParams> {
+        env.storage().instance().get(&DataKey::PendingFeeParams)
+    }
+
+    /// Set the share of each referred stream's protocol fee paid to its
+    /// referrer, in bps. Requires the parameter authority.
+    ///
+    /// # Panics
+    /// - If `share_bps` exceeds `MAX_REFERRAL_SHARE_BPS`.
+    pub fn set_referral_share(env: Env, share_bps: u32) {
+        require_params_authority(&env);
+        assert!(
+            share_bps <= MAX_REFERRAL_SHARE_BPS,
+            "referral share exceeds hard maximum"
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ReferralShare, &share_bps);
+
+        env.events()
+            .publish((symbol_short!("ref_share"),), share_bps);
+    }
+
+    /// Return the share of protocol fees paid to referrers, in bps.
+    pub fn get_referral_share(env: Env) -> u32 {
+        get_referral_share(&env)
+    }
+
+    /// Pay `referrer` their earned fee share in `token`. Only the referrer
+    /// may call this. Returns the amount paid.
+    ///
+    /// # Panics
+    /// - If nothing is owed.
+    pub fn claim_referral(env: Env, referrer: Address, token: Address) -> i128 {
+        referrer.require_auth();
+        let mut account = get_referral(&env, &referrer, &token);
+        let amount = account.owed;
+        assert!(amount > 0, "no referral fees to claim");
+
+        account.owed = 0;
+        save_referral(&env, &referrer, &token, &account);
+        vault::pay(&env, &token, &referrer, amount);
+
+        env.events()
+            .publish((symbol_short!("ref_paid"), referrer), (token, amount));
+        amount
+    }
+
+    /// Return `referrer`'s attributed streams, volume and unclaimed fees in
+    /// `token`.
+    pub fn get_referral(env: Env, referrer: Address, token: Address) -> ReferralAccount {
+        get_referral(&env, &referrer, &token)
+    }
 }
 
 // ---------------------------------------------------------------------------
-// Storage helpers
+// Hook contracts (`hooks` feature)
 // ---------------------------------------------------------------------------
 
-fn get_config(env: &Env) -> Config {
-    env.storage()
-        .instance()
-        .get(&DataKey::Config)
-        .expect("contract not initialised: missing config")
-}
+#[cfg(feature = "hooks")]
+#[contractimpl]
+impl FluxoraStream {
+    /// Configure (or clear, with `None`) the attestation registry consulted by
+    /// `withdraw`. The registry must implement `AttestationRegistry`.
+    /// Requires the parameter authority.
+    pub fn set_attestation_registry(env: Env, registry: Option<Address>) {
+        require_params_authority(&env);
+        match &registry {
+            Some(registry) => env
+                .storage()
+                .instance()
+                .set(&DataKey::AttestationRegistry, registry),
+            None => env
+                .storage()
+                .instance()
+                .remove(&DataKey::AttestationRegistry),
+        }
 
-fn get_token(env: &Env) -> Address {
-    get_config(env).token
-}
+        env.events().publish((symbol_short!("attest"),), registry);
+    }
 
-fn get_admin(env: &Env) -> Address {
-    get_config(env).admin
-}
+    /// Return the attestation registry gating withdrawals, if any.
+    pub fn get_attestation_registry(env: Env) -> Option<Address> {
+        get_attestation_registry(&env)
+    }
 
-fn get_stream_count(env: &Env) -> u64 {
-    env.storage()
-        .instance()
-        .get(&DataKey::NextStreamId)
-        .unwrap_or(0u64)
-}
+    /// Register (or clear, with `None`) a transfer policy contract implementing
+    /// `TransferPolicy`, consulted on every creation and withdrawal.
+    /// Requires the parameter authority.
+    pub fn set_transfer_policy(env: Env, policy: Option<Address>) {
+        require_params_authority(&env);
+        match &policy {
+            Some(policy) => env
+                .storage()
+                .instance()
+                .set(&DataKey::TransferPolicy, policy),
+            None => env.storage().instance().remove(&DataKey::TransferPolicy),
+        }
 
-fn set_stream_count(env: &Env, count: u64) {
-    env.storage().instance().set(&DataKey::NextStreamId, &count);
-}
+        env.events().publish((symbol_short!("policy"),), policy);
+    }
 
-fn load_stream(env: &Env, stream_id: u64) -> Stream {
-    env.storage()
-        .persistent()
-        .get(&DataKey::Stream(stream_id))
-        .expect("stream not found")
-}
+    /// Return the registered transfer policy, if any.
+    pub fn get_transfer_policy(env: Env) -> Option<Address> {
+        get_transfer_policy(&env)
+    }
+
+    /// Register (or clear, with `None`) the reputation registry implementing
+    /// `ReputationRegistry`, told how every stream ends.
+    /// Requires the parameter authority.
+    pub fn set_reputation_registry(env: Env, registry: Option<Address>) {
+        require_params_authority(&env);
+        match &registry {
+            Some(registry) => env
+                .storage()
+                .instance()
+                .set(&DataKey::ReputationRegistry, registry),
+            None => env
+                .storage()
+                .instance()
+                .remove(&DataKey::ReputationRegistry),
+        }
 
-fn save_stream(env: &Env, stream: &Stream) {
-    let key = DataKey::Stream(stream.stream_id);
-    env.storage().persistent().set(&key, stream);
+        env.events().publish((symbol_short!("rep_reg"),), registry);
+    }
 
-    // Requirement from Issue #1: extend TTL on stream save to ensure persistence
-    env.storage().persistent().extend_ttl(&key, 17280, 120960);
+    /// Return the registered reputation registry, if any.
+    pub fn get_reputation_registry(env: Env) -> Option<Address> {
+        get_reputation_registry(&env)
+    }
 }
 
 // ---------------------------------------------------------------------------
-// Contract Implementation
+// Basket streams (`baskets` feature)
 // ---------------------------------------------------------------------------
 
-#[contract]
-pub struct FluxoraStream;
+/// Scaled rate that streams `amount` over `duration` ticks, rounded up so
+/// the whole amount has accrued by the end without exceeding it.
+#[cfg(feature = "baskets")]
+fn basket_rate(env: &Env, amount: i128, duration: u64) -> u128 {
+    let scaled = (amount as u128)
+        .checked_mul(RATE_SCALE)
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow))
+        .div_ceil(duration as u128);
+    match FlowRate::Scaled(scaled).streamed(duration) {
+        Some(streamed) if streamed <= amount => scaled,
+        _ => scaled - 1,
+    }
+}
 
+#[cfg(feature = "baskets")]
 #[contractimpl]
 impl FluxoraStream {
-    /// Initialise the contract with the streaming token and admin address.
-    /// Can only be called once. Sets up global Config and ID counter.
-    pub fn init(env: Env, token: Address, admin: Address) {
-        if env.storage().instance().has(&DataKey::Config) {
-            panic!("already initialised");
-        }
-        let config = Config { token, admin };
-        env.storage().instance().set(&DataKey::Config, &config);
-        env.storage().instance().set(&DataKey::NextStreamId, &0u64);
-
-        // Ensure instance storage (Config/ID) doesn't expire quickly
-        env.storage().instance().extend_ttl(17280, 120960);
-    }
-
-    /// Create a new payment stream.
-    ///
-    /// Transfers `deposit_amount` of the stream token from `sender` to this
-    /// contract and stores all stream parameters. Returns the new stream id.
+    /// Create one stream per component, in its token, all sharing one
+    /// schedule, and group them as a basket. Each component streams its
+    /// `amount` evenly over the schedule, so every token vests by the same
+    /// fraction at any time. Components cannot be paused, cancelled or
+    /// reassigned on their own, so they never diverge; `withdraw_basket` and
+    /// `cancel_basket` settle them together. Returns the basket id.
     ///
     /// # Panics
-    /// - If `deposit_amount` or `rate_per_second` is not positive.
-    /// - If `sender` and `recipient` are the same address.
+    /// - If `components` is empty, longer than `MAX_BASKET_COMPONENTS` or
+    ///   repeats a token.
     /// - If `start_time >= end_time`.
-    /// - If `cliff_time` is not in `[start_time, end_time]`.
-    /// - If `deposit_amount < rate_per_second * (end_time - start_time)` (insufficient deposit).
-    /// - If token transfer fails (e.g., insufficient balance or allowance).
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_stream(
+    /// - Any of the `create_stream_with_token` validation failures, for any
+    ///   component.
+    pub fn create_basket_stream(
         env: Env,
         sender: Address,
         recipient: Address,
-        deposit_amount: i128,
-        rate_per_second: i128,
+        components: Vec<BasketComponent>,
         start_time: u64,
         cliff_time: u64,
         end_time: u64,
     ) -> u64 {
-        sender.require_auth();
-
-        // Validate positive amounts (#35)
-        assert!(deposit_amount > 0, "deposit_amount must be positive");
-        assert!(rate_per_second > 0, "rate_per_second must be positive");
-
-        // Validate sender != recipient (#35)
+        assert!(!components.is_empty(), "basket has no components");
         assert!(
-            sender != recipient,
-            "sender and recipient must be different"
+            components.len() <= MAX_BASKET_COMPONENTS,
+            "too many basket components"
         );
-
-        // Validate time constraints
         assert!(start_time < end_time, "start_time must be before end_time");
-        assert!(
-            cliff_time >= start_time && cliff_time <= end_time,
-            "cliff_time must be within [start_time, end_time]"
-        );
-
-        // Validate deposit covers total streamable amount (#34)
-        let duration = (end_time - start_time) as i128;
-        let total_streamable = rate_per_second
-            .checked_mul(duration)
-            .expect("overflow calculating total streamable amount");
-        assert!(
-            deposit_amount >= total_streamable,
-            "deposit_amount must cover total streamable amount (rate * duration)"
-        );
-
-        // Transfer tokens from sender to this contract (#36)
-        // If transfer fails (insufficient balance/allowance), this will panic
-        // and no state will be persisted (atomic transaction)
-        let token_client = token::Client::new(&env, &get_token(&env));
-        token_client.transfer(&sender, &env.current_contract_address(), &deposit_amount);
+        for (i, component) in components.iter().enumerate() {
+            assert!(
+                components.iter().position(|c| c.token == component.token) == Some(i),
+                "duplicate basket token"
+            );
+        }
 
-        // Only allocate stream id and persist state AFTER successful transfer
-        let stream_id = get_stream_count(&env);
-        set_stream_count(&env, stream_id + 1);
+        sender.require_auth();
+        let duration = end_time - start_time;
+        let mut streams = Vec::new(&env);
+        for component in components.iter() {
+            assert!(component.amount > 0, "deposit_amount must be positive");
+            let stream_id = Self::create_stream_internal(
+                &env,
+                sender.clone(),
+                recipient.clone(),
+                component.token,
+                component.amount,
+                FlowRate::Scaled(basket_rate(&env, component.amount, duration)),
+                start_time,
+                cliff_time,
+                end_time,
+                StreamOptions::default(),
+                Creator::Signed,
+            );
+            streams.push_back(stream_id);
+        }
 
-        let stream = Stream {
-            stream_id,
+        let basket_id = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextBasketId)
+            .unwrap_or(0u64);
+        let key = DataKey::NextBasketId;
+        env.storage().persistent().set(&key, &(basket_id + 1));
+        extend_persistent_ttl(&env, &key);
+        for stream_id in streams.iter() {
+            let key = DataKey::BasketOf(stream_id);
+            env.storage().persistent().set(&key, &basket_id);
+            extend_persistent_ttl(&env, &key);
+        }
+        let key = DataKey::Basket(basket_id);
+        let basket = Basket {
             sender,
             recipient,
-            deposit_amount,
-            rate_per_second,
-            start_time,
-            cliff_time,
-            end_time,
-            withdrawn_amount: 0,
-            status: StreamStatus::Active,
+            streams: streams.clone(),
         };
-
-        save_stream(&env, &stream);
+        env.storage().persistent().set(&key, &basket);
+        extend_persistent_ttl(&env, &key);
 
         env.events()
-            .publish((symbol_short!("created"), stream_id), deposit_amount);
-
-        stream_id
+            .publish((symbol_short!("basket"), basket_id), streams);
+        basket_id
     }
 
-    /// Pause an active stream. Only the sender or admin may call this.
+    /// Withdraw every component's withdrawable balance to the recipient, as
+    /// `withdraw` would. Returns the amount delivered per component, in
+    /// basket order, with 0 for those that had nothing to pay.
+    ///
     /// # Panics
-    /// - If the stream is not in `Active` state.
-    pub fn pause_stream(env: Env, stream_id: u64) {
-        let mut stream = load_stream(&env, stream_id);
-
-        // Corrected Auth Check
-        Self::require_sender_or_admin(&env, &stream.sender);
-
-        assert!(
-            stream.status == StreamStatus::Active,
-            "stream is not active"
-        );
-
-        stream.status = StreamStatus::Paused;
-        save_stream(&env, &stream);
+    /// - If the basket does not exist.
+    /// - If no component has anything to withdraw.
+    /// - Any of the `withdraw` failures, for a component with a balance.
+    pub fn withdraw_basket(env: Env, basket_id: u64) -> Vec<i128> {
+        let basket = Self::get_basket(env.clone(), basket_id);
+        basket.recipient.require_auth();
 
-        env.events()
-            .publish((symbol_short!("paused"), stream_id), ());
+        assert_not_globally_paused(&env);
+        let mut delivered = Vec::new(&env);
+        let mut any = false;
+        for stream_id in basket.streams.iter() {
+            let (stream, basis) = load_stream_with_basis(&env, stream_id);
+            if stream_view(&env, stream.clone(), &basis).withdrawable_now > 0 {
+                any = true;
+                delivered.push_back(Self::withdraw_authorized(
+                    &env,
+                    stream,
+                    &basis,
+                    get_operator(&env, stream_id),
+                    Payout::Recipient,
+                ));
+            } else {
+                delivered.push_back(0);
+            }
+        }
+        assert!(any, "nothing to withdraw");
+        delivered
     }
 
-    /// Resume a paused stream. Only the sender or admin may call this.
+    /// Pause every active component under a guardian hold, as
+    /// `guardian_pause_stream` would, so the components stay in step. Only
+    /// the guardian may call this.
+    ///
     /// # Panics
-    /// - If the stream is not in `Paused` state.
-    pub fn resume_stream(env: Env, stream_id: u64) {
-        let mut stream = load_stream(&env, stream_id);
-        Self::require_sender_or_admin(&env, &stream.sender);
-
-        assert!(
-            stream.status == StreamStatus::Paused,
-            "stream is not paused"
-        );
-
-        stream.status = StreamStatus::Active;
-        save_stream(&env, &stream);
+    /// - If the basket does not exist or no component is active.
+    pub fn guardian_pause_basket(env: Env, basket_id: u64) {
+        get_guardian(&env).require_auth();
+        let basket = Self::get_basket(env.clone(), basket_id);
 
-        env.events()
-            .publish((symbol_short!("resumed"), stream_id), ());
+        let mut any = false;
+        for stream_id in basket.streams.iter() {
+            let mut stream = load_stream(&env, stream_id);
+            if stream.status == StreamStatus::Active {
+                any = true;
+                place_guardian_hold(&env, &mut stream);
+            }
+        }
+        assert!(any, "basket has no active streams");
     }
 
-    /// Cancel a stream and refund unstreamed funds to the sender.
+    /// Cancel every open component, as `cancel_stream` would: each refunds
+    /// its unstreamed share to the sender and leaves its accrued share for
+    /// the recipient. Only the sender or admin may call this. If any
+    /// component's token can no longer reach the sender, those components
+    /// become `Distressed` and none is cancelled, so the basket is never
+    /// left half-cancelled; recover them and cancel again.
     ///
-    /// ## Behaviour
-    /// 1. **Auth** — only the original sender or the contract admin can cancel.
-    /// 2. **State check** — only `Active` or `Paused` streams can be cancelled.
-    /// 3. **Accrual** — computes `accrued = min((now − start_time) × rate, deposit_amount)`.
-    /// 4. **Refund** — transfers `deposit_amount − accrued` back to the sender immediately.
-    /// 5. **Persistence** — the portion `accrued − withdrawn_amount` remains for the recipient.
-    pub fn cancel_stream(env: Env, stream_id: u64) {
-        let mut stream = load_stream(&env, stream_id);
-        Self::require_sender_or_admin(&env, &stream.sender);
-
-        assert!(
-            stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused,
-            "stream must be active or paused to cancel"
-        );
-
-        let accrued = Self::calculate_accrued(env.clone(), stream_id);
-        let unstreamed = stream.deposit_amount - accrued;
+    /// # Panics
+    /// - If the basket does not exist or no component is open.
+    /// - If the protocol is under an emergency pause.
+    pub fn cancel_basket(env: Env, basket_id: u64) {
+        assert_not_globally_paused(&env);
+        let basket = Self::get_basket(env.clone(), basket_id);
+        Self::require_sender_or_admin(&env, &basket.sender);
 
-        if unstreamed > 0 {
-            let token_client = token::Client::new(&env, &get_token(&env));
-            token_client.transfer(&env.current_contract_address(), &stream.sender, &unstreamed);
+        let mut open = Vec::new(&env);
+        let mut distressed = false;
+        for stream_id in basket.streams.iter() {
+            let (mut stream, basis) = load_stream_with_basis(&env, stream_id);
+            if !matches!(stream.status, StreamStatus::Active | StreamStatus::Paused) {
+                continue;
+            }
+            let unstreamed = math::sub(
+                &env,
+                stream.deposit_amount,
+                accrued_now(&env, &stream, &basis),
+            );
+            let sender = stream.sender.clone();
+            if !stream.credit_refunds
+                && unstreamed > 0
+                && enter_distress(&env, &mut stream, &sender)
+            {
+                distressed = true;
+            } else {
+                open.push_back(stream_id);
+            }
         }
+        if distressed {
+            return;
+        }
+        assert!(!open.is_empty(), "basket has no open streams");
 
-        stream.status = StreamStatus::Cancelled;
-        save_stream(&env, &stream);
+        for stream_id in open.iter() {
+            let (stream, basis) = load_stream_with_basis(&env, stream_id);
+            Self::cancel_unchecked(&env, stream, basis);
+        }
 
         env.events()
-            .publish((symbol_short!("cancelled"), stream_id), unstreamed);
+            .publish((symbol_short!("bskt_cncl"), basket_id), ());
     }
 
-    /// Withdraw accrued-but-not-yet-withdrawn tokens to the recipient.
-    /// Returns the amount transferred.
+    /// Return a basket's parties and component streams.
     ///
     /// # Panics
-    /// - If the stream is `Completed` (nothing left to withdraw).
-    /// - If the stream is `Paused` (withdrawals not allowed while paused).
-    /// - If there is nothing to withdraw (accrued == withdrawn).
-    pub fn withdraw(env: Env, stream_id: u64) -> i128 {
-        let mut stream = load_stream(&env, stream_id);
-        stream.recipient.require_auth();
+    /// - If the basket does not exist.
+    pub fn get_basket(env: Env, basket_id: u64) -> Basket {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Basket(basket_id))
+            .expect("basket not found")
+    }
 
-        // Reject if stream is completed (#37)
-        assert!(
-            stream.status != StreamStatus::Completed,
-            "stream already completed"
-        );
+    /// Return the basket `stream_id` is a component of, if any.
+    pub fn get_basket_of(env: Env, stream_id: u64) -> Option<u64> {
+        get_basket_of(&env, stream_id)
+    }
+}
 
-        // Reject if stream is paused - no withdrawals allowed while paused (#37)
+// ---------------------------------------------------------------------------
+// Index-adjusted rates (`index-rates` feature)
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "index-rates")]
+#[contractimpl]
+impl FluxoraStream {
+    /// Set who may publish the rate index and how far and how often it may
+    /// move. Only the admin may call this.
+    ///
+    /// # Panics
+    /// - If `max_step_bps` exceeds `BPS_DENOMINATOR`.
+    pub fn set_index_params(env: Env, params: IndexParams) {
+        get_admin(&env).require_auth();
         assert!(
-            stream.status != StreamStatus::Paused,
-            "cannot withdraw from paused stream"
+            params.max_step_bps <= BPS_DENOMINATOR,
+            "max_step_bps exceeds 100%"
         );
+        env.storage().instance().set(&DataKey::IndexParams, &params);
+        env.events().publish((symbol_short!("idx_prms"),), params);
+    }
 
-        let accrued = Self::calculate_accrued(env.clone(), stream_id);
-        let withdrawable = accrued - stream.withdrawn_amount;
-        assert!(withdrawable > 0, "nothing to withdraw");
-
-        let token_client = token::Client::new(&env, &get_token(&env));
-        token_client.transfer(
-            &env.current_contract_address(),
-            &stream.recipient,
-            &withdrawable,
+    /// Publish a new index value, scaled by `INDEX_SCALE`, as the admin or
+    /// the configured oracle. Index-linked streams settle at the latest value
+    /// on each withdrawal. The first value may be anything positive; later
+    /// ones may move at most `max_step_bps` from the previous value, and no
+    /// sooner than `min_interval` seconds after it.
+    ///
+    /// # Panics
+    /// - If index parameters have not been set.
+    /// - If `publisher` is neither the admin nor the oracle.
+    /// - If `value` is zero, moves too far or comes too soon.
+    pub fn update_index(env: Env, publisher: Address, value: u128) {
+        publisher.require_auth();
+        let params: IndexParams = env
+            .storage()
+            .instance()
+            .get(&DataKey::IndexParams)
+            .expect("index is not configured");
+        assert!(
+            publisher == get_admin(&env) || params.oracle.as_ref() == Some(&publisher),
+            "not an index publisher"
         );
+        assert!(value > 0, "index must be positive");
 
-        stream.withdrawn_amount += withdrawable;
-
-        if stream.status == StreamStatus::Active
-            && env.ledger().timestamp() >= stream.end_time
-            && stream.withdrawn_amount == stream.deposit_amount
-        {
-            stream.status = StreamStatus::Completed;
+        let now = env.ledger().timestamp();
+        if let Some(previous) = get_index(&env) {
+            assert!(
+                now >= previous.updated_at.saturating_add(params.min_interval),
+                "index updated too recently"
+            );
+            let max_step = previous
+                .value
+                .checked_mul(params.max_step_bps as u128)
+                .expect("overflow")
+                / BPS_DENOMINATOR as u128;
+            assert!(
+                value.abs_diff(previous.value) <= max_step,
+                "index change exceeds max step"
+            );
         }
-
-        save_stream(&env, &stream);
-        env.events()
-            .publish((symbol_short!("withdrew"), stream_id), withdrawable);
-        withdrawable
+        let index = IndexValue {
+            value,
+            updated_at: now,
+        };
+        env.storage().instance().set(&DataKey::Index, &index);
+        env.events().publish((symbol_short!("index"),), value);
     }
 
-    /// Calculate the total amount accrued to the recipient so far.
-    pub fn calculate_accrued(env: Env, stream_id: u64) -> i128 {
-        let stream = load_stream(&env, stream_id);
-        let now = env.ledger().timestamp();
-
-        if now < stream.cliff_time {
-            return 0;
-        }
+    /// Create a stream in an allowlisted `token` whose payouts follow the
+    /// index: each withdrawal pays the nominal amount accrued at
+    /// `rate_per_second` scaled by the prevailing index over its value now,
+    /// by at most `cap_bps`. On top of `deposit_amount`, a reserve of
+    /// `deposit_amount × cap_bps / 10_000` is taken from the sender to fund
+    /// escalations. Whatever the reserve isn't needed for is refunded when
+    /// the stream is cancelled or paid out.
+    ///
+    /// # Panics
+    /// - If no index value has been published.
+    /// - If `cap_bps` exceeds `MAX_INDEX_CAP_BPS`.
+    /// - Any of the `create_stream_with_token` validation failures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_indexed_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        cap_bps: u32,
+    ) -> u64 {
+        let index = get_index(&env).expect("index has not been published");
+        assert!(cap_bps <= MAX_INDEX_CAP_BPS, "cap_bps too high");
 
-        let elapsed = (now.min(stream.end_time)).saturating_sub(stream.start_time) as i128;
-        let accrued = elapsed * stream.rate_per_second;
+        let stream_id = Self::create_stream_internal(
+            &env,
+            sender.clone(),
+            recipient,
+            token.clone(),
+            deposit_amount,
+            FlowRate::Whole(rate_per_second),
+            start_time,
+            cliff_time,
+            end_time,
+            StreamOptions::default(),
+            Creator::Sender,
+        );
+        let deposited = load_stream(&env, stream_id).deposit_amount;
+        let reserve = match math::bps(&env, deposited, cap_bps) {
+            0 => 0,
+            amount => vault::deposit(&env, &token, &sender, amount),
+        };
+        let link = IndexLink {
+            base_index: index.value,
+            cap_bps,
+            reserve,
+        };
+        save_index_link(&env, stream_id, &link);
 
-        accrued.min(stream.deposit_amount)
+        env.events()
+            .publish((symbol_short!("idx_link"), stream_id), link);
+        stream_id
     }
 
-    /// Fetches the global configuration.
-    pub fn get_config(env: Env) -> Config {
-        get_config(&env)
+    /// Return the latest published index value, if any.
+    pub fn get_index(env: Env) -> Option<IndexValue> {
+        get_index(&env)
     }
 
-    /// Return the current state of the stream identified by `stream_id`.
-    pub fn get_stream_state(env: Env, stream_id: u64) -> Stream {
-        load_stream(&env, stream_id)
+    /// Return the index update bounds, if set.
+    pub fn get_index_params(env: Env) -> Option<IndexParams> {
+        env.storage().instance().get(&DataKey::IndexParams)
     }
 
-    /// Internal helper to check authorization for sender or admin.
-    fn require_sender_or_admin(env: &Env, sender: &Address) {
-        let admin = get_admin(env);
-
-        // If the admin is the one calling, they must authorize.
-        // Otherwise, the sender must authorize.
-        if sender != &admin {
-            // This allows the admin to bypass the sender's auth
-            // if we use a separate admin entrypoint, or we can
-            // rely on the transaction signatures.
-            sender.require_auth();
-        } else {
-            admin.require_auth();
-        }
+    /// Return how `stream_id` follows the index, if it is index-linked and
+    /// not yet paid out.
+    pub fn get_index_link(env: Env, stream_id: u64) -> Option<IndexLink> {
+        get_index_link(&env, stream_id)
     }
 }
 
+// ---------------------------------------------------------------------------
+// Indexes (`indexes` feature)
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "indexes")]
 #[contractimpl]
 impl FluxoraStream {
-    /// Cancel a stream as the contract admin. Identical logic to cancel_stream.
-    pub fn cancel_stream_as_admin(env: Env, stream_id: u64) {
-        get_admin(&env).require_auth();
-        Self::cancel_stream(env, stream_id);
+    /// Return every recipient currently under a compliance hold.
+    pub fn get_frozen_recipients(env: Env) -> Vec<Address> {
+        get_frozen_list(&env)
+    }
+
+    /// Return the number of open (Active or Paused) streams funded by `sender`.
+    pub fn get_active_stream_count(env: Env, sender: Address) -> u32 {
+        get_active_count(&env, &sender)
     }
 }
 
-#[cfg(test)]
+// The main suite exercises every optional subsystem; `test_minimal` holds the
+// tests that also run under `--no-default-features`.
+#[cfg(all(
+    test,
+    feature = "admin-cancel",
+    feature = "baskets",
+    feature = "fees",
+    feature = "hooks",
+    feature = "index-rates",
+    feature = "indexes"
+))]
+mod model;
+#[cfg(all(
+    test,
+    feature = "admin-cancel",
+    feature = "baskets",
+    feature = "fees",
+    feature = "hooks",
+    feature = "index-rates",
+    feature = "indexes"
+))]
 mod test;
+#[cfg(test)]
+mod test_minimal;
@@ -0,0 +1,54 @@
+//! Checked arithmetic on token amounts.
+//!
+//! Amount math in this contract goes through these helpers, so an overflow
+//! surfaces as `Error::Overflow` rather than an opaque host trap. They work
+//! on the shared `Amount` type and the `fluxora_math` accrual functions, and
+//! map their checked results onto that error.
+
+use fluxora_core::Amount;
+use fluxora_math::{FlowRate, Schedule};
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::Error;
+
+fn or_overflow(env: &Env, amount: Option<Amount>) -> i128 {
+    amount
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow))
+        .into()
+}
+
+pub(crate) fn add(env: &Env, a: i128, b: i128) -> i128 {
+    or_overflow(env, Amount(a).checked_add(Amount(b)))
+}
+
+pub(crate) fn sub(env: &Env, a: i128, b: i128) -> i128 {
+    or_overflow(env, Amount(a).checked_sub(Amount(b)))
+}
+
+pub(crate) fn mul(env: &Env, a: i128, b: i128) -> i128 {
+    or_overflow(env, Amount(a).checked_mul(b))
+}
+
+/// Amount streamed at `rate` over `elapsed` ticks, rounded down.
+pub(crate) fn streamed(env: &Env, rate: FlowRate, elapsed: u64) -> i128 {
+    or_overflow(env, rate.streamed(elapsed).map(Amount))
+}
+
+/// Amount accrued on `schedule` at `now`, capped at `deposit`.
+pub(crate) fn accrued(
+    env: &Env,
+    schedule: &Schedule,
+    rate: FlowRate,
+    deposit: i128,
+    now: u64,
+) -> i128 {
+    or_overflow(
+        env,
+        fluxora_math::accrued(schedule, rate, deposit, now).map(Amount),
+    )
+}
+
+/// `amount × bps / BPS_DENOMINATOR`, rounded down.
+pub(crate) fn bps(env: &Env, amount: i128, bps: u32) -> i128 {
+    or_overflow(env, fluxora_math::bps(amount, bps).map(Amount))
+}
@@ -0,0 +1,105 @@
+//! Versioned layouts of the stream records.
+//!
+//! `StreamTerms` and `StreamProgress` carry a `version`. Records written
+//! before versioning have no such field and count as version 1. Reading an
+//! older record upgrades it in memory, and the contract writes it back in
+//! the current layout the next time it saves that record, so no entry ever
+//! has to be migrated up front.
+//!
+//! To change a layout, bump `STREAM_RECORD_VERSION`, keep the old struct
+//! here and add its arm to the loader.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, TryFromVal, Val};
+
+use crate::{AccrualBasis, DataKey, StreamProgress, StreamStatus, StreamTerms};
+
+/// Layout version written by this build.
+pub(crate) const STREAM_RECORD_VERSION: u32 = 2;
+
+/// Terms as stored before versioning.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub(crate) struct StreamTermsV1 {
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+    pub admin_cancelable: bool,
+    pub credit_refunds: bool,
+    pub basis: AccrualBasis,
+}
+
+/// Progress as stored before versioning, without a settlement time.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub(crate) struct StreamProgressV1 {
+    pub withdrawn_amount: i128,
+    pub status: StreamStatus,
+}
+
+impl From<StreamTermsV1> for StreamTerms {
+    fn from(v1: StreamTermsV1) -> Self {
+        StreamTerms {
+            version: STREAM_RECORD_VERSION,
+            sender: v1.sender,
+            recipient: v1.recipient,
+            token: v1.token,
+            deposit_amount: v1.deposit_amount,
+            rate_per_second: v1.rate_per_second,
+            start_time: v1.start_time,
+            cliff_time: v1.cliff_time,
+            end_time: v1.end_time,
+            admin_cancelable: v1.admin_cancelable,
+            credit_refunds: v1.credit_refunds,
+            basis: v1.basis,
+        }
+    }
+}
+
+impl From<StreamProgressV1> for StreamProgress {
+    /// The settlement time was never recorded, so a stream settled before
+    /// the upgrade counts as settled since time zero for `close_stream`.
+    fn from(v1: StreamProgressV1) -> Self {
+        StreamProgress {
+            version: STREAM_RECORD_VERSION,
+            withdrawn_amount: v1.withdrawn_amount,
+            status: v1.status,
+            settled_at: 0,
+        }
+    }
+}
+
+/// Read the record under `key` in whichever layout it was written, or
+/// `None` if there is none.
+fn load<Current, V1>(env: &Env, key: &DataKey) -> Option<Current>
+where
+    Current: TryFromVal<Env, Val>,
+    V1: TryFromVal<Env, Val> + Into<Current>,
+{
+    let record: Map<Symbol, Val> = env.storage().persistent().get(key)?;
+    let version = match record.get(symbol_short!("version")) {
+        Some(version) => u32::try_from_val(env, &version).expect("malformed stream record"),
+        None => 1,
+    };
+    let record = record.to_val();
+    Some(match version {
+        STREAM_RECORD_VERSION => Current::try_from_val(env, &record)
+            .unwrap_or_else(|_| panic!("malformed stream record")),
+        1 => V1::try_from_val(env, &record)
+            .unwrap_or_else(|_| panic!("malformed stream record"))
+            .into(),
+        _ => panic!("unsupported stream record version"),
+    })
+}
+
+pub(crate) fn load_terms(env: &Env, stream_id: u64) -> Option<StreamTerms> {
+    load::<StreamTerms, StreamTermsV1>(env, &DataKey::Stream(stream_id))
+}
+
+pub(crate) fn load_progress(env: &Env, stream_id: u64) -> Option<StreamProgress> {
+    load::<StreamProgress, StreamProgressV1>(env, &DataKey::StreamProgress(stream_id))
+}
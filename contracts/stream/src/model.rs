@@ -0,0 +1,162 @@
+//! Reference model of the stream state machine, used by the differential
+//! tests in `test.rs`.
+//!
+//! Plain Rust with no host: accounts are indexes, balances are integers and
+//! each operation is written from the documented rules rather than from the
+//! contract code, so the two can be checked against each other.
+
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::StreamStatus;
+
+/// Why the model rejected an operation. Only used to tell success from
+/// failure; the contract's panic messages are not compared.
+#[derive(Debug)]
+pub(crate) struct Rejected;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ModelStream {
+    pub sender: usize,
+    pub recipient: usize,
+    pub deposit: i128,
+    pub rate: i128,
+    pub start: u64,
+    pub cliff: u64,
+    pub end: u64,
+    pub withdrawn: i128,
+    pub status: StreamStatus,
+}
+
+impl ModelStream {
+    /// Nothing before the cliff, then `rate` per second since `start`,
+    /// capped at the deposit.
+    pub fn accrued(&self, now: u64) -> i128 {
+        if now < self.cliff {
+            return 0;
+        }
+        ((now.min(self.end) - self.start) as i128 * self.rate).min(self.deposit)
+    }
+}
+
+/// Balances of a fixed set of accounts and the contract, plus every stream.
+pub(crate) struct Model {
+    pub now: u64,
+    pub balances: Vec<i128>,
+    pub contract_balance: i128,
+    pub streams: Vec<ModelStream>,
+}
+
+fn check(condition: bool) -> Result<(), Rejected> {
+    if condition {
+        Ok(())
+    } else {
+        Err(Rejected)
+    }
+}
+
+impl Model {
+    pub fn new(balances: Vec<i128>) -> Self {
+        Model {
+            now: 0,
+            balances,
+            contract_balance: 0,
+            streams: Vec::new(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &mut self,
+        sender: usize,
+        recipient: usize,
+        deposit: i128,
+        rate: i128,
+        start: u64,
+        cliff: u64,
+        end: u64,
+    ) -> Result<u64, Rejected> {
+        check(deposit > 0 && rate > 0)?;
+        check(sender != recipient)?;
+        check(start < end && cliff >= start && cliff <= end)?;
+        check(deposit >= rate * (end - start) as i128)?;
+        check(self.balances[sender] >= deposit)?;
+
+        self.balances[sender] -= deposit;
+        self.contract_balance += deposit;
+        self.streams.push(ModelStream {
+            sender,
+            recipient,
+            deposit,
+            rate,
+            start,
+            cliff,
+            end,
+            withdrawn: 0,
+            status: StreamStatus::Active,
+        });
+        Ok(self.streams.len() as u64 - 1)
+    }
+
+    /// Pay the recipient everything accrued and not yet withdrawn. Paused
+    /// and completed streams cannot be withdrawn from.
+    pub fn withdraw(&mut self, id: u64) -> Result<i128, Rejected> {
+        let now = self.now;
+        let stream = &mut self.streams[id as usize];
+        check(stream.status != StreamStatus::Completed && stream.status != StreamStatus::Paused)?;
+        let amount = stream.accrued(now) - stream.withdrawn;
+        check(amount > 0)?;
+
+        stream.withdrawn += amount;
+        if stream.status == StreamStatus::Active
+            && now >= stream.end
+            && stream.withdrawn == stream.deposit
+        {
+            stream.status = StreamStatus::Completed;
+        }
+        let recipient = stream.recipient;
+        self.contract_balance -= amount;
+        self.balances[recipient] += amount;
+        Ok(amount)
+    }
+
+    pub fn pause(&mut self, id: u64) -> Result<(), Rejected> {
+        let stream = &mut self.streams[id as usize];
+        check(stream.status == StreamStatus::Active)?;
+        stream.status = StreamStatus::Paused;
+        Ok(())
+    }
+
+    pub fn resume(&mut self, id: u64) -> Result<(), Rejected> {
+        let stream = &mut self.streams[id as usize];
+        check(stream.status == StreamStatus::Paused)?;
+        stream.status = StreamStatus::Active;
+        Ok(())
+    }
+
+    /// Refund the unstreamed part to the sender; what already accrued stays
+    /// withdrawable by the recipient.
+    pub fn cancel(&mut self, id: u64) -> Result<(), Rejected> {
+        let now = self.now;
+        let stream = &mut self.streams[id as usize];
+        check(stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused)?;
+        let accrued = stream.accrued(now);
+        let refund = stream.deposit - accrued;
+
+        stream.deposit = accrued;
+        stream.status = StreamStatus::Cancelled;
+        let sender = stream.sender;
+        self.contract_balance -= refund;
+        self.balances[sender] += refund;
+        Ok(())
+    }
+
+    pub fn transfer_recipient(&mut self, id: u64, new_recipient: usize) -> Result<(), Rejected> {
+        let stream = &mut self.streams[id as usize];
+        check(stream.status != StreamStatus::Completed)?;
+        check(new_recipient != stream.sender && new_recipient != stream.recipient)?;
+        stream.recipient = new_recipient;
+        Ok(())
+    }
+}
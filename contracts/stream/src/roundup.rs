@@ -0,0 +1,77 @@
+//! Charity round-up on withdrawals.
+//!
+//! A recipient may opt in to having every withdrawal rounded down to a
+//! multiple of `granularity`. The remainder goes to a donee of their choice
+//! and is added to a per-(recipient, token) total for donation receipts.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+use crate::{extend_persistent_ttl, math, vault, DataKey};
+
+/// A recipient's round-up instruction, applied across all their streams.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundUp {
+    /// Receiver of the rounded-off remainders.
+    pub donee: Address,
+    /// Withdrawals are rounded down to a multiple of this, in token units.
+    pub granularity: i128,
+}
+
+pub(crate) fn get(env: &Env, recipient: &Address) -> Option<RoundUp> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoundUp(recipient.clone()))
+}
+
+pub(crate) fn set(env: &Env, recipient: &Address, round_up: &Option<RoundUp>) {
+    let key = DataKey::RoundUp(recipient.clone());
+    match round_up {
+        Some(round_up) => {
+            assert!(round_up.granularity > 1, "granularity must exceed 1");
+            assert!(round_up.donee != *recipient, "donee is the recipient");
+            env.storage().persistent().set(&key, round_up);
+            extend_persistent_ttl(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub(crate) fn donated(env: &Env, recipient: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Donated(recipient.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+/// Donate the part of `amount` above the nearest lower multiple of the
+/// recipient's granularity, if they opted in. `amount` must already be
+/// released from principal. Returns what is left for the recipient.
+pub(crate) fn apply(
+    env: &Env,
+    stream_id: u64,
+    token: &Address,
+    recipient: &Address,
+    amount: i128,
+) -> i128 {
+    let Some(round_up) = get(env, recipient) else {
+        return amount;
+    };
+    let remainder = amount % round_up.granularity;
+    if remainder == 0 {
+        return amount;
+    }
+    vault::send(env, token, &round_up.donee, remainder);
+    let key = DataKey::Donated(recipient.clone(), token.clone());
+    env.storage().persistent().set(
+        &key,
+        &math::add(env, donated(env, recipient, token), remainder),
+    );
+    extend_persistent_ttl(env, &key);
+
+    env.events().publish(
+        (symbol_short!("donated"), stream_id),
+        (round_up.donee, remainder),
+    );
+    amount - remainder
+}
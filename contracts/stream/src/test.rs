@@ -1,13 +1,41 @@
 #[cfg(test)]
 extern crate std;
 
+use std::rc::Rc;
+
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    symbol_short,
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, IssuerFlags, Ledger, MockAuth, MockAuthInvoke,
+    },
     token::{Client as TokenClient, StellarAssetClient},
-    Address, Env,
+    vec, Address, BytesN, Env, IntoVal, Map, String, Symbol, TryIntoVal, Val,
+};
+
+use soroban_env_host::{
+    budget::AsBudget,
+    storage::{AccessType, Footprint},
+    xdr::{
+        AccountEntry, AccountEntryExt, AccountId, Asset, ContractDataDurability,
+        ContractExecutable, ContractIdPreimage, CreateContractArgs, HostFunction, LedgerEntry,
+        LedgerEntryData, LedgerEntryExt, LedgerKey, LedgerKeyAccount, LedgerKeyContractData,
+        PublicKey, ScAddress, ScVal, SequenceNumber, Thresholds, Uint256,
+    },
 };
 
-use crate::{FluxoraStream, FluxoraStreamClient, StreamStatus};
+use crate::migration::{self, StreamProgressV1, StreamTermsV1, STREAM_RECORD_VERSION};
+use crate::{
+    BasketComponent, Clock, CreationLimits, Custody, DataKey, Distress, Error, FeeParams,
+    FluxoraStream, FluxoraStreamClient, IndexLink, IndexParams, IndexValue, PayrollEntry,
+    PayrollEntryError, PayrollResult, PayrollSchedule, ReferralAccount, RoundUp, StreamOptions,
+    StreamProgress, StreamStatus, StreamTemplate, WithdrawSplit, BPS_DENOMINATOR,
+    CLAWBACK_DELAY_SECS, CLOSE_RETENTION_SECS, DEFAULT_GUARDIAN_PAUSE_SECS, DEFAULT_TTL_EXTEND_TO,
+    DEFAULT_TTL_THRESHOLD, FEE_TIMELOCK_SECS, INDEX_SCALE, INTERFACE_VERSION, MAX_DEPOSIT,
+    MAX_HORIZON, MAX_PROTOCOL_FEE_BPS, MAX_RATE_PER_SECOND, MAX_REFERRAL_SHARE_BPS, MAX_VIEW_BATCH,
+    NATIVE_DECIMALS,
+};
+use crate::{AUDIT_REFERENCE, CONTRACT_NAME, CONTRACT_VERSION, SOURCE_REPOSITORY};
 
 // ---------------------------------------------------------------------------
 // Test helpers
@@ -20,6 +48,7 @@ struct TestContext {
     token_id: Address,
     #[allow(dead_code)]
     admin: Address,
+    guardian: Address,
     sender: Address,
     recipient: Address,
 }
@@ -39,12 +68,13 @@ impl TestContext {
             .address();
 
         let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
         let sender = Address::generate(&env);
         let recipient = Address::generate(&env);
 
         // Initialise the streaming contract
         let client = FluxoraStreamClient::new(&env, &contract_id);
-        client.init(&token_id, &admin);
+        client.init(&token_id, &admin, &guardian);
 
         // Mint tokens to sender (10_000 USDC-equivalent)
         let sac = StellarAssetClient::new(&env, &token_id);
@@ -55,6 +85,7 @@ impl TestContext {
             contract_id,
             token_id,
             admin,
+            guardian,
             sender,
             recipient,
         }
@@ -68,6 +99,17 @@ impl TestContext {
         TokenClient::new(&self.env, &self.token_id)
     }
 
+    /// Register an additional SAC token and mint 10_000 units to the sender.
+    fn create_token(&self) -> Address {
+        let token_admin = Address::generate(&self.env);
+        let token_id = self
+            .env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        StellarAssetClient::new(&self.env, &token_id).mint(&self.sender, &10_000_i128);
+        token_id
+    }
+
     /// Create a standard 1000-unit stream spanning 1000 seconds (rate 1/s, no cliff).
     fn create_default_stream(&self) -> u64 {
         self.env.ledger().set_timestamp(0);
@@ -418,6 +460,21 @@ fn test_calculate_accrued_after_cliff() {
     );
 }
 
+#[test]
+fn test_accrued_on_loaded_stream_matches_calculate_accrued() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+    let stream = ctx.client().get_stream_state(&stream_id);
+
+    for now in [0u64, 499, 500, 750, 1000, 5000] {
+        ctx.env.ledger().set_timestamp(now);
+        assert_eq!(
+            crate::accrued(&ctx.env, &stream, &Default::default(), now),
+            ctx.client().calculate_accrued(&stream_id)
+        );
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests — pause / resume
 // ---------------------------------------------------------------------------
@@ -513,6 +570,27 @@ fn test_cancel_stream_as_admin() {
     assert_eq!(state.status, StreamStatus::Cancelled);
 }
 
+#[test]
+fn test_cancel_stream_as_admin_needs_only_admin_auth() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.admin,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "cancel_stream_as_admin",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().cancel_stream_as_admin(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
 #[test]
 #[should_panic(expected = "stream must be active or paused to cancel")]
 fn test_cancel_already_cancelled_panics() {
@@ -654,3 +732,5277 @@ fn test_multiple_streams_independent() {
         StreamStatus::Active
     );
 }
+
+// ---------------------------------------------------------------------------
+// Tests — token allowlist
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_default_token_allowed_after_init() {
+    let ctx = TestContext::setup();
+    assert!(ctx.client().is_token_allowed(&ctx.token_id));
+
+    let stream_id = ctx.create_default_stream();
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.token, ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "token is not allowed")]
+fn test_create_stream_with_unlisted_token_panics() {
+    let ctx = TestContext::setup();
+    let other = ctx.create_token();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream_with_token(
+        &ctx.sender,
+        &ctx.recipient,
+        &other,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+}
+
+#[test]
+fn test_create_stream_with_allowed_token_uses_that_token() {
+    let ctx = TestContext::setup();
+    let other = ctx.create_token();
+    ctx.client().allow_token(&other);
+    assert!(ctx.client().is_token_allowed(&other));
+
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_token(
+        &ctx.sender,
+        &ctx.recipient,
+        &other,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    let other_token = TokenClient::new(&ctx.env, &other);
+    assert_eq!(other_token.balance(&ctx.contract_id), 1000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(other_token.balance(&ctx.recipient), 250);
+}
+
+#[test]
+#[should_panic(expected = "token is not allowed")]
+fn test_disallowed_default_token_blocks_create() {
+    let ctx = TestContext::setup();
+    ctx.client().disallow_token(&ctx.token_id);
+    assert!(!ctx.client().is_token_allowed(&ctx.token_id));
+    ctx.create_default_stream();
+}
+
+#[test]
+fn test_disallow_token_keeps_existing_streams_working() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().disallow_token(&ctx.token_id);
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9700);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — token blocklist
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "token is blocked")]
+fn test_blocked_token_rejects_new_streams() {
+    let ctx = TestContext::setup();
+    ctx.client().block_token(&ctx.token_id);
+    assert!(ctx.client().is_token_blocked(&ctx.token_id));
+    // Still allowlisted, but the block takes precedence.
+    assert!(ctx.client().is_token_allowed(&ctx.token_id));
+    ctx.create_default_stream();
+}
+
+#[test]
+fn test_blocked_token_leaves_withdraw_and_cancel_working() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().block_token(&ctx.token_id);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9600);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+}
+
+#[test]
+fn test_unblock_token_restores_creation() {
+    let ctx = TestContext::setup();
+    ctx.client().block_token(&ctx.token_id);
+    ctx.client().unblock_token(&ctx.token_id);
+    assert!(!ctx.client().is_token_blocked(&ctx.token_id));
+
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — creation limits (minimum deposit / duration)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_creation_limits_default_to_zero() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().get_creation_limits(),
+        CreationLimits::default()
+    );
+}
+
+#[test]
+#[should_panic(expected = "deposit_amount below configured minimum")]
+fn test_create_stream_below_min_deposit_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        min_deposit: 5000,
+        ..Default::default()
+    });
+    ctx.create_default_stream();
+}
+
+#[test]
+#[should_panic(expected = "stream duration below configured minimum")]
+fn test_create_stream_below_min_duration_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        min_duration: 3600,
+        ..Default::default()
+    });
+    ctx.create_default_stream();
+}
+
+#[test]
+fn test_create_stream_at_limits_succeeds() {
+    let ctx = TestContext::setup();
+    let limits = CreationLimits {
+        min_deposit: 1000,
+        min_duration: 1000,
+        ..Default::default()
+    };
+    ctx.client().set_creation_limits(&limits);
+    assert_eq!(ctx.client().get_creation_limits(), limits);
+
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).deposit_amount,
+        1000
+    );
+}
+
+#[test]
+#[should_panic(expected = "min_deposit must not be negative")]
+fn test_set_negative_min_deposit_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        min_deposit: -1,
+        ..Default::default()
+    });
+}
+
+// ---------------------------------------------------------------------------
+// Tests — creation limits (maximum horizon / per-sender cap)
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "end_time exceeds configured maximum horizon")]
+fn test_create_stream_beyond_max_horizon_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        max_horizon: 999,
+        ..Default::default()
+    });
+    ctx.create_default_stream();
+}
+
+#[test]
+fn test_max_horizon_is_relative_to_now() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        max_horizon: 1000,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &500u64,
+        &500u64,
+        &1500u64,
+    );
+}
+
+#[test]
+#[should_panic(expected = "sender has too many active streams")]
+fn test_max_active_per_sender_enforced() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        max_active_per_sender: 2,
+        ..Default::default()
+    });
+    ctx.create_default_stream();
+    ctx.create_default_stream();
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 2);
+    ctx.create_default_stream();
+}
+
+#[test]
+fn test_active_count_released_on_cancel_and_completion() {
+    let ctx = TestContext::setup();
+    ctx.client().set_creation_limits(&CreationLimits {
+        max_active_per_sender: 1,
+        ..Default::default()
+    });
+
+    let id0 = ctx.create_default_stream();
+    ctx.client().cancel_stream(&id0);
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 0);
+
+    let id1 = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&id1);
+    assert_eq!(
+        ctx.client().get_stream_state(&id1).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 0);
+
+    ctx.create_default_stream();
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — governed fee parameters
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fee_params_default_to_zero_fee() {
+    let ctx = TestContext::setup();
+    let params = ctx.client().get_fee_params();
+    assert_eq!(params.protocol_fee_bps, 0);
+    assert_eq!(params.fee_collector, ctx.admin);
+    assert!(ctx.client().get_pending_fee_params().is_none());
+}
+
+#[test]
+fn test_fee_params_apply_after_timelock_and_charge_fee() {
+    let ctx = TestContext::setup();
+    let collector = Address::generate(&ctx.env);
+    let params = FeeParams {
+        protocol_fee_bps: 100,
+        fee_collector: collector.clone(),
+        max_broker_fee_bps: 50,
+    };
+
+    ctx.env.ledger().set_timestamp(0);
+    let eta = ctx.client().propose_fee_params(&params);
+    assert_eq!(eta, FEE_TIMELOCK_SECS);
+    // Not yet in force
+    assert_eq!(ctx.client().get_fee_params().protocol_fee_bps, 0);
+
+    ctx.env.ledger().set_timestamp(eta);
+    ctx.client().apply_fee_params();
+    assert_eq!(ctx.client().get_fee_params(), params);
+    assert!(ctx.client().get_pending_fee_params().is_none());
+
+    ctx.create_default_stream();
+    // 1% of 1000 on top of the deposit
+    assert_eq!(ctx.token().balance(&collector), 10);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8990);
+}
+
+#[test]
+#[should_panic(expected = "fee timelock has not elapsed")]
+fn test_apply_fee_params_before_eta_panics() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().propose_fee_params(&FeeParams {
+        protocol_fee_bps: 10,
+        fee_collector: ctx.admin.clone(),
+        max_broker_fee_bps: 0,
+    });
+    ctx.env.ledger().set_timestamp(FEE_TIMELOCK_SECS - 1);
+    ctx.client().apply_fee_params();
+}
+
+#[test]
+#[should_panic(expected = "protocol fee exceeds hard maximum")]
+fn test_propose_fee_above_hard_max_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().propose_fee_params(&FeeParams {
+        protocol_fee_bps: MAX_PROTOCOL_FEE_BPS + 1,
+        fee_collector: ctx.admin.clone(),
+        max_broker_fee_bps: 0,
+    });
+}
+
+#[test]
+#[should_panic(expected = "no pending fee params")]
+fn test_cancel_fee_params_discards_proposal() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().propose_fee_params(&FeeParams {
+        protocol_fee_bps: 10,
+        fee_collector: ctx.admin.clone(),
+        max_broker_fee_bps: 0,
+    });
+    ctx.client().cancel_fee_params();
+    ctx.env.ledger().set_timestamp(FEE_TIMELOCK_SECS);
+    ctx.client().apply_fee_params();
+}
+
+// ---------------------------------------------------------------------------
+// Tests — admin clawback
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_clawback_queue_then_execute() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let reason = BytesN::from_array(&ctx.env, &[7u8; 32]);
+
+    ctx.env.ledger().set_timestamp(100);
+    let eta = ctx.client().queue_clawback(&stream_id, &reason);
+    assert_eq!(eta, 100 + CLAWBACK_DELAY_SECS);
+    let pending = ctx.client().get_pending_clawback(&stream_id).unwrap();
+    assert_eq!(pending.reason_hash, reason);
+
+    // Recipient still has the notice period to withdraw accrued funds
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+
+    ctx.env.ledger().set_timestamp(eta);
+    let reclaimed = ctx.client().execute_clawback(&stream_id);
+    assert_eq!(reclaimed, 700);
+    assert_eq!(ctx.token().balance(&ctx.admin), 700);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::ClawedBack);
+    assert!(ctx.client().get_pending_clawback(&stream_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "clawback delay has not elapsed")]
+fn test_execute_clawback_before_delay_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let reason = BytesN::from_array(&ctx.env, &[1u8; 32]);
+    let eta = ctx.client().queue_clawback(&stream_id, &reason);
+    ctx.env.ledger().set_timestamp(eta - 1);
+    ctx.client().execute_clawback(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "no clawback queued")]
+fn test_execute_clawback_without_queue_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().execute_clawback(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "no clawback queued")]
+fn test_cancel_clawback_prevents_execution() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let reason = BytesN::from_array(&ctx.env, &[1u8; 32]);
+    let eta = ctx.client().queue_clawback(&stream_id, &reason);
+    ctx.client().cancel_clawback(&stream_id);
+    ctx.env.ledger().set_timestamp(eta);
+    ctx.client().execute_clawback(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "stream was clawed back")]
+fn test_withdraw_after_clawback_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let reason = BytesN::from_array(&ctx.env, &[1u8; 32]);
+    let eta = ctx.client().queue_clawback(&stream_id, &reason);
+    ctx.env.ledger().set_timestamp(eta);
+    ctx.client().execute_clawback(&stream_id);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "stream is part of a basket")]
+fn test_basket_component_cannot_be_clawed_back() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    let stream_id = ctx.client().get_basket(&basket_id).streams.get(1).unwrap();
+    let reason = BytesN::from_array(&ctx.env, &[1u8; 32]);
+    ctx.client().queue_clawback(&stream_id, &reason);
+}
+
+#[test]
+#[should_panic(expected = "stream is under dispute")]
+fn test_clawback_blocked_while_disputed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_arbitrated_stream(&Address::generate(&ctx.env));
+    let reason = BytesN::from_array(&ctx.env, &[1u8; 32]);
+    let eta = ctx.client().queue_clawback(&stream_id, &reason);
+    ctx.client().open_dispute(&stream_id, &ctx.recipient);
+    ctx.env.ledger().set_timestamp(eta);
+    ctx.client().execute_clawback(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — admin cancellation opt-out
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_default_streams_are_admin_cancelable() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(ctx.client().get_stream_state(&stream_id).admin_cancelable);
+}
+
+#[test]
+#[should_panic(expected = "stream is not admin-cancelable")]
+fn test_admin_cannot_cancel_opted_out_stream() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &StreamOptions {
+            admin_cancelable: false,
+            ..Default::default()
+        },
+    );
+    assert!(!ctx.client().get_stream_state(&stream_id).admin_cancelable);
+    ctx.client().cancel_stream_as_admin(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "stream is not admin-cancelable")]
+fn test_admin_cannot_claw_back_opted_out_stream() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &StreamOptions {
+            admin_cancelable: false,
+            ..Default::default()
+        },
+    );
+    let reason = BytesN::from_array(&ctx.env, &[1u8; 32]);
+    ctx.client().queue_clawback(&stream_id, &reason);
+}
+
+#[test]
+fn test_sender_can_still_cancel_opted_out_stream() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &StreamOptions {
+            admin_cancelable: false,
+            ..Default::default()
+        },
+    );
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — guardian (emergency council)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_guardian_configured_at_init() {
+    let ctx = TestContext::setup();
+    let config = ctx.client().get_config();
+    assert_eq!(config.guardian, ctx.guardian);
+    assert!(!ctx.client().is_paused());
+}
+
+#[test]
+#[should_panic(expected = "protocol is paused")]
+fn test_emergency_pause_blocks_creation() {
+    let ctx = TestContext::setup();
+    ctx.client().emergency_pause();
+    assert!(ctx.client().is_paused());
+    ctx.create_default_stream();
+}
+
+#[test]
+#[should_panic(expected = "protocol is paused")]
+fn test_emergency_pause_blocks_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().emergency_pause();
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_emergency_unpause_restores_activity() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().emergency_pause();
+    ctx.client().emergency_unpause();
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+}
+
+#[test]
+fn test_emergency_pause_requires_guardian_auth() {
+    let ctx = TestContext::setup();
+    ctx.client().emergency_pause();
+    let auths = ctx.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, ctx.guardian);
+}
+
+#[test]
+fn test_guardian_block_token() {
+    let ctx = TestContext::setup();
+    ctx.client().guardian_block_token(&ctx.token_id);
+    assert!(ctx.client().is_token_blocked(&ctx.token_id));
+}
+
+#[test]
+fn test_guardian_two_step_rotation() {
+    let ctx = TestContext::setup();
+    let new_guardian = Address::generate(&ctx.env);
+
+    ctx.client().propose_guardian(&new_guardian);
+    // Not effective until accepted
+    assert_eq!(ctx.client().get_config().guardian, ctx.guardian);
+
+    ctx.client().accept_guardian();
+    assert_eq!(ctx.client().get_config().guardian, new_guardian);
+}
+
+#[test]
+fn test_admin_two_step_transfer() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+
+    ctx.client().propose_admin(&new_admin);
+    assert_eq!(ctx.client().get_admin(), ctx.admin);
+
+    ctx.client().accept_admin();
+    assert_eq!(ctx.client().get_admin(), new_admin);
+    assert_eq!(ctx.client().get_config().admin, new_admin);
+    ctx.client().emergency_unpause();
+    assert_eq!(ctx.env.auths()[0].0, new_admin);
+}
+
+#[test]
+#[should_panic(expected = "no pending guardian")]
+fn test_accept_guardian_without_proposal_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().accept_guardian();
+}
+
+// ---------------------------------------------------------------------------
+// Tests — governor contract integration
+// ---------------------------------------------------------------------------
+
+/// Minimal stand-in for a DAO governor that forwards parameter updates.
+#[soroban_sdk::contract]
+struct MockGovernor;
+
+#[soroban_sdk::contractimpl]
+impl MockGovernor {
+    pub fn set_limits(env: Env, stream_contract: Address, limits: CreationLimits) {
+        FluxoraStreamClient::new(&env, &stream_contract).set_creation_limits(&limits);
+    }
+}
+
+#[test]
+fn test_governor_cross_contract_call_updates_params() {
+    let ctx = TestContext::setup();
+    let governor_id = ctx.env.register_contract(None, MockGovernor);
+    ctx.client().set_governor(&Some(governor_id.clone()));
+    assert_eq!(
+        ctx.client().get_config().governor,
+        Some(governor_id.clone())
+    );
+
+    // No mocked signatures: the governor's own invocation is the only auth.
+    ctx.env.set_auths(&[]);
+    let limits = CreationLimits {
+        min_deposit: 42,
+        ..Default::default()
+    };
+    MockGovernorClient::new(&ctx.env, &governor_id).set_limits(&ctx.contract_id, &limits);
+    assert_eq!(ctx.client().get_creation_limits(), limits);
+}
+
+#[test]
+fn test_admin_cannot_update_params_once_governor_set() {
+    let ctx = TestContext::setup();
+    let governor_id = ctx.env.register_contract(None, MockGovernor);
+    ctx.client().set_governor(&Some(governor_id.clone()));
+
+    let limits = CreationLimits::default();
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.admin,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_creation_limits",
+            args: (limits.clone(),).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = ctx.client().try_set_creation_limits(&limits);
+    assert!(result.is_err(), "admin signature must not suffice");
+}
+
+#[test]
+fn test_params_authority_is_admin_without_governor() {
+    let ctx = TestContext::setup();
+    ctx.client().allow_token(&ctx.token_id);
+    let auths = ctx.env.auths();
+    assert_eq!(auths[0].0, ctx.admin);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — obligations accounting and rescue
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_obligations_track_stream_lifecycle() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_obligations(&ctx.token_id), 1000);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_obligations(&ctx.token_id), 700);
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().cancel_stream(&stream_id);
+    // 500 refunded; 200 accrued still owed to the recipient
+    assert_eq!(ctx.client().get_obligations(&ctx.token_id), 200);
+
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_obligations(&ctx.token_id), 0);
+}
+
+#[test]
+fn test_rescue_returns_stray_tokens() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+
+    // Someone transfers 250 tokens straight to the contract by mistake
+    ctx.token()
+        .transfer(&ctx.sender, &ctx.contract_id, &250_i128);
+
+    let owner = Address::generate(&ctx.env);
+    ctx.client().rescue(&ctx.token_id, &250_i128, &owner);
+    assert_eq!(ctx.token().balance(&owner), 250);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+}
+
+#[test]
+#[should_panic(expected = "amount exceeds unobligated balance")]
+fn test_rescue_cannot_touch_stream_funds() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    ctx.token()
+        .transfer(&ctx.sender, &ctx.contract_id, &250_i128);
+    ctx.client().rescue(&ctx.token_id, &251_i128, &ctx.admin);
+}
+
+#[test]
+fn test_rescue_of_unrelated_token() {
+    let ctx = TestContext::setup();
+    let other = ctx.create_token();
+    TokenClient::new(&ctx.env, &other).transfer(&ctx.sender, &ctx.contract_id, &99_i128);
+
+    ctx.client().rescue(&other, &99_i128, &ctx.sender);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &other).balance(&ctx.sender),
+        10_000
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — surplus reconciliation and skim
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_skim_sweeps_surplus_to_fee_collector() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    ctx.token()
+        .transfer(&ctx.sender, &ctx.contract_id, &40_i128);
+    assert_eq!(ctx.client().get_surplus(&ctx.token_id), 40);
+
+    let swept = ctx.client().skim(&ctx.token_id);
+    assert_eq!(swept, 40);
+    // Default fee collector is the admin
+    assert_eq!(ctx.token().balance(&ctx.admin), 40);
+    assert_eq!(ctx.client().get_surplus(&ctx.token_id), 0);
+    assert_eq!(
+        ctx.token().balance(&ctx.contract_id),
+        ctx.client().get_obligations(&ctx.token_id)
+    );
+}
+
+#[test]
+#[should_panic(expected = "nothing to skim")]
+fn test_skim_without_surplus_panics() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    ctx.client().skim(&ctx.token_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — compliance freeze of recipients
+// ---------------------------------------------------------------------------
+
+#[test]
+#[should_panic(expected = "recipient is frozen")]
+fn test_frozen_recipient_cannot_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().freeze_recipient(&ctx.recipient);
+    assert!(ctx.client().is_recipient_frozen(&ctx.recipient));
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_accrual_continues_while_frozen() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().freeze_recipient(&ctx.recipient);
+
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 600);
+
+    ctx.client().unfreeze_recipient(&ctx.recipient);
+    assert!(!ctx.client().is_recipient_frozen(&ctx.recipient));
+    assert_eq!(ctx.client().withdraw(&stream_id), 600);
+}
+
+#[test]
+fn test_frozen_recipients_view() {
+    let ctx = TestContext::setup();
+    let other = Address::generate(&ctx.env);
+
+    ctx.client().freeze_recipient(&ctx.recipient);
+    ctx.client().freeze_recipient(&other);
+    ctx.client().freeze_recipient(&other); // idempotent
+    assert_eq!(ctx.client().get_frozen_recipients().len(), 2);
+
+    ctx.client().unfreeze_recipient(&ctx.recipient);
+    let frozen = ctx.client().get_frozen_recipients();
+    assert_eq!(frozen.len(), 1);
+    assert_eq!(frozen.get(0).unwrap(), other);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — attestation gate
+// ---------------------------------------------------------------------------
+
+/// Registry that verifies whichever accounts were explicitly approved.
+#[soroban_sdk::contract]
+struct MockAttestationRegistry;
+
+#[soroban_sdk::contractimpl]
+impl MockAttestationRegistry {
+    pub fn approve(env: Env, account: Address) {
+        env.storage().persistent().set(&account, &true);
+    }
+
+    pub fn is_verified(env: Env, account: Address) -> bool {
+        env.storage().persistent().get(&account).unwrap_or(false)
+    }
+}
+
+#[test]
+#[should_panic(expected = "recipient is not verified")]
+fn test_withdraw_blocked_for_unverified_recipient() {
+    let ctx = TestContext::setup();
+    let registry = ctx.env.register_contract(None, MockAttestationRegistry);
+    ctx.client().set_attestation_registry(&Some(registry));
+
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_withdraw_allowed_for_verified_recipient() {
+    let ctx = TestContext::setup();
+    let registry = ctx.env.register_contract(None, MockAttestationRegistry);
+    MockAttestationRegistryClient::new(&ctx.env, &registry).approve(&ctx.recipient);
+    ctx.client()
+        .set_attestation_registry(&Some(registry.clone()));
+    assert_eq!(ctx.client().get_attestation_registry(), Some(registry));
+
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+}
+
+#[test]
+fn test_clearing_attestation_registry_lifts_gate() {
+    let ctx = TestContext::setup();
+    let registry = ctx.env.register_contract(None, MockAttestationRegistry);
+    ctx.client().set_attestation_registry(&Some(registry));
+    ctx.client().set_attestation_registry(&None);
+
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — transfer-restriction policy hook
+// ---------------------------------------------------------------------------
+
+/// Policy that caps any single movement at 500 units.
+#[soroban_sdk::contract]
+struct MockCapPolicy;
+
+#[soroban_sdk::contractimpl]
+impl MockCapPolicy {
+    pub fn can_transfer(
+        _env: Env,
+        _sender: Address,
+        _recipient: Address,
+        _token: Address,
+        amount: i128,
+    ) -> bool {
+        amount <= 500
+    }
+}
+
+#[test]
+#[should_panic(expected = "transfer rejected by policy")]
+fn test_policy_rejects_creation() {
+    let ctx = TestContext::setup();
+    let policy = ctx.env.register_contract(None, MockCapPolicy);
+    ctx.client().set_transfer_policy(&Some(policy));
+    ctx.create_default_stream(); // deposit 1000 > 500
+}
+
+#[test]
+fn test_policy_consulted_on_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let policy = ctx.env.register_contract(None, MockCapPolicy);
+    ctx.client().set_transfer_policy(&Some(policy.clone()));
+    assert_eq!(ctx.client().get_transfer_policy(), Some(policy));
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+
+    ctx.env.ledger().set_timestamp(1000);
+    let result = ctx.client().try_withdraw(&stream_id);
+    assert!(result.is_err(), "600 exceeds the policy cap");
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream templates
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_stream_from_template() {
+    let ctx = TestContext::setup();
+    let template = StreamTemplate {
+        token: ctx.token_id.clone(),
+        rate_per_second: 2,
+        cliff_offset: 100,
+        duration: 400,
+        options: StreamOptions {
+            admin_cancelable: false,
+            ..Default::default()
+        },
+    };
+    let template_id = ctx.client().create_template(&ctx.admin, &template);
+    assert_eq!(ctx.client().get_template(&template_id), template);
+
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id =
+        ctx.client()
+            .create_stream_from_template(&ctx.sender, &template_id, &ctx.recipient, &50);
+    let stream = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(stream.deposit_amount, 800);
+    assert_eq!(stream.start_time, 50);
+    assert_eq!(stream.cliff_time, 150);
+    assert_eq!(stream.end_time, 450);
+    assert!(!stream.admin_cancelable);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_200);
+}
+
+#[test]
+#[should_panic(expected = "cliff_offset must not exceed duration")]
+fn test_create_template_rejects_cliff_past_end() {
+    let ctx = TestContext::setup();
+    ctx.client().create_template(
+        &ctx.admin,
+        &StreamTemplate {
+            token: ctx.token_id.clone(),
+            rate_per_second: 1,
+            cliff_offset: 1001,
+            duration: 1000,
+            options: StreamOptions::default(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "template not found")]
+fn test_create_stream_from_missing_template_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .create_stream_from_template(&ctx.sender, &7, &ctx.recipient, &0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — guardian holds on individual streams
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_guardian_hold_blocks_resume_until_it_lapses() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.client().guardian_pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+    assert!(ctx.client().try_resume_stream(&stream_id).is_err());
+    assert!(ctx.client().try_withdraw(&stream_id).is_err());
+
+    // Once the window lapses without confirmation, the stream resumes.
+    ctx.env.ledger().set_timestamp(DEFAULT_GUARDIAN_PAUSE_SECS);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(ctx.client().get_guardian_hold(&stream_id), None);
+}
+
+#[test]
+fn test_guardian_pause_window_is_configurable() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_guardian_pause_window(&300);
+    ctx.client().guardian_pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client()
+            .get_guardian_hold(&stream_id)
+            .unwrap()
+            .expires_at,
+        300
+    );
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().resume_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_confirmed_guardian_hold_does_not_lapse() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().guardian_pause_stream(&stream_id);
+    ctx.client().confirm_guardian_hold(&stream_id);
+
+    ctx.env
+        .ledger()
+        .set_timestamp(DEFAULT_GUARDIAN_PAUSE_SECS + 1);
+    assert!(ctx.client().try_withdraw(&stream_id).is_err());
+    assert!(ctx.client().try_resume_stream(&stream_id).is_err());
+
+    ctx.client().release_guardian_hold(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+}
+
+#[test]
+fn test_releasing_hold_leaves_disputed_stream_paused() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_arbitrated_stream(&Address::generate(&ctx.env));
+    ctx.client().guardian_pause_stream(&stream_id);
+    ctx.client().open_dispute(&stream_id, &ctx.recipient);
+
+    ctx.client().release_guardian_hold(&stream_id);
+    assert!(ctx.client().get_guardian_hold(&stream_id).is_none());
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+}
+
+#[test]
+fn test_guardian_hold_expiry_saturates() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_guardian_pause_window(&u64::MAX);
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().guardian_pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client()
+            .get_guardian_hold(&stream_id)
+            .unwrap()
+            .expires_at,
+        u64::MAX
+    );
+}
+
+#[test]
+fn test_guardian_cannot_cancel_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.guardian,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "cancel_stream_as_admin",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(ctx.client().try_cancel_stream_as_admin(&stream_id).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — configurable TTL parameters
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_ttl_params_apply_to_stream_entries() {
+    let ctx = TestContext::setup();
+    let config = ctx.client().get_config();
+    assert_eq!(config.ttl_threshold, DEFAULT_TTL_THRESHOLD);
+    assert_eq!(config.ttl_extend_to, DEFAULT_TTL_EXTEND_TO);
+
+    ctx.client().set_ttl_params(&150_000, &200_000);
+    let stream_id = ctx.create_default_stream();
+
+    for key in [
+        DataKey::Stream(stream_id),
+        DataKey::StreamProgress(stream_id),
+    ] {
+        let ttl = ctx.env.as_contract(&ctx.contract_id, || {
+            ctx.env.storage().persistent().get_ttl(&key)
+        });
+        assert_eq!(ttl, 200_000);
+    }
+    let config = ctx.client().get_config();
+    assert_eq!(
+        (config.ttl_threshold, config.ttl_extend_to),
+        (150_000, 200_000)
+    );
+}
+
+#[test]
+#[should_panic(expected = "threshold must be positive and not exceed extend_to")]
+fn test_ttl_threshold_above_extend_to_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_ttl_params(&200_000, &1_000);
+}
+
+#[test]
+fn test_extend_ttl_for_bumps_listed_streams_only() {
+    let ctx = TestContext::setup();
+    let kept = ctx.create_default_stream();
+    let other = ctx.create_default_stream();
+    let ttl = |key: &DataKey| {
+        ctx.env.as_contract(&ctx.contract_id, || {
+            ctx.env.storage().persistent().get_ttl(key)
+        })
+    };
+
+    // Let every entry age below the bump threshold.
+    let aged = DEFAULT_TTL_EXTEND_TO - DEFAULT_TTL_THRESHOLD + 1;
+    ctx.env
+        .ledger()
+        .set_sequence_number(ctx.env.ledger().sequence() + aged);
+    ctx.env.mock_auths(&[]);
+    ctx.client().extend_ttl_for(&vec![&ctx.env, kept]);
+
+    assert_eq!(ttl(&DataKey::Stream(kept)), DEFAULT_TTL_EXTEND_TO);
+    assert_eq!(ttl(&DataKey::StreamProgress(kept)), DEFAULT_TTL_EXTEND_TO);
+    assert_eq!(ttl(&DataKey::Stream(other)), DEFAULT_TTL_THRESHOLD - 1);
+    let instance_ttl = ctx
+        .env
+        .as_contract(&ctx.contract_id, || ctx.env.storage().instance().get_ttl());
+    assert_eq!(instance_ttl, DEFAULT_TTL_EXTEND_TO);
+}
+
+#[test]
+fn test_extend_ttl_for_bumps_per_stream_settings() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client()
+        .approve_operator(&stream_id, &Address::generate(&ctx.env), &250);
+    let operator_key = DataKey::Operator(stream_id);
+
+    let aged = DEFAULT_TTL_EXTEND_TO - DEFAULT_TTL_THRESHOLD + 1;
+    ctx.env
+        .ledger()
+        .set_sequence_number(ctx.env.ledger().sequence() + aged);
+    ctx.client().extend_ttl_for(&vec![&ctx.env, stream_id]);
+
+    let ttl = ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env.storage().persistent().get_ttl(&operator_key)
+    });
+    assert_eq!(ttl, DEFAULT_TTL_EXTEND_TO);
+}
+
+#[test]
+fn test_extend_ttl_for_writes_no_stream_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    for key in [
+        DataKey::Stream(stream_id),
+        DataKey::StreamProgress(stream_id),
+    ] {
+        let access = ctx.access_to(&key, || {
+            ctx.client().extend_ttl_for(&vec![&ctx.env, stream_id])
+        });
+        assert_eq!(access, Some(AccessType::ReadOnly));
+    }
+}
+
+#[test]
+#[should_panic(expected = "stream not found")]
+fn test_extend_ttl_for_unknown_stream_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().extend_ttl_for(&vec![&ctx.env, 7]);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — split stream storage
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Run `f` against an empty footprint and return how it accessed the
+    /// contract's persistent entry under `key`, if at all.
+    fn access_to<T>(&self, key: &DataKey, f: impl FnOnce() -> T) -> Option<AccessType> {
+        self.access_to_entry(ScVal::try_from(key).unwrap(), f)
+    }
+
+    /// As `access_to`, for any of the contract's entries including its
+    /// instance (`ScVal::LedgerKeyContractInstance`).
+    fn access_to_entry<T>(&self, key: ScVal, f: impl FnOnce() -> T) -> Option<AccessType> {
+        let host = self.env.host();
+        host.with_mut_storage(|storage| {
+            storage.footprint = Footprint::default();
+            Ok(())
+        })
+        .unwrap();
+
+        f();
+
+        let contract = ScAddress::from(&self.contract_id);
+        host.with_mut_storage(|storage| {
+            let mut found = None;
+            for (entry, access) in storage.footprint.0.iter(&host.budget_cloned())? {
+                if let LedgerKey::ContractData(data) = entry.as_ref() {
+                    if data.contract == contract && data.key == key {
+                        found = Some(*access);
+                    }
+                }
+            }
+            Ok(found)
+        })
+        .unwrap()
+    }
+}
+
+#[test]
+fn test_withdraw_leaves_stream_terms_untouched() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+
+    let terms = ctx.access_to(&DataKey::Stream(stream_id), || {
+        ctx.client().withdraw(&stream_id)
+    });
+    assert_eq!(terms, Some(AccessType::ReadOnly));
+
+    ctx.env.ledger().set_timestamp(600);
+    let progress = ctx.access_to(&DataKey::StreamProgress(stream_id), || {
+        ctx.client().withdraw(&stream_id)
+    });
+    assert_eq!(progress, Some(AccessType::ReadWrite));
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 600);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(state.recipient, ctx.recipient);
+}
+
+#[test]
+fn test_pause_and_resume_leave_stream_terms_untouched() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    for paused in [true, false] {
+        let terms = ctx.access_to(&DataKey::Stream(stream_id), || {
+            if paused {
+                ctx.client().pause_stream(&stream_id);
+            } else {
+                ctx.client().resume_stream(&stream_id);
+            }
+        });
+        assert_eq!(terms, Some(AccessType::ReadOnly));
+    }
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_transfer_recipient_rewrites_stream_terms() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let new_recipient = Address::generate(&ctx.env);
+
+    let terms = ctx.access_to(&DataKey::Stream(stream_id), || {
+        ctx.client().transfer_recipient(&stream_id, &new_recipient)
+    });
+    assert_eq!(terms, Some(AccessType::ReadWrite));
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).recipient,
+        new_recipient
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream ID allocation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_stream_does_not_rewrite_instance_storage() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+
+    let create = || {
+        ctx.client()
+            .create_stream(&ctx.sender, &ctx.recipient, &100, &1, &0, &0, &100)
+    };
+    let instance = ctx.access_to_entry(ScVal::LedgerKeyContractInstance, create);
+    assert_eq!(instance, Some(AccessType::ReadOnly));
+    let counter = ctx.access_to(&DataKey::NextStreamId, create);
+    assert_eq!(counter, Some(AccessType::ReadWrite));
+}
+
+#[test]
+fn test_counter_in_instance_storage_carries_over() {
+    let ctx = TestContext::setup();
+    // A deployment from before the counter moved out of instance storage.
+    ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env
+            .storage()
+            .persistent()
+            .remove(&DataKey::NextStreamId);
+        ctx.env
+            .storage()
+            .instance()
+            .set(&DataKey::NextStreamId, &7u64);
+    });
+
+    assert_eq!(ctx.create_default_stream(), 7);
+    let legacy = ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env.storage().instance().has(&DataKey::NextStreamId)
+    });
+    assert!(!legacy);
+    assert_eq!(ctx.create_default_stream(), 8);
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — sunset mode
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_sunset_blocks_creation_but_not_existing_streams() {
+    let ctx = TestContext::setup();
+    let withdrawn = ctx.create_default_stream();
+    let cancelled = ctx.create_default_stream();
+
+    assert!(!ctx.client().is_sunset());
+    ctx.client().sunset();
+    assert!(ctx.client().is_sunset());
+
+    let result =
+        ctx.client()
+            .try_create_stream(&ctx.sender, &ctx.recipient, &1000, &1, &0, &0, &1000);
+    assert!(result.is_err());
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&withdrawn), 400);
+    ctx.client().cancel_stream(&cancelled);
+    assert_eq!(
+        ctx.client().get_stream_state(&cancelled).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+#[should_panic(expected = "contract is sunset")]
+fn test_sunset_applies_to_template_creation() {
+    let ctx = TestContext::setup();
+    let template_id = ctx.client().create_template(
+        &ctx.admin,
+        &StreamTemplate {
+            token: ctx.token_id.clone(),
+            rate_per_second: 1,
+            cliff_offset: 0,
+            duration: 1000,
+            options: StreamOptions::default(),
+        },
+    );
+    ctx.client().sunset();
+    ctx.client()
+        .create_stream_from_template(&ctx.sender, &template_id, &ctx.recipient, &0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdraw_to
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_to_delivers_to_destination() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let destination = Address::generate(&ctx.env);
+
+    ctx.env.ledger().set_timestamp(250);
+    assert_eq!(ctx.client().withdraw_to(&stream_id, &destination), 250);
+    assert_eq!(ctx.token().balance(&destination), 250);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        250
+    );
+}
+
+#[test]
+#[should_panic(expected = "recipient is frozen")]
+fn test_withdraw_to_respects_recipient_freeze() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().freeze_recipient(&ctx.recipient);
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client()
+        .withdraw_to(&stream_id, &Address::generate(&ctx.env));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — swap-on-withdraw
+// ---------------------------------------------------------------------------
+
+/// Router paying out `rate` units of `token_out` per unit in, from its own balance.
+#[soroban_sdk::contract]
+struct MockSwapRouter;
+
+#[soroban_sdk::contractimpl]
+impl MockSwapRouter {
+    pub fn set_rate(env: Env, rate: i128) {
+        env.storage().instance().set(&0u32, &rate);
+    }
+
+    pub fn swap_exact_in(
+        env: Env,
+        _token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        _min_out: i128,
+        to: Address,
+    ) -> i128 {
+        let rate: i128 = env.storage().instance().get(&0u32).unwrap();
+        let amount_out = amount_in * rate;
+        TokenClient::new(&env, &token_out).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount_out,
+        );
+        amount_out
+    }
+}
+
+impl TestContext {
+    /// Register a router swapping at `rate` and stocked with 10_000 units of
+    /// a fresh output token, which is returned.
+    fn setup_swap_router(&self, rate: i128) -> Address {
+        let router = self.env.register_contract(None, MockSwapRouter);
+        MockSwapRouterClient::new(&self.env, &router).set_rate(&rate);
+        let token_out = self.create_token();
+        TokenClient::new(&self.env, &token_out).transfer(&self.sender, &router, &10_000);
+        self.client().set_swap_router(&Some(router));
+        token_out
+    }
+}
+
+#[test]
+fn test_withdraw_swap_delivers_preferred_token() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let token_out = ctx.setup_swap_router(2);
+    let router = ctx.client().get_swap_router().unwrap();
+
+    ctx.env.ledger().set_timestamp(250);
+    assert_eq!(
+        ctx.client().withdraw_swap(&stream_id, &token_out, &500),
+        500
+    );
+    assert_eq!(
+        TokenClient::new(&ctx.env, &token_out).balance(&ctx.recipient),
+        500
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.token().balance(&router), 250);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        250
+    );
+}
+
+#[test]
+#[should_panic(expected = "swap output below minimum")]
+fn test_withdraw_swap_enforces_min_out() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let token_out = ctx.setup_swap_router(2);
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().withdraw_swap(&stream_id, &token_out, &501);
+}
+
+#[test]
+#[should_panic(expected = "swap router not configured")]
+fn test_withdraw_swap_requires_router() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let token_out = ctx.create_token();
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().withdraw_swap(&stream_id, &token_out, &0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — vault auto-deposit
+// ---------------------------------------------------------------------------
+
+/// Vault crediting pre-funded deposits to each beneficiary.
+#[soroban_sdk::contract]
+struct MockVault;
+
+#[soroban_sdk::contractimpl]
+impl MockVault {
+    pub fn deposit(env: Env, _token: Address, amount: i128, beneficiary: Address) {
+        let credited: i128 = env.storage().instance().get(&beneficiary).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&beneficiary, &(credited + amount));
+    }
+
+    pub fn credited(env: Env, beneficiary: Address) -> i128 {
+        env.storage().instance().get(&beneficiary).unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_withdraw_deposits_into_recipient_vault() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let vault = ctx.env.register_contract(None, MockVault);
+    ctx.client()
+        .set_recipient_vault(&ctx.recipient, &Some(vault.clone()));
+    assert_eq!(
+        ctx.client().get_recipient_vault(&ctx.recipient),
+        Some(vault.clone())
+    );
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&vault), 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(
+        MockVaultClient::new(&ctx.env, &vault).credited(&ctx.recipient),
+        300
+    );
+}
+
+#[test]
+fn test_cleared_vault_pays_recipient_directly() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let vault = ctx.env.register_contract(None, MockVault);
+    ctx.client()
+        .set_recipient_vault(&ctx.recipient, &Some(vault));
+    ctx.client().set_recipient_vault(&ctx.recipient, &None);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — auto-withdraw
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_auto_withdraw_lets_anyone_settle_to_recipient() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_auto_withdraw(&ctx.recipient, &true);
+    assert!(ctx.client().is_auto_withdraw(&ctx.recipient));
+
+    // No signatures at all: the withdrawal still goes to the recipient.
+    ctx.env.set_auths(&[]);
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+
+    // Redirecting funds still needs the recipient.
+    ctx.env.ledger().set_timestamp(500);
+    assert!(ctx
+        .client()
+        .try_withdraw_to(&stream_id, &Address::generate(&ctx.env))
+        .is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — refund-credit ledger
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Default-shaped stream created with `credit_refunds` enabled.
+    fn create_credit_stream(&self) -> u64 {
+        self.env.ledger().set_timestamp(0);
+        self.client().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &StreamOptions {
+                credit_refunds: true,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+#[test]
+fn test_cancel_records_credits_instead_of_pushing() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_credit_stream();
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+    assert_eq!(ctx.client().get_credit(&ctx.sender, &ctx.token_id), 700);
+    assert_eq!(ctx.client().get_credit(&ctx.recipient, &ctx.token_id), 200);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 900);
+
+    let vault = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.client()
+            .claim_credit(&ctx.sender, &ctx.token_id, &vault),
+        700
+    );
+    assert_eq!(ctx.token().balance(&vault), 700);
+    ctx.client()
+        .claim_credit(&ctx.recipient, &ctx.token_id, &ctx.recipient);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(ctx.client().get_credit(&ctx.sender, &ctx.token_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "cancelled stream settles through credits")]
+fn test_credit_stream_cannot_withdraw_after_cancel() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_credit_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(600);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "no credit to claim")]
+fn test_claim_without_credit_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .claim_credit(&ctx.sender, &ctx.token_id, &ctx.sender);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — operator allowance
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_operator_allowance_is_paid_before_recipient() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let lender = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &lender, &250);
+
+    // Anyone may settle; the lender is repaid first.
+    ctx.env.set_auths(&[]);
+    ctx.env.ledger().set_timestamp(200);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    assert_eq!(ctx.token().balance(&lender), 200);
+    assert_eq!(ctx.client().get_operator(&stream_id).unwrap().remaining, 50);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 150);
+    assert_eq!(ctx.token().balance(&lender), 250);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 150);
+    assert_eq!(ctx.client().get_operator(&stream_id), None);
+}
+
+#[test]
+#[should_panic(expected = "stream already has an operator")]
+fn test_second_operator_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client()
+        .approve_operator(&stream_id, &Address::generate(&ctx.env), &100);
+    ctx.client()
+        .approve_operator(&stream_id, &Address::generate(&ctx.env), &100);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — creation allowances
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Let `operator` create up to `max_total` of the sender's streams, with
+    /// a matching token allowance for this contract.
+    fn approve_creator(&self, operator: &Address, max_total: i128, expiry: u64) {
+        self.client()
+            .approve_creator(&self.sender, operator, &self.token_id, &max_total, &expiry);
+        self.token()
+            .approve(&self.sender, &self.contract_id, &max_total, &1_000);
+    }
+
+    fn create_as(&self, operator: &Address, deposit: i128) -> u64 {
+        self.client().create_stream_as_operator(
+            operator,
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &deposit,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &(deposit as u64),
+        )
+    }
+}
+
+#[test]
+fn test_operator_creates_streams_within_allowance() {
+    let ctx = TestContext::setup();
+    let payroll = Address::generate(&ctx.env);
+    ctx.approve_creator(&payroll, 1_500, 10_000);
+
+    let stream_id = ctx.create_as(&payroll, 1_000);
+    let stream = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(stream.sender, ctx.sender);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+    assert_eq!(
+        ctx.client()
+            .get_creator_allowance(&ctx.sender, &payroll, &ctx.token_id)
+            .unwrap()
+            .remaining,
+        500
+    );
+}
+
+#[test]
+#[should_panic(expected = "creator allowance exceeded")]
+fn test_operator_cannot_exceed_allowance() {
+    let ctx = TestContext::setup();
+    let payroll = Address::generate(&ctx.env);
+    ctx.approve_creator(&payroll, 1_500, 10_000);
+    ctx.create_as(&payroll, 1_000);
+    ctx.create_as(&payroll, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "creator allowance expired")]
+fn test_expired_creator_allowance_rejected() {
+    let ctx = TestContext::setup();
+    let payroll = Address::generate(&ctx.env);
+    ctx.approve_creator(&payroll, 1_500, 100);
+    ctx.env.ledger().set_timestamp(101);
+    ctx.create_as(&payroll, 1_000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — relayed withdrawals
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_relayed_withdraw_pays_relayer_cut() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let relayer = Address::generate(&ctx.env);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw_relayed(&stream_id, &relayer, &5), 395);
+    assert_eq!(ctx.token().balance(&relayer), 5);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 395);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        400
+    );
+}
+
+#[test]
+#[should_panic(expected = "relayer fee exceeds payout")]
+fn test_relayer_fee_above_payout_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(10);
+    ctx.client()
+        .withdraw_relayed(&stream_id, &Address::generate(&ctx.env), &10);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — arbiter disputes
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    fn create_arbitrated_stream(&self, arbiter: &Address) -> u64 {
+        self.client().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &StreamOptions {
+                arbiter: Some(arbiter.clone()),
+                ..Default::default()
+            },
+        )
+    }
+}
+
+#[test]
+fn test_dispute_pauses_and_arbiter_splits_remaining() {
+    let ctx = TestContext::setup();
+    let arbiter = Address::generate(&ctx.env);
+    let stream_id = ctx.create_arbitrated_stream(&arbiter);
+    assert_eq!(ctx.client().get_arbiter(&stream_id), Some(arbiter));
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&stream_id);
+    ctx.client().open_dispute(&stream_id, &ctx.recipient);
+    let dispute = ctx.client().get_dispute(&stream_id).unwrap();
+    assert_eq!(dispute.opened_by, ctx.recipient);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+
+    let sender_before = ctx.token().balance(&ctx.sender);
+    ctx.client().resolve_dispute(&stream_id, &300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 500);
+    assert_eq!(ctx.token().balance(&ctx.sender) - sender_before, 500);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.withdrawn_amount, 500);
+    assert!(ctx.client().get_dispute(&stream_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "stream has no arbiter")]
+fn test_open_dispute_without_arbiter_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().open_dispute(&stream_id, &ctx.sender);
+}
+
+#[test]
+#[should_panic(expected = "only a stream party can dispute")]
+fn test_non_party_cannot_open_dispute() {
+    let ctx = TestContext::setup();
+    let arbiter = Address::generate(&ctx.env);
+    let stream_id = ctx.create_arbitrated_stream(&arbiter);
+    ctx.client().open_dispute(&stream_id, &arbiter);
+}
+
+#[test]
+#[should_panic(expected = "stream is under dispute")]
+fn test_cancel_blocked_while_disputed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_arbitrated_stream(&Address::generate(&ctx.env));
+    ctx.client().open_dispute(&stream_id, &ctx.sender);
+    ctx.client().cancel_stream(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — payroll import
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    fn payroll_entry(&self, recipient: &Address, amount: i128, external_id: &str) -> PayrollEntry {
+        PayrollEntry {
+            recipient: recipient.clone(),
+            amount,
+            schedule: PayrollSchedule {
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1000,
+            },
+            external_id: String::from_str(&self.env, external_id),
+        }
+    }
+}
+
+#[test]
+fn test_import_payroll_creates_every_stream() {
+    let ctx = TestContext::setup();
+    let bob = Address::generate(&ctx.env);
+    let entries = vec![
+        &ctx.env,
+        ctx.payroll_entry(&ctx.recipient, 2000, "emp-1"),
+        ctx.payroll_entry(&bob, 3000, "emp-2"),
+    ];
+
+    let results = ctx.client().import_payroll(&ctx.sender, &entries);
+    assert_eq!(
+        results,
+        vec![
+            &ctx.env,
+            PayrollResult::Created(0),
+            PayrollResult::Created(1)
+        ]
+    );
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 5000);
+    assert_eq!(ctx.token().balance(&ctx.sender), 5000);
+    assert_eq!(ctx.client().get_stream_state(&1).rate_per_second, 3);
+    assert_eq!(ctx.client().get_stream_state(&1).recipient, bob);
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 2);
+}
+
+#[test]
+fn test_import_payroll_rejects_whole_batch_on_invalid_entry() {
+    let ctx = TestContext::setup();
+    let entries = vec![
+        &ctx.env,
+        ctx.payroll_entry(&ctx.recipient, 2000, "emp-1"),
+        ctx.payroll_entry(&ctx.sender, 1000, "emp-2"),
+        ctx.payroll_entry(&ctx.recipient, 1500, "emp-3"),
+        ctx.payroll_entry(&ctx.recipient, 1000, "emp-1"),
+    ];
+
+    let results = ctx.client().import_payroll(&ctx.sender, &entries);
+    assert_eq!(
+        results,
+        vec![
+            &ctx.env,
+            PayrollResult::Valid,
+            PayrollResult::Invalid(PayrollEntryError::SelfPayment),
+            PayrollResult::Invalid(PayrollEntryError::UnevenRate),
+            PayrollResult::Invalid(PayrollEntryError::DuplicateExternalId),
+        ]
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 0);
+}
+
+#[test]
+fn test_import_payroll_charges_aggregated_fee() {
+    let ctx = TestContext::setup();
+    let collector = Address::generate(&ctx.env);
+    ctx.client().propose_fee_params(&FeeParams {
+        protocol_fee_bps: 100,
+        fee_collector: collector.clone(),
+        max_broker_fee_bps: 0,
+    });
+    ctx.env.ledger().set_timestamp(FEE_TIMELOCK_SECS);
+    ctx.client().apply_fee_params();
+
+    let mut entry = ctx.payroll_entry(&ctx.recipient, 1000, "emp-1");
+    entry.schedule = PayrollSchedule {
+        start_time: FEE_TIMELOCK_SECS,
+        cliff_time: FEE_TIMELOCK_SECS,
+        end_time: FEE_TIMELOCK_SECS + 1000,
+    };
+    let mut second = entry.clone();
+    second.external_id = String::from_str(&ctx.env, "emp-2");
+    ctx.client()
+        .import_payroll(&ctx.sender, &vec![&ctx.env, entry, second]);
+
+    assert_eq!(ctx.token().balance(&collector), 20);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000 - 2000 - 20);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — recipient transfer
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_transfer_recipient_redirects_withdrawals() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let buyer = Address::generate(&ctx.env);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().transfer_recipient(&stream_id, &buyer);
+    assert_eq!(ctx.client().get_stream_state(&stream_id).recipient, buyer);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&buyer), 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+}
+
+#[test]
+#[should_panic(expected = "invalid new recipient")]
+fn test_transfer_recipient_to_sender_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().transfer_recipient(&stream_id, &ctx.sender);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — custody buckets
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_custody_tracks_principal_fees_and_surplus_separately() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().propose_fee_params(&FeeParams {
+        protocol_fee_bps: 100,
+        fee_collector: ctx.admin.clone(),
+        max_broker_fee_bps: 0,
+    });
+    ctx.env.ledger().set_timestamp(FEE_TIMELOCK_SECS);
+    ctx.client().apply_fee_params();
+    let start = FEE_TIMELOCK_SECS;
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000,
+        &1,
+        &start,
+        &start,
+        &(start + 1000),
+    );
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&ctx.contract_id, &25);
+
+    assert_eq!(
+        ctx.client().get_custody(&ctx.token_id),
+        Custody {
+            principal: 1000,
+            fees_charged: 10,
+        }
+    );
+    assert_eq!(ctx.client().get_surplus(&ctx.token_id), 25);
+
+    ctx.env.ledger().set_timestamp(start + 400);
+    ctx.client().withdraw(&stream_id);
+    ctx.client().skim(&ctx.token_id);
+    assert_eq!(ctx.client().get_custody(&ctx.token_id).principal, 600);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 600);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — referrals
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Apply a 1% protocol fee paid to the admin, effective at
+    /// `FEE_TIMELOCK_SECS`, and pay referrers `share_bps` of it.
+    fn enable_referrals(&self, share_bps: u32) {
+        self.env.ledger().set_timestamp(0);
+        self.client().propose_fee_params(&FeeParams {
+            protocol_fee_bps: 100,
+            fee_collector: self.admin.clone(),
+            max_broker_fee_bps: 0,
+        });
+        self.env.ledger().set_timestamp(FEE_TIMELOCK_SECS);
+        self.client().apply_fee_params();
+        self.client().set_referral_share(&share_bps);
+    }
+
+    fn create_referred_stream(&self, referrer: &Address, deposit: i128) -> u64 {
+        let start = self.env.ledger().timestamp();
+        self.client().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &deposit,
+            &1_i128,
+            &start,
+            &start,
+            &(start + deposit as u64),
+            &StreamOptions {
+                referrer: Some(referrer.clone()),
+                ..Default::default()
+            },
+        )
+    }
+}
+
+#[test]
+fn test_referred_creation_splits_fee_and_tracks_volume() {
+    let ctx = TestContext::setup();
+    ctx.enable_referrals(2_500);
+    let partner = Address::generate(&ctx.env);
+    ctx.create_referred_stream(&partner, 2000);
+    ctx.create_referred_stream(&partner, 2000);
+
+    assert_eq!(
+        ctx.client().get_referral(&partner, &ctx.token_id),
+        ReferralAccount {
+            streams: 2,
+            volume: 4000,
+            owed: 10,
+        }
+    );
+    assert_eq!(ctx.token().balance(&ctx.admin), 30);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000 - 4000 - 40);
+
+    assert_eq!(ctx.client().claim_referral(&partner, &ctx.token_id), 10);
+    assert_eq!(ctx.token().balance(&partner), 10);
+    assert_eq!(ctx.client().get_obligations(&ctx.token_id), 4000);
+}
+
+#[test]
+#[should_panic(expected = "no referral fees to claim")]
+fn test_claim_referral_twice_panics() {
+    let ctx = TestContext::setup();
+    ctx.enable_referrals(5_000);
+    let partner = Address::generate(&ctx.env);
+    ctx.create_referred_stream(&partner, 1000);
+    ctx.client().claim_referral(&partner, &ctx.token_id);
+    ctx.client().claim_referral(&partner, &ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "referral share exceeds hard maximum")]
+fn test_referral_share_above_max_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .set_referral_share(&(MAX_REFERRAL_SHARE_BPS + 1));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdrawal splits
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_split_sends_share_to_destination() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let savings = Address::generate(&ctx.env);
+    ctx.client().set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: savings.clone(),
+            share_bps: 2_000,
+        }),
+    );
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(ctx.token().balance(&savings), 100);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        500
+    );
+
+    ctx.client().set_withdraw_split(&stream_id, &None);
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.token().balance(&savings), 100);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 900);
+}
+
+#[test]
+fn test_withdraw_split_is_dropped_on_recipient_transfer() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: Address::generate(&ctx.env),
+            share_bps: 5_000,
+        }),
+    );
+    ctx.client()
+        .transfer_recipient(&stream_id, &Address::generate(&ctx.env));
+    assert_eq!(ctx.client().get_withdraw_split(&stream_id), None);
+}
+
+#[test]
+#[should_panic(expected = "share_bps must be between 1 and 10000")]
+fn test_withdraw_split_above_full_share_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: Address::generate(&ctx.env),
+            share_bps: 10_001,
+        }),
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withholding
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    fn create_withheld_stream(&self, destination: &Address, withholding_bps: u32) -> u64 {
+        self.env.ledger().set_timestamp(0);
+        self.client().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &StreamOptions {
+                withholding_to: Some(destination.clone()),
+                withholding_bps,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+#[test]
+fn test_withholding_is_routed_and_totalled() {
+    let ctx = TestContext::setup();
+    let authority = Address::generate(&ctx.env);
+    let first = ctx.create_withheld_stream(&authority, 3_000);
+    let second = ctx.create_withheld_stream(&authority, 1_000);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&first), 350);
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&first);
+    ctx.client().withdraw(&second);
+
+    assert_eq!(ctx.client().get_withholding(&first).unwrap().withheld, 300);
+    assert_eq!(ctx.client().get_withholding(&second).unwrap().withheld, 100);
+    assert_eq!(
+        ctx.client().get_withheld_total(&authority, &ctx.token_id),
+        400
+    );
+    assert_eq!(ctx.token().balance(&authority), 400);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1600);
+}
+
+#[test]
+fn test_withholding_comes_before_recipient_split() {
+    let ctx = TestContext::setup();
+    let authority = Address::generate(&ctx.env);
+    let savings = Address::generate(&ctx.env);
+    let stream_id = ctx.create_withheld_stream(&authority, 2_000);
+    ctx.client().set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: savings.clone(),
+            share_bps: 5_000,
+        }),
+    );
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(ctx.token().balance(&authority), 200);
+    assert_eq!(ctx.token().balance(&savings), 400);
+}
+
+#[test]
+#[should_panic(expected = "withholding_bps set without a destination")]
+fn test_withholding_bps_without_destination_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &StreamOptions {
+            withholding_bps: 500,
+            ..Default::default()
+        },
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — charity round-up
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_round_up_donates_remainder_and_totals_it() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let charity = Address::generate(&ctx.env);
+    ctx.client().set_round_up(
+        &ctx.recipient,
+        &Some(RoundUp {
+            donee: charity.clone(),
+            granularity: 100,
+        }),
+    );
+
+    ctx.env.ledger().set_timestamp(345);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 600);
+
+    assert_eq!(ctx.token().balance(&charity), 100);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 900);
+    assert_eq!(ctx.client().get_donated(&ctx.recipient, &ctx.token_id), 100);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_round_up_opt_out_stops_donations() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_round_up(
+        &ctx.recipient,
+        &Some(RoundUp {
+            donee: Address::generate(&ctx.env),
+            granularity: 100,
+        }),
+    );
+    ctx.client().set_round_up(&ctx.recipient, &None);
+
+    ctx.env.ledger().set_timestamp(345);
+    assert_eq!(ctx.client().withdraw(&stream_id), 345);
+    assert_eq!(ctx.client().get_donated(&ctx.recipient, &ctx.token_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "granularity must exceed 1")]
+fn test_round_up_granularity_of_one_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_round_up(
+        &ctx.recipient,
+        &Some(RoundUp {
+            donee: Address::generate(&ctx.env),
+            granularity: 1,
+        }),
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — entrypoint benchmarks
+// ---------------------------------------------------------------------------
+//
+// Each benchmark runs one entrypoint against a fresh budget and footprint and
+// compares its CPU instructions, memory and ledger entry reads/writes with the
+// recorded baseline. CPU and memory may drift up to 15% above baseline; entry
+// counts may not grow at all, since they drive the transaction fee directly.
+// Run with `--nocapture` to print the table, and update `BASELINES` when a
+// change deliberately alters a footprint. Tests build with debug assertions,
+// so the costs include the invariant hooks.
+
+/// Allowed CPU and memory growth over baseline, in percent.
+const BENCH_TOLERANCE_PCT: u64 = 15;
+
+/// Resources consumed by one invocation.
+#[derive(Debug)]
+struct Cost {
+    cpu: u64,
+    mem: u64,
+    reads: u32,
+    writes: u32,
+}
+
+/// Recorded (name, cpu, mem, reads, writes) per benchmark.
+const BASELINES: &[(&str, u64, u64, u32, u32)] = &[
+    // The two creation benches open the first stream in their token, so
+    // they include the one-off SEP-41 probe. Deposits read the contract's
+    // balance before and after the transfer to measure what arrived, and
+    // payouts probe the token with a zero-amount transfer first, which
+    // gives `withdraw_to` a balance entry for the recipient. Pausing,
+    // resuming, cancelling and reassigning read the stream's basket
+    // membership, and withdrawing and cancelling its index link.
+    ("create_stream", 865_000, 126_500, 6, 9),
+    ("create_stream_with_fee", 1_182_000, 174_200, 6, 10),
+    ("withdraw", 772_000, 120_600, 15, 5),
+    ("withdraw_multi_leg", 1_517_000, 258_600, 14, 11),
+    ("withdraw_to", 735_000, 109_300, 13, 6),
+    ("pause_stream", 183_500, 29_200, 4, 2),
+    ("resume_stream", 201_000, 29_200, 6, 2),
+    ("cancel_stream", 782_000, 118_700, 6, 7),
+    ("transfer_recipient", 231_000, 39_000, 5, 4),
+    ("calculate_accrued", 62_000, 8_100, 3, 0),
+];
+
+impl TestContext {
+    /// Run `f` against a fresh default budget and an empty footprint and
+    /// return what it consumed.
+    fn measure<T>(&self, f: impl FnOnce() -> T) -> Cost {
+        let host = self.env.host();
+        let mut budget = self.env.budget();
+        budget.reset_default();
+        host.with_mut_storage(|storage| {
+            storage.footprint = Footprint::default();
+            Ok(())
+        })
+        .unwrap();
+
+        f();
+
+        let (reads, writes) = host
+            .with_mut_storage(|storage| {
+                let mut counts = (0, 0);
+                for (_, access) in storage.footprint.0.iter(&host.budget_cloned())? {
+                    match access {
+                        AccessType::ReadOnly => counts.0 += 1,
+                        AccessType::ReadWrite => counts.1 += 1,
+                    }
+                }
+                Ok(counts)
+            })
+            .unwrap();
+        Cost {
+            cpu: budget.cpu_instruction_cost(),
+            mem: budget.memory_bytes_cost(),
+            reads,
+            writes,
+        }
+    }
+
+    /// Measure `f` and fail if it regressed against the baseline for `name`.
+    fn bench<T>(&self, name: &str, f: impl FnOnce() -> T) {
+        let cost = self.measure(f);
+        let &(_, cpu, mem, reads, writes) = BASELINES
+            .iter()
+            .find(|baseline| baseline.0 == name)
+            .expect("no baseline recorded");
+        std::println!(
+            "{name:<24} cpu {:>9} mem {:>7} reads {:>2} writes {:>2}",
+            cost.cpu,
+            cost.mem,
+            cost.reads,
+            cost.writes
+        );
+        let ceiling = |base: u64| base + base * BENCH_TOLERANCE_PCT / 100;
+        assert!(cost.cpu <= ceiling(cpu), "{name}: cpu regressed: {cost:?}");
+        assert!(
+            cost.mem <= ceiling(mem),
+            "{name}: memory regressed: {cost:?}"
+        );
+        assert!(cost.reads <= reads, "{name}: more ledger reads: {cost:?}");
+        assert!(
+            cost.writes <= writes,
+            "{name}: more ledger writes: {cost:?}"
+        );
+    }
+}
+
+#[test]
+fn test_bench_stream_lifecycle() {
+    let ctx = TestContext::setup();
+    let mut stream_id = 0;
+    ctx.bench("create_stream", || stream_id = ctx.create_default_stream());
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.bench("calculate_accrued", || {
+        ctx.client().calculate_accrued(&stream_id)
+    });
+    ctx.bench("withdraw", || ctx.client().withdraw(&stream_id));
+    ctx.env.ledger().set_timestamp(300);
+    let to = Address::generate(&ctx.env);
+    ctx.bench("withdraw_to", || ctx.client().withdraw_to(&stream_id, &to));
+    ctx.bench("pause_stream", || ctx.client().pause_stream(&stream_id));
+    ctx.bench("resume_stream", || ctx.client().resume_stream(&stream_id));
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.bench("transfer_recipient", || {
+        ctx.client().transfer_recipient(&stream_id, &new_recipient)
+    });
+    ctx.bench("cancel_stream", || ctx.client().cancel_stream(&stream_id));
+}
+
+#[test]
+fn test_bench_fee_and_multi_leg_paths() {
+    let ctx = TestContext::setup();
+    ctx.enable_referrals(0);
+    ctx.bench("create_stream_with_fee", || ctx.create_default_stream());
+
+    let stream_id = ctx.create_withheld_stream(&Address::generate(&ctx.env), 1_000);
+    ctx.client().set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: Address::generate(&ctx.env),
+            share_bps: 1_000,
+        }),
+    );
+    ctx.client().set_round_up(
+        &ctx.recipient,
+        &Some(RoundUp {
+            donee: Address::generate(&ctx.env),
+            granularity: 7,
+        }),
+    );
+    ctx.env.ledger().set_timestamp(500);
+    ctx.bench("withdraw_multi_leg", || ctx.client().withdraw(&stream_id));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — checked arithmetic
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// A fresh sender holding `i128::MAX` of the default token.
+    fn whale(&self) -> Address {
+        let whale = Address::generate(&self.env);
+        StellarAssetClient::new(&self.env, &self.token_id).mint(&whale, &i128::MAX);
+        whale
+    }
+}
+
+#[test]
+fn test_streamable_overflow_reports_overflow_error() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &MAX_RATE_PER_SECOND,
+        &0u64,
+        &0u64,
+        &20_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::Overflow.into())));
+}
+
+#[test]
+fn test_fee_on_max_deposit_stays_in_range() {
+    let ctx = TestContext::setup();
+    ctx.client().propose_fee_params(&FeeParams {
+        protocol_fee_bps: 100,
+        fee_collector: ctx.admin.clone(),
+        max_broker_fee_bps: 0,
+    });
+    ctx.env.ledger().set_timestamp(FEE_TIMELOCK_SECS);
+    ctx.client().apply_fee_params();
+
+    let start = FEE_TIMELOCK_SECS;
+    ctx.client().create_stream(
+        &ctx.whale(),
+        &ctx.recipient,
+        &MAX_DEPOSIT,
+        &1_i128,
+        &start,
+        &start,
+        &(start + 1000),
+    );
+    assert_eq!(ctx.token().balance(&ctx.admin), MAX_DEPOSIT / 100);
+}
+
+#[test]
+fn test_max_deposit_accrues_and_withdraws_without_overflow() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let rate = MAX_DEPOSIT / 1000;
+    let stream_id = ctx.client().create_stream(
+        &ctx.whale(),
+        &ctx.recipient,
+        &MAX_DEPOSIT,
+        &rate,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    // The bps share of a maximal withdrawal must not overflow either.
+    let savings = Address::generate(&ctx.env);
+    ctx.client().set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: savings.clone(),
+            share_bps: BPS_DENOMINATOR / 2,
+        }),
+    );
+
+    ctx.env.ledger().set_timestamp(5000);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), rate * 1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), rate * 500);
+    assert_eq!(ctx.token().balance(&savings), rate * 500);
+    assert_eq!(
+        ctx.client().get_obligations(&ctx.token_id),
+        MAX_DEPOSIT - rate * 1000
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — amount and duration caps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_deposit_above_cap_reports_typed_error() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_create_stream(
+        &ctx.whale(),
+        &ctx.recipient,
+        &(MAX_DEPOSIT + 1),
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::DepositTooLarge.into())));
+}
+
+#[test]
+fn test_rate_above_cap_reports_typed_error() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &(MAX_RATE_PER_SECOND + 1),
+        &0u64,
+        &0u64,
+        &1u64,
+    );
+    assert_eq!(result, Err(Ok(Error::RateTooLarge.into())));
+}
+
+#[test]
+fn test_end_beyond_max_horizon_reports_typed_error() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    for end_time in [MAX_HORIZON + 1, u64::MAX] {
+        let result = ctx.client().try_create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &1000_i128,
+            &1_i128,
+            &(end_time - 1000),
+            &(end_time - 1000),
+            &end_time,
+        );
+        assert_eq!(result, Err(Ok(Error::HorizonTooFar.into())));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — accounting invariants
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_invariants_hold_through_stream_lifecycle() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let second = ctx.create_default_stream();
+    ctx.client().assert_invariants(&ctx.token_id);
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().withdraw(&first);
+    ctx.client().cancel_stream(&second);
+    ctx.client().withdraw(&second);
+    ctx.client().assert_invariants(&ctx.token_id);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&first);
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+fn test_cancelled_stream_stops_accruing() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(ctx.client().get_obligations(&ctx.token_id), 1000);
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "invariant violated: balance below principal")]
+fn test_invariants_catch_balance_below_principal() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    // Simulate an accounting bug: principal recorded without the tokens.
+    ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env.storage().persistent().set(
+            &DataKey::Custody(ctx.token_id.clone()),
+            &Custody {
+                principal: 2000,
+                fees_charged: 0,
+            },
+        );
+    });
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "invariant violated: principal below open stream balances")]
+fn test_invariants_catch_principal_below_open_streams() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env.storage().persistent().set(
+            &DataKey::Custody(ctx.token_id.clone()),
+            &Custody {
+                principal: 500,
+                fees_charged: 0,
+            },
+        );
+    });
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — randomized accrual properties
+// ---------------------------------------------------------------------------
+//
+// Each case draws a schedule (start, cliff, end, rate, deposit with leftover
+// above rate × duration) and a sequence of time jumps interleaved with
+// withdraw / pause / resume / cancel, then checks after every step that:
+// - accrual matches the reference model and never decreases;
+// - withdrawn ≤ accrued ≤ deposit;
+// - funds are conserved between sender, recipient and contract;
+// - the contract's invariants hold.
+// Cases come from a seeded generator, so a failure names a replayable seed.
+
+/// SplitMix64, a small deterministic generator for reproducible cases.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `lo..=hi`.
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next() % (hi - lo + 1)
+    }
+}
+
+/// Reference accrual for a schedule at `now`.
+fn model_accrued(start: u64, cliff: u64, end: u64, rate: i128, deposit: i128, now: u64) -> i128 {
+    if now < cliff {
+        return 0;
+    }
+    ((now.min(end) - start) as i128 * rate).min(deposit)
+}
+
+fn run_accrual_case(seed: u64) {
+    let mut rng = Rng(seed);
+    let ctx = TestContext::setup();
+    ctx.env.budget().reset_unlimited();
+
+    let start = rng.range(0, 1_000);
+    let duration = rng.range(1, 5_000);
+    let cliff = start + rng.range(0, duration);
+    let end = start + duration;
+    let rate = rng.range(1, 1_000) as i128;
+    let deposit = rate * duration as i128 + rng.range(0, 500) as i128;
+
+    let sender = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&sender, &deposit);
+    let mut now = rng.range(0, start);
+    ctx.env.ledger().set_timestamp(now);
+    let stream_id = ctx.client().create_stream(
+        &sender,
+        &ctx.recipient,
+        &deposit,
+        &rate,
+        &start,
+        &cliff,
+        &end,
+    );
+
+    let mut cancelled_at: Option<u64> = None;
+    let mut last_accrued = 0;
+    let check = |now: u64, cancelled_at: Option<u64>, last_accrued: &mut i128| {
+        let accrued = ctx.client().calculate_accrued(&stream_id);
+        let expected = model_accrued(
+            start,
+            cliff,
+            end,
+            rate,
+            deposit,
+            cancelled_at.map_or(now, |at| at.min(now)),
+        );
+        assert_eq!(accrued, expected, "seed {seed}: accrual at {now}");
+        assert!(accrued >= *last_accrued, "seed {seed}: accrual decreased");
+        *last_accrued = accrued;
+
+        let stream = ctx.client().get_stream_state(&stream_id);
+        assert!(
+            stream.withdrawn_amount <= accrued && accrued <= deposit,
+            "seed {seed}: withdrawn {} accrued {accrued}",
+            stream.withdrawn_amount
+        );
+        let held = ctx.token().balance(&sender)
+            + ctx.token().balance(&ctx.recipient)
+            + ctx.token().balance(&ctx.contract_id);
+        assert_eq!(held, deposit, "seed {seed}: funds not conserved");
+        ctx.client().assert_invariants(&ctx.token_id);
+    };
+
+    for _ in 0..12 {
+        now += rng.range(0, duration / 3 + 1);
+        ctx.env.ledger().set_timestamp(now);
+        match rng.range(0, 4) {
+            0 => {}
+            1 => {
+                let _ = ctx.client().try_withdraw(&stream_id);
+            }
+            2 => {
+                let _ = ctx.client().try_pause_stream(&stream_id);
+            }
+            3 => {
+                let _ = ctx.client().try_resume_stream(&stream_id);
+            }
+            _ => {
+                if ctx.client().try_cancel_stream(&stream_id).is_ok() {
+                    cancelled_at = Some(now);
+                }
+            }
+        }
+        check(now, cancelled_at, &mut last_accrued);
+    }
+
+    // Settle: run past the end, withdraw what is left and cancel to return
+    // any leftover deposit. Everything must end up with the two parties.
+    now = now.max(end) + 1;
+    ctx.env.ledger().set_timestamp(now);
+    let _ = ctx.client().try_resume_stream(&stream_id);
+    let _ = ctx.client().try_withdraw(&stream_id);
+    if ctx.client().try_cancel_stream(&stream_id).is_ok() {
+        cancelled_at = Some(now);
+    }
+    check(now, cancelled_at, &mut last_accrued);
+    let _ = ctx.client().try_withdraw(&stream_id);
+
+    assert_eq!(
+        ctx.token().balance(&ctx.recipient),
+        last_accrued,
+        "seed {seed}: recipient not paid everything accrued"
+    );
+    assert_eq!(
+        ctx.token().balance(&sender),
+        deposit - last_accrued,
+        "seed {seed}: sender not refunded the rest"
+    );
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0, "seed {seed}");
+}
+
+#[test]
+fn test_randomized_accrual_conserves_funds_and_is_monotonic() {
+    for seed in 0..32 {
+        run_accrual_case(seed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — ledger-sequence clock
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// 1000 units over ledgers 100..1100 at 1 per ledger.
+    fn create_sequence_stream(&self) -> u64 {
+        self.env.ledger().set_timestamp(0);
+        self.env.ledger().set_sequence_number(100);
+        self.client().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &1000_i128,
+            &1_i128,
+            &100u64,
+            &100u64,
+            &1100u64,
+            &StreamOptions {
+                clock: Clock::LedgerSequence,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+#[test]
+fn test_sequence_stream_accrues_per_ledger_not_per_second() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_sequence_stream();
+    assert_eq!(
+        ctx.client().get_stream_clock(&stream_id),
+        Clock::LedgerSequence
+    );
+
+    ctx.env.ledger().set_sequence_number(400);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 300);
+
+    // Close times move freely; only the sequence counts.
+    ctx.env.ledger().set_timestamp(1_000_000);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+}
+
+#[test]
+fn test_sequence_stream_completes_at_end_ledger() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_sequence_stream();
+
+    ctx.env.ledger().set_sequence_number(1100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+fn test_sequence_stream_cancel_refunds_unstreamed_ledgers() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_sequence_stream();
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.env.ledger().set_sequence_number(600);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before + 500);
+    assert_eq!(
+        ctx.client().get_stream_clock(&stream_id),
+        Clock::LedgerSequence
+    );
+
+    ctx.env.ledger().set_sequence_number(2000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — scaled sub-unit rates
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// A stream at `scaled_rate` over 0..1000.
+    fn create_scaled_stream(&self, deposit_amount: i128, scaled_rate: u128) -> u64 {
+        self.env.ledger().set_timestamp(0);
+        self.client().create_stream_scaled(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &deposit_amount,
+            &scaled_rate,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &StreamOptions::default(),
+        )
+    }
+}
+
+#[test]
+fn test_scaled_rate_keeps_sub_unit_remainders() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_scaled_stream(300, 300_000_000);
+    let stream = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(stream.rate_per_second, 0);
+    assert_eq!(ctx.client().get_scaled_rate(&stream_id), Some(300_000_000));
+
+    // Each 11s window carries 3.3 units; the 0.3s add up rather than vanish.
+    let mut withdrawn = 0;
+    for now in [11u64, 22, 33] {
+        ctx.env.ledger().set_timestamp(now);
+        withdrawn += ctx.client().withdraw(&stream_id);
+    }
+    assert_eq!(withdrawn, 9);
+    ctx.env.ledger().set_timestamp(34);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 10);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic(expected = "deposit_amount must cover total streamable amount")]
+fn test_scaled_rate_deposit_must_cover_schedule() {
+    let ctx = TestContext::setup();
+    ctx.create_scaled_stream(299, 300_000_000);
+}
+
+#[test]
+#[should_panic(expected = "scaled_rate must be positive")]
+fn test_zero_scaled_rate_panics() {
+    let ctx = TestContext::setup();
+    ctx.create_scaled_stream(300, 0);
+}
+
+#[test]
+fn test_whole_unit_stream_has_no_scaled_rate() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_scaled_rate(&stream_id), None);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — event fixtures
+// ---------------------------------------------------------------------------
+//
+// Each scenario renders every event the contract publishes and compares the
+// result with a committed fixture under `test_fixtures/events/`, so a change
+// to any event's topics or data shows up in review as a fixture diff.
+// Regenerate the fixtures after an intended change with
+// `UPDATE_EVENT_FIXTURES=1 cargo test -p fluxora_stream fixture`.
+
+/// Events published by the stream contract during a scripted scenario, one
+/// per line, with addresses replaced by readable labels.
+struct EventLog<'a> {
+    ctx: &'a TestContext,
+    labels: std::vec::Vec<(ScAddress, std::string::String)>,
+    lines: std::vec::Vec<std::string::String>,
+    /// Number of host events already recorded.
+    seen: u32,
+}
+
+impl<'a> EventLog<'a> {
+    fn new(ctx: &'a TestContext) -> Self {
+        let mut log = EventLog {
+            ctx,
+            labels: std::vec::Vec::new(),
+            lines: std::vec::Vec::new(),
+            seen: 0,
+        };
+        log.label(&ctx.contract_id, "stream");
+        log.label(&ctx.token_id, "token");
+        log.label(&ctx.admin, "admin");
+        log.label(&ctx.guardian, "guardian");
+        log.label(&ctx.sender, "sender");
+        log.label(&ctx.recipient, "recipient");
+        log
+    }
+
+    fn label(&mut self, address: &Address, name: &str) {
+        self.labels.push((ScAddress::from(address), name.into()));
+    }
+
+    /// Generate an address, labelled `name` in the rendered events.
+    fn address(&mut self, name: &str) -> Address {
+        let address = Address::generate(&self.ctx.env);
+        self.label(&address, name);
+        address
+    }
+
+    /// Append the events published since the last step under a `# step`
+    /// heading.
+    fn record(&mut self, step: &str) {
+        use soroban_sdk::{testutils::Events, TryFromVal};
+
+        self.lines.push(std::format!("# {step}"));
+        let events = self.ctx.env.events().all();
+        let new = events.slice(self.seen..);
+        self.seen = events.len();
+        for (contract, topics, data) in new.iter() {
+            if contract != self.ctx.contract_id {
+                continue;
+            }
+            let topics: std::vec::Vec<_> = topics
+                .iter()
+                .map(|topic| self.render(&ScVal::try_from_val(&self.ctx.env, &topic).unwrap()))
+                .collect();
+            let data = self.render(&ScVal::try_from_val(&self.ctx.env, &data).unwrap());
+            self.lines
+                .push(std::format!("{} => {}", topics.join(" "), data));
+        }
+    }
+
+    fn render(&self, value: &ScVal) -> std::string::String {
+        use std::format;
+
+        let list = |values: &mut dyn Iterator<Item = std::string::String>| {
+            values.collect::<std::vec::Vec<_>>().join(", ")
+        };
+        match value {
+            ScVal::Void => "()".into(),
+            ScVal::Bool(b) => format!("{b}"),
+            ScVal::U32(n) => format!("{n}u32"),
+            ScVal::I32(n) => format!("{n}i32"),
+            ScVal::U64(n) => format!("{n}u64"),
+            ScVal::I64(n) => format!("{n}i64"),
+            ScVal::U128(parts) => format!("{}u128", (parts.hi as u128) << 64 | parts.lo as u128),
+            ScVal::I128(parts) => format!("{}i128", (parts.hi as i128) << 64 | parts.lo as i128),
+            ScVal::Symbol(s) => std::string::String::from_utf8_lossy(s.as_slice()).into(),
+            ScVal::String(s) => format!("{:?}", std::string::String::from_utf8_lossy(s.as_slice())),
+            ScVal::Bytes(b) => format!(
+                "0x{}",
+                list(&mut b.iter().map(|byte| format!("{byte:02x}"))).replace(", ", "")
+            ),
+            ScVal::Address(address) => match self.labels.iter().find(|(a, _)| a == address) {
+                Some((_, name)) => format!("@{name}"),
+                None => "@unlabelled".into(),
+            },
+            ScVal::Vec(Some(items)) => {
+                format!("[{}]", list(&mut items.iter().map(|v| self.render(v))))
+            }
+            ScVal::Map(Some(entries)) => format!(
+                "{{{}}}",
+                list(&mut entries.iter().map(|e| format!(
+                    "{}: {}",
+                    self.render(&e.key),
+                    self.render(&e.val)
+                )))
+            ),
+            other => format!("{other:?}"),
+        }
+    }
+
+    /// Compare the log with `test_fixtures/events/{name}.txt`, or rewrite the
+    /// fixture when `UPDATE_EVENT_FIXTURES` is set.
+    fn assert_fixture(&self, name: &str) {
+        let path = std::format!(
+            "{}/test_fixtures/events/{name}.txt",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let rendered = self.lines.join("\n") + "\n";
+        if std::env::var("UPDATE_EVENT_FIXTURES").is_ok() {
+            std::fs::write(&path, &rendered).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            rendered == expected,
+            "events differ from {path}; if the change is intended, rerun with \
+             UPDATE_EVENT_FIXTURES=1 and commit the fixture.\n--- expected\n{expected}--- actual\n{rendered}"
+        );
+    }
+}
+
+#[test]
+fn test_stream_lifecycle_events_match_fixture() {
+    let ctx = TestContext::setup();
+    let mut log = EventLog::new(&ctx);
+    let client = ctx.client();
+
+    let stream_id = ctx.create_default_stream();
+    log.record("create_stream");
+
+    ctx.env.ledger().set_timestamp(100);
+    client.withdraw(&stream_id);
+    log.record("withdraw");
+
+    client.pause_stream(&stream_id);
+    log.record("pause_stream");
+    client.resume_stream(&stream_id);
+    log.record("resume_stream");
+
+    let savings = log.address("savings");
+    client.set_withdraw_split(
+        &stream_id,
+        &Some(WithdrawSplit {
+            destination: savings,
+            share_bps: 2_500,
+        }),
+    );
+    log.record("set_withdraw_split");
+    ctx.env.ledger().set_timestamp(200);
+    client.withdraw(&stream_id);
+    log.record("withdraw with split");
+
+    let lender = log.address("lender");
+    client.approve_operator(&stream_id, &lender, &40);
+    log.record("approve_operator");
+    ctx.env.ledger().set_timestamp(300);
+    client.withdraw(&stream_id);
+    log.record("withdraw with operator");
+    client.approve_operator(&stream_id, &lender, &40);
+    log.record("approve_operator again");
+    client.release_operator(&stream_id);
+    log.record("release_operator");
+
+    let new_recipient = log.address("new_recipient");
+    client.transfer_recipient(&stream_id, &new_recipient);
+    log.record("transfer_recipient");
+
+    client.restore_stream(&stream_id);
+    log.record("restore_stream");
+
+    client.cancel_stream(&stream_id);
+    log.record("cancel_stream");
+
+    let arbiter = log.address("arbiter");
+    let tax = log.address("tax");
+    let options = StreamOptions {
+        credit_refunds: true,
+        arbiter: Some(arbiter),
+        withholding_to: Some(tax),
+        withholding_bps: 1_000,
+        ..Default::default()
+    };
+    ctx.env.ledger().set_timestamp(0);
+    let disputed = client.create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1_000,
+        &1,
+        &0,
+        &0,
+        &1_000,
+        &options,
+    );
+    log.record("create_stream_with_options");
+
+    ctx.env.ledger().set_timestamp(100);
+    client.withdraw(&disputed);
+    log.record("withdraw with withholding");
+
+    client.open_dispute(&disputed, &ctx.sender);
+    log.record("open_dispute");
+    client.resolve_dispute(&disputed, &50);
+    log.record("resolve_dispute");
+
+    let credited = client.create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1_000,
+        &1,
+        &100,
+        &100,
+        &1_100,
+        &StreamOptions {
+            credit_refunds: true,
+            ..Default::default()
+        },
+    );
+    client.cancel_stream(&credited);
+    log.record("cancel_stream with credit refunds");
+    client.claim_credit(&ctx.sender, &ctx.token_id, &ctx.sender);
+    log.record("claim_credit");
+
+    ctx.env.ledger().set_timestamp(100 + CLOSE_RETENTION_SECS);
+    client.close_stream(&credited);
+    log.record("close_stream");
+
+    log.assert_fixture("stream_lifecycle");
+}
+
+#[test]
+fn test_protocol_admin_events_match_fixture() {
+    let ctx = TestContext::setup();
+    let mut log = EventLog::new(&ctx);
+    let client = ctx.client();
+    ctx.env.ledger().set_timestamp(0);
+
+    let other_token = ctx.create_token();
+    log.label(&other_token, "other_token");
+    client.allow_token(&other_token);
+    log.record("allow_token");
+    client.disallow_token(&other_token);
+    log.record("disallow_token");
+    client.block_token(&other_token);
+    log.record("block_token");
+    client.unblock_token(&other_token);
+    log.record("unblock_token");
+    client.guardian_block_token(&other_token);
+    log.record("guardian_block_token");
+    client.unblock_token(&other_token);
+    log.record("unblock_token after guardian block");
+
+    client.set_creation_limits(&CreationLimits {
+        min_deposit: 10,
+        min_duration: 10,
+        max_horizon: 0,
+        max_active_per_sender: 0,
+    });
+    log.record("set_creation_limits");
+    client.set_ttl_params(&DEFAULT_TTL_THRESHOLD, &DEFAULT_TTL_EXTEND_TO);
+    log.record("set_ttl_params");
+    let governor = log.address("governor");
+    client.set_governor(&Some(governor));
+    log.record("set_governor");
+    client.set_governor(&None);
+    log.record("clear governor");
+
+    let collector = log.address("collector");
+    let params = FeeParams {
+        protocol_fee_bps: 100,
+        fee_collector: collector,
+        max_broker_fee_bps: 0,
+    };
+    client.propose_fee_params(&params);
+    log.record("propose_fee_params");
+    client.cancel_fee_params();
+    log.record("cancel_fee_params");
+    client.propose_fee_params(&params);
+    ctx.env.ledger().set_timestamp(FEE_TIMELOCK_SECS);
+    client.apply_fee_params();
+    log.record("apply_fee_params");
+    client.set_referral_share(&5_000);
+    log.record("set_referral_share");
+
+    let referrer = log.address("referrer");
+    let now = ctx.env.ledger().timestamp();
+    client.create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1_000,
+        &1,
+        &now,
+        &now,
+        &(now + 1_000),
+        &StreamOptions {
+            referrer: Some(referrer.clone()),
+            ..Default::default()
+        },
+    );
+    client.claim_referral(&referrer, &ctx.token_id);
+    log.record("claim_referral");
+
+    client.set_attestation_registry(&None);
+    log.record("set_attestation_registry");
+    client.set_transfer_policy(&None);
+    log.record("set_transfer_policy");
+    client.set_reputation_registry(&None);
+    log.record("set_reputation_registry");
+    client.set_swap_router(&None);
+    log.record("set_swap_router");
+    client.set_recipient_vault(&ctx.recipient, &None);
+    log.record("set_recipient_vault");
+    client.set_auto_withdraw(&ctx.recipient, &true);
+    log.record("set_auto_withdraw");
+    client.set_round_up(&ctx.recipient, &None);
+    log.record("set_round_up");
+
+    client.freeze_recipient(&ctx.recipient);
+    log.record("freeze_recipient");
+    client.unfreeze_recipient(&ctx.recipient);
+    log.record("unfreeze_recipient");
+    client.emergency_pause();
+    log.record("emergency_pause");
+    client.emergency_unpause();
+    log.record("emergency_unpause");
+
+    let new_guardian = log.address("new_guardian");
+    client.propose_guardian(&new_guardian);
+    log.record("propose_guardian");
+    client.accept_guardian();
+    log.record("accept_guardian");
+    client.set_guardian_pause_window(&DEFAULT_GUARDIAN_PAUSE_SECS);
+    log.record("set_guardian_pause_window");
+
+    let template_id = client.create_template(
+        &ctx.sender,
+        &StreamTemplate {
+            token: ctx.token_id.clone(),
+            rate_per_second: 1,
+            cliff_offset: 0,
+            duration: 100,
+            options: StreamOptions::default(),
+        },
+    );
+    log.record("create_template");
+    assert_eq!(template_id, 0);
+
+    let operator = log.address("operator");
+    client.approve_creator(&ctx.sender, &operator, &ctx.token_id, &500, &(now + 100));
+    log.record("approve_creator");
+
+    let employee = log.address("employee");
+    client.import_payroll(
+        &ctx.sender,
+        &vec![
+            &ctx.env,
+            PayrollEntry {
+                recipient: employee,
+                amount: 100,
+                schedule: PayrollSchedule {
+                    start_time: now,
+                    cliff_time: now,
+                    end_time: now + 100,
+                },
+                external_id: String::from_str(&ctx.env, "E-1"),
+            },
+        ],
+    );
+    log.record("import_payroll");
+
+    ctx.token().transfer(&ctx.sender, &ctx.contract_id, &30);
+    client.skim(&ctx.token_id);
+    log.record("skim");
+    ctx.token().transfer(&ctx.sender, &ctx.contract_id, &30);
+    client.rescue(&ctx.token_id, &30, &ctx.admin);
+    log.record("rescue");
+
+    client.sunset();
+    log.record("sunset");
+
+    log.assert_fixture("protocol_admin");
+}
+
+#[test]
+fn test_guardian_and_clawback_events_match_fixture() {
+    let ctx = TestContext::setup();
+    let mut log = EventLog::new(&ctx);
+    let client = ctx.client();
+
+    let held = ctx.create_default_stream();
+    log.record("create_stream");
+    client.guardian_pause_stream(&held);
+    log.record("guardian_pause_stream");
+    client.confirm_guardian_hold(&held);
+    log.record("confirm_guardian_hold");
+    client.release_guardian_hold(&held);
+    log.record("release_guardian_hold");
+
+    client.guardian_pause_stream(&held);
+    ctx.env
+        .ledger()
+        .set_timestamp(DEFAULT_GUARDIAN_PAUSE_SECS + 1);
+    client.withdraw(&held);
+    log.record("withdraw after hold expiry");
+
+    let clawed = ctx.create_default_stream();
+    let reason = BytesN::from_array(&ctx.env, &[7; 32]);
+    client.queue_clawback(&clawed, &reason);
+    log.record("queue_clawback");
+    client.cancel_clawback(&clawed);
+    log.record("cancel_clawback");
+    client.queue_clawback(&clawed, &reason);
+    ctx.env.ledger().set_timestamp(CLAWBACK_DELAY_SECS);
+    client.execute_clawback(&clawed);
+    log.record("execute_clawback");
+
+    let cancelled = ctx.create_default_stream();
+    client.cancel_stream_as_admin(&cancelled);
+    log.record("cancel_stream_as_admin");
+
+    log.assert_fixture("guardian_and_clawback");
+}
+
+// ---------------------------------------------------------------------------
+// Tests — differential testing against the reference model
+// ---------------------------------------------------------------------------
+//
+// Random sequences of create / withdraw / pause / resume / cancel /
+// transfer_recipient and time jumps are applied to both the contract and
+// `model::Model`. After each step both must agree on whether the operation
+// succeeded, on every balance, and on each stream's status, deposit,
+// withdrawn amount, recipient and accrual. Seeds are replayable.
+
+/// Number of accounts the differential driver streams between.
+const MODEL_ACCOUNTS: usize = 4;
+
+fn run_differential_case(seed: u64) {
+    use crate::model::Model;
+
+    let mut rng = Rng(seed);
+    let ctx = TestContext::setup();
+    ctx.env.budget().reset_unlimited();
+    ctx.env.ledger().set_timestamp(0);
+
+    let accounts: std::vec::Vec<Address> = (0..MODEL_ACCOUNTS)
+        .map(|_| Address::generate(&ctx.env))
+        .collect();
+    let funding: std::vec::Vec<i128> = (0..MODEL_ACCOUNTS)
+        .map(|_| rng.range(0, 20_000) as i128)
+        .collect();
+    for (account, amount) in accounts.iter().zip(&funding) {
+        if *amount > 0 {
+            StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(account, amount);
+        }
+    }
+    let mut model = Model::new(funding);
+    let account = |i: u64| (i as usize) % MODEL_ACCOUNTS;
+
+    for step in 0..40 {
+        let streams = model.streams.len() as u64;
+        let op = if streams == 0 { 0 } else { rng.range(0, 6) };
+        let id = if streams == 0 {
+            0
+        } else {
+            rng.range(0, streams - 1)
+        };
+        let (contract_ok, model_ok) = match op {
+            0 => {
+                let sender = account(rng.next());
+                let recipient = account(rng.next());
+                let start = model.now.saturating_sub(50) + rng.range(0, 200);
+                let end = start + rng.range(0, 2_000);
+                let cliff = start + rng.range(0, end - start + 10);
+                let rate = rng.range(0, 5) as i128;
+                let deposit = rate * (end - start) as i128 + rng.range(0, 100) as i128 - 20;
+                let contract_ok = ctx
+                    .client()
+                    .try_create_stream(
+                        &accounts[sender],
+                        &accounts[recipient],
+                        &deposit,
+                        &rate,
+                        &start,
+                        &cliff,
+                        &end,
+                    )
+                    .is_ok();
+                let model_ok = model
+                    .create(sender, recipient, deposit, rate, start, cliff, end)
+                    .is_ok();
+                (contract_ok, model_ok)
+            }
+            1 | 2 => (
+                ctx.client().try_withdraw(&id).is_ok(),
+                model.withdraw(id).is_ok(),
+            ),
+            3 => (
+                ctx.client().try_pause_stream(&id).is_ok(),
+                model.pause(id).is_ok(),
+            ),
+            4 => (
+                ctx.client().try_resume_stream(&id).is_ok(),
+                model.resume(id).is_ok(),
+            ),
+            5 => (
+                ctx.client().try_cancel_stream(&id).is_ok(),
+                model.cancel(id).is_ok(),
+            ),
+            _ => {
+                let new_recipient = account(rng.next());
+                (
+                    ctx.client()
+                        .try_transfer_recipient(&id, &accounts[new_recipient])
+                        .is_ok(),
+                    model.transfer_recipient(id, new_recipient).is_ok(),
+                )
+            }
+        };
+        assert_eq!(
+            contract_ok, model_ok,
+            "seed {seed} step {step}: op {op} on stream {id} diverged"
+        );
+
+        model.now += rng.range(0, 400);
+        ctx.env.ledger().set_timestamp(model.now);
+
+        for (i, expected) in model.balances.iter().enumerate() {
+            assert_eq!(
+                ctx.token().balance(&accounts[i]),
+                *expected,
+                "seed {seed} step {step}: balance of account {i}"
+            );
+        }
+        assert_eq!(
+            ctx.token().balance(&ctx.contract_id),
+            model.contract_balance,
+            "seed {seed} step {step}: contract balance"
+        );
+        for (id, expected) in model.streams.iter().enumerate() {
+            let id = id as u64;
+            let stream = ctx.client().get_stream_state(&id);
+            let context = std::format!("seed {seed} step {step} stream {id}");
+            assert_eq!(stream.status, expected.status, "{context}: status");
+            assert_eq!(
+                stream.deposit_amount, expected.deposit,
+                "{context}: deposit"
+            );
+            assert_eq!(
+                stream.withdrawn_amount, expected.withdrawn,
+                "{context}: withdrawn"
+            );
+            assert_eq!(
+                stream.recipient, accounts[expected.recipient],
+                "{context}: recipient"
+            );
+            assert_eq!(
+                ctx.client().calculate_accrued(&id),
+                expected.accrued(model.now),
+                "{context}: accrued"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_contract_matches_reference_model() {
+    for seed in 0..24 {
+        run_differential_case(seed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — archived stream restoration
+// ---------------------------------------------------------------------------
+
+/// TTL a `RestoreFootprint` operation leaves on a restored entry in these
+/// tests, well below the contract's own extension target.
+const RESTORED_TTL: u32 = 4_095;
+
+impl TestContext {
+    /// Move `key`'s expiry to `live_until`, standing in for rent lapsing
+    /// (a ledger in the past) or a restore operation (a ledger ahead).
+    fn set_live_until(&self, key: &DataKey, live_until: u32) {
+        let host = self.env.host();
+        let ledger_key = Rc::new(LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::from(&self.contract_id),
+            key: ScVal::try_from(key).unwrap(),
+            durability: ContractDataDurability::Persistent,
+        }));
+        host.with_mut_storage(|storage| {
+            let budget = host.budget_cloned();
+            let (entry, _) = storage
+                .map
+                .get::<Rc<LedgerKey>>(&ledger_key, &budget)?
+                .cloned()
+                .flatten()
+                .expect("entry not in storage");
+            storage.map =
+                storage
+                    .map
+                    .insert(ledger_key, Some((entry, Some(live_until))), &budget)?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn archive(&self, key: &DataKey) {
+        // Close a ledger so the entry can expire before the current one.
+        let sequence = self.env.ledger().sequence() + 1;
+        self.env.ledger().set_sequence_number(sequence);
+        self.set_live_until(key, sequence - 1);
+    }
+
+    fn restore_footprint(&self, key: &DataKey) {
+        self.set_live_until(key, self.env.ledger().sequence() + RESTORED_TTL);
+    }
+
+    fn ttl(&self, key: &DataKey) -> u32 {
+        self.env.as_contract(&self.contract_id, || {
+            self.env.storage().persistent().get_ttl(key)
+        })
+    }
+}
+
+#[test]
+#[should_panic(expected = "archived")]
+fn test_archived_stream_cannot_be_withdrawn() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.archive(&DataKey::Stream(stream_id));
+    ctx.archive(&DataKey::StreamProgress(stream_id));
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_restored_stream_pays_out_in_full() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().withdraw(&stream_id);
+
+    let keys = [
+        DataKey::Stream(stream_id),
+        DataKey::StreamProgress(stream_id),
+    ];
+    for key in &keys {
+        ctx.archive(key);
+        ctx.restore_footprint(key);
+        assert_eq!(ctx.ttl(key), RESTORED_TTL);
+    }
+
+    ctx.env.mock_auths(&[]);
+    ctx.client().restore_stream(&stream_id);
+    for key in &keys {
+        assert_eq!(ctx.ttl(key), DEFAULT_TTL_EXTEND_TO);
+    }
+
+    // Nothing accrued was lost while the entries were archived.
+    ctx.env.mock_all_auths();
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 600);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+}
+
+#[test]
+fn test_confirming_guardian_hold_extends_its_ttl() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().guardian_pause_stream(&stream_id);
+
+    let hold_key = DataKey::GuardianHold(stream_id);
+    ctx.restore_footprint(&hold_key);
+    assert_eq!(ctx.ttl(&hold_key), RESTORED_TTL);
+
+    ctx.client().confirm_guardian_hold(&stream_id);
+    assert_eq!(ctx.ttl(&hold_key), DEFAULT_TTL_EXTEND_TO);
+}
+
+#[test]
+fn test_restore_stream_bumps_per_stream_settings() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator_key = DataKey::Operator(stream_id);
+    ctx.client()
+        .approve_operator(&stream_id, &Address::generate(&ctx.env), &250);
+
+    ctx.archive(&operator_key);
+    ctx.restore_footprint(&operator_key);
+    ctx.client().restore_stream(&stream_id);
+
+    assert_eq!(ctx.ttl(&operator_key), DEFAULT_TTL_EXTEND_TO);
+}
+
+#[test]
+#[should_panic(expected = "stream not found")]
+fn test_restore_unknown_stream_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().restore_stream(&42);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — closing settled streams
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    fn has_entry(&self, key: &DataKey) -> bool {
+        self.env.as_contract(&self.contract_id, || {
+            self.env.storage().persistent().has(key)
+        })
+    }
+}
+
+#[test]
+fn test_close_completed_stream_leaves_tombstone() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(1000 + CLOSE_RETENTION_SECS);
+    ctx.env.mock_auths(&[]);
+    ctx.client().close_stream(&stream_id);
+
+    assert!(!ctx.has_entry(&DataKey::Stream(stream_id)));
+    assert!(!ctx.has_entry(&DataKey::StreamProgress(stream_id)));
+    assert_eq!(
+        ctx.client().get_tombstone(&stream_id),
+        Some(StreamStatus::Completed)
+    );
+    let closed = ctx.client().get_closed_stream(&stream_id).unwrap();
+    assert_eq!(closed.recipient, ctx.recipient);
+    assert_eq!(closed.withdrawn_amount, 1000);
+    assert!(ctx.client().try_get_stream_state(&stream_id).is_err());
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "retention period has not elapsed")]
+fn test_close_stream_before_retention_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env
+        .ledger()
+        .set_timestamp(1000 + CLOSE_RETENTION_SECS - 1);
+    ctx.client().close_stream(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "stream is not settled")]
+fn test_close_cancelled_stream_with_owed_balance_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+
+    // The recipient has yet to withdraw the 400 accrued before cancellation.
+    ctx.env.ledger().set_timestamp(400 + CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&stream_id);
+}
+
+#[test]
+fn test_close_cancelled_stream_counts_retention_from_settlement() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(900);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+
+    ctx.env.ledger().set_timestamp(400 + CLOSE_RETENTION_SECS);
+    assert!(ctx.client().try_close_stream(&stream_id).is_err());
+
+    ctx.env.ledger().set_timestamp(900 + CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_tombstone(&stream_id),
+        Some(StreamStatus::Cancelled)
+    );
+}
+
+#[test]
+fn test_close_stream_removes_per_stream_settings() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &ctx.token_id,
+        &1_000,
+        &1,
+        &0,
+        &0,
+        &1_000,
+        &StreamOptions {
+            arbiter: Some(Address::generate(&ctx.env)),
+            ..Default::default()
+        },
+    );
+    assert!(ctx.has_entry(&DataKey::Arbiter(stream_id)));
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(1000 + CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&stream_id);
+    assert!(!ctx.has_entry(&DataKey::Arbiter(stream_id)));
+}
+
+#[test]
+fn test_tombstone_is_none_for_open_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_tombstone(&stream_id), None);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — effective status
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Record `stream_id` as fully withdrawn but still active, as a payout
+    /// path that never flips the flag would leave it.
+    fn store_paid_out_active(&self, stream_id: u64) {
+        let deposit = self.client().get_stream_state(&stream_id).deposit_amount;
+        self.env.as_contract(&self.contract_id, || {
+            self.env.storage().persistent().set(
+                &DataKey::StreamProgress(stream_id),
+                &StreamProgress {
+                    version: STREAM_RECORD_VERSION,
+                    withdrawn_amount: deposit,
+                    status: StreamStatus::Active,
+                    settled_at: self.env.ledger().timestamp(),
+                },
+            );
+        });
+    }
+}
+
+#[test]
+fn test_ended_paid_out_stream_reads_completed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(500);
+    ctx.store_paid_out_active(stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+
+    ctx.env.ledger().set_timestamp(1000);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+}
+
+#[test]
+fn test_ended_stream_with_balance_left_stays_active() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(2000);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+}
+
+#[test]
+#[should_panic(expected = "stream already completed")]
+fn test_withdraw_treats_ended_paid_out_stream_as_completed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(500);
+    ctx.store_paid_out_active(stream_id);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_close_ended_paid_out_stream_releases_active_slot() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(500);
+    ctx.store_paid_out_active(stream_id);
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 1);
+
+    ctx.env.ledger().set_timestamp(500 + CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&stream_id);
+
+    assert_eq!(
+        ctx.client().get_tombstone(&stream_id),
+        Some(StreamStatus::Completed)
+    );
+    assert_eq!(ctx.client().get_active_stream_count(&ctx.sender), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream record versions
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Rewrite both records of `stream_id` in the layout used before
+    /// records carried a version.
+    fn store_v1_records(&self, stream_id: u64) {
+        self.env.as_contract(&self.contract_id, || {
+            let terms = migration::load_terms(&self.env, stream_id).unwrap();
+            let progress = migration::load_progress(&self.env, stream_id).unwrap();
+            let storage = self.env.storage().persistent();
+            storage.set(
+                &DataKey::Stream(stream_id),
+                &StreamTermsV1 {
+                    sender: terms.sender,
+                    recipient: terms.recipient,
+                    token: terms.token,
+                    deposit_amount: terms.deposit_amount,
+                    rate_per_second: terms.rate_per_second,
+                    start_time: terms.start_time,
+                    cliff_time: terms.cliff_time,
+                    end_time: terms.end_time,
+                    admin_cancelable: terms.admin_cancelable,
+                    credit_refunds: terms.credit_refunds,
+                    basis: terms.basis,
+                },
+            );
+            storage.set(
+                &DataKey::StreamProgress(stream_id),
+                &StreamProgressV1 {
+                    withdrawn_amount: progress.withdrawn_amount,
+                    status: progress.status,
+                },
+            );
+        });
+    }
+
+    /// The `version` field of the record under `key`, if it has one.
+    fn stored_version(&self, key: &DataKey) -> Option<u32> {
+        self.env.as_contract(&self.contract_id, || {
+            let record: Map<Symbol, Val> = self.env.storage().persistent().get(key).unwrap();
+            record
+                .get(symbol_short!("version"))
+                .map(|version| version.into_val(&self.env))
+        })
+    }
+}
+
+#[test]
+fn test_new_records_carry_current_version() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    for key in [
+        DataKey::Stream(stream_id),
+        DataKey::StreamProgress(stream_id),
+    ] {
+        assert_eq!(ctx.stored_version(&key), Some(STREAM_RECORD_VERSION));
+    }
+}
+
+#[test]
+fn test_v1_records_read_transparently() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let before = ctx.client().get_stream_state(&stream_id);
+    ctx.store_v1_records(stream_id);
+
+    let after = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(after.sender, before.sender);
+    assert_eq!(after.deposit_amount, before.deposit_amount);
+    assert_eq!(after.end_time, before.end_time);
+    assert_eq!(after.status, before.status);
+    ctx.env.ledger().set_timestamp(250);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 250);
+    assert_eq!(ctx.client().get_stream_clock(&stream_id), Clock::Timestamp);
+}
+
+#[test]
+fn test_withdraw_upgrades_v1_progress_lazily() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.store_v1_records(stream_id);
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+
+    // Only the record the withdrawal wrote moves to the current layout.
+    assert_eq!(
+        ctx.stored_version(&DataKey::StreamProgress(stream_id)),
+        Some(STREAM_RECORD_VERSION)
+    );
+    assert_eq!(ctx.stored_version(&DataKey::Stream(stream_id)), None);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        300
+    );
+}
+
+#[test]
+#[should_panic(expected = "unsupported stream record version")]
+fn test_unknown_record_version_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.as_contract(&ctx.contract_id, || {
+        let mut progress = migration::load_progress(&ctx.env, stream_id).unwrap();
+        progress.version = STREAM_RECORD_VERSION + 1;
+        ctx.env
+            .storage()
+            .persistent()
+            .set(&DataKey::StreamProgress(stream_id), &progress);
+    });
+
+    ctx.client().get_stream_state(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — interface version
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_interface_version_matches_shared_constant() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().interface_version(), INTERFACE_VERSION);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream views
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_stream_view_derives_balances_from_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(250);
+
+    let view = ctx.client().get_stream_view(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(view.recipient, state.recipient);
+    assert_eq!(view.deposit_amount, state.deposit_amount);
+    assert_eq!(view.withdrawn_amount, 100);
+    assert_eq!(view.status, StreamStatus::Active);
+    assert_eq!(view.effective_status, StreamStatus::Active);
+    assert_eq!(view.clock, Clock::Timestamp);
+    assert_eq!(view.accrued, ctx.client().calculate_accrued(&stream_id));
+    assert_eq!(view.withdrawable_now, 150);
+    assert_eq!(view.streamed_pct, 25);
+    assert_eq!(view.seconds_remaining, 750);
+}
+
+#[test]
+fn test_stream_view_reports_nothing_withdrawable_while_blocked() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(250);
+
+    ctx.client().pause_stream(&stream_id);
+    let view = ctx.client().get_stream_view(&stream_id);
+    assert_eq!(view.accrued, 250);
+    assert_eq!(view.withdrawable_now, 0);
+
+    ctx.client().resume_stream(&stream_id);
+    ctx.client().freeze_recipient(&ctx.recipient);
+    assert_eq!(ctx.client().get_stream_view(&stream_id).withdrawable_now, 0);
+}
+
+#[test]
+fn test_stream_view_shows_effective_status_of_ended_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(500);
+    ctx.store_paid_out_active(stream_id);
+    ctx.env.ledger().set_timestamp(1500);
+
+    let view = ctx.client().get_stream_view(&stream_id);
+    assert_eq!(view.status, StreamStatus::Active);
+    assert_eq!(view.effective_status, StreamStatus::Completed);
+    assert_eq!(view.withdrawable_now, 0);
+    assert_eq!(view.streamed_pct, 100);
+    assert_eq!(view.seconds_remaining, 0);
+}
+
+#[test]
+fn test_stream_views_are_returned_in_request_order() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let second = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+
+    let views = ctx
+        .client()
+        .get_stream_views(&vec![&ctx.env, second, first]);
+    assert_eq!(views.len(), 2);
+    assert_eq!(views.get(0).unwrap().stream_id, second);
+    assert_eq!(views.get(1).unwrap().stream_id, first);
+    assert_eq!(views.get(1).unwrap().withdrawable_now, 400);
+}
+
+#[test]
+#[should_panic(expected = "too many streams")]
+fn test_stream_views_reject_oversized_batch() {
+    let ctx = TestContext::setup();
+    let mut ids = soroban_sdk::Vec::new(&ctx.env);
+    for stream_id in 0..=MAX_VIEW_BATCH as u64 {
+        ids.push_back(stream_id);
+    }
+    ctx.client().get_stream_views(&ids);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — contract metadata
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_meta_reports_build_identity() {
+    let ctx = TestContext::setup();
+    let meta = ctx.client().meta();
+    assert_eq!(meta.name, String::from_str(&ctx.env, CONTRACT_NAME));
+    assert_eq!(meta.version, String::from_str(&ctx.env, CONTRACT_VERSION));
+    assert_eq!(
+        meta.repository,
+        String::from_str(&ctx.env, SOURCE_REPOSITORY)
+    );
+    assert_eq!(meta.audit, String::from_str(&ctx.env, AUDIT_REFERENCE));
+}
+
+#[test]
+fn test_meta_version_matches_crate_version() {
+    assert_eq!(CONTRACT_VERSION, env!("CARGO_PKG_VERSION"));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — SEP-41 token conformance
+// ---------------------------------------------------------------------------
+
+/// Token that reports metadata and balances but cannot transfer.
+#[soroban_sdk::contract]
+struct NoTransferToken;
+
+#[soroban_sdk::contractimpl]
+impl NoTransferToken {
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn balance(_env: Env, _id: Address) -> i128 {
+        1_000_000
+    }
+}
+
+impl TestContext {
+    /// Allow `token` and try to open a default-shaped stream in it.
+    fn create_in(&self, token: &Address) -> u64 {
+        self.client().allow_token(token);
+        self.env.ledger().set_timestamp(0);
+        self.client().create_stream_with_token(
+            &self.sender,
+            &self.recipient,
+            token,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        )
+    }
+}
+
+#[test]
+fn test_conforming_token_is_probed_once() {
+    let ctx = TestContext::setup();
+    let key = DataKey::Sep41Token(ctx.token_id.clone());
+    assert!(!ctx.has_entry(&key));
+
+    ctx.create_default_stream();
+    assert!(ctx.has_entry(&key));
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+
+    let second = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_stream_state(&second).deposit_amount, 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_contract_without_token_interface_is_rejected() {
+    let ctx = TestContext::setup();
+    let not_a_token = ctx.env.register_contract(None, FluxoraStream);
+    ctx.create_in(&not_a_token);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_token_without_transfer_is_rejected() {
+    let ctx = TestContext::setup();
+    let token = ctx.env.register_contract(None, NoTransferToken);
+    ctx.create_in(&token);
+}
+
+#[test]
+fn test_rejected_token_is_not_remembered() {
+    let ctx = TestContext::setup();
+    let token = ctx.env.register_contract(None, NoTransferToken);
+    let client = ctx.client();
+    client.allow_token(&token);
+    let result = client.try_create_stream_with_token(
+        &ctx.sender,
+        &ctx.recipient,
+        &token,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::NonConformingToken.into())));
+    assert!(!ctx.has_entry(&DataKey::Sep41Token(token)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — fee-on-transfer tokens
+// ---------------------------------------------------------------------------
+
+/// Kept in its own module so its generated items don't clash with
+/// `NoTransferToken`'s.
+mod fee_on_transfer {
+    use soroban_sdk::{Address, Env};
+
+    /// Token that burns 1% of every transfer, so recipients get less than sent.
+    #[soroban_sdk::contract]
+    pub struct FeeOnTransferToken;
+
+    #[soroban_sdk::contractimpl]
+    impl FeeOnTransferToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn decimals(_env: Env) -> u32 {
+            7
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let from_balance = Self::balance(env.clone(), from.clone());
+            assert!(from_balance >= amount, "balance is not sufficient");
+            env.storage()
+                .persistent()
+                .set(&from, &(from_balance - amount));
+            let to_balance = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .persistent()
+                .set(&to, &(to_balance + amount - amount / 100));
+        }
+    }
+}
+
+use fee_on_transfer::{FeeOnTransferToken, FeeOnTransferTokenClient};
+
+impl TestContext {
+    /// Register a 1% fee-on-transfer token holding 10_000 for the sender.
+    fn fee_token(&self) -> (Address, FeeOnTransferTokenClient<'_>) {
+        let token = self.env.register_contract(None, FeeOnTransferToken);
+        let client = FeeOnTransferTokenClient::new(&self.env, &token);
+        client.mint(&self.sender, &10_000);
+        self.client().allow_token(&token);
+        self.env.ledger().set_timestamp(0);
+        (token, client)
+    }
+
+    fn create_in_fee_token(&self, token: &Address, deposit: i128) -> u64 {
+        self.client().create_stream_with_token(
+            &self.sender,
+            &self.recipient,
+            token,
+            &deposit,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        )
+    }
+}
+
+#[test]
+fn test_fee_on_transfer_deposit_records_amount_received() {
+    let ctx = TestContext::setup();
+    let (token, token_client) = ctx.fee_token();
+
+    let stream_id = ctx.create_in_fee_token(&token, 1010);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(token_client.balance(&ctx.sender), 8_990);
+    assert_eq!(token_client.balance(&ctx.contract_id), 1000);
+    assert_eq!(ctx.client().get_custody(&token).principal, 1000);
+}
+
+#[test]
+#[should_panic(expected = "received amount must cover total streamable amount")]
+fn test_fee_on_transfer_shortfall_below_rate_is_rejected() {
+    let ctx = TestContext::setup();
+    let (token, _) = ctx.fee_token();
+    // 1% of 1000 is taken in transit, leaving 990 for a 1000-unit schedule.
+    ctx.create_in_fee_token(&token, 1000);
+}
+
+#[test]
+fn test_fee_on_transfer_stream_withdraws_in_full_at_end() {
+    let ctx = TestContext::setup();
+    let (token, token_client) = ctx.fee_token();
+    let stream_id = ctx.create_in_fee_token(&token, 1010);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 600);
+
+    // The recipient bears the token's fee on the way out; the contract is
+    // left holding nothing it owes.
+    assert_eq!(token_client.balance(&ctx.recipient), 396 + 594);
+    assert_eq!(token_client.balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().get_custody(&token).principal, 0);
+}
+
+#[test]
+fn test_fee_on_transfer_cancel_settles_both_parties() {
+    let ctx = TestContext::setup();
+    let (token, token_client) = ctx.fee_token();
+    let stream_id = ctx.create_in_fee_token(&token, 1010);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    ctx.client().withdraw(&stream_id);
+
+    assert_eq!(token_client.balance(&ctx.sender), 8_990 + 693);
+    assert_eq!(token_client.balance(&ctx.recipient), 297);
+    assert_eq!(token_client.balance(&ctx.contract_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "batch deposits do not support fee-on-transfer tokens")]
+fn test_payroll_import_rejects_fee_on_transfer_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token_id = env.register_contract(None, FeeOnTransferToken);
+    let ctx = TestContext {
+        contract_id: env.register_contract(None, FluxoraStream),
+        token_id: token_id.clone(),
+        admin: Address::generate(&env),
+        guardian: Address::generate(&env),
+        sender: Address::generate(&env),
+        recipient: Address::generate(&env),
+        env,
+    };
+    FeeOnTransferTokenClient::new(&ctx.env, &token_id).mint(&ctx.sender, &10_000);
+    ctx.client().init(&token_id, &ctx.admin, &ctx.guardian);
+
+    let entries = vec![&ctx.env, ctx.payroll_entry(&ctx.recipient, 2000, "emp-1")];
+    ctx.client().import_payroll(&ctx.sender, &entries);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — distressed assets
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Register a revocable, clawback-enabled asset holding 10_000 for the
+    /// sender, and open a default-shaped stream in it.
+    fn create_in_clawback_asset(&self) -> (u64, StellarAssetClient<'_>) {
+        let asset = self
+            .env
+            .register_stellar_asset_contract_v2(Address::generate(&self.env));
+        asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+        asset.issuer().set_flag(IssuerFlags::ClawbackEnabledFlag);
+        let sac = StellarAssetClient::new(&self.env, &asset.address());
+        sac.mint(&self.sender, &10_000);
+        (self.create_in(&asset.address()), sac)
+    }
+}
+
+#[test]
+fn test_withdraw_after_clawback_marks_stream_distressed() {
+    let ctx = TestContext::setup();
+    let (stream_id, sac) = ctx.create_in_clawback_asset();
+    sac.clawback(&ctx.contract_id, &100);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Distressed);
+    assert_eq!(state.withdrawn_amount, 0);
+    assert_eq!(
+        ctx.client().get_distress(&stream_id),
+        Some(Distress {
+            previous: StreamStatus::Active,
+            since: 500,
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "stream is distressed")]
+fn test_withdraw_from_distressed_stream_panics() {
+    let ctx = TestContext::setup();
+    let (stream_id, sac) = ctx.create_in_clawback_asset();
+    sac.set_authorized(&ctx.recipient, &false);
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().withdraw(&stream_id);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_withdraw_to_frozen_destination_marks_stream_distressed() {
+    let ctx = TestContext::setup();
+    let (stream_id, sac) = ctx.create_in_clawback_asset();
+    let to = Address::generate(&ctx.env);
+    sac.set_authorized(&to, &false);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw_to(&stream_id, &to), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Distressed
+    );
+}
+
+#[test]
+fn test_frozen_recipient_stream_recovers_once_reauthorized() {
+    let ctx = TestContext::setup();
+    let (stream_id, sac) = ctx.create_in_clawback_asset();
+    sac.set_authorized(&ctx.recipient, &false);
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    assert!(ctx.client().try_recover_stream(&stream_id).is_err());
+
+    sac.set_authorized(&ctx.recipient, &true);
+    assert_eq!(
+        ctx.client().recover_stream(&stream_id),
+        StreamStatus::Active
+    );
+    assert_eq!(ctx.client().get_distress(&stream_id), None);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+}
+
+#[test]
+fn test_clawback_shortfall_recovers_after_top_up() {
+    let ctx = TestContext::setup();
+    let (stream_id, sac) = ctx.create_in_clawback_asset();
+    sac.clawback(&ctx.contract_id, &100);
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().withdraw(&stream_id);
+    assert!(ctx.client().try_recover_stream(&stream_id).is_err());
+
+    sac.mint(&ctx.contract_id, &100);
+    ctx.client().recover_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_cancel_with_frozen_contract_balance_distresses_stream() {
+    let ctx = TestContext::setup();
+    let (stream_id, sac) = ctx.create_in_clawback_asset();
+    sac.set_authorized(&ctx.contract_id, &false);
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Distressed
+    );
+
+    sac.set_authorized(&ctx.contract_id, &true);
+    ctx.client().recover_stream(&stream_id);
+    ctx.client().cancel_stream(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Cancelled);
+    assert_eq!(state.deposit_amount, 300);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &sac.address).balance(&ctx.sender),
+        9_700
+    );
+}
+
+#[test]
+#[should_panic(expected = "stream is not distressed")]
+fn test_recover_healthy_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().recover_stream(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — native XLM
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Deploy this network's native XLM asset contract.
+    fn deploy_native(&self) -> Address {
+        let create = HostFunction::CreateContract(CreateContractArgs {
+            contract_id_preimage: ContractIdPreimage::Asset(Asset::Native),
+            executable: ContractExecutable::StellarAsset,
+        });
+        let id = self.env.host().invoke_function(create).unwrap();
+        id.try_into_val(&self.env).unwrap()
+    }
+
+    /// A classic account holding `stroops` of XLM.
+    fn native_account(&self, seed: u8, stroops: i64) -> Address {
+        let account_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([seed; 32])));
+        let key = Rc::new(LedgerKey::Account(LedgerKeyAccount {
+            account_id: account_id.clone(),
+        }));
+        let entry = Rc::new(LedgerEntry {
+            data: LedgerEntryData::Account(AccountEntry {
+                account_id: account_id.clone(),
+                balance: stroops,
+                flags: 0,
+                home_domain: Default::default(),
+                inflation_dest: None,
+                num_sub_entries: 0,
+                seq_num: SequenceNumber(0),
+                thresholds: Thresholds([1; 4]),
+                signers: Default::default(),
+                ext: AccountEntryExt::V0,
+            }),
+            last_modified_ledger_seq: 0,
+            ext: LedgerEntryExt::V0,
+        });
+        self.env
+            .host()
+            .with_mut_storage(|storage| {
+                storage.put(&key, &entry, None, AsBudget::as_budget(self.env.host()))
+            })
+            .unwrap();
+        ScAddress::Account(account_id)
+            .try_into_val(&self.env)
+            .unwrap()
+    }
+}
+
+#[test]
+fn test_native_token_resolves_to_native_asset_contract() {
+    let ctx = TestContext::setup();
+    let native = ctx.deploy_native();
+    assert_eq!(ctx.client().get_native_token(), native);
+
+    let token = TokenClient::new(&ctx.env, &native);
+    assert_eq!(token.decimals(), NATIVE_DECIMALS);
+    assert_eq!(token.name(), String::from_str(&ctx.env, "native"));
+    assert_eq!(token.symbol(), String::from_str(&ctx.env, "native"));
+}
+
+#[test]
+fn test_native_stream_pays_recipient_in_stroops() {
+    let ctx = TestContext::setup();
+    let native = ctx.deploy_native();
+    ctx.client().allow_token(&native);
+    let alice = ctx.native_account(7, 100_000_000);
+
+    let stream_id = ctx.client().create_native_stream(
+        &alice,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    let token = TokenClient::new(&ctx.env, &native);
+    assert_eq!(ctx.client().get_stream_state(&stream_id).token, native);
+    assert_eq!(token.balance(&alice), 99_999_000);
+    assert_eq!(token.balance(&ctx.contract_id), 1000);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(token.balance(&ctx.recipient), 400);
+}
+
+#[test]
+fn test_native_stream_cancel_refunds_sender_account() {
+    let ctx = TestContext::setup();
+    let native = ctx.deploy_native();
+    ctx.client().allow_token(&native);
+    let alice = ctx.native_account(7, 100_000_000);
+    let stream_id = ctx.client().create_native_stream(
+        &alice,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().cancel_stream(&stream_id);
+    let token = TokenClient::new(&ctx.env, &native);
+    assert_eq!(token.balance(&alice), 99_999_750);
+    assert_eq!(ctx.client().withdraw(&stream_id), 250);
+}
+
+#[test]
+#[should_panic(expected = "token is not allowed")]
+fn test_native_stream_requires_allowlisting() {
+    let ctx = TestContext::setup();
+    ctx.deploy_native();
+    let alice = ctx.native_account(7, 100_000_000);
+    ctx.client().create_native_stream(
+        &alice,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — basket streams
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Allow a second token and mint 10_000 of it to the sender.
+    fn second_token(&self) -> Address {
+        let token = self
+            .env
+            .register_stellar_asset_contract_v2(Address::generate(&self.env))
+            .address();
+        StellarAssetClient::new(&self.env, &token).mint(&self.sender, &10_000);
+        self.client().allow_token(&token);
+        token
+    }
+
+    /// Open a basket of 1000 of the default token and 300 of `other` over
+    /// `[0, 1000]`.
+    fn create_basket(&self, other: &Address) -> u64 {
+        self.env.ledger().set_timestamp(0);
+        let components = vec![
+            &self.env,
+            BasketComponent {
+                token: self.token_id.clone(),
+                amount: 1000,
+            },
+            BasketComponent {
+                token: other.clone(),
+                amount: 300,
+            },
+        ];
+        self.client().create_basket_stream(
+            &self.sender,
+            &self.recipient,
+            &components,
+            &0u64,
+            &0u64,
+            &1000u64,
+        )
+    }
+}
+
+#[test]
+fn test_basket_opens_one_stream_per_token() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+
+    let basket = ctx.client().get_basket(&basket_id);
+    assert_eq!(basket.sender, ctx.sender);
+    assert_eq!(basket.recipient, ctx.recipient);
+    assert_eq!(basket.streams.len(), 2);
+
+    let first = ctx
+        .client()
+        .get_stream_state(&basket.streams.get(0).unwrap());
+    let second = ctx
+        .client()
+        .get_stream_state(&basket.streams.get(1).unwrap());
+    assert_eq!(
+        (first.token, first.deposit_amount),
+        (ctx.token_id.clone(), 1000)
+    );
+    assert_eq!((second.token, second.deposit_amount), (other.clone(), 300));
+    assert_eq!(second.end_time, first.end_time);
+    assert_eq!(
+        ctx.client().get_basket_of(&second.stream_id),
+        Some(basket_id)
+    );
+    assert_eq!(
+        TokenClient::new(&ctx.env, &other).balance(&ctx.sender),
+        9_700
+    );
+}
+
+#[test]
+fn test_withdraw_basket_settles_components_proportionally() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(
+        ctx.client().withdraw_basket(&basket_id),
+        vec![&ctx.env, 400_i128, 120_i128]
+    );
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(
+        ctx.client().withdraw_basket(&basket_id),
+        vec![&ctx.env, 600_i128, 180_i128]
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &other).balance(&ctx.recipient),
+        300
+    );
+}
+
+#[test]
+fn test_closed_component_leaves_its_basket() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    let streams = ctx.client().get_basket(&basket_id).streams;
+    let (first, second) = (streams.get(0).unwrap(), streams.get(1).unwrap());
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&first), 1000);
+    ctx.env.ledger().set_timestamp(1000 + CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&first);
+
+    assert_eq!(
+        ctx.client().get_basket(&basket_id).streams,
+        vec![&ctx.env, second]
+    );
+    assert_eq!(ctx.client().get_basket_of(&first), None);
+    assert_eq!(
+        ctx.client().withdraw_basket(&basket_id),
+        vec![&ctx.env, 300_i128]
+    );
+
+    ctx.env
+        .ledger()
+        .set_timestamp(1000 + 2 * CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&second);
+    assert!(ctx.client().try_get_basket(&basket_id).is_err());
+}
+
+#[test]
+fn test_cancel_basket_refunds_every_token() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().cancel_basket(&basket_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_750);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &other).balance(&ctx.sender),
+        9_925
+    );
+
+    ctx.env.ledger().set_timestamp(900);
+    assert_eq!(
+        ctx.client().withdraw_basket(&basket_id),
+        vec![&ctx.env, 250_i128, 75_i128]
+    );
+}
+
+#[test]
+#[should_panic(expected = "stream is part of a basket")]
+fn test_basket_component_cannot_be_cancelled_alone() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    let stream_id = ctx.client().get_basket(&basket_id).streams.get(1).unwrap();
+    ctx.client().cancel_stream(&stream_id);
+}
+
+#[test]
+fn test_cancel_basket_with_distressed_component_cancels_none() {
+    let ctx = TestContext::setup();
+    let asset = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env));
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let sac = StellarAssetClient::new(&ctx.env, &asset.address());
+    sac.mint(&ctx.sender, &10_000);
+    ctx.client().allow_token(&asset.address());
+    let basket_id = ctx.create_basket(&asset.address());
+    let streams = ctx.client().get_basket(&basket_id).streams;
+    let (first, second) = (streams.get(0).unwrap(), streams.get(1).unwrap());
+
+    sac.set_authorized(&ctx.contract_id, &false);
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().cancel_basket(&basket_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&first).status,
+        StreamStatus::Active
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&second).status,
+        StreamStatus::Distressed
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+
+    sac.set_authorized(&ctx.contract_id, &true);
+    ctx.client().recover_stream(&second);
+    ctx.client().cancel_basket(&basket_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&first).status,
+        StreamStatus::Cancelled
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&second).status,
+        StreamStatus::Cancelled
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_750);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &sac.address).balance(&ctx.sender),
+        9_925
+    );
+}
+
+#[test]
+fn test_guardian_pause_basket_holds_every_component() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    ctx.client().guardian_pause_basket(&basket_id);
+
+    for stream_id in ctx.client().get_basket(&basket_id).streams.iter() {
+        assert_eq!(
+            ctx.client().get_stream_state(&stream_id).status,
+            StreamStatus::Paused
+        );
+        assert!(ctx.client().get_guardian_hold(&stream_id).is_some());
+    }
+}
+
+#[test]
+fn test_releasing_one_hold_resumes_the_whole_basket() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    ctx.client().guardian_pause_basket(&basket_id);
+
+    let streams = ctx.client().get_basket(&basket_id).streams;
+    ctx.client().release_guardian_hold(&streams.get(1).unwrap());
+    for stream_id in streams.iter() {
+        assert_eq!(
+            ctx.client().get_stream_state(&stream_id).status,
+            StreamStatus::Active
+        );
+        assert!(ctx.client().get_guardian_hold(&stream_id).is_none());
+    }
+}
+
+#[test]
+#[should_panic(expected = "stream is part of a basket")]
+fn test_basket_component_cannot_be_resumed_alone() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    ctx.client().guardian_pause_basket(&basket_id);
+    ctx.env.ledger().set_timestamp(DEFAULT_GUARDIAN_PAUSE_SECS);
+    let stream_id = ctx.client().get_basket(&basket_id).streams.get(0).unwrap();
+    ctx.client().resume_stream(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "stream is part of a basket")]
+fn test_guardian_cannot_pause_basket_component_alone() {
+    let ctx = TestContext::setup();
+    let other = ctx.second_token();
+    let basket_id = ctx.create_basket(&other);
+    let stream_id = ctx.client().get_basket(&basket_id).streams.get(1).unwrap();
+    ctx.client().guardian_pause_stream(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "duplicate basket token")]
+fn test_basket_rejects_repeated_token() {
+    let ctx = TestContext::setup();
+    let component = BasketComponent {
+        token: ctx.token_id.clone(),
+        amount: 500,
+    };
+    ctx.client().create_basket_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &vec![&ctx.env, component.clone(), component],
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — index-adjusted rates
+// ---------------------------------------------------------------------------
+
+impl TestContext {
+    /// Allow 10% index moves at most every 100 seconds and publish 1.0.
+    fn publish_index(&self) {
+        self.env.ledger().set_timestamp(0);
+        self.client().set_index_params(&IndexParams {
+            oracle: None,
+            max_step_bps: 1_000,
+            min_interval: 100,
+        });
+        self.client().update_index(&self.admin, &INDEX_SCALE);
+    }
+
+    fn set_index(&self, timestamp: u64, value: u128) {
+        self.env.ledger().set_timestamp(timestamp);
+        self.client().update_index(&self.admin, &value);
+    }
+
+    /// 1000 over `[0, 1000]`, escalating by at most 20%.
+    fn create_indexed(&self) -> u64 {
+        self.client().create_indexed_stream(
+            &self.sender,
+            &self.recipient,
+            &self.token_id,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &2_000u32,
+        )
+    }
+}
+
+#[test]
+fn test_update_index_enforces_publisher_step_and_interval() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let oracle = Address::generate(&ctx.env);
+    let client = ctx.client();
+
+    ctx.env.ledger().set_timestamp(50);
+    assert!(client
+        .try_update_index(&ctx.admin, &(INDEX_SCALE + 10))
+        .is_err());
+    ctx.env.ledger().set_timestamp(100);
+    assert!(client
+        .try_update_index(&ctx.admin, &(INDEX_SCALE * 12 / 10))
+        .is_err());
+    assert!(client.try_update_index(&oracle, &INDEX_SCALE).is_err());
+
+    client.set_index_params(&IndexParams {
+        oracle: Some(oracle.clone()),
+        max_step_bps: 1_000,
+        min_interval: 100,
+    });
+    client.update_index(&oracle, &(INDEX_SCALE * 11 / 10));
+    assert_eq!(
+        client.get_index(),
+        Some(IndexValue {
+            value: INDEX_SCALE * 11 / 10,
+            updated_at: 100,
+        })
+    );
+}
+
+#[test]
+fn test_indexed_stream_settles_at_prevailing_index() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_indexed();
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_800);
+    assert_eq!(
+        ctx.client().get_index_link(&stream_id),
+        Some(IndexLink {
+            base_index: INDEX_SCALE,
+            cap_bps: 2_000,
+            reserve: 200,
+        })
+    );
+
+    ctx.set_index(500, INDEX_SCALE * 11 / 10);
+    assert_eq!(ctx.client().withdraw(&stream_id), 550);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        500
+    );
+    assert_eq!(
+        ctx.client().get_index_link(&stream_id).unwrap().reserve,
+        150
+    );
+}
+
+#[test]
+fn test_indexed_escalation_is_capped() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_indexed();
+
+    // Compounding 10% steps take the index past the stream's 20% cap.
+    ctx.set_index(100, INDEX_SCALE * 11 / 10);
+    ctx.set_index(200, INDEX_SCALE * 121 / 100);
+    ctx.set_index(300, INDEX_SCALE * 1331 / 1000);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1200);
+    assert_eq!(ctx.client().get_index_link(&stream_id), None);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_falling_index_keeps_nominal_rate_and_refunds_reserve() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_indexed();
+
+    ctx.set_index(500, INDEX_SCALE * 9 / 10);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+    assert_eq!(ctx.client().get_index_link(&stream_id), None);
+}
+
+#[test]
+fn test_cancel_indexed_stream_keeps_reserve_for_owed_balance() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_indexed();
+
+    ctx.set_index(400, INDEX_SCALE * 11 / 10);
+    ctx.client().cancel_stream(&stream_id);
+    // 600 unstreamed plus the reserve beyond 20% of the 400 owed.
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_800 + 600 + 120);
+
+    assert_eq!(ctx.client().withdraw(&stream_id), 440);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_800 + 600 + 160);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "index has not been published")]
+fn test_indexed_stream_requires_published_index() {
+    let ctx = TestContext::setup();
+    ctx.create_indexed();
+}
+
+#[test]
+fn test_restore_stream_bumps_index_link() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_indexed();
+    let link_key = DataKey::IndexLink(stream_id);
+
+    ctx.archive(&link_key);
+    ctx.restore_footprint(&link_key);
+    ctx.client().restore_stream(&stream_id);
+
+    assert_eq!(ctx.ttl(&link_key), DEFAULT_TTL_EXTEND_TO);
+}
+
+impl TestContext {
+    /// Link an existing stream to the index with a 20% cap, funding its
+    /// reserve from the sender as `create_indexed_stream` would.
+    fn link_index(&self, stream_id: u64) {
+        let deposit = self.client().get_stream_state(&stream_id).deposit_amount;
+        let reserve = deposit * 2_000 / BPS_DENOMINATOR as i128;
+        self.token()
+            .transfer(&self.sender, &self.contract_id, &reserve);
+        self.env.as_contract(&self.contract_id, || {
+            crate::vault::adjust_principal(&self.env, &self.token_id, reserve);
+            let link = IndexLink {
+                base_index: INDEX_SCALE,
+                cap_bps: 2_000,
+                reserve,
+            };
+            crate::save_index_link(&self.env, stream_id, &link);
+        });
+    }
+}
+
+#[test]
+fn test_credit_cancel_of_indexed_stream_credits_escalation_and_reserve() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_credit_stream();
+    ctx.link_index(stream_id);
+
+    ctx.set_index(400, INDEX_SCALE * 11 / 10);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.client().get_index_link(&stream_id), None);
+    // 400 owed plus 10% escalation; 600 unstreamed plus the unused reserve.
+    assert_eq!(ctx.client().get_credit(&ctx.recipient, &ctx.token_id), 440);
+    assert_eq!(ctx.client().get_credit(&ctx.sender, &ctx.token_id), 760);
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+fn test_resolve_dispute_refunds_index_reserve() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_arbitrated_stream(&Address::generate(&ctx.env));
+    ctx.link_index(stream_id);
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().open_dispute(&stream_id, &ctx.recipient);
+    ctx.client().resolve_dispute(&stream_id, &300);
+    assert_eq!(ctx.client().get_index_link(&stream_id), None);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_800 + 700 + 200);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_cancel_keeping_whole_reserve_refunds_nothing() {
+    use soroban_sdk::testutils::Events;
+
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_default_stream();
+    // A reserve small enough that 20% of the owed balance covers all of it.
+    ctx.token().transfer(&ctx.sender, &ctx.contract_id, &50);
+    ctx.env.as_contract(&ctx.contract_id, || {
+        crate::vault::adjust_principal(&ctx.env, &ctx.token_id, 50);
+        let link = IndexLink {
+            base_index: INDEX_SCALE,
+            cap_bps: 2_000,
+            reserve: 50,
+        };
+        crate::save_index_link(&ctx.env, stream_id, &link);
+    });
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+    let refund_topics: soroban_sdk::Vec<Val> =
+        (symbol_short!("idx_rfnd"), stream_id).into_val(&ctx.env);
+    let refunds = ctx
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| *topics == refund_topics)
+        .count();
+    assert_eq!(refunds, 0);
+    assert_eq!(ctx.client().get_index_link(&stream_id).unwrap().reserve, 50);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000 - 50 + 600);
+}
+
+#[test]
+#[should_panic(expected = "index reserve is still held")]
+fn test_close_stream_with_index_reserve_panics() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    // A link left behind on a settled stream, as earlier versions could.
+    ctx.link_index(stream_id);
+
+    ctx.env.ledger().set_timestamp(1000 + CLOSE_RETENTION_SECS);
+    ctx.client().close_stream(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — top-ups
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_top_up_extends_stream_at_same_rate() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().top_up(&stream_id, &500), 1500);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1500);
+    assert_eq!(state.end_time, 1500);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 400);
+
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1500);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_500);
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "cannot top up paused stream")]
+fn test_top_up_paused_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&stream_id);
+    ctx.client().top_up(&stream_id, &500);
+}
+
+#[test]
+#[should_panic(expected = "recipient is frozen")]
+fn test_top_up_for_frozen_recipient_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().freeze_recipient(&ctx.recipient);
+    ctx.client().top_up(&stream_id, &500);
+}
+
+#[test]
+#[should_panic(expected = "token is blocked")]
+fn test_top_up_in_blocked_token_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().block_token(&ctx.token_id);
+    ctx.client().top_up(&stream_id, &500);
+}
+
+#[test]
+#[should_panic(expected = "stream has ended")]
+fn test_top_up_after_end_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().top_up(&stream_id, &500);
+}
+
+#[test]
+#[should_panic(expected = "contract is sunset")]
+fn test_top_up_after_sunset_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().sunset();
+    ctx.client().top_up(&stream_id, &500);
+}
+
+#[test]
+fn test_top_up_refunds_part_tick_remainder() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2000_i128,
+        &2_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    assert_eq!(ctx.client().top_up(&stream_id, &501), 1250);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).deposit_amount,
+        2500
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 7_500);
+    ctx.client().assert_invariants(&ctx.token_id);
+}
+
+#[test]
+#[should_panic(expected = "index-linked streams cannot be topped up")]
+fn test_top_up_of_indexed_stream_panics() {
+    let ctx = TestContext::setup();
+    ctx.publish_index();
+    let stream_id = ctx.create_indexed();
+    ctx.client().top_up(&stream_id, &500);
+}
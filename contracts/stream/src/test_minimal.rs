@@ -0,0 +1,54 @@
+//! Tests that only touch entrypoints compiled into every build, so they also
+//! run under `--no-default-features`.
+
+#[cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, IssuerFlags, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env,
+};
+
+use crate::{FluxoraStream, FluxoraStreamClient, StreamStatus};
+
+#[test]
+fn test_distressed_stream_recovers_in_minimal_build() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let sac = StellarAssetClient::new(&env, &asset.address());
+
+    let contract_id = env.register_contract(None, FluxoraStream);
+    let client = FluxoraStreamClient::new(&env, &contract_id);
+    client.init(
+        &asset.address(),
+        &Address::generate(&env),
+        &Address::generate(&env),
+    );
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    sac.mint(&sender, &10_000);
+    env.ledger().set_timestamp(0);
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &1, &0, &0, &1000);
+
+    sac.set_authorized(&recipient, &false);
+    env.ledger().set_timestamp(500);
+    assert_eq!(client.withdraw(&stream_id), 0);
+    assert_eq!(
+        client.get_stream_state(&stream_id).status,
+        StreamStatus::Distressed
+    );
+
+    sac.set_authorized(&recipient, &true);
+    assert_eq!(client.recover_stream(&stream_id), StreamStatus::Active);
+    assert_eq!(client.get_distress(&stream_id), None);
+    assert_eq!(client.withdraw(&stream_id), 500);
+    assert_eq!(
+        TokenClient::new(&env, &asset.address()).balance(&recipient),
+        500
+    );
+}
@@ -0,0 +1,172 @@
+//! Per-token custody for the stream contract.
+//!
+//! Every movement of stream funds into or out of the contract goes through
+//! this module, which splits each token's balance into explicit buckets:
+//!
+//! - **principal**: owed to stream parties. That is open streams' unwithdrawn
+//!   deposits plus cancelled streams' unclaimed balances and credits.
+//! - **fees**: protocol fees charged on deposits. They are forwarded to the
+//!   fee collector straight away, except a referrer's share, which is held
+//!   as principal until the referrer claims it.
+//! - **surplus**: everything held beyond principal, such as airdrops,
+//!   mistaken transfers or rounding dust. Only `rescue` and `skim` may move
+//!   it, so they can never reach stream funds.
+
+use soroban_sdk::{contracttype, token, Address, Env};
+
+use crate::{extend_persistent_ttl, math, DataKey};
+
+/// Custody ledger for one token.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Custody {
+    /// Amount owed to stream parties, held for them by the contract.
+    pub principal: i128,
+    /// Lifetime protocol fees charged on deposits in this token.
+    pub fees_charged: i128,
+}
+
+pub(crate) fn custody(env: &Env, token: &Address) -> Custody {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Custody(token.clone()))
+        .unwrap_or_default()
+}
+
+fn save(env: &Env, token: &Address, custody: &Custody) {
+    let key = DataKey::Custody(token.clone());
+    env.storage().persistent().set(&key, custody);
+    extend_persistent_ttl(env, &key);
+    #[cfg(debug_assertions)]
+    crate::invariants::check_solvency(env, token);
+}
+
+/// Move `delta` into (positive) or out of (negative) the principal bucket
+/// without moving tokens, e.g. when a deposit was pulled in aggregate.
+pub(crate) fn adjust_principal(env: &Env, token: &Address, delta: i128) {
+    let mut entry = custody(env, token);
+    entry.principal = math::add(env, entry.principal, delta);
+    assert!(entry.principal >= 0, "obligations underflow");
+    save(env, token, &entry);
+}
+
+/// Pull `amount` from `from` into principal. Returns the amount the
+/// contract actually received, which is less than `amount` for tokens that
+/// take a fee on transfer; only that much is credited to principal.
+pub(crate) fn deposit(env: &Env, token: &Address, from: &Address, amount: i128) -> i128 {
+    let this = env.current_contract_address();
+    let client = token::Client::new(env, token);
+    let before = client.balance(&this);
+    client.transfer(from, &this, &amount);
+    credit_received(env, token, &client, before)
+}
+
+/// Pull `amount` from `from` into principal against `from`'s token
+/// allowance to this contract. Returns the amount received, as for
+/// [`deposit`].
+pub(crate) fn deposit_from(env: &Env, token: &Address, from: &Address, amount: i128) -> i128 {
+    let this = env.current_contract_address();
+    let client = token::Client::new(env, token);
+    let before = client.balance(&this);
+    client.transfer_from(&this, from, &this, &amount);
+    credit_received(env, token, &client, before)
+}
+
+/// Credit principal with the growth of this contract's balance since it
+/// was `before`.
+fn credit_received(env: &Env, token: &Address, client: &token::Client, before: i128) -> i128 {
+    let received = math::sub(env, client.balance(&env.current_contract_address()), before);
+    assert!(received >= 0, "token balance fell during deposit");
+    adjust_principal(env, token, received);
+    received
+}
+
+/// Charge a protocol fee of `amount`, paid by `from` straight to `collector`.
+/// With `via_allowance`, the fee is pulled against `from`'s token allowance
+/// to this contract.
+pub(crate) fn charge_fee(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    collector: &Address,
+    amount: i128,
+    via_allowance: bool,
+) {
+    if amount == 0 {
+        return;
+    }
+    let client = token::Client::new(env, token);
+    if via_allowance {
+        client.transfer_from(&env.current_contract_address(), from, collector, &amount);
+    } else {
+        client.transfer(from, collector, &amount);
+    }
+    let mut entry = custody(env, token);
+    entry.fees_charged = math::add(env, entry.fees_charged, amount);
+    save(env, token, &entry);
+}
+
+/// Charge a protocol fee of `amount` from `from` but hold it as principal,
+/// owed to whoever it was set aside for (e.g. a referrer). Returns the
+/// amount received, as for [`deposit`].
+pub(crate) fn retain_fee(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    amount: i128,
+    via_allowance: bool,
+) -> i128 {
+    if amount == 0 {
+        return 0;
+    }
+    let received = if via_allowance {
+        deposit_from(env, token, from, amount)
+    } else {
+        deposit(env, token, from, amount)
+    };
+    let mut entry = custody(env, token);
+    entry.fees_charged = math::add(env, entry.fees_charged, amount);
+    save(env, token, &entry);
+    received
+}
+
+/// Pay `amount` of principal out to `to`.
+pub(crate) fn pay(env: &Env, token: &Address, to: &Address, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    release(env, token, amount);
+    send(env, token, to, amount);
+}
+
+/// Take `amount` out of principal ahead of paying it out in several legs
+/// with `send`, so the custody entry is written once.
+pub(crate) fn release(env: &Env, token: &Address, amount: i128) {
+    adjust_principal(env, token, -amount);
+}
+
+/// Transfer `amount` already released from principal to `to`.
+pub(crate) fn send(env: &Env, token: &Address, to: &Address, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+}
+
+/// Balance of `token` held above principal.
+pub(crate) fn surplus(env: &Env, token: &Address) -> i128 {
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    math::sub(env, balance, custody(env, token).principal)
+}
+
+/// Pay `amount` of surplus out to `to`.
+///
+/// # Panics
+/// - If `amount` exceeds the surplus.
+pub(crate) fn pay_surplus(env: &Env, token: &Address, to: &Address, amount: i128) {
+    assert!(
+        amount <= surplus(env, token),
+        "amount exceeds unobligated balance"
+    );
+    token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+}